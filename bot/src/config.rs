@@ -0,0 +1,320 @@
+//! The strategy config file, in TOML; amounts are in lamports
+
+use std::{fs, path::Path, path::PathBuf, str::FromStr};
+
+use bytemuck::Zeroable;
+use ore_ev_client::{
+    fee::FeePolicy,
+    ix_data::{OreDeployIxData, FLAG_ROUND_RECORD, FLAG_TRACK_STATS},
+    ORE_PROGRAM_ID,
+};
+use serde::Deserialize;
+use solana_sdk::pubkey::Pubkey;
+
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct Config {
+    /// RPC endpoint
+    pub rpc_url: String,
+
+    /// WebSocket endpoint the board and round are watched on
+    pub ws_url: String,
+
+    /// Keypair signing and paying for deploys
+    #[serde(default)]
+    pub keypair: Option<PathBuf>,
+
+    /// Further wallets deploying in turn with `keypair`, or alongside it
+    #[serde(default)]
+    pub keypairs: Vec<PathBuf>,
+
+    /// How the wallets share rounds
+    #[serde(default)]
+    pub rotation: Rotation,
+
+    /// Ledger file every deploy, settle and claim is appended to
+    #[serde(default)]
+    pub ledger: Option<PathBuf>,
+
+    /// Checkpoint and claim each wallet's SOL once a round it deployed in
+    /// is over
+    #[serde(default)]
+    pub auto_claim: bool,
+
+    /// ORE deployment, when not ORE v3
+    #[serde(default)]
+    pub ore_program: Option<String>,
+
+    pub strategy: StrategyConfig,
+
+    #[serde(default)]
+    pub trigger: TriggerConfig,
+
+    pub limits: LimitsConfig,
+
+    #[serde(default)]
+    pub fee: FeeConfig,
+
+    /// Snipe the end of each round instead of deploying on pool updates
+    #[serde(default)]
+    pub snipe: Option<SnipeConfig>,
+}
+
+/// How several wallets share the rounds deployed into
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum Rotation {
+    /// One wallet per round, taking turns by round id
+    #[default]
+    Rotate,
+
+    /// Every wallet in every round, the budget split evenly and each quoted
+    /// against the pool the previous ones leave
+    Split,
+}
+
+/// How each deploy's budget is planned
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct StrategyConfig {
+    /// ORE price in lamports
+    pub ore_price_lamports: u64,
+
+    /// Minimum EV per block, in bps of the block's deploy
+    #[serde(default)]
+    pub min_ev_threshold_bps: i32,
+
+    /// Blocks to target
+    #[serde(default = "default_num_blocks")]
+    pub num_blocks: u8,
+
+    /// Most deploy CPIs (0 = program default)
+    #[serde(default)]
+    pub max_cpis: u8,
+
+    /// Largest share of a block's pool to own, in bps (0 = no cap)
+    #[serde(default)]
+    pub max_block_share_bps: u16,
+
+    /// Allocator objective (`Objective` as u8)
+    #[serde(default)]
+    pub objective: u8,
+
+    /// Track deploys in UserStats and RoundRecords
+    #[serde(default)]
+    pub track: bool,
+}
+
+/// When a round is worth deploying into
+#[derive(Clone, Debug, Default, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct TriggerConfig {
+    /// Least modeled EV of the whole plan, in bps of its deployment
+    #[serde(default)]
+    pub min_plan_ev_bps: i64,
+
+    /// Deploy only in the last this many slots of the round (0 = any time)
+    #[serde(default)]
+    pub window_slots: u64,
+
+    /// Skip a round with fewer slots left
+    #[serde(default)]
+    pub min_slots_left: u64,
+
+    /// Smallest round pool worth deploying into
+    #[serde(default)]
+    pub min_pool_lamports: u64,
+
+    /// Largest round pool to deploy into (0 = no bound)
+    #[serde(default)]
+    pub max_pool_lamports: u64,
+}
+
+/// Spending caps
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct LimitsConfig {
+    /// Most lamports deployed in one round
+    pub round_budget_lamports: u64,
+
+    /// Most lamports deployed per UTC day (0 = no cap)
+    #[serde(default)]
+    pub daily_budget_lamports: u64,
+}
+
+/// Priority fees
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct FeeConfig {
+    /// Percentile of recent priority fees to pay, in bps
+    #[serde(default = "default_fee_percentile")]
+    pub percentile_bps: u16,
+
+    /// Most lamports paid in priority fees per deploy
+    #[serde(default = "default_max_fee")]
+    pub max_fee_lamports: u64,
+}
+
+/// End-of-round sniping: one deploy per round, quoted against the round as
+/// it stands `lead_slots` before its end slot
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct SnipeConfig {
+    /// Slots before the round's end slot to fire at
+    #[serde(default = "default_lead_slots")]
+    pub lead_slots: u64,
+
+    /// Priority fees for the snipe, by default a higher percentile than
+    /// `[fee]`
+    #[serde(default = "default_snipe_fee")]
+    pub fee: FeeConfig,
+
+    /// Tip sending the deploy as a Jito bundle (0 = send over RPC); needs
+    /// the `jito` feature
+    #[serde(default)]
+    pub jito_tip_lamports: u64,
+
+    /// Block engine bundles are sent to, by default Jito's mainnet one
+    #[serde(default)]
+    pub block_engine_url: Option<String>,
+}
+
+impl Default for FeeConfig {
+    fn default() -> Self {
+        FeeConfig {
+            percentile_bps: default_fee_percentile(),
+            max_fee_lamports: default_max_fee(),
+        }
+    }
+}
+
+fn default_num_blocks() -> u8 {
+    5
+}
+
+fn default_lead_slots() -> u64 {
+    2
+}
+
+fn default_snipe_fee() -> FeeConfig {
+    FeeConfig {
+        percentile_bps: 9_500,
+        max_fee_lamports: 5_000_000,
+    }
+}
+
+fn default_fee_percentile() -> u16 {
+    7_500
+}
+
+fn default_max_fee() -> u64 {
+    1_000_000
+}
+
+impl Config {
+    /// Read and check the config at `path`
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let text =
+            fs::read_to_string(path).map_err(|e| format!("reading {}: {e}", path.display()))?;
+        let config: Config =
+            toml::from_str(&text).map_err(|e| format!("{}: {e}", path.display()))?;
+        config.check()?;
+        Ok(config)
+    }
+
+    /// Reject configs that could never deploy
+    pub fn check(&self) -> Result<(), String> {
+        self.ore_program()?;
+        if self.keypairs().is_empty() {
+            return Err("set keypair or keypairs".into());
+        }
+        if !(1..=25).contains(&self.strategy.num_blocks) {
+            return Err("strategy.num_blocks must be 1 to 25".into());
+        }
+        if self.limits.round_budget_lamports == 0 {
+            return Err("limits.round_budget_lamports must be set".into());
+        }
+        if let Some(snipe) = &self.snipe {
+            snipe.check()?;
+        }
+        Ok(())
+    }
+
+    /// Every wallet's keypair file, `keypair` first
+    pub fn keypairs(&self) -> Vec<&Path> {
+        self.keypair
+            .iter()
+            .chain(&self.keypairs)
+            .map(PathBuf::as_path)
+            .collect()
+    }
+
+    /// The ORE deployment deployed into
+    pub fn ore_program(&self) -> Result<Pubkey, String> {
+        match &self.ore_program {
+            Some(address) => {
+                Pubkey::from_str(address).map_err(|e| format!("ore_program {address}: {e}"))
+            }
+            None => Ok(ORE_PROGRAM_ID),
+        }
+    }
+}
+
+impl StrategyConfig {
+    /// The deploy's instruction data for a budget of `total_amount` lamports
+    pub fn deploy_data(&self, total_amount: u64) -> OreDeployIxData {
+        OreDeployIxData {
+            total_amount,
+            ore_price_lamports: self.ore_price_lamports,
+            min_ev_threshold: self.min_ev_threshold_bps,
+            num_blocks: self.num_blocks,
+            max_cpis: self.max_cpis,
+            max_block_share_bps: self.max_block_share_bps,
+            objective: self.objective,
+            flags: if self.track {
+                FLAG_TRACK_STATS | FLAG_ROUND_RECORD
+            } else {
+                0
+            },
+            ..OreDeployIxData::zeroed()
+        }
+    }
+}
+
+impl SnipeConfig {
+    fn check(&self) -> Result<(), String> {
+        if self.lead_slots == 0 {
+            return Err("snipe.lead_slots must be at least 1".into());
+        }
+        if self.jito_tip_lamports != 0 {
+            check_tip(self.jito_tip_lamports)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "jito")]
+fn check_tip(lamports: u64) -> Result<(), String> {
+    use ore_ev_client::jito::MIN_TIP_LAMPORTS;
+    if lamports < MIN_TIP_LAMPORTS {
+        return Err(format!(
+            "snipe.jito_tip_lamports must be at least {MIN_TIP_LAMPORTS}"
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(not(feature = "jito"))]
+fn check_tip(_lamports: u64) -> Result<(), String> {
+    Err("snipe.jito_tip_lamports needs the `jito` feature".into())
+}
+
+impl FeeConfig {
+    pub fn policy(&self) -> FeePolicy {
+        FeePolicy {
+            percentile_bps: self.percentile_bps,
+            max_fee_lamports: self.max_fee_lamports,
+            ..FeePolicy::default()
+        }
+    }
+}
@@ -0,0 +1,408 @@
+//! The round handler that quotes and deploys on each pool update
+
+use std::{
+    error::Error,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use ore_ev_client::{
+    accounts::{fetch_board, fetch_config, fetch_miner, fetch_round, fetch_round_exposure},
+    fee::FeePolicy,
+    instructions::DeployAccounts,
+    ix_data::OreDeployIxData,
+    ore::{claim_instructions, pending_checkpoint},
+    quote,
+    state::{GlobalConfig, OreBoard, OreMiner, OreRound},
+    transaction::TransactionBuilder,
+    watch::{RoundDiff, RoundHandler},
+    QuoteState,
+};
+use ore_ev_math::Allocation;
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::{
+    pubkey::Pubkey,
+    signature::{Keypair, Signature},
+    signer::Signer,
+};
+
+use crate::{
+    config::Config,
+    ledger::{Entry, EntryKind, Ledger, Position},
+    limits::{utc_day, Spending},
+    trigger::{check_plan, check_round, plan_amount, plan_ev_bps, Skip},
+    wallets::{assign, with_plan},
+};
+
+/// Compute units the Jito tip's system transfer adds to a deploy
+#[cfg(feature = "jito")]
+const TIP_COMPUTE_UNITS: u32 = 300;
+
+pub type Result<T> = std::result::Result<T, Box<dyn Error>>;
+
+/// What an evaluation of the round did
+#[derive(Clone, Debug, PartialEq)]
+pub enum Outcome {
+    /// Deploys of `amount` lamports in all were sent, one per wallet
+    Deployed {
+        signatures: Vec<Signature>,
+        amount: u64,
+    },
+
+    /// The round was passed over
+    Skipped(Skip),
+}
+
+/// Deploys into rounds meeting the config's trigger, within its limits
+pub struct Deployer<'a> {
+    rpc: &'a RpcClient,
+    config: Config,
+    ore_program: Pubkey,
+    signers: Vec<Keypair>,
+    program_config: GlobalConfig,
+    spending: Spending,
+    ledger: Option<Ledger>,
+    positions: Vec<Position>,
+}
+
+impl<'a> Deployer<'a> {
+    /// A deployer rotating through `signers` as the config's `rotation`
+    /// says, reading the program config once
+    pub fn new(rpc: &'a RpcClient, config: Config, signers: Vec<Keypair>) -> Result<Self> {
+        if signers.is_empty() {
+            return Err("no wallets to deploy from".into());
+        }
+        let ore_program = config.ore_program()?;
+        let program_config = fetch_config(rpc)?.ok_or("program config not initialized")?;
+        Ok(Deployer {
+            rpc,
+            ore_program,
+            signers,
+            program_config,
+            spending: Spending::default(),
+            ledger: config.ledger.clone().map(Ledger::new),
+            positions: Vec::new(),
+            config,
+        })
+    }
+
+    pub fn rpc(&self) -> &'a RpcClient {
+        self.rpc
+    }
+
+    /// The ORE deployment deployed into
+    pub fn ore_program(&self) -> &Pubkey {
+        &self.ore_program
+    }
+
+    /// Quote `round` and deploy if it and the plan meet the trigger, paying
+    /// the `[fee]` priority fee
+    pub fn evaluate(&mut self, round: &OreRound) -> Result<Outcome> {
+        // Read fresh: the watcher reports the board only when its round changes
+        let board = fetch_board(self.rpc, &self.ore_program)?.ok_or("ORE board not found")?;
+        if board.round_id != round.id {
+            return Err(format!("round {} is not the board's", round.id).into());
+        }
+        let slot = self.rpc.get_slot()?;
+        let fee = self.config.fee.policy();
+        self.deploy_at(&board, round, slot, fee, 0)
+    }
+
+    /// Quote `round` as of `slot` and deploy if it and the plan meet the
+    /// trigger, paying priority fees by `fee` and, when `tip_lamports` is
+    /// set, tipping Jito to send it as a bundle
+    pub fn deploy_at(
+        &mut self,
+        board: &OreBoard,
+        round: &OreRound,
+        slot: u64,
+        fee: FeePolicy,
+        tip_lamports: u64,
+    ) -> Result<Outcome> {
+        if let Err(skip) = check_round(&self.config.trigger, board, round, slot) {
+            return Ok(Outcome::Skipped(skip));
+        }
+
+        let day = utc_day(unix_time());
+        let allowance = self.spending.allowance(&self.config.limits, round.id, day);
+        if allowance == 0 {
+            return Ok(Outcome::Skipped(Skip::BudgetSpent));
+        }
+
+        let wallets = assign(
+            self.config.rotation,
+            round.id,
+            self.signers.len(),
+            allowance,
+        );
+        let mut round = *round;
+        let mut signatures = Vec::new();
+        let mut amount = 0;
+        let mut skipped = None;
+        for (wallet, budget) in wallets {
+            if budget == 0 {
+                continue;
+            }
+            let signer = &self.signers[wallet];
+            let data = self.config.strategy.deploy_data(budget);
+            let authority = signer.pubkey();
+            let state = QuoteState {
+                miner: fetch_miner(self.rpc, &self.ore_program, &authority)?,
+                round_exposure: fetch_round_exposure(self.rpc, &authority, round.id)?,
+                ..QuoteState::default()
+            };
+            let plan = quote(&round, &self.program_config, &data.into(), &state)
+                .map_err(|e| format!("quote failed: {e}"))?;
+            if let Err(skip) = check_plan(&self.config.trigger, &plan) {
+                skipped.get_or_insert(skip);
+                continue;
+            }
+
+            let signature = self.send(signer, &round, data, fee, tip_lamports)?;
+            let deployed = plan_amount(&plan);
+            self.spending.record(round.id, day, deployed);
+            let ev_bps = plan_ev_bps(&plan);
+            if ev_bps == i64::MIN || ev_bps == i64::MAX {
+                eprintln!("round {}: plan EV in bps clamped to {ev_bps}", round.id);
+            }
+            println!(
+                "round {}: {authority} deployed {deployed} lamports over {} blocks at {ev_bps} bps EV: {signature}",
+                round.id,
+                plan.count,
+            );
+            self.record_deploy(&round, &authority, &plan, &signature);
+            // Later wallets quote against the pool this deploy leaves
+            round = with_plan(&round, &plan);
+            signatures.push(signature);
+            amount += deployed;
+        }
+
+        if signatures.is_empty() {
+            return Ok(Outcome::Skipped(skipped.unwrap_or(Skip::BudgetSpent)));
+        }
+        Ok(Outcome::Deployed { signatures, amount })
+    }
+
+    /// Track `plan` as part of `authority`'s position in `round`, and ledger
+    /// its deploy
+    fn record_deploy(
+        &mut self,
+        round: &OreRound,
+        authority: &Pubkey,
+        plan: &Allocation,
+        signature: &Signature,
+    ) {
+        let index = match self
+            .positions
+            .iter()
+            .position(|p| p.round_id == round.id && p.wallet == *authority)
+        {
+            Some(index) => index,
+            None => {
+                self.positions.push(Position::new(round.id, *authority));
+                self.positions.len() - 1
+            }
+        };
+        self.positions[index].add(plan);
+        self.log(Entry {
+            time: unix_time(),
+            kind: EntryKind::Deploy,
+            round_id: Some(round.id),
+            wallet: authority.to_string(),
+            amount: plan_amount(plan),
+            modeled_ev: Some(plan.portfolio_ev),
+            payout: None,
+            signature: Some(signature.to_string()),
+        });
+    }
+
+    /// Settle the positions in rounds `board` has moved past once they are
+    /// drawn, then claim for their wallets if `auto_claim` is set
+    pub fn settle(&mut self, board: &OreBoard) -> Result<()> {
+        let mut i = 0;
+        while i < self.positions.len() {
+            let position = self.positions[i];
+            if position.round_id >= board.round_id {
+                i += 1;
+                continue;
+            }
+            let round = fetch_round(self.rpc, &self.ore_program, position.round_id)?;
+            let Some(round) = round.filter(|round| round.slot_hash != [0; 32]) else {
+                // Not drawn yet
+                i += 1;
+                continue;
+            };
+            let miner = fetch_miner(self.rpc, &self.ore_program, &position.wallet)?;
+            self.positions.remove(i);
+
+            // What the miner holds is what landed; the plans are the fallback
+            // once it has moved on to a later round
+            let settled = match miner {
+                Some(miner) if miner.round_id == round.id => Position {
+                    deployed: miner.deployed,
+                    ..position
+                },
+                _ => position,
+            };
+            let payout = settled.payout(&round);
+            println!(
+                "round {}: {} settled, paid {payout} of {} lamports",
+                round.id,
+                position.wallet,
+                settled.amount()
+            );
+            self.log(Entry {
+                time: unix_time(),
+                kind: EntryKind::Settle,
+                round_id: Some(round.id),
+                wallet: position.wallet.to_string(),
+                amount: settled.amount(),
+                modeled_ev: Some(position.modeled_ev),
+                payout: Some(payout),
+                signature: None,
+            });
+
+            if let (true, Some(miner)) = (self.config.auto_claim, miner) {
+                self.claim(board, &position.wallet, &miner, &settled, &round)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Checkpoint and claim `wallet`'s SOL, counting the payout of
+    /// `position` when its round is the checkpoint still pending
+    fn claim(
+        &self,
+        board: &OreBoard,
+        wallet: &Pubkey,
+        miner: &OreMiner,
+        position: &Position,
+        round: &OreRound,
+    ) -> Result<()> {
+        let Some(signer) = self.signers.iter().find(|s| s.pubkey() == *wallet) else {
+            return Ok(());
+        };
+        let instructions = claim_instructions(&self.ore_program, wallet, miner, board);
+        if instructions.is_empty() {
+            return Ok(());
+        }
+        let pending = match pending_checkpoint(miner, board) {
+            Some(round_id) if round_id == round.id => position.payout(round),
+            _ => 0,
+        };
+        let tx = instructions
+            .into_iter()
+            .fold(
+                TransactionBuilder::new(*wallet),
+                TransactionBuilder::instruction,
+            )
+            .priority_fee(self.rpc, &self.config.fee.policy())?
+            .sign(self.rpc.get_latest_blockhash()?, &[signer])?;
+        let signature = self.rpc.send_transaction(&tx)?;
+        let amount = miner.rewards_sol.saturating_add(pending);
+        println!("{wallet} claimed {amount} lamports: {signature}");
+        self.log(Entry {
+            time: unix_time(),
+            kind: EntryKind::Claim,
+            round_id: None,
+            wallet: wallet.to_string(),
+            amount,
+            modeled_ev: None,
+            payout: None,
+            signature: Some(signature.to_string()),
+        });
+        Ok(())
+    }
+
+    /// Append `entry` to the ledger, if one is configured
+    fn log(&self, entry: Entry) {
+        if let Some(ledger) = &self.ledger {
+            if let Err(e) = ledger.append(&entry) {
+                eprintln!("ledger {}: {e}", ledger.path().display());
+            }
+        }
+    }
+
+    /// Send the deploy without waiting on confirmation, so the round keeps
+    /// being followed
+    fn send(
+        &self,
+        signer: &Keypair,
+        round: &OreRound,
+        data: OreDeployIxData,
+        fee: FeePolicy,
+        tip_lamports: u64,
+    ) -> Result<Signature> {
+        let accounts = DeployAccounts {
+            ore_program: self.ore_program,
+            round_exposure: self.program_config.params.max_round_deploy_lamports != 0,
+            ..DeployAccounts::new(signer.pubkey(), round.id)
+        };
+        let tx = TransactionBuilder::deploy(&accounts, data).priority_fee(self.rpc, &fee)?;
+        if tip_lamports != 0 {
+            return self.send_bundle(signer, round, tx, tip_lamports);
+        }
+        let tx = tx.sign(self.rpc.get_latest_blockhash()?, &[signer])?;
+        Ok(self.rpc.send_transaction(&tx)?)
+    }
+
+    /// Send `tx` tipping `tip_lamports` as a single-transaction Jito bundle
+    #[cfg(feature = "jito")]
+    fn send_bundle(
+        &self,
+        signer: &Keypair,
+        round: &OreRound,
+        tx: TransactionBuilder,
+        tip_lamports: u64,
+    ) -> Result<Signature> {
+        use ore_ev_client::jito::{self, BlockEngine, MAINNET_BLOCK_ENGINE_URL};
+
+        let tip =
+            jito::tip_instruction(&signer.pubkey(), &jito::tip_account(round.id), tip_lamports);
+        let tx = tx
+            .compute_unit_limit(tx.compute_units() + TIP_COMPUTE_UNITS)
+            .instruction(tip)
+            .sign(self.rpc.get_latest_blockhash()?, &[signer])?;
+        let url = self
+            .config
+            .snipe
+            .as_ref()
+            .and_then(|snipe| snipe.block_engine_url.as_deref())
+            .unwrap_or(MAINNET_BLOCK_ENGINE_URL);
+        BlockEngine::new(url).send_bundle(std::slice::from_ref(&tx))?;
+        Ok(tx.signatures[0])
+    }
+
+    #[cfg(not(feature = "jito"))]
+    fn send_bundle(
+        &self,
+        _: &Keypair,
+        _: &OreRound,
+        _: TransactionBuilder,
+        _: u64,
+    ) -> Result<Signature> {
+        Err("Jito tips need the `jito` feature".into())
+    }
+}
+
+impl RoundHandler for Deployer<'_> {
+    fn new_round(&mut self, board: &OreBoard) {
+        println!("round {}: started", board.round_id);
+        if let Err(e) = self.settle(board) {
+            eprintln!("settling: {e}");
+        }
+    }
+
+    fn pool_growth(&mut self, diff: &RoundDiff) {
+        match self.evaluate(&diff.round) {
+            Ok(Outcome::Skipped(skip)) => println!("round {}: skipped: {skip}", diff.round.id),
+            Ok(Outcome::Deployed { .. }) => {}
+            Err(e) => eprintln!("round {}: {e}", diff.round.id),
+        }
+    }
+}
+
+fn unix_time() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_secs())
+}
@@ -0,0 +1,189 @@
+//! The local ledger of every deploy, settle and claim, kept as JSON lines
+//! and exported as CSV or JSON
+
+use std::{
+    fmt,
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    path::{Path, PathBuf},
+};
+
+use ore_ev_client::state::{OreRound, BOARD_SQUARES};
+use ore_ev_math::Allocation;
+use serde::{Deserialize, Serialize};
+use solana_sdk::pubkey::Pubkey;
+
+/// What an entry records
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum EntryKind {
+    /// A deploy was sent
+    Deploy,
+
+    /// A round a wallet deployed in was drawn
+    Settle,
+
+    /// A wallet's SOL was claimed
+    Claim,
+}
+
+impl fmt::Display for EntryKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            EntryKind::Deploy => "deploy",
+            EntryKind::Settle => "settle",
+            EntryKind::Claim => "claim",
+        })
+    }
+}
+
+/// One ledger line; amounts are in lamports
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct Entry {
+    /// Unix time the entry was recorded
+    pub time: u64,
+
+    pub kind: EntryKind,
+
+    /// Round deployed in or settled; `None` for claims
+    pub round_id: Option<u64>,
+
+    pub wallet: String,
+
+    /// Lamports deployed (deploys, settles) or claimed (claims)
+    pub amount: u64,
+
+    /// Modeled EV of what was deployed
+    pub modeled_ev: Option<i64>,
+
+    /// Lamports the round paid out, on settles
+    pub payout: Option<u64>,
+
+    /// Transaction sent, on deploys and claims
+    pub signature: Option<String>,
+}
+
+impl Entry {
+    /// Realized PnL of a settle
+    pub fn pnl(&self) -> Option<i128> {
+        self.payout
+            .map(|payout| payout as i128 - self.amount as i128)
+    }
+}
+
+/// A wallet's deploys in a round, until the round is drawn
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Position {
+    pub round_id: u64,
+
+    pub wallet: Pubkey,
+
+    /// Lamports planned per square
+    pub deployed: [u64; BOARD_SQUARES],
+
+    /// Modeled EV of the plans
+    pub modeled_ev: i64,
+}
+
+impl Position {
+    pub fn new(round_id: u64, wallet: Pubkey) -> Self {
+        Position {
+            round_id,
+            wallet,
+            deployed: [0; BOARD_SQUARES],
+            modeled_ev: 0,
+        }
+    }
+
+    /// Add `plan`'s blocks and EV
+    pub fn add(&mut self, plan: &Allocation) {
+        for i in 0..plan.count as usize {
+            let square = plan.indices[i] as usize;
+            self.deployed[square] = self.deployed[square].saturating_add(plan.amounts[i]);
+        }
+        self.modeled_ev = self.modeled_ev.saturating_add(plan.portfolio_ev);
+    }
+
+    /// Lamports deployed
+    pub fn amount(&self) -> u64 {
+        self.deployed.iter().sum()
+    }
+
+    /// SOL the drawn `round` pays the position
+    pub fn payout(&self, round: &OreRound) -> u64 {
+        (0..BOARD_SQUARES)
+            .map(|square| round.sol_payout(square, self.deployed[square]))
+            .sum()
+    }
+}
+
+/// A ledger file, one JSON entry per line
+#[derive(Clone, Debug)]
+pub struct Ledger {
+    path: PathBuf,
+}
+
+impl Ledger {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Ledger { path: path.into() }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Append `entry`, creating the file if needed
+    pub fn append(&self, entry: &Entry) -> io::Result<()> {
+        let mut line = serde_json::to_string(entry).map_err(io::Error::other)?;
+        line.push('\n');
+        OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?
+            .write_all(line.as_bytes())
+    }
+
+    /// Every entry, in the order recorded
+    pub fn read(&self) -> Result<Vec<Entry>, String> {
+        let text = fs::read_to_string(&self.path)
+            .map_err(|e| format!("reading {}: {e}", self.path.display()))?;
+        text.lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(i, line)| {
+                serde_json::from_str(line)
+                    .map_err(|e| format!("{} line {}: {e}", self.path.display(), i + 1))
+            })
+            .collect()
+    }
+}
+
+/// `entries` as CSV with a header row; absent fields are left empty
+pub fn to_csv(entries: &[Entry]) -> String {
+    fn field<T: ToString>(value: Option<T>) -> String {
+        value.map(|value| value.to_string()).unwrap_or_default()
+    }
+
+    let mut csv =
+        String::from("time,kind,round_id,wallet,amount,modeled_ev,payout,pnl,signature\n");
+    for entry in entries {
+        csv.push_str(&format!(
+            "{},{},{},{},{},{},{},{},{}\n",
+            entry.time,
+            entry.kind,
+            field(entry.round_id),
+            entry.wallet,
+            entry.amount,
+            field(entry.modeled_ev),
+            field(entry.payout),
+            field(entry.pnl()),
+            field(entry.signature.as_deref()),
+        ));
+    }
+    csv
+}
+
+/// `entries` as a JSON array
+pub fn to_json(entries: &[Entry]) -> String {
+    serde_json::to_string_pretty(entries).expect("ledger entries serialize")
+}
@@ -0,0 +1,15 @@
+//! Autodeploy daemon for the ORE EV program: follows the board, and when a
+//! round meets the configured conditions, quotes and submits a deploy
+//! within per-round and daily budgets, either on pool updates or sniped a
+//! few slots before the round ends.
+
+pub mod config;
+pub mod daemon;
+pub mod ledger;
+pub mod limits;
+pub mod snipe;
+pub mod trigger;
+pub mod wallets;
+
+pub use config::Config;
+pub use daemon::Deployer;
@@ -0,0 +1,50 @@
+//! Per-round and daily spending against the configured limits
+
+use crate::config::LimitsConfig;
+
+const SECS_PER_DAY: u64 = 86_400;
+
+/// The UTC day `unix_time` falls on, as days since the epoch
+pub fn utc_day(unix_time: u64) -> u64 {
+    unix_time / SECS_PER_DAY
+}
+
+/// Lamports deployed in the latest round and on the latest day
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Spending {
+    round_id: u64,
+    round_spent: u64,
+    day: u64,
+    day_spent: u64,
+}
+
+impl Spending {
+    /// Most lamports a deploy into `round_id` on `day` may spend
+    pub fn allowance(&self, limits: &LimitsConfig, round_id: u64, day: u64) -> u64 {
+        let round_spent = if round_id == self.round_id {
+            self.round_spent
+        } else {
+            0
+        };
+        let mut allowance = limits.round_budget_lamports.saturating_sub(round_spent);
+        if limits.daily_budget_lamports != 0 {
+            let day_spent = if day == self.day { self.day_spent } else { 0 };
+            allowance = allowance.min(limits.daily_budget_lamports.saturating_sub(day_spent));
+        }
+        allowance
+    }
+
+    /// Record `lamports` deployed into `round_id` on `day`
+    pub fn record(&mut self, round_id: u64, day: u64, lamports: u64) {
+        if round_id != self.round_id {
+            self.round_id = round_id;
+            self.round_spent = 0;
+        }
+        if day != self.day {
+            self.day = day;
+            self.day_spent = 0;
+        }
+        self.round_spent = self.round_spent.saturating_add(lamports);
+        self.day_spent = self.day_spent.saturating_add(lamports);
+    }
+}
@@ -0,0 +1,118 @@
+//! `ore-ev-bot`: deploy into ORE rounds unattended, as a strategy config
+//! file directs
+
+use std::{fs, path::PathBuf, process::ExitCode, thread, time::Duration};
+
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use ore_ev_bot::{
+    daemon::Result,
+    ledger::{to_csv, to_json, Ledger},
+    snipe, Config, Deployer,
+};
+use ore_ev_client::watch::watch;
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::{commitment_config::CommitmentConfig, signature::read_keypair_file};
+
+/// Wait before resubscribing after the WebSocket drops
+const RECONNECT_DELAY: Duration = Duration::from_secs(5);
+
+#[derive(Parser)]
+#[command(name = "ore-ev-bot", version, about)]
+struct Cli {
+    /// Strategy config file
+    #[arg(long, short = 'c', global = true, default_value = "ore-ev-bot.toml")]
+    config: PathBuf,
+
+    /// Run the daemon when omitted
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Export the ledger of deploys, settles and claims
+    Export(ExportArgs),
+}
+
+#[derive(Args)]
+struct ExportArgs {
+    /// Ledger file [default: the config's `ledger`]
+    #[arg(long)]
+    ledger: Option<PathBuf>,
+
+    #[arg(long, value_enum, default_value_t = Format::Csv)]
+    format: Format,
+
+    /// File to write [default: stdout]
+    #[arg(long, short = 'o')]
+    output: Option<PathBuf>,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Format {
+    Csv,
+    Json,
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    let result = match &cli.command {
+        None => run(&cli),
+        Some(Command::Export(args)) => export(&cli, args),
+    };
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("error: {e}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// Load the config and follow the board until setup fails; a dropped
+/// WebSocket is only logged and resubscribed, and sniping polls RPC instead
+fn run(cli: &Cli) -> Result<()> {
+    let config = Config::load(&cli.config)?;
+    let signers = config
+        .keypairs()
+        .into_iter()
+        .map(|path| {
+            read_keypair_file(path).map_err(|e| format!("reading keypair {}: {e}", path.display()))
+        })
+        .collect::<std::result::Result<Vec<_>, _>>()?;
+    let rpc = RpcClient::new_with_commitment(config.rpc_url.clone(), CommitmentConfig::confirmed());
+    let ws_url = config.ws_url.clone();
+    let ore_program = config.ore_program()?;
+    let snipe = config.snipe.clone();
+    let mut deployer = Deployer::new(&rpc, config, signers)?;
+    if let Some(snipe) = snipe {
+        snipe::run(&mut deployer, &snipe);
+    }
+
+    loop {
+        if let Err(e) = watch(&rpc, &ws_url, &ore_program, &mut deployer) {
+            eprintln!("watch stopped: {e}; reconnecting");
+        }
+        thread::sleep(RECONNECT_DELAY);
+    }
+}
+
+/// Write the ledger as CSV or JSON
+fn export(cli: &Cli, args: &ExportArgs) -> Result<()> {
+    let path = match &args.ledger {
+        Some(path) => path.clone(),
+        None => Config::load(&cli.config)?
+            .ledger
+            .ok_or("no --ledger and no ledger in the config")?,
+    };
+    let entries = Ledger::new(path).read()?;
+    let text = match args.format {
+        Format::Csv => to_csv(&entries),
+        Format::Json => to_json(&entries),
+    };
+    match &args.output {
+        Some(output) => fs::write(output, text)?,
+        None => print!("{text}"),
+    }
+    Ok(())
+}
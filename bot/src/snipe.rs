@@ -0,0 +1,101 @@
+//! End-of-round sniping: hold fire until a few slots before the round's end
+//! slot, then quote the round as it stands and deploy once
+
+use std::{thread, time::Duration};
+
+use ore_ev_client::{
+    accounts::{fetch_board, fetch_round},
+    state::OreBoard,
+};
+
+use crate::{
+    config::SnipeConfig,
+    daemon::{Deployer, Outcome, Result},
+    trigger::Skip,
+};
+
+/// Mainnet's target slot time
+pub const SLOT_DURATION: Duration = Duration::from_millis(400);
+
+/// Poll interval in the last slot before firing
+pub const FIRE_POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Longest sleep between board reads, so a new round's end slot is picked up
+pub const MAX_POLL_DELAY: Duration = Duration::from_secs(10);
+
+/// Slot to fire at in `board`'s round, `lead_slots` before its end slot;
+/// `None` while the round has no end slot yet
+pub fn fire_slot(board: &OreBoard, lead_slots: u64) -> Option<u64> {
+    (board.end_slot != u64::MAX).then(|| board.end_slot.saturating_sub(lead_slots))
+}
+
+/// How long to sleep at `slot` before checking the slot again on the way to
+/// `target`: through all but the last slot at the target slot time, then in
+/// short polls so slow slots don't make the sniper late
+pub fn poll_delay(slot: u64, target: u64) -> Duration {
+    match target.saturating_sub(slot) {
+        0 | 1 => FIRE_POLL_INTERVAL,
+        slots => SLOT_DURATION
+            .saturating_mul((slots - 1).min(u32::MAX as u64) as u32)
+            .min(MAX_POLL_DELAY),
+    }
+}
+
+/// Snipe every round with `deployer` until the process stops; RPC failures
+/// are logged and retried
+pub fn run(deployer: &mut Deployer<'_>, snipe: &SnipeConfig) -> ! {
+    let mut fired = None;
+    loop {
+        let delay = step(deployer, snipe, &mut fired).unwrap_or_else(|e| {
+            eprintln!("snipe: {e}");
+            SLOT_DURATION
+        });
+        thread::sleep(delay);
+    }
+}
+
+/// Fire once if the current round is within `snipe.lead_slots` of its end
+/// and not fired at yet, returning how long to wait before the next step
+fn step(
+    deployer: &mut Deployer<'_>,
+    snipe: &SnipeConfig,
+    fired: &mut Option<u64>,
+) -> Result<Duration> {
+    let rpc = deployer.rpc();
+    let board = fetch_board(rpc, deployer.ore_program())?.ok_or("ORE board not found")?;
+    if let Err(e) = deployer.settle(&board) {
+        eprintln!("settling: {e}");
+    }
+    let slot = rpc.get_slot()?;
+    let Some(fire_slot) = fire_slot(&board, snipe.lead_slots) else {
+        return Ok(SLOT_DURATION);
+    };
+    if *fired == Some(board.round_id) || slot >= board.end_slot {
+        // Wait out the round for the next one's board
+        return Ok(poll_delay(slot, board.end_slot.saturating_add(1)).max(SLOT_DURATION));
+    }
+    if slot < fire_slot {
+        return Ok(poll_delay(slot, fire_slot));
+    }
+
+    let round =
+        fetch_round(rpc, deployer.ore_program(), board.round_id)?.ok_or("ORE round not found")?;
+    let outcome = deployer.deploy_at(
+        &board,
+        &round,
+        slot,
+        snipe.fee.policy(),
+        snipe.jito_tip_lamports,
+    )?;
+    match outcome {
+        // The trigger's own window may open later than the lead
+        Outcome::Skipped(skip @ Skip::TooEarly { .. }) => {
+            println!("round {}: holding: {skip}", round.id);
+            return Ok(poll_delay(slot, slot + 1));
+        }
+        Outcome::Skipped(skip) => println!("round {}: skipped: {skip}", round.id),
+        Outcome::Deployed { .. } => {}
+    }
+    *fired = Some(board.round_id);
+    Ok(SLOT_DURATION)
+}
@@ -0,0 +1,102 @@
+//! The conditions a round and its plan must meet before the daemon deploys
+
+use std::fmt;
+
+use ore_ev_client::state::{OreBoard, OreRound};
+use ore_ev_math::Allocation;
+
+use crate::config::TriggerConfig;
+
+/// Why a round was passed over
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Skip {
+    /// More slots are left than the deploy window
+    TooEarly { slots_left: u64 },
+
+    /// Fewer slots are left than the deploy needs
+    TooLate { slots_left: u64 },
+
+    /// The pool is smaller than configured
+    PoolTooSmall,
+
+    /// The pool is larger than configured
+    PoolTooLarge,
+
+    /// The round or daily budget is spent
+    BudgetSpent,
+
+    /// The quote planned no blocks
+    NoPlan,
+
+    /// The plan's EV is below the configured floor
+    LowEv { ev_bps: i64 },
+}
+
+impl fmt::Display for Skip {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Skip::TooEarly { slots_left } => write!(f, "{slots_left} slots left, window not open"),
+            Skip::TooLate { slots_left } => write!(f, "only {slots_left} slots left"),
+            Skip::PoolTooSmall => write!(f, "pool below min_pool_lamports"),
+            Skip::PoolTooLarge => write!(f, "pool above max_pool_lamports"),
+            Skip::BudgetSpent => write!(f, "budget spent"),
+            Skip::NoPlan => write!(f, "no block worth deploying"),
+            Skip::LowEv { ev_bps } => write!(f, "plan EV {ev_bps} bps below min_plan_ev_bps"),
+        }
+    }
+}
+
+/// Slots before `board`'s round ends at `slot`
+pub fn slots_left(board: &OreBoard, slot: u64) -> u64 {
+    board.end_slot.saturating_sub(slot)
+}
+
+/// Check the round's timing and pool, before quoting
+pub fn check_round(
+    trigger: &TriggerConfig,
+    board: &OreBoard,
+    round: &OreRound,
+    slot: u64,
+) -> Result<(), Skip> {
+    let slots_left = slots_left(board, slot);
+    if trigger.window_slots != 0 && slots_left > trigger.window_slots {
+        return Err(Skip::TooEarly { slots_left });
+    }
+    if slots_left < trigger.min_slots_left.max(1) {
+        return Err(Skip::TooLate { slots_left });
+    }
+    if round.total_deployed < trigger.min_pool_lamports {
+        return Err(Skip::PoolTooSmall);
+    }
+    if trigger.max_pool_lamports != 0 && round.total_deployed > trigger.max_pool_lamports {
+        return Err(Skip::PoolTooLarge);
+    }
+    Ok(())
+}
+
+/// Check the quoted plan
+pub fn check_plan(trigger: &TriggerConfig, plan: &Allocation) -> Result<(), Skip> {
+    if plan.count == 0 {
+        return Err(Skip::NoPlan);
+    }
+    let ev_bps = plan_ev_bps(plan);
+    if ev_bps < trigger.min_plan_ev_bps {
+        return Err(Skip::LowEv { ev_bps });
+    }
+    Ok(())
+}
+
+/// Lamports `plan` deploys
+pub fn plan_amount(plan: &Allocation) -> u64 {
+    plan.amounts[..plan.count as usize].iter().sum()
+}
+
+/// Modeled EV of `plan` as a whole, in bps of what it deploys, clamped to
+/// the i64 range (a few lamports at a large EV fall outside it)
+pub fn plan_ev_bps(plan: &Allocation) -> i64 {
+    let ev_bps = match plan_amount(plan) {
+        0 => 0,
+        amount => plan.portfolio_ev as i128 * 10_000 / amount as i128,
+    };
+    ev_bps.clamp(i64::MIN as i128, i64::MAX as i128) as i64
+}
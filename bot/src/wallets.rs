@@ -0,0 +1,37 @@
+//! Which of the configured wallets deploy in a round, and with what budget
+
+use ore_ev_client::state::OreRound;
+use ore_ev_math::Allocation;
+
+use crate::config::Rotation;
+
+/// The wallets deploying in `round_id` out of `count`, by index, each with
+/// its share of `budget`; a split's remainder goes to the first wallet
+pub fn assign(rotation: Rotation, round_id: u64, count: usize, budget: u64) -> Vec<(usize, u64)> {
+    if count == 0 {
+        return Vec::new();
+    }
+    match rotation {
+        Rotation::Rotate => vec![((round_id % count as u64) as usize, budget)],
+        Rotation::Split => {
+            let share = budget / count as u64;
+            let remainder = budget % count as u64;
+            (0..count)
+                .map(|wallet| (wallet, share + if wallet == 0 { remainder } else { 0 }))
+                .collect()
+        }
+    }
+}
+
+/// `round` as it would stand once `plan` lands, each planned square joined
+/// by one more miner
+pub fn with_plan(round: &OreRound, plan: &Allocation) -> OreRound {
+    let mut round = *round;
+    for i in 0..plan.count as usize {
+        let square = plan.indices[i] as usize;
+        round.deployed[square] = round.deployed[square].saturating_add(plan.amounts[i]);
+        round.count[square] = round.count[square].saturating_add(1);
+        round.total_deployed = round.total_deployed.saturating_add(plan.amounts[i]);
+    }
+    round
+}
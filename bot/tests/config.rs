@@ -0,0 +1,100 @@
+use std::path::Path;
+
+use ore_ev_bot::{config::Rotation, Config};
+use ore_ev_client::{
+    ix_data::{FLAG_ROUND_RECORD, FLAG_TRACK_STATS},
+    ORE_PROGRAM_ID,
+};
+
+const EXAMPLE: &str = include_str!("../ore-ev-bot.example.toml");
+
+const MINIMAL: &str = r#"
+rpc_url = "http://localhost:8899"
+ws_url = "ws://localhost:8900"
+keypair = "id.json"
+
+[strategy]
+ore_price_lamports = 1_000_000_000
+
+[limits]
+round_budget_lamports = 100_000_000
+"#;
+
+#[test]
+fn example_config_parses_and_checks() {
+    let config: Config = toml::from_str(EXAMPLE).unwrap();
+    config.check().unwrap();
+    assert_eq!(config.trigger.window_slots, 20);
+    assert_eq!(config.limits.daily_budget_lamports, 5_000_000_000);
+
+    let data = config.strategy.deploy_data(123);
+    assert_eq!((data.total_amount, data.num_blocks), (123, 5));
+    assert_eq!(data.flags, FLAG_TRACK_STATS | FLAG_ROUND_RECORD);
+}
+
+#[test]
+fn omitted_sections_take_defaults() {
+    let config: Config = toml::from_str(MINIMAL).unwrap();
+    config.check().unwrap();
+    assert_eq!(config.ore_program(), Ok(ORE_PROGRAM_ID));
+    assert_eq!(config.strategy.num_blocks, 5);
+    assert_eq!(config.trigger.window_slots, 0);
+    assert_eq!(config.limits.daily_budget_lamports, 0);
+    assert_eq!(config.fee.percentile_bps, 7_500);
+    assert_eq!(config.rotation, Rotation::Rotate);
+}
+
+#[test]
+fn wallets_combine_keypair_and_keypairs() {
+    let config: Config = toml::from_str(&MINIMAL.replace(
+        "keypair = \"id.json\"",
+        "keypair = \"id.json\"\nkeypairs = [\"b.json\"]\nrotation = \"split\"",
+    ))
+    .unwrap();
+    config.check().unwrap();
+    assert_eq!(config.rotation, Rotation::Split);
+    assert_eq!(
+        config.keypairs(),
+        [Path::new("id.json"), Path::new("b.json")]
+    );
+
+    let none: Config = toml::from_str(&MINIMAL.replace("keypair = \"id.json\"", "")).unwrap();
+    assert!(none.check().is_err());
+}
+
+#[test]
+fn snipe_section_selects_sniping_with_a_higher_fee() {
+    let config: Config = toml::from_str(&format!("{MINIMAL}\n[snipe]\n")).unwrap();
+    config.check().unwrap();
+    let snipe = config.snipe.unwrap();
+    assert_eq!(snipe.lead_slots, 2);
+    assert_eq!(snipe.jito_tip_lamports, 0);
+    assert!(snipe.fee.percentile_bps > config.fee.percentile_bps);
+
+    let zero_lead = format!("{MINIMAL}\n[snipe]\nlead_slots = 0\n");
+    assert!(toml::from_str::<Config>(&zero_lead)
+        .unwrap()
+        .check()
+        .is_err());
+}
+
+#[cfg(not(feature = "jito"))]
+#[test]
+fn jito_tip_needs_the_feature() {
+    let tipped = format!("{MINIMAL}\n[snipe]\njito_tip_lamports = 100_000\n");
+    assert!(toml::from_str::<Config>(&tipped).unwrap().check().is_err());
+}
+
+#[test]
+fn config_rejects_unknown_keys_and_unusable_values() {
+    let typo = MINIMAL.replace("round_budget_lamports", "round_budget");
+    assert!(toml::from_str::<Config>(&typo).is_err());
+
+    let mut config: Config = toml::from_str(MINIMAL).unwrap();
+    config.limits.round_budget_lamports = 0;
+    assert!(config.check().is_err());
+
+    let mut config: Config = toml::from_str(MINIMAL).unwrap();
+    config.ore_program = Some("not a pubkey".into());
+    assert!(config.check().is_err());
+}
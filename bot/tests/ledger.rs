@@ -0,0 +1,90 @@
+use bytemuck::Zeroable;
+use ore_ev_bot::ledger::{to_csv, to_json, Entry, EntryKind, Ledger, Position};
+use ore_ev_client::state::OreRound;
+use ore_ev_math::Allocation;
+use solana_sdk::pubkey::Pubkey;
+
+fn settle() -> Entry {
+    Entry {
+        time: 1_700_000_000,
+        kind: EntryKind::Settle,
+        round_id: Some(12),
+        wallet: "wallet".into(),
+        amount: 1_000,
+        modeled_ev: Some(40),
+        payout: Some(900),
+        signature: None,
+    }
+}
+
+fn claim() -> Entry {
+    Entry {
+        kind: EntryKind::Claim,
+        round_id: None,
+        amount: 900,
+        modeled_ev: None,
+        payout: None,
+        signature: Some("sig".into()),
+        ..settle()
+    }
+}
+
+#[test]
+fn ledger_appends_and_reads_back_in_order() {
+    let path = std::env::temp_dir().join(format!("ore-ev-bot-ledger-{}.jsonl", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+    let ledger = Ledger::new(&path);
+    ledger.append(&settle()).unwrap();
+    ledger.append(&claim()).unwrap();
+
+    assert_eq!(ledger.read().unwrap(), [settle(), claim()]);
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn csv_export_derives_pnl_and_leaves_absent_fields_empty() {
+    let csv = to_csv(&[settle(), claim()]);
+    let lines: Vec<&str> = csv.lines().collect();
+    assert_eq!(
+        lines,
+        [
+            "time,kind,round_id,wallet,amount,modeled_ev,payout,pnl,signature",
+            "1700000000,settle,12,wallet,1000,40,900,-100,",
+            "1700000000,claim,,wallet,900,,,,sig",
+        ]
+    );
+}
+
+#[test]
+fn json_export_is_an_array_of_entries() {
+    let json = to_json(&[settle()]);
+    let entries: Vec<Entry> = serde_json::from_str(&json).unwrap();
+    assert_eq!(entries, [settle()]);
+    assert!(json.contains("\"kind\": \"settle\""));
+}
+
+#[test]
+fn position_pays_out_only_on_the_winning_square() {
+    let mut round = OreRound::zeroed();
+    round.slot_hash = [1; 32];
+    let winner = round.winning_square().unwrap();
+    let loser = (winner + 1) % 25;
+    round.deployed[winner] = 4_000;
+    round.total_winnings = 2_000;
+
+    let mut plan = Allocation {
+        count: 2,
+        portfolio_ev: 25,
+        ..Allocation::default()
+    };
+    plan.indices[..2].copy_from_slice(&[winner as u8, loser as u8]);
+    plan.amounts[..2].copy_from_slice(&[1_000, 500]);
+    let mut position = Position::new(12, Pubkey::new_unique());
+    position.add(&plan);
+    position.add(&plan);
+
+    assert_eq!(position.amount(), 3_000);
+    assert_eq!(position.modeled_ev, 50);
+    // 2_000 of stake plus half the winnings
+    assert_eq!(position.payout(&round), 3_000);
+}
@@ -0,0 +1,46 @@
+use ore_ev_bot::{
+    config::LimitsConfig,
+    limits::{utc_day, Spending},
+};
+
+const LIMITS: LimitsConfig = LimitsConfig {
+    round_budget_lamports: 100,
+    daily_budget_lamports: 250,
+};
+
+#[test]
+fn round_budget_resets_each_round() {
+    let mut spending = Spending::default();
+    assert_eq!(spending.allowance(&LIMITS, 1, 0), 100);
+
+    spending.record(1, 0, 60);
+    assert_eq!(spending.allowance(&LIMITS, 1, 0), 40);
+    spending.record(1, 0, 40);
+    assert_eq!(spending.allowance(&LIMITS, 1, 0), 0);
+
+    assert_eq!(spending.allowance(&LIMITS, 2, 0), 100);
+}
+
+#[test]
+fn daily_budget_caps_across_rounds_until_the_day_turns() {
+    let mut spending = Spending::default();
+    spending.record(1, 7, 100);
+    spending.record(2, 7, 100);
+    assert_eq!(spending.allowance(&LIMITS, 3, 7), 50);
+    spending.record(3, 7, 50);
+    assert_eq!(spending.allowance(&LIMITS, 4, 7), 0);
+
+    assert_eq!(spending.allowance(&LIMITS, 4, 8), 100);
+
+    let uncapped = LimitsConfig {
+        daily_budget_lamports: 0,
+        ..LIMITS
+    };
+    assert_eq!(spending.allowance(&uncapped, 4, 7), 100);
+}
+
+#[test]
+fn days_turn_at_utc_midnight() {
+    assert_eq!(utc_day(86_399), 0);
+    assert_eq!(utc_day(86_400), 1);
+}
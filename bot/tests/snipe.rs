@@ -0,0 +1,27 @@
+use bytemuck::Zeroable;
+use ore_ev_bot::snipe::{fire_slot, poll_delay, FIRE_POLL_INTERVAL, MAX_POLL_DELAY, SLOT_DURATION};
+use ore_ev_client::state::OreBoard;
+
+#[test]
+fn fire_slot_leads_the_end_slot() {
+    let board = OreBoard {
+        end_slot: 1_150,
+        ..OreBoard::zeroed()
+    };
+    assert_eq!(fire_slot(&board, 2), Some(1_148));
+    assert_eq!(fire_slot(&board, 2_000), Some(0));
+
+    let unstarted = OreBoard {
+        end_slot: u64::MAX,
+        ..OreBoard::zeroed()
+    };
+    assert_eq!(fire_slot(&unstarted, 2), None);
+}
+
+#[test]
+fn poll_delay_sleeps_to_the_last_slot_then_polls() {
+    assert_eq!(poll_delay(100, 105), SLOT_DURATION * 4);
+    assert_eq!(poll_delay(104, 105), FIRE_POLL_INTERVAL);
+    assert_eq!(poll_delay(106, 105), FIRE_POLL_INTERVAL);
+    assert_eq!(poll_delay(0, 1_000), MAX_POLL_DELAY);
+}
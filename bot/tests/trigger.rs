@@ -0,0 +1,103 @@
+use bytemuck::Zeroable;
+use ore_ev_bot::{
+    config::TriggerConfig,
+    trigger::{check_plan, check_round, plan_ev_bps, Skip},
+};
+use ore_ev_client::state::{OreBoard, OreRound};
+use ore_ev_math::Allocation;
+
+fn board() -> OreBoard {
+    OreBoard {
+        round_id: 4,
+        start_slot: 1_000,
+        end_slot: 1_150,
+        ..OreBoard::zeroed()
+    }
+}
+
+fn round(total_deployed: u64) -> OreRound {
+    OreRound {
+        id: 4,
+        total_deployed,
+        ..OreRound::zeroed()
+    }
+}
+
+fn plan(amounts: &[u64], portfolio_ev: i64) -> Allocation {
+    let mut plan = Allocation {
+        count: amounts.len() as u8,
+        portfolio_ev,
+        ..Allocation::default()
+    };
+    plan.amounts[..amounts.len()].copy_from_slice(amounts);
+    plan
+}
+
+#[test]
+fn round_is_checked_against_the_slot_window() {
+    let trigger = TriggerConfig {
+        window_slots: 20,
+        min_slots_left: 3,
+        ..TriggerConfig::default()
+    };
+    let (board, round) = (board(), round(0));
+    assert_eq!(
+        check_round(&trigger, &board, &round, 1_100),
+        Err(Skip::TooEarly { slots_left: 50 })
+    );
+    assert_eq!(check_round(&trigger, &board, &round, 1_130), Ok(()));
+    assert_eq!(
+        check_round(&trigger, &board, &round, 1_148),
+        Err(Skip::TooLate { slots_left: 2 })
+    );
+    // An ended round is never deployed into, whatever the config
+    assert_eq!(
+        check_round(&TriggerConfig::default(), &board, &round, 1_150),
+        Err(Skip::TooLate { slots_left: 0 })
+    );
+}
+
+#[test]
+fn round_is_checked_against_the_pool_bounds() {
+    let trigger = TriggerConfig {
+        min_pool_lamports: 1_000,
+        max_pool_lamports: 5_000,
+        ..TriggerConfig::default()
+    };
+    let board = board();
+    assert_eq!(
+        check_round(&trigger, &board, &round(999), 1_100),
+        Err(Skip::PoolTooSmall)
+    );
+    assert_eq!(check_round(&trigger, &board, &round(5_000), 1_100), Ok(()));
+    assert_eq!(
+        check_round(&trigger, &board, &round(5_001), 1_100),
+        Err(Skip::PoolTooLarge)
+    );
+}
+
+#[test]
+fn plan_must_clear_the_ev_floor() {
+    let trigger = TriggerConfig {
+        min_plan_ev_bps: 200,
+        ..TriggerConfig::default()
+    };
+    assert_eq!(
+        check_plan(&trigger, &Allocation::default()),
+        Err(Skip::NoPlan)
+    );
+
+    let thin = plan(&[600, 400], 15);
+    assert_eq!(plan_ev_bps(&thin), 150);
+    assert_eq!(
+        check_plan(&trigger, &thin),
+        Err(Skip::LowEv { ev_bps: 150 })
+    );
+    assert_eq!(check_plan(&trigger, &plan(&[600, 400], 20)), Ok(()));
+}
+
+#[test]
+fn plan_ev_bps_clamps_instead_of_wrapping() {
+    assert_eq!(plan_ev_bps(&plan(&[1], i64::MAX)), i64::MAX);
+    assert_eq!(plan_ev_bps(&plan(&[1], i64::MIN)), i64::MIN);
+}
@@ -0,0 +1,43 @@
+use bytemuck::Zeroable;
+use ore_ev_bot::{
+    config::Rotation,
+    wallets::{assign, with_plan},
+};
+use ore_ev_client::state::OreRound;
+use ore_ev_math::Allocation;
+
+#[test]
+fn rotation_takes_turns_by_round() {
+    assert_eq!(assign(Rotation::Rotate, 7, 3, 100), [(1, 100)]);
+    assert_eq!(assign(Rotation::Rotate, 8, 3, 100), [(2, 100)]);
+    assert_eq!(assign(Rotation::Rotate, 9, 3, 100), [(0, 100)]);
+    assert!(assign(Rotation::Rotate, 9, 0, 100).is_empty());
+}
+
+#[test]
+fn split_shares_the_budget_across_every_wallet() {
+    assert_eq!(
+        assign(Rotation::Split, 7, 3, 100),
+        [(0, 34), (1, 33), (2, 33)]
+    );
+}
+
+#[test]
+fn later_wallets_quote_against_the_pool_earlier_plans_leave() {
+    let mut round = OreRound::zeroed();
+    round.deployed[4] = 1_000;
+    round.count[4] = 2;
+    round.total_deployed = 1_000;
+
+    let mut plan = Allocation {
+        count: 2,
+        ..Allocation::default()
+    };
+    plan.indices[..2].copy_from_slice(&[4, 9]);
+    plan.amounts[..2].copy_from_slice(&[500, 300]);
+
+    let after = with_plan(&round, &plan);
+    assert_eq!((after.deployed[4], after.count[4]), (1_500, 3));
+    assert_eq!((after.deployed[9], after.count[9]), (300, 1));
+    assert_eq!(after.total_deployed, 1_800);
+}
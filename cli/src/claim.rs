@@ -0,0 +1,170 @@
+//! `ore-ev claim` and `ore-ev compound`: settle and claim what the miner
+//! won, or redeploy it into the current round
+
+use clap::Args;
+use ore_ev_client::{
+    accounts::{fetch_bankroll, fetch_board, fetch_miner, fetch_round_records, fetch_user_stats},
+    instructions::{ore_deploy, settle_round},
+    ix_data::{SETTLE_FLAG_BANKROLL, SETTLE_FLAG_USER_STATS},
+    ore::{claim_instructions, pending_checkpoint},
+    quote,
+    transaction::{deploy_compute_units, max_deploy_blocks, TransactionBuilder},
+};
+use solana_sdk::{instruction::Instruction, signer::Signer};
+
+use crate::{
+    deploy::{confirm, FeeArgs},
+    output::{print_plan, sol},
+    strategy::{RoundState, StrategyArgs},
+    Context, Result,
+};
+
+/// SettleRounds per transaction, keeping each under the size limit
+const SETTLES_PER_TRANSACTION: usize = 4;
+
+/// Compute units reserved for ORE's Checkpoint and ClaimSOL
+const CLAIM_COMPUTE_UNITS: u32 = 50_000;
+
+#[derive(Args)]
+pub struct ClaimArgs {
+    /// Also settle the most recent unsettled RoundRecords, up to this many
+    #[arg(long, default_value_t = 0)]
+    rounds: usize,
+
+    /// Claim without asking for confirmation
+    #[arg(long, short = 'y')]
+    yes: bool,
+}
+
+#[derive(Args)]
+pub struct CompoundArgs {
+    #[command(flatten)]
+    strategy: StrategyArgs,
+
+    #[command(flatten)]
+    fee: FeeArgs,
+
+    /// Compound without asking for confirmation
+    #[arg(long, short = 'y')]
+    yes: bool,
+}
+
+pub fn claim(ctx: &Context, args: ClaimArgs) -> Result<()> {
+    let signer = ctx.signer()?;
+    let authority = signer.pubkey();
+    let board = fetch_board(&ctx.rpc, &ctx.ore_program)?.ok_or("ORE board not found")?;
+    let miner = fetch_miner(&ctx.rpc, &ctx.ore_program, &authority)?.ok_or("no ORE miner")?;
+
+    let claims = claim_instructions(&ctx.ore_program, &authority, &miner, &board);
+    if let Some(round_id) = pending_checkpoint(&miner, &board) {
+        println!("Round {round_id} is not checkpointed yet; checkpointing it");
+    }
+    println!(
+        "Claimable: {} SOL before any checkpoint",
+        sol(miner.rewards_sol)
+    );
+
+    // Drawn rounds only; the board's current round has no result yet
+    let mut records = fetch_round_records(&ctx.rpc, &authority)?;
+    records.retain(|record| record.settled == 0 && record.round_id < board.round_id);
+    records.sort_by_key(|record| std::cmp::Reverse(record.round_id));
+    records.truncate(args.rounds);
+    let mut flags = 0;
+    if fetch_user_stats(&ctx.rpc, &authority)?.is_some() {
+        flags |= SETTLE_FLAG_USER_STATS;
+    }
+    if fetch_bankroll(&ctx.rpc, &authority)?.is_some() {
+        flags |= SETTLE_FLAG_BANKROLL;
+    }
+    let settles: Vec<Instruction> = records
+        .iter()
+        .map(|record| {
+            println!("Settling round {}", record.round_id);
+            settle_round(
+                &ctx.ore_program,
+                &authority,
+                record.round_id,
+                flags,
+                record.leaderboard != 0,
+            )
+        })
+        .collect();
+
+    if claims.is_empty() && settles.is_empty() {
+        println!("Nothing to claim");
+        return Ok(());
+    }
+    if !args.yes && !confirm("Submit?")? {
+        return Ok(());
+    }
+
+    // Checkpoint before the settles so they sync the credited winnings, and
+    // claim after them
+    let (checkpoint, claim) = match claims.len() {
+        2 => (claims[..1].to_vec(), claims[1..].to_vec()),
+        _ => (Vec::new(), claims),
+    };
+    let mut chunks = settles.chunks(SETTLES_PER_TRANSACTION);
+    let mut first = checkpoint;
+    first.extend(chunks.next().into_iter().flatten().cloned());
+    let mut batches = vec![first];
+    batches.extend(chunks.map(<[Instruction]>::to_vec));
+    batches
+        .last_mut()
+        .expect("at least one batch")
+        .extend(claim);
+
+    for batch in batches {
+        let tx = batch.into_iter().fold(
+            TransactionBuilder::new(authority),
+            TransactionBuilder::instruction,
+        );
+        println!("Submitted: {}", ctx.send(&signer, tx)?);
+    }
+    Ok(())
+}
+
+pub fn compound(ctx: &Context, args: CompoundArgs) -> Result<()> {
+    let signer = ctx.signer()?;
+    let authority = signer.pubkey();
+    let state = RoundState::fetch(ctx)?;
+    let miner = fetch_miner(&ctx.rpc, &ctx.ore_program, &authority)?.ok_or("no ORE miner")?;
+
+    // Winnings a pending checkpoint credits are only known once it runs, so
+    // they are claimed now and compounded next time
+    if let Some(round_id) = pending_checkpoint(&miner, &state.board) {
+        println!(
+            "Round {round_id} is not checkpointed yet; its winnings are claimed, not compounded"
+        );
+    }
+    if miner.rewards_sol == 0 {
+        return Err("no claimed SOL to compound".into());
+    }
+    println!("Compounding {} SOL", sol(miner.rewards_sol));
+
+    let data = args.strategy.deploy_data(miner.rewards_sol);
+    let quote_state = args.strategy.quote_state(ctx, &state, &authority)?;
+    let plan = quote(&state.round, &state.config, &data.into(), &quote_state)
+        .map_err(|e| format!("deploy would fail: {e}"))?;
+    print_plan(&state.round, &plan);
+    if plan.count == 0 {
+        return Ok(());
+    }
+    if !args.yes && !confirm("Claim and deploy?")? {
+        return Ok(());
+    }
+
+    let accounts = args.strategy.accounts(ctx, &state, authority);
+    let units = deploy_compute_units(max_deploy_blocks(&data.into())) + CLAIM_COMPUTE_UNITS;
+    let tx = claim_instructions(&ctx.ore_program, &authority, &miner, &state.board)
+        .into_iter()
+        .fold(
+            TransactionBuilder::new(authority),
+            TransactionBuilder::instruction,
+        )
+        .instruction(ore_deploy(&accounts, data))
+        .compute_unit_limit(units)
+        .priority_fee(&ctx.rpc, &args.fee.policy())?;
+    println!("Compounded: {}", ctx.send(&signer, tx)?);
+    Ok(())
+}
@@ -0,0 +1,93 @@
+//! `ore-ev deploy`: quote the current round, confirm and submit
+
+use std::io::{self, BufRead, Write};
+
+use clap::Args;
+use ore_ev_client::{fee::FeePolicy, quote, transaction::TransactionBuilder};
+use solana_sdk::signer::Signer;
+
+use crate::{
+    output::{parse_sol, print_plan, sol},
+    strategy::{BudgetArgs, RoundState, StrategyArgs},
+    Context, Result,
+};
+
+#[derive(Args)]
+pub struct DeployArgs {
+    #[command(flatten)]
+    budget: BudgetArgs,
+
+    #[command(flatten)]
+    strategy: StrategyArgs,
+
+    #[command(flatten)]
+    fee: FeeArgs,
+
+    /// Deploy without asking for confirmation
+    #[arg(long, short = 'y')]
+    yes: bool,
+}
+
+/// Priority fee of a transaction racing other deploys
+#[derive(Args)]
+pub struct FeeArgs {
+    /// Percentile of recent priority fees to pay, in bps
+    #[arg(long, default_value_t = 7_500)]
+    fee_percentile: u16,
+
+    /// Most SOL to pay in priority fees
+    #[arg(long, value_parser = parse_sol, default_value = "0.001")]
+    max_fee: u64,
+}
+
+impl FeeArgs {
+    pub fn policy(&self) -> FeePolicy {
+        FeePolicy {
+            percentile_bps: self.fee_percentile,
+            max_fee_lamports: self.max_fee,
+            ..FeePolicy::default()
+        }
+    }
+}
+
+pub fn run(ctx: &Context, args: DeployArgs) -> Result<()> {
+    let signer = ctx.signer()?;
+    let authority = signer.pubkey();
+    let state = RoundState::fetch(ctx)?;
+    let data = args.budget.deploy_data(&args.strategy);
+
+    let quote_state = args
+        .budget
+        .quote_state(&args.strategy, ctx, &state, &authority)?;
+    let plan = quote(&state.round, &state.config, &data.into(), &quote_state)
+        .map_err(|e| format!("deploy would fail: {e}"))?;
+    println!(
+        "Round {} ends at slot {}, {} SOL deployed",
+        state.round.id,
+        state.board.end_slot,
+        sol(state.round.total_deployed)
+    );
+    print_plan(&state.round, &plan);
+    if plan.count == 0 {
+        return Ok(());
+    }
+    if !args.yes && !confirm("Deploy?")? {
+        return Ok(());
+    }
+
+    let accounts = args.budget.accounts(&args.strategy, ctx, &state, authority);
+    let tx =
+        TransactionBuilder::deploy(&accounts, data).priority_fee(&ctx.rpc, &args.fee.policy())?;
+    let signature = ctx.send(&signer, tx)?;
+    println!("Deployed: {signature}");
+    Ok(())
+}
+
+/// Ask `prompt` on stdin, true on `y` or `yes`
+pub fn confirm(prompt: &str) -> Result<bool> {
+    print!("{prompt} [y/N] ");
+    io::stdout().flush()?;
+    let mut answer = String::new();
+    io::stdin().lock().read_line(&mut answer)?;
+    Ok(matches!(answer.trim().to_lowercase().as_str(), "y" | "yes"))
+}
@@ -0,0 +1,121 @@
+//! `ore-ev`: quote and deploy through the ORE EV program from the command
+//! line
+
+mod claim;
+mod deploy;
+mod output;
+mod quote;
+mod schedule;
+mod stats;
+mod strategy;
+
+use std::{error::Error, path::PathBuf, process::ExitCode};
+
+use clap::{Parser, Subcommand};
+use ore_ev_client::{transaction::TransactionBuilder, ORE_PROGRAM_ID};
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::{
+    commitment_config::CommitmentConfig,
+    pubkey::Pubkey,
+    signature::{read_keypair_file, Keypair, Signature},
+};
+
+pub type Result<T> = std::result::Result<T, Box<dyn Error>>;
+
+#[derive(Parser)]
+#[command(name = "ore-ev", version, about)]
+struct Cli {
+    /// RPC endpoint
+    #[arg(
+        long,
+        short = 'u',
+        global = true,
+        default_value = "https://api.mainnet-beta.solana.com"
+    )]
+    url: String,
+
+    /// Keypair signing and paying for transactions [default:
+    /// ~/.config/solana/id.json]
+    #[arg(long, short = 'k', global = true)]
+    keypair: Option<PathBuf>,
+
+    /// ORE deployment to use, for configured environments other than ORE v3
+    #[arg(long, global = true, default_value_t = ORE_PROGRAM_ID)]
+    ore_program: Pubkey,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Quote the current round, confirm and deploy
+    Deploy(deploy::DeployArgs),
+
+    /// Print every square's pool and EV, and the plan a deploy would execute
+    Quote(quote::QuoteArgs),
+
+    /// Print cumulative results and the model's drift from them
+    Stats(stats::StatsArgs),
+
+    /// Checkpoint and claim the miner's SOL, settling recent RoundRecords
+    Claim(claim::ClaimArgs),
+
+    /// Claim the miner's SOL and deploy it into the current round
+    Compound(claim::CompoundArgs),
+
+    /// Manage the keypair's Schedule and the vault its deploys are cranked from
+    #[command(subcommand)]
+    Schedule(schedule::ScheduleCommand),
+}
+
+/// What every command runs against
+pub struct Context {
+    pub rpc: RpcClient,
+    pub ore_program: Pubkey,
+    keypair: PathBuf,
+}
+
+impl Context {
+    /// The keypair from `--keypair`
+    pub fn signer(&self) -> Result<Keypair> {
+        read_keypair_file(&self.keypair)
+            .map_err(|e| format!("reading keypair {}: {e}", self.keypair.display()).into())
+    }
+
+    /// Sign `tx` with `signer` at the latest blockhash, send it and wait
+    /// for confirmation
+    pub fn send(&self, signer: &Keypair, tx: TransactionBuilder) -> Result<Signature> {
+        let tx = tx.sign(self.rpc.get_latest_blockhash()?, &[signer])?;
+        Ok(self.rpc.send_and_confirm_transaction(&tx)?)
+    }
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    let keypair = cli.keypair.unwrap_or_else(|| {
+        let home = std::env::var_os("HOME").unwrap_or_default();
+        PathBuf::from(home).join(".config/solana/id.json")
+    });
+    let ctx = Context {
+        rpc: RpcClient::new_with_commitment(cli.url, CommitmentConfig::confirmed()),
+        ore_program: cli.ore_program,
+        keypair,
+    };
+
+    let result = match cli.command {
+        Command::Deploy(args) => deploy::run(&ctx, args),
+        Command::Quote(args) => quote::run(&ctx, args),
+        Command::Stats(args) => stats::run(&ctx, args),
+        Command::Schedule(command) => schedule::run(&ctx, command),
+        Command::Claim(args) => claim::claim(&ctx, args),
+        Command::Compound(args) => claim::compound(&ctx, args),
+    };
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("error: {e}");
+            ExitCode::FAILURE
+        }
+    }
+}
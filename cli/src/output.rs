@@ -0,0 +1,90 @@
+//! Parsing and printing amounts, and printing plans
+
+use ore_ev_client::state::OreRound;
+use ore_ev_math::Allocation;
+
+const LAMPORTS_PER_SOL: u64 = 1_000_000_000;
+
+/// `lamports` as SOL, to 9 decimals with trailing zeros trimmed
+pub fn sol(lamports: u64) -> String {
+    signed_sol(lamports as i128)
+}
+
+/// `lamports`, which may be negative, as SOL
+pub fn signed_sol(lamports: i128) -> String {
+    let sign = if lamports < 0 { "-" } else { "" };
+    let lamports = lamports.unsigned_abs();
+    let whole = lamports / LAMPORTS_PER_SOL as u128;
+    let frac = lamports % LAMPORTS_PER_SOL as u128;
+    if frac == 0 {
+        return format!("{sign}{whole}");
+    }
+    let frac = format!("{frac:09}");
+    format!("{sign}{whole}.{}", frac.trim_end_matches('0'))
+}
+
+/// Parse a decimal amount with up to `decimals` fractional digits into
+/// base units
+pub fn parse_decimal(s: &str, decimals: u32) -> Result<u64, String> {
+    let (whole, frac) = s.split_once('.').unwrap_or((s, ""));
+    if frac.len() > decimals as usize || (whole.is_empty() && frac.is_empty()) {
+        return Err(format!(
+            "`{s}` is not an amount with at most {decimals} decimals"
+        ));
+    }
+    let parse = |digits: &str| match digits {
+        "" => Ok(0),
+        digits => digits
+            .parse::<u64>()
+            .map_err(|_| format!("`{s}` is not an amount")),
+    };
+    let scale = 10u64.pow(decimals);
+    let frac = parse(frac)? * 10u64.pow(decimals - frac.len() as u32);
+    parse(whole)?
+        .checked_mul(scale)
+        .and_then(|whole| whole.checked_add(frac))
+        .ok_or_else(|| format!("`{s}` is too large"))
+}
+
+/// Parse SOL into lamports
+pub fn parse_sol(s: &str) -> Result<u64, String> {
+    parse_decimal(s, 9)
+}
+
+/// Parse USD into cents
+pub fn parse_usd(s: &str) -> Result<u64, String> {
+    parse_decimal(s, 2)
+}
+
+/// Print the blocks of `plan` with the pool each lands in
+pub fn print_plan(round: &OreRound, plan: &Allocation) {
+    if plan.count == 0 {
+        println!("Plan: no deploy");
+        return;
+    }
+    println!("Plan for round {}:", round.id);
+    println!(
+        "  {:>6}  {:>14}  {:>14}  {:>14}",
+        "square", "deploy SOL", "pool SOL", "EV SOL"
+    );
+    let (mut total, mut ev) = (0u64, 0i128);
+    for i in 0..plan.count as usize {
+        let square = plan.indices[i] as usize;
+        println!(
+            "  {:>6}  {:>14}  {:>14}  {:>14}",
+            square,
+            sol(plan.amounts[i]),
+            sol(round.deployed[square]),
+            signed_sol(plan.evs[i] as i128),
+        );
+        total += plan.amounts[i];
+        ev += plan.evs[i] as i128;
+    }
+    println!(
+        "  {:>6}  {:>14}  {:>14}  {:>14}",
+        "total",
+        sol(total),
+        sol(round.total_deployed),
+        signed_sol(ev)
+    );
+}
@@ -0,0 +1,143 @@
+//! `ore-ev quote`: every square of the current round with the EV of
+//! deploying into it, and the plan a deploy would execute
+
+use clap::Args;
+use ore_ev_client::{
+    quote,
+    state::{GlobalConfig, OreRound, BOARD_SQUARES},
+};
+use ore_ev_math::{calculate_ev_scaled, motherlode_value, ore_value, Allocation, FLAT_PAYOUT_BPS};
+use solana_sdk::{pubkey::Pubkey, signer::Signer};
+
+use crate::{
+    output::{print_plan, sol},
+    strategy::{BudgetArgs, RoundState, StrategyArgs},
+    Context, Result,
+};
+
+#[derive(Args)]
+pub struct QuoteArgs {
+    #[command(flatten)]
+    budget: BudgetArgs,
+
+    #[command(flatten)]
+    strategy: StrategyArgs,
+
+    /// Miner to quote for [default: the keypair's]
+    #[arg(long)]
+    authority: Option<Pubkey>,
+}
+
+pub fn run(ctx: &Context, args: QuoteArgs) -> Result<()> {
+    let authority = match args.authority {
+        Some(authority) => authority,
+        None => ctx.signer()?.pubkey(),
+    };
+    let state = RoundState::fetch(ctx)?;
+    let data = args.budget.deploy_data(&args.strategy);
+    let quote_state = args
+        .budget
+        .quote_state(&args.strategy, ctx, &state, &authority)?;
+
+    // Squares outside the plan are priced at an even split of the budget
+    let budget = quote_state.usd_budget_lamports.unwrap_or(data.total_amount);
+    let even_split = budget / data.num_blocks.max(1) as u64;
+    let plan = quote(&state.round, &state.config, &data.into(), &quote_state);
+
+    println!(
+        "Round {} ends at slot {}, {} SOL deployed, motherlode {} ORE",
+        state.round.id,
+        state.board.end_slot,
+        sol(state.round.total_deployed),
+        ore(state.round.motherlode),
+    );
+    print_squares(
+        &state.round,
+        &state.config,
+        data.ore_price_lamports,
+        plan.as_ref().ok(),
+        even_split,
+    );
+    println!();
+    match plan {
+        Ok(plan) => print_plan(&state.round, &plan),
+        Err(e) => println!("Plan: deploy would fail: {e}"),
+    }
+    Ok(())
+}
+
+/// Print one row per square: its pool and miners, the size quoted (the
+/// plan's amount, else `even_split`), the EV of that size in bps and the
+/// marginal EV of the next lamports in bps
+fn print_squares(
+    round: &OreRound,
+    config: &GlobalConfig,
+    ore_price_lamports: u64,
+    plan: Option<&Allocation>,
+    even_split: u64,
+) {
+    let params = &config.params;
+    let motherlode = motherlode_value(ore_price_lamports, round.motherlode, params);
+    let ore_value = ore_value(ore_price_lamports, motherlode, params);
+    let payout_bps = |square: usize| {
+        config
+            .payout_multipliers()
+            .map_or(FLAT_PAYOUT_BPS, |multipliers| multipliers[square] as u64)
+    };
+    let ev = |square: usize, amount: u64| {
+        calculate_ev_scaled(
+            round.deployed[square],
+            amount,
+            round.total_deployed,
+            ore_value,
+            params,
+            payout_bps(square),
+        )
+    };
+
+    println!(
+        "{:>6}  {:>14}  {:>6}  {:>12}  {:>8}  {:>10}  {:>4}",
+        "square", "pool SOL", "miners", "size SOL", "EV bps", "margin bps", "plan"
+    );
+    for square in 0..BOARD_SQUARES {
+        let planned = plan.and_then(|plan| {
+            let count = plan.count as usize;
+            let rank = plan.indices[..count]
+                .iter()
+                .position(|&i| i as usize == square)?;
+            Some(plan.amounts[rank])
+        });
+        let size = planned.unwrap_or(even_split);
+        let step = (size / 100).max(1_000);
+        let (ev_bps, margin_bps) = if round.deployed[square] == 0 || size == 0 {
+            ("-".to_string(), "-".to_string())
+        } else {
+            let at_size = ev(square, size);
+            let next = ev(square, size + step);
+            (
+                bps(at_size as i128, size),
+                bps(next as i128 - at_size as i128, step),
+            )
+        };
+        println!(
+            "{:>6}  {:>14}  {:>6}  {:>12}  {:>8}  {:>10}  {:>4}",
+            square,
+            sol(round.deployed[square]),
+            round.count[square],
+            sol(size),
+            ev_bps,
+            margin_bps,
+            if planned.is_some() { "*" } else { "" },
+        );
+    }
+}
+
+/// `ev` as bps of `amount`
+fn bps(ev: i128, amount: u64) -> String {
+    (ev * 10_000 / amount as i128).to_string()
+}
+
+/// ORE base units (11 decimals) as ORE, to 2 decimals
+fn ore(units: u64) -> String {
+    format!("{:.2}", units as f64 / 1e11)
+}
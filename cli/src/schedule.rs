@@ -0,0 +1,243 @@
+//! `ore-ev schedule`: the keypair's own Schedule, whose vault is funded up
+//! front and deployed from one round at a time by ExecuteSchedule cranks.
+//! A schedule has a single owner, who can fund more rounds; there are no
+//! pooled shares.
+
+use clap::{Args, Subcommand};
+use ore_ev_client::{
+    accounts::{fetch_board, fetch_miner, fetch_schedule},
+    instructions::{claim_schedule, close_schedule, create_schedule, deposit_schedule},
+    ix_data::SCHEDULE_CLAIM_FLAG_ORE,
+    ore, pda,
+    state::NO_ROUND,
+    transaction::TransactionBuilder,
+};
+use solana_sdk::{instruction::Instruction, pubkey::Pubkey, signer::Signer};
+
+use crate::{
+    deploy::confirm,
+    output::{parse_sol, sol},
+    strategy::StrategyArgs,
+    Context, Result,
+};
+
+#[derive(Subcommand)]
+pub enum ScheduleCommand {
+    /// Create the keypair's Schedule and fund its vault for `--rounds`
+    /// deploys of `--budget` SOL
+    Init(InitArgs),
+
+    /// Fund `--rounds` more deploys of the keypair's Schedule at its budget
+    /// per round
+    Deposit {
+        /// Rounds to add
+        #[arg(long)]
+        rounds: u32,
+    },
+
+    /// Print a Schedule and its vault balance
+    Status {
+        /// Schedule authority [default: the keypair]
+        #[arg(long)]
+        authority: Option<Pubkey>,
+    },
+
+    /// Checkpoint and claim the vault's ORE winnings to the keypair; claimed
+    /// ORE goes to the keypair's ORE token account, which must exist
+    Claim,
+
+    /// Claim the vault's winnings and close the keypair's Schedule,
+    /// withdrawing what is left in the vault
+    Close {
+        /// Close without asking for confirmation
+        #[arg(long, short = 'y')]
+        yes: bool,
+    },
+}
+
+#[derive(Args)]
+pub struct InitArgs {
+    /// SOL deployed each round
+    #[arg(long, value_parser = parse_sol)]
+    budget: u64,
+
+    #[command(flatten)]
+    strategy: StrategyArgs,
+
+    /// Rounds to fund
+    #[arg(long)]
+    rounds: u32,
+}
+
+pub fn run(ctx: &Context, command: ScheduleCommand) -> Result<()> {
+    match command {
+        ScheduleCommand::Init(args) => init(ctx, args),
+        ScheduleCommand::Deposit { rounds } => deposit(ctx, rounds),
+        ScheduleCommand::Status { authority } => {
+            let authority = match authority {
+                Some(authority) => authority,
+                None => ctx.signer()?.pubkey(),
+            };
+            status(ctx, &authority)
+        }
+        ScheduleCommand::Claim => claim(ctx),
+        ScheduleCommand::Close { yes } => close(ctx, yes),
+    }
+}
+
+fn init(ctx: &Context, args: InitArgs) -> Result<()> {
+    let signer = ctx.signer()?;
+    let budget_per_round = args.budget;
+    let strategy = args.strategy.deploy_data(budget_per_round);
+    let funding = budget_per_round
+        .checked_mul(args.rounds as u64)
+        .ok_or("vault funding overflows")?;
+    println!(
+        "Funding {} rounds of {} SOL: {} SOL plus the vault's rent",
+        args.rounds,
+        sol(budget_per_round),
+        sol(funding)
+    );
+
+    let ix = create_schedule(&signer.pubkey(), budget_per_round, args.rounds, strategy);
+    let signature = ctx.send(
+        &signer,
+        TransactionBuilder::new(signer.pubkey()).instruction(ix),
+    )?;
+    println!("Created: {signature}");
+    status(ctx, &signer.pubkey())
+}
+
+fn deposit(ctx: &Context, rounds: u32) -> Result<()> {
+    let signer = ctx.signer()?;
+    let authority = signer.pubkey();
+    let Some(schedule) = fetch_schedule(&ctx.rpc, &authority)? else {
+        println!("No Schedule for {authority}");
+        return Ok(());
+    };
+    let funding = schedule
+        .budget_per_round
+        .checked_mul(rounds as u64)
+        .ok_or("vault funding overflows")?;
+    println!(
+        "Funding {} more rounds of {} SOL: {} SOL",
+        rounds,
+        sol(schedule.budget_per_round),
+        sol(funding)
+    );
+
+    let ix = deposit_schedule(&authority, rounds);
+    let signature = ctx.send(&signer, TransactionBuilder::new(authority).instruction(ix))?;
+    println!("Deposited: {signature}");
+    status(ctx, &authority)
+}
+
+fn status(ctx: &Context, authority: &Pubkey) -> Result<()> {
+    let schedule_address = pda::schedule_address(authority).0;
+    let Some(schedule) = fetch_schedule(&ctx.rpc, authority)? else {
+        println!("No Schedule for {authority}");
+        return Ok(());
+    };
+    let vault = pda::schedule_vault_address(&schedule_address).0;
+    let balance = ctx.rpc.get_balance(&vault)?;
+    let committed = schedule
+        .budget_per_round
+        .saturating_mul(schedule.rounds_remaining as u64);
+
+    println!("Schedule {schedule_address}");
+    println!("  vault             {vault}");
+    println!("  balance           {} SOL", sol(balance));
+    println!("  budget per round  {} SOL", sol(schedule.budget_per_round));
+    println!("  rounds remaining  {}", schedule.rounds_remaining);
+    println!("  committed         {} SOL", sol(committed));
+    println!("  blocks            {}", schedule.strategy.num_blocks);
+    println!("  last round        {}", last_round(schedule.last_round_id));
+    Ok(())
+}
+
+fn claim(ctx: &Context) -> Result<()> {
+    let signer = ctx.signer()?;
+    let authority = signer.pubkey();
+    if fetch_schedule(&ctx.rpc, &authority)?.is_none() {
+        println!("No Schedule for {authority}");
+        return Ok(());
+    }
+    let claims = claim_instructions(ctx, &authority)?;
+    if claims.is_empty() {
+        println!("Nothing to claim");
+        return Ok(());
+    }
+    let tx = claims
+        .into_iter()
+        .fold(TransactionBuilder::new(authority), |tx, ix| {
+            tx.instruction(ix)
+        });
+    let signature = ctx.send(&signer, tx)?;
+    println!("Claimed: {signature}");
+    Ok(())
+}
+
+fn close(ctx: &Context, yes: bool) -> Result<()> {
+    let signer = ctx.signer()?;
+    status(ctx, &signer.pubkey())?;
+    if fetch_schedule(&ctx.rpc, &signer.pubkey())?.is_none() {
+        return Ok(());
+    }
+    if !yes && !confirm("Close the schedule and withdraw the vault?")? {
+        return Ok(());
+    }
+    let authority = signer.pubkey();
+    let tx = claim_instructions(ctx, &authority)?
+        .into_iter()
+        .fold(TransactionBuilder::new(authority), |tx, ix| {
+            tx.instruction(ix)
+        })
+        .instruction(close_schedule(&ctx.ore_program, &authority));
+    let signature = ctx.send(&signer, tx)?;
+    println!("Closed: {signature}");
+    Ok(())
+}
+
+/// Instructions paying out what the vault's miner has won: a Checkpoint of
+/// its last round if pending, then ClaimSchedule if anything is or may be
+/// credited
+fn claim_instructions(ctx: &Context, authority: &Pubkey) -> Result<Vec<Instruction>> {
+    let vault = pda::schedule_vault_address(&pda::schedule_address(authority).0).0;
+    let Some(miner) = fetch_miner(&ctx.rpc, &ctx.ore_program, &vault)? else {
+        return Ok(Vec::new());
+    };
+    let board = fetch_board(&ctx.rpc, &ctx.ore_program)?.ok_or("ORE board not found")?;
+
+    let mut instructions = Vec::new();
+    let pending = ore::pending_checkpoint(&miner, &board);
+    if let Some(round_id) = pending {
+        println!("Round {round_id} is not checkpointed yet; checkpointing it");
+        instructions.push(ore::checkpoint(
+            &ctx.ore_program,
+            authority,
+            &vault,
+            round_id,
+        ));
+    }
+    println!(
+        "Claimable: {} SOL before any checkpoint",
+        sol(miner.rewards_sol)
+    );
+    // A pending checkpoint may credit winnings the miner doesn't show yet
+    if pending.is_some() || miner.rewards_sol > 0 || miner.rewards_ore > 0 {
+        let flags = if pending.is_some() || miner.rewards_ore > 0 {
+            SCHEDULE_CLAIM_FLAG_ORE
+        } else {
+            0
+        };
+        instructions.push(claim_schedule(&ctx.ore_program, authority, flags));
+    }
+    Ok(instructions)
+}
+
+fn last_round(round_id: u64) -> String {
+    match round_id {
+        NO_ROUND => "none".to_string(),
+        id => id.to_string(),
+    }
+}
@@ -0,0 +1,111 @@
+//! `ore-ev stats`: cumulative results from UserStats and the model's
+//! accuracy from RoundRecords
+
+use clap::Args;
+use ore_ev_client::{
+    accounts::{fetch_round_records, fetch_user_stats},
+    stats::RecordSummary,
+};
+use solana_sdk::{pubkey::Pubkey, signer::Signer};
+
+use crate::{
+    output::{signed_sol, sol},
+    Context, Result,
+};
+
+#[derive(Args)]
+pub struct StatsArgs {
+    /// Miner to report on [default: the keypair's]
+    #[arg(long)]
+    authority: Option<Pubkey>,
+
+    /// Also list each settled round's record, most recent first
+    #[arg(long)]
+    rounds: bool,
+}
+
+pub fn run(ctx: &Context, args: StatsArgs) -> Result<()> {
+    let authority = match args.authority {
+        Some(authority) => authority,
+        None => ctx.signer()?.pubkey(),
+    };
+
+    match fetch_user_stats(&ctx.rpc, &authority)? {
+        Some(stats) => {
+            let pnl = stats.total_won as i128 - stats.total_deployed as i128;
+            println!("Deploys tracked in UserStats:");
+            println!("  deployed      {} SOL", sol(stats.total_deployed));
+            println!("  won           {} SOL", sol(stats.total_won));
+            println!("  realized PnL  {} SOL", signed_sol(pnl));
+            println!("  peak PnL      {} SOL", signed_sol(stats.peak_pnl as i128));
+            println!(
+                "  rounds        {} ({} settled, {} won{})",
+                stats.rounds_participated,
+                stats.rounds_settled,
+                stats.rounds_won,
+                percent(stats.rounds_won, stats.rounds_settled)
+            );
+            if stats.rounds_settled != 0 {
+                println!(
+                    "  best round    {} ({} SOL)",
+                    stats.best_round_id,
+                    signed_sol(stats.best_round_pnl as i128)
+                );
+                println!(
+                    "  worst round   {} ({} SOL)",
+                    stats.worst_round_id,
+                    signed_sol(stats.worst_round_pnl as i128)
+                );
+            }
+        }
+        None => println!("No UserStats for {authority} (deploy with --track)"),
+    }
+
+    let mut records = fetch_round_records(&ctx.rpc, &authority)?;
+    if records.is_empty() {
+        println!("No RoundRecords for {authority}");
+        return Ok(());
+    }
+    let summary = RecordSummary::new(&records);
+    println!();
+    println!(
+        "Modeled vs realized over {} settled RoundRecords:",
+        summary.rounds
+    );
+    println!("  deployed      {} SOL", sol(summary.deployed));
+    println!("  realized PnL  {} SOL", signed_sol(summary.realized_pnl()));
+    println!("  modeled EV    {} SOL", signed_sol(summary.modeled_ev));
+    print!("  drift         {} SOL", signed_sol(summary.ev_drift()));
+    match summary.drift_sigmas() {
+        Some(sigmas) => println!(" ({sigmas:+.2} sigma)"),
+        None => println!(),
+    }
+
+    if args.rounds {
+        records.retain(|record| record.settled != 0);
+        records.sort_by_key(|record| std::cmp::Reverse(record.round_id));
+        println!();
+        println!(
+            "{:>10}  {:>14}  {:>14}  {:>14}",
+            "round", "deployed SOL", "PnL SOL", "modeled EV SOL"
+        );
+        for record in &records {
+            println!(
+                "{:>10}  {:>14}  {:>14}  {:>14}",
+                record.round_id,
+                sol(record.total_deployed),
+                signed_sol(record.realized_pnl() as i128),
+                signed_sol(record.modeled_ev as i128),
+            );
+        }
+    }
+    Ok(())
+}
+
+/// `part` as a percentage of `whole`, formatted as ", N.N%"
+fn percent(part: u64, whole: u64) -> String {
+    if whole == 0 {
+        return String::new();
+    }
+    format!(", {:.1}%", part as f64 * 100.0 / whole as f64)
+}
@@ -0,0 +1,182 @@
+//! Deploy parameters shared by the commands that plan a deploy, and the
+//! accounts a plan is quoted against
+
+use bytemuck::Zeroable;
+use clap::Args;
+use ore_ev_client::{
+    accounts::{fetch_config, fetch_current_round, fetch_miner, fetch_round_exposure},
+    instructions::DeployAccounts,
+    ix_data::{OreDeployIxData, FLAG_ROUND_RECORD, FLAG_TRACK_STATS, FLAG_USD_BUDGET},
+    state::{read_pyth_price, GlobalConfig, OreBoard, OreRound},
+    QuoteState,
+};
+use solana_sdk::pubkey::Pubkey;
+
+use crate::{
+    output::{parse_sol, parse_usd},
+    Context, Result,
+};
+
+/// A deploy's budget, in SOL or in USD at an oracle's price
+#[derive(Args)]
+pub struct BudgetArgs {
+    /// Budget in SOL
+    #[arg(long, value_parser = parse_sol, required_unless_present = "budget_usd")]
+    pub budget: Option<u64>,
+
+    /// Budget in USD, converted at the SOL/USD price of `--price-feed`
+    #[arg(long, value_parser = parse_usd, conflicts_with = "budget", requires = "price_feed")]
+    pub budget_usd: Option<u64>,
+
+    /// Pyth SOL/USD `PriceUpdateV2` account the program converts a USD
+    /// budget with
+    #[arg(long)]
+    pub price_feed: Option<Pubkey>,
+}
+
+/// How a deploy's budget is planned, whatever it is
+#[derive(Args)]
+pub struct StrategyArgs {
+    /// ORE price in SOL
+    #[arg(long, value_parser = parse_sol)]
+    pub ore_price: u64,
+
+    /// Minimum EV per block, in bps of the block's deploy
+    #[arg(long, default_value_t = 0, allow_negative_numbers = true)]
+    pub threshold: i32,
+
+    /// Blocks to target
+    #[arg(long, default_value_t = 5, value_parser = clap::value_parser!(u8).range(1..=25))]
+    pub blocks: u8,
+
+    /// Most deploy CPIs to execute (0 = program default)
+    #[arg(long, default_value_t = 0)]
+    pub max_cpis: u8,
+
+    /// Largest share of a block's pool to own, in bps (0 = no cap)
+    #[arg(long, default_value_t = 0)]
+    pub max_share: u16,
+
+    /// Track the deploy in UserStats and a RoundRecord
+    #[arg(long)]
+    pub track: bool,
+}
+
+/// The board, its current round and the program config
+pub struct RoundState {
+    pub board: OreBoard,
+    pub round: OreRound,
+    pub config: GlobalConfig,
+}
+
+impl RoundState {
+    pub fn fetch(ctx: &Context) -> Result<Self> {
+        let (board, round) = fetch_current_round(&ctx.rpc, &ctx.ore_program)?
+            .ok_or("ORE board or round not found")?;
+        let config = fetch_config(&ctx.rpc)?.ok_or("program config not initialized")?;
+        Ok(RoundState {
+            board,
+            round,
+            config,
+        })
+    }
+}
+
+impl BudgetArgs {
+    /// The deploy's instruction data planning this budget with `strategy`
+    pub fn deploy_data(&self, strategy: &StrategyArgs) -> OreDeployIxData {
+        match self.budget_usd {
+            Some(cents) => {
+                let mut data = strategy.deploy_data(cents);
+                data.flags |= FLAG_USD_BUDGET;
+                data
+            }
+            None => strategy.deploy_data(self.budget.unwrap_or_default()),
+        }
+    }
+
+    /// The deploy's accounts for `authority`, with the price feed of a USD
+    /// budget
+    pub fn accounts(
+        &self,
+        strategy: &StrategyArgs,
+        ctx: &Context,
+        state: &RoundState,
+        authority: Pubkey,
+    ) -> DeployAccounts {
+        DeployAccounts {
+            price_feed: self.price_feed,
+            ..strategy.accounts(ctx, state, authority)
+        }
+    }
+
+    /// The accounts the deploy reads, fetched for quoting, with the lamports
+    /// a USD budget converts to at the feed's current price
+    pub fn quote_state(
+        &self,
+        strategy: &StrategyArgs,
+        ctx: &Context,
+        state: &RoundState,
+        authority: &Pubkey,
+    ) -> Result<QuoteState> {
+        let usd_budget_lamports = match (self.budget_usd, self.price_feed) {
+            (Some(cents), Some(feed)) => {
+                let data = ctx.rpc.get_account_data(&feed)?;
+                let price = read_pyth_price(&data)
+                    .map_err(|_| format!("{feed} is not a SOL/USD price update"))?;
+                let lamports = price
+                    .usd_cents_to_lamports(cents)
+                    .map_err(|_| "USD budget does not convert to lamports")?;
+                Some(lamports)
+            }
+            _ => None,
+        };
+        Ok(QuoteState {
+            usd_budget_lamports,
+            ..strategy.quote_state(ctx, state, authority)?
+        })
+    }
+}
+
+impl StrategyArgs {
+    /// The deploy's instruction data for a budget of `total_amount` lamports
+    pub fn deploy_data(&self, total_amount: u64) -> OreDeployIxData {
+        let mut flags = 0;
+        if self.track {
+            flags |= FLAG_TRACK_STATS | FLAG_ROUND_RECORD;
+        }
+        OreDeployIxData {
+            total_amount,
+            ore_price_lamports: self.ore_price,
+            min_ev_threshold: self.threshold,
+            num_blocks: self.blocks,
+            max_cpis: self.max_cpis,
+            max_block_share_bps: self.max_share,
+            flags,
+            ..OreDeployIxData::zeroed()
+        }
+    }
+
+    /// The deploy's accounts for `authority` in the current round
+    pub fn accounts(&self, ctx: &Context, state: &RoundState, authority: Pubkey) -> DeployAccounts {
+        DeployAccounts {
+            ore_program: ctx.ore_program,
+            round_exposure: state.config.params.max_round_deploy_lamports != 0,
+            ..DeployAccounts::new(authority, state.round.id)
+        }
+    }
+
+    /// The accounts the deploy reads, fetched for quoting
+    pub fn quote_state(
+        &self,
+        ctx: &Context,
+        state: &RoundState,
+        authority: &Pubkey,
+    ) -> Result<QuoteState> {
+        Ok(QuoteState {
+            miner: fetch_miner(&ctx.rpc, &ctx.ore_program, authority)?,
+            round_exposure: fetch_round_exposure(&ctx.rpc, authority, state.round.id)?,
+            ..QuoteState::default()
+        })
+    }
+}
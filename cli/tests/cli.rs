@@ -0,0 +1,55 @@
+use std::process::Command;
+
+fn ore_ev(args: &[&str]) -> std::process::Output {
+    Command::new(env!("CARGO_BIN_EXE_ore-ev"))
+        .args(args)
+        .output()
+        .unwrap()
+}
+
+#[test]
+fn deploy_requires_a_budget_and_ore_price() {
+    let output = ore_ev(&["deploy", "--ore-price", "0.5"]);
+    assert_eq!(output.status.code(), Some(2));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("--budget"));
+
+    let output = ore_ev(&["deploy", "--budget", "1"]);
+    assert_eq!(output.status.code(), Some(2));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("--ore-price"));
+}
+
+#[test]
+fn amounts_reject_more_decimals_than_the_unit_has() {
+    let output = ore_ev(&["deploy", "--budget", "0.0000000001", "--ore-price", "1"]);
+    assert_eq!(output.status.code(), Some(2));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("at most 9 decimals"));
+}
+
+#[test]
+fn quote_takes_the_deploy_parameters() {
+    let output = ore_ev(&[
+        "quote",
+        "--budget",
+        "1",
+        "--blocks",
+        "26",
+        "--ore-price",
+        "1",
+    ]);
+    assert_eq!(output.status.code(), Some(2));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("--blocks"));
+}
+
+#[test]
+fn schedule_init_needs_the_rounds_to_fund() {
+    let output = ore_ev(&["schedule", "init", "--budget", "0.1", "--ore-price", "1"]);
+    assert_eq!(output.status.code(), Some(2));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("--rounds"));
+}
+
+#[test]
+fn usd_budget_needs_a_price_feed() {
+    let output = ore_ev(&["deploy", "--budget-usd", "25.50", "--ore-price", "1"]);
+    assert_eq!(output.status.code(), Some(2));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("--price-feed"));
+}
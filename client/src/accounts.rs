@@ -0,0 +1,352 @@
+//! Decoding of ORE's accounts and the program's PDAs from raw account data,
+//! and RPC helpers fetching them by address
+
+use std::fmt;
+
+use bytemuck::{Pod, Zeroable};
+use solana_account_decoder::UiAccountEncoding;
+use solana_client::{
+    client_error::ClientError,
+    rpc_client::RpcClient,
+    rpc_config::{RpcAccountInfoConfig, RpcProgramAccountsConfig},
+    rpc_filter::{Memcmp, RpcFilterType},
+};
+use solana_pinocchio_starter::state::{
+    utils::DataLen, AccountDiscriminator, Bankroll, DeployNonce, GlobalConfig, Leaderboard,
+    OreAccount, OreAutomation, OreBoard, OreMiner, OreRound, Plan, RoundExposure, RoundRecord,
+    Schedule, UserStats, GLOBAL_CONFIG_V1_LEN, SCHEDULE_V0_LEN, USER_STATS_V0_LEN,
+};
+use solana_sdk::{account::Account, pubkey::Pubkey};
+
+use crate::{pda, PROGRAM_ID};
+
+/// An account owned by this program
+pub trait ProgramAccount: Pod + DataLen {
+    const DISCRIMINATOR: AccountDiscriminator;
+
+    /// Length of the oldest layout still decoded; accounts not yet migrated
+    /// read their missing fields as zeros, as the program does
+    const MIN_LEN: usize = Self::LEN;
+}
+
+impl ProgramAccount for GlobalConfig {
+    const DISCRIMINATOR: AccountDiscriminator = AccountDiscriminator::GlobalConfig;
+    const MIN_LEN: usize = GLOBAL_CONFIG_V1_LEN;
+}
+
+impl ProgramAccount for RoundExposure {
+    const DISCRIMINATOR: AccountDiscriminator = AccountDiscriminator::RoundExposure;
+}
+
+impl ProgramAccount for UserStats {
+    const DISCRIMINATOR: AccountDiscriminator = AccountDiscriminator::UserStats;
+    const MIN_LEN: usize = USER_STATS_V0_LEN;
+}
+
+impl ProgramAccount for Bankroll {
+    const DISCRIMINATOR: AccountDiscriminator = AccountDiscriminator::Bankroll;
+}
+
+impl ProgramAccount for Schedule {
+    const DISCRIMINATOR: AccountDiscriminator = AccountDiscriminator::Schedule;
+    const MIN_LEN: usize = SCHEDULE_V0_LEN;
+}
+
+impl ProgramAccount for DeployNonce {
+    const DISCRIMINATOR: AccountDiscriminator = AccountDiscriminator::DeployNonce;
+}
+
+impl ProgramAccount for RoundRecord {
+    const DISCRIMINATOR: AccountDiscriminator = AccountDiscriminator::RoundRecord;
+}
+
+impl ProgramAccount for Leaderboard {
+    const DISCRIMINATOR: AccountDiscriminator = AccountDiscriminator::Leaderboard;
+}
+
+impl ProgramAccount for Plan {
+    const DISCRIMINATOR: AccountDiscriminator = AccountDiscriminator::Plan;
+}
+
+/// An account owned by an ORE deployment
+pub trait OreAccountType: Pod + DataLen {
+    const KIND: OreAccount;
+}
+
+impl OreAccountType for OreBoard {
+    const KIND: OreAccount = OreAccount::Board;
+}
+
+impl OreAccountType for OreRound {
+    const KIND: OreAccount = OreAccount::Round;
+}
+
+impl OreAccountType for OreMiner {
+    const KIND: OreAccount = OreAccount::Miner;
+}
+
+impl OreAccountType for OreAutomation {
+    const KIND: OreAccount = OreAccount::Automation;
+}
+
+/// Why account data did not decode as the requested type
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum DecodeError {
+    /// Owned by another program
+    WrongOwner(Pubkey),
+
+    /// Leading bytes of another account type
+    WrongDiscriminator,
+
+    /// Data length no layout of the type has
+    WrongLength(usize),
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DecodeError::WrongOwner(owner) => write!(f, "account is owned by {owner}"),
+            DecodeError::WrongDiscriminator => write!(f, "account is of another type"),
+            DecodeError::WrongLength(len) => write!(f, "account data is {len} bytes"),
+        }
+    }
+}
+
+impl std::error::Error for DecodeError {}
+
+/// Why an account could not be fetched
+#[derive(Debug)]
+pub enum FetchError {
+    /// The RPC request failed
+    Rpc(Box<ClientError>),
+
+    /// The account exists but did not decode
+    Decode(Pubkey, DecodeError),
+}
+
+impl fmt::Display for FetchError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FetchError::Rpc(e) => write!(f, "RPC error: {e}"),
+            FetchError::Decode(address, e) => write!(f, "{address}: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for FetchError {}
+
+impl From<ClientError> for FetchError {
+    fn from(e: ClientError) -> Self {
+        FetchError::Rpc(Box::new(e))
+    }
+}
+
+/// Decode one of the program's accounts, zero-filling the fields a layout
+/// older than the current one lacks
+pub fn decode_program_account<T: ProgramAccount>(account: &Account) -> Result<T, DecodeError> {
+    if account.owner != PROGRAM_ID {
+        return Err(DecodeError::WrongOwner(account.owner));
+    }
+    let data = &account.data;
+    if data.len() < T::MIN_LEN || data.len() > T::LEN {
+        return Err(DecodeError::WrongLength(data.len()));
+    }
+    if data[0] != T::DISCRIMINATOR as u8 {
+        return Err(DecodeError::WrongDiscriminator);
+    }
+    let mut decoded = T::zeroed();
+    bytemuck::bytes_of_mut(&mut decoded)[..data.len()].copy_from_slice(data);
+    Ok(decoded)
+}
+
+/// Decode an account of the ORE deployment `ore_program`
+pub fn decode_ore_account<T: OreAccountType>(
+    ore_program: &Pubkey,
+    account: &Account,
+) -> Result<T, DecodeError> {
+    if &account.owner != ore_program {
+        return Err(DecodeError::WrongOwner(account.owner));
+    }
+    let data = &account.data;
+    if data.len() < T::LEN {
+        return Err(DecodeError::WrongLength(data.len()));
+    }
+    if data[..8] != T::KIND.discriminator() {
+        return Err(DecodeError::WrongDiscriminator);
+    }
+    Ok(bytemuck::pod_read_unaligned(&data[..T::LEN]))
+}
+
+/// Fetch and decode one of the program's accounts, `None` if it doesn't exist
+pub fn fetch_program_account<T: ProgramAccount>(
+    rpc: &RpcClient,
+    address: &Pubkey,
+) -> Result<Option<T>, FetchError> {
+    let Some(account) = fetch_account(rpc, address)? else {
+        return Ok(None);
+    };
+    decode_program_account(&account)
+        .map(Some)
+        .map_err(|e| FetchError::Decode(*address, e))
+}
+
+/// Fetch and decode an account of `ore_program`, `None` if it doesn't exist
+pub fn fetch_ore_account<T: OreAccountType>(
+    rpc: &RpcClient,
+    ore_program: &Pubkey,
+    address: &Pubkey,
+) -> Result<Option<T>, FetchError> {
+    let Some(account) = fetch_account(rpc, address)? else {
+        return Ok(None);
+    };
+    decode_ore_account(ore_program, &account)
+        .map(Some)
+        .map_err(|e| FetchError::Decode(*address, e))
+}
+
+/// The GlobalConfig
+pub fn fetch_config(rpc: &RpcClient) -> Result<Option<GlobalConfig>, FetchError> {
+    fetch_program_account(rpc, &pda::config_address().0)
+}
+
+/// `authority`'s UserStats
+pub fn fetch_user_stats(
+    rpc: &RpcClient,
+    authority: &Pubkey,
+) -> Result<Option<UserStats>, FetchError> {
+    fetch_program_account(rpc, &pda::user_stats_address(authority).0)
+}
+
+/// `authority`'s Bankroll
+pub fn fetch_bankroll(rpc: &RpcClient, authority: &Pubkey) -> Result<Option<Bankroll>, FetchError> {
+    fetch_program_account(rpc, &pda::bankroll_address(authority).0)
+}
+
+/// `authority`'s DeployNonce
+pub fn fetch_deploy_nonce(
+    rpc: &RpcClient,
+    authority: &Pubkey,
+) -> Result<Option<DeployNonce>, FetchError> {
+    fetch_program_account(rpc, &pda::deploy_nonce_address(authority).0)
+}
+
+/// `authority`'s Schedule
+pub fn fetch_schedule(rpc: &RpcClient, authority: &Pubkey) -> Result<Option<Schedule>, FetchError> {
+    fetch_program_account(rpc, &pda::schedule_address(authority).0)
+}
+
+/// `authority`'s RoundExposure in `round_id`
+pub fn fetch_round_exposure(
+    rpc: &RpcClient,
+    authority: &Pubkey,
+    round_id: u64,
+) -> Result<Option<RoundExposure>, FetchError> {
+    fetch_program_account(rpc, &pda::round_exposure_address(authority, round_id).0)
+}
+
+/// `authority`'s RoundRecord of `round_id`
+pub fn fetch_round_record(
+    rpc: &RpcClient,
+    authority: &Pubkey,
+    round_id: u64,
+) -> Result<Option<RoundRecord>, FetchError> {
+    fetch_program_account(rpc, &pda::round_record_address(authority, round_id).0)
+}
+
+/// Every RoundRecord of `authority`, in no particular order
+pub fn fetch_round_records(
+    rpc: &RpcClient,
+    authority: &Pubkey,
+) -> Result<Vec<RoundRecord>, FetchError> {
+    let filters = vec![
+        RpcFilterType::Memcmp(Memcmp::new_raw_bytes(
+            0,
+            vec![AccountDiscriminator::RoundRecord as u8],
+        )),
+        RpcFilterType::Memcmp(Memcmp::new_raw_bytes(
+            core::mem::offset_of!(RoundRecord, authority),
+            authority.to_bytes().to_vec(),
+        )),
+    ];
+    let config = RpcProgramAccountsConfig {
+        filters: Some(filters),
+        account_config: RpcAccountInfoConfig {
+            encoding: Some(UiAccountEncoding::Base64),
+            commitment: Some(rpc.commitment()),
+            ..RpcAccountInfoConfig::default()
+        },
+        ..RpcProgramAccountsConfig::default()
+    };
+    rpc.get_program_accounts_with_config(&PROGRAM_ID, config)?
+        .into_iter()
+        .map(|(address, account)| {
+            decode_program_account(&account).map_err(|e| FetchError::Decode(address, e))
+        })
+        .collect()
+}
+
+/// The global Leaderboard
+pub fn fetch_leaderboard(rpc: &RpcClient) -> Result<Option<Leaderboard>, FetchError> {
+    fetch_program_account(rpc, &pda::leaderboard_address().0)
+}
+
+/// The board of `ore_program`
+pub fn fetch_board(rpc: &RpcClient, ore_program: &Pubkey) -> Result<Option<OreBoard>, FetchError> {
+    fetch_ore_account(rpc, ore_program, &pda::board_address(ore_program).0)
+}
+
+/// Round `round_id` of `ore_program`
+pub fn fetch_round(
+    rpc: &RpcClient,
+    ore_program: &Pubkey,
+    round_id: u64,
+) -> Result<Option<OreRound>, FetchError> {
+    fetch_ore_account(
+        rpc,
+        ore_program,
+        &pda::round_address(ore_program, round_id).0,
+    )
+}
+
+/// The board of `ore_program` and its current round
+pub fn fetch_current_round(
+    rpc: &RpcClient,
+    ore_program: &Pubkey,
+) -> Result<Option<(OreBoard, OreRound)>, FetchError> {
+    let Some(board) = fetch_board(rpc, ore_program)? else {
+        return Ok(None);
+    };
+    Ok(fetch_round(rpc, ore_program, board.round_id)?.map(|round| (board, round)))
+}
+
+/// `authority`'s miner under `ore_program`, `None` before its first deploy
+pub fn fetch_miner(
+    rpc: &RpcClient,
+    ore_program: &Pubkey,
+    authority: &Pubkey,
+) -> Result<Option<OreMiner>, FetchError> {
+    fetch_ore_account(
+        rpc,
+        ore_program,
+        &pda::miner_address(ore_program, authority).0,
+    )
+}
+
+/// `authority`'s automation under `ore_program`
+pub fn fetch_automation(
+    rpc: &RpcClient,
+    ore_program: &Pubkey,
+    authority: &Pubkey,
+) -> Result<Option<OreAutomation>, FetchError> {
+    fetch_ore_account(
+        rpc,
+        ore_program,
+        &pda::automation_address(ore_program, authority).0,
+    )
+}
+
+fn fetch_account(rpc: &RpcClient, address: &Pubkey) -> Result<Option<Account>, FetchError> {
+    Ok(rpc
+        .get_account_with_commitment(address, rpc.commitment())?
+        .value)
+}
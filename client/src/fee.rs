@@ -0,0 +1,83 @@
+//! Priority fees for landing deploys at round end: a percentile of the
+//! fees recent transactions paid to write-lock the same accounts, bounded
+//! by a floor and a cap on the total fee
+
+use solana_client::{client_error::ClientError, rpc_client::RpcClient};
+use solana_sdk::pubkey::Pubkey;
+
+/// Compute-unit limit the runtime applies to a transaction that sets none,
+/// per instruction
+pub const DEFAULT_INSTRUCTION_COMPUTE_UNITS: u32 = 200_000;
+
+const MICRO_LAMPORTS_PER_LAMPORT: u128 = 1_000_000;
+
+/// How a compute-unit price is chosen from recent fees
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct FeePolicy {
+    /// Percentile of recent per-slot fees to pay, in bps (7_500 pays more
+    /// than three quarters of recent slots' landing fee)
+    pub percentile_bps: u16,
+
+    /// Least price paid, in micro-lamports per compute unit, even when
+    /// recent slots were uncontended
+    pub min_micro_lamports: u64,
+
+    /// Most lamports the priority fee may total across the transaction's
+    /// compute-unit limit; 0 for no cap
+    pub max_fee_lamports: u64,
+}
+
+impl Default for FeePolicy {
+    fn default() -> Self {
+        FeePolicy {
+            percentile_bps: 7_500,
+            min_micro_lamports: 0,
+            max_fee_lamports: 0,
+        }
+    }
+}
+
+impl FeePolicy {
+    /// Price per compute unit for a transaction requesting `compute_units`,
+    /// given the fees of recent slots
+    pub fn compute_unit_price(&self, recent_fees: &[u64], compute_units: u32) -> u64 {
+        let price = percentile(recent_fees, self.percentile_bps).max(self.min_micro_lamports);
+        if self.max_fee_lamports == 0 || compute_units == 0 {
+            return price;
+        }
+        let cap =
+            self.max_fee_lamports as u128 * MICRO_LAMPORTS_PER_LAMPORT / compute_units as u128;
+        price.min(cap.min(u64::MAX as u128) as u64)
+    }
+}
+
+/// The `percentile_bps` percentile of `fees` (nearest rank, rounding down),
+/// 0 when there are none
+pub fn percentile(fees: &[u64], percentile_bps: u16) -> u64 {
+    if fees.is_empty() {
+        return 0;
+    }
+    let mut sorted = fees.to_vec();
+    sorted.sort_unstable();
+    let rank = (sorted.len() - 1) * percentile_bps.min(10_000) as usize / 10_000;
+    sorted[rank]
+}
+
+/// Lamports a priority fee of `micro_lamports` per unit costs over
+/// `compute_units`, rounded up as the runtime charges it
+pub fn priority_fee_lamports(micro_lamports: u64, compute_units: u32) -> u64 {
+    let micro = micro_lamports as u128 * compute_units as u128;
+    micro
+        .div_ceil(MICRO_LAMPORTS_PER_LAMPORT)
+        .min(u64::MAX as u128) as u64
+}
+
+/// Per-slot fees recent transactions write-locking any of `accounts` paid
+/// to land, in micro-lamports per compute unit
+pub fn recent_fees(rpc: &RpcClient, accounts: &[Pubkey]) -> Result<Vec<u64>, ClientError> {
+    Ok(rpc
+        .get_recent_prioritization_fees(accounts)?
+        .into_iter()
+        .map(|fee| fee.prioritization_fee)
+        .collect())
+}
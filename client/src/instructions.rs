@@ -0,0 +1,502 @@
+//! Typed builders for every instruction of the program, with the account
+//! lists laid out as the program reads them
+
+use bytemuck::Pod;
+use solana_pinocchio_starter::{
+    instruction::{
+        ClaimScheduleIxData, ConfigIxDataV2, ConfigIxDataV3, ConfigIxDataV4, CreateScheduleIxData,
+        DepositScheduleIxData, OreDeployIxData, OreDeployIxDataV2, OreDeployIxDataV3,
+        SetBankrollIxData, SettleRoundIxData, FLAG_AUTO_EV_THRESHOLD, FLAG_BANKROLL_SIZING,
+        FLAG_DRAWDOWN_SIZING, FLAG_NONCE, FLAG_ROUND_RECORD, FLAG_STORE_PLAN, FLAG_TRACK_STATS,
+        FLAG_USD_BUDGET, SCHEDULE_CLAIM_FLAG_ORE, SETTLE_FLAG_BANKROLL, SETTLE_FLAG_USER_STATS,
+    },
+    state::ConfigParams,
+};
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    system_program,
+};
+
+use crate::{
+    instruction as discriminator, pda, ASSOCIATED_TOKEN_PROGRAM_ID, ENTROPY_PROGRAM_ID, ORE_MINT,
+    ORE_PROGRAM_ID, PROGRAM_ID, TOKEN_PROGRAM_ID,
+};
+
+/// OreDeploy data in any of its layouts, selected on chain by length
+#[derive(Clone, Copy, Debug)]
+pub enum DeployData {
+    V1(OreDeployIxData),
+    /// With per-rank EV thresholds
+    V2(OreDeployIxDataV2),
+    /// With per-rank EV thresholds and a block quote
+    V3(OreDeployIxDataV3),
+}
+
+impl DeployData {
+    /// The parameters every layout starts with
+    pub fn base(&self) -> &OreDeployIxData {
+        match self {
+            DeployData::V1(data) => data,
+            DeployData::V2(data) => &data.base,
+            DeployData::V3(data) => &data.v2.base,
+        }
+    }
+
+    fn bytes(&self) -> &[u8] {
+        match self {
+            DeployData::V1(data) => bytemuck::bytes_of(data),
+            DeployData::V2(data) => bytemuck::bytes_of(data),
+            DeployData::V3(data) => bytemuck::bytes_of(data),
+        }
+    }
+}
+
+impl From<OreDeployIxData> for DeployData {
+    fn from(data: OreDeployIxData) -> Self {
+        DeployData::V1(data)
+    }
+}
+
+impl From<OreDeployIxDataV2> for DeployData {
+    fn from(data: OreDeployIxDataV2) -> Self {
+        DeployData::V2(data)
+    }
+}
+
+impl From<OreDeployIxDataV3> for DeployData {
+    fn from(data: OreDeployIxDataV3) -> Self {
+        DeployData::V3(data)
+    }
+}
+
+/// InitializeConfig / UpdateConfig data in any of its layouts; shorter
+/// layouts leave the trailing fields as they are
+#[derive(Clone, Copy, Debug)]
+pub enum ConfigData {
+    V1(ConfigParams),
+    /// With the ORE program override
+    V2(ConfigIxDataV2),
+    /// With the override and the payout multiplier table
+    V3(ConfigIxDataV3),
+    /// With the override, the multipliers and the ORE environments
+    V4(ConfigIxDataV4),
+}
+
+impl ConfigData {
+    fn bytes(&self) -> &[u8] {
+        match self {
+            ConfigData::V1(data) => bytemuck::bytes_of(data),
+            ConfigData::V2(data) => bytemuck::bytes_of(data),
+            ConfigData::V3(data) => bytemuck::bytes_of(data),
+            ConfigData::V4(data) => bytemuck::bytes_of(data),
+        }
+    }
+}
+
+impl From<ConfigParams> for ConfigData {
+    fn from(data: ConfigParams) -> Self {
+        ConfigData::V1(data)
+    }
+}
+
+impl From<ConfigIxDataV2> for ConfigData {
+    fn from(data: ConfigIxDataV2) -> Self {
+        ConfigData::V2(data)
+    }
+}
+
+impl From<ConfigIxDataV3> for ConfigData {
+    fn from(data: ConfigIxDataV3) -> Self {
+        ConfigData::V3(data)
+    }
+}
+
+impl From<ConfigIxDataV4> for ConfigData {
+    fn from(data: ConfigIxDataV4) -> Self {
+        ConfigData::V4(data)
+    }
+}
+
+/// Accounts of an OreDeploy into one round, with the mode-specific accounts
+/// its flags select
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct DeployAccounts {
+    /// ORE deployment deployed into
+    pub ore_program: Pubkey,
+
+    /// Entropy program the deployment's boards draw from
+    pub entropy_program: Pubkey,
+
+    /// Pays for each deploy and any PDAs created along the way
+    pub signer: Pubkey,
+
+    /// Owns the miner the deployments land on
+    pub authority: Pubkey,
+
+    /// The board's current round
+    pub round_id: u64,
+
+    /// SOL/USD `PriceUpdateV2` account, passed with `FLAG_USD_BUDGET`
+    pub price_feed: Option<Pubkey>,
+
+    /// Pass the authority's RoundExposure; the program requires it when the
+    /// config caps lamports per round
+    pub round_exposure: bool,
+
+    /// Pass the event authority and this program, for `event-cpi` builds
+    pub event_cpi: bool,
+}
+
+impl DeployAccounts {
+    /// ORE v3 accounts for `authority` deploying and paying for itself
+    pub fn new(authority: Pubkey, round_id: u64) -> Self {
+        DeployAccounts {
+            ore_program: ORE_PROGRAM_ID,
+            entropy_program: ENTROPY_PROGRAM_ID,
+            signer: authority,
+            authority,
+            round_id,
+            price_feed: None,
+            round_exposure: false,
+            event_cpi: false,
+        }
+    }
+
+    /// Account metas for a deploy with `flags`; `signed` marks `signer` and
+    /// `authority` as transaction signers, which a PDA signing through the
+    /// program is not
+    fn metas(&self, flags: u32, signed: bool) -> Vec<AccountMeta> {
+        let board = pda::board_address(&self.ore_program).0;
+        let mut metas = vec![
+            AccountMeta::new_readonly(self.ore_program, false),
+            AccountMeta::new(self.signer, signed),
+            AccountMeta::new(self.authority, signed),
+            AccountMeta::new(
+                pda::automation_address(&self.ore_program, &self.authority).0,
+                false,
+            ),
+            AccountMeta::new(board, false),
+            AccountMeta::new(
+                pda::miner_address(&self.ore_program, &self.authority).0,
+                false,
+            ),
+            AccountMeta::new(
+                pda::round_address(&self.ore_program, self.round_id).0,
+                false,
+            ),
+            AccountMeta::new_readonly(system_program::ID, false),
+            AccountMeta::new(
+                pda::entropy_var_address(&self.entropy_program, &board).0,
+                false,
+            ),
+            AccountMeta::new_readonly(self.entropy_program, false),
+            AccountMeta::new_readonly(pda::config_address().0, false),
+        ];
+
+        if flags & FLAG_USD_BUDGET != 0 {
+            if let Some(price_feed) = self.price_feed {
+                metas.push(AccountMeta::new_readonly(price_feed, false));
+            }
+        }
+        if self.round_exposure {
+            let exposure = pda::round_exposure_address(&self.authority, self.round_id).0;
+            metas.push(AccountMeta::new(exposure, false));
+        }
+        if flags & (FLAG_DRAWDOWN_SIZING | FLAG_AUTO_EV_THRESHOLD | FLAG_TRACK_STATS) != 0 {
+            let stats = pda::user_stats_address(&self.authority).0;
+            metas.push(AccountMeta::new(stats, false));
+        }
+        if flags & FLAG_BANKROLL_SIZING != 0 {
+            let bankroll = pda::bankroll_address(&self.authority).0;
+            metas.push(AccountMeta::new(bankroll, false));
+        }
+        if flags & FLAG_NONCE != 0 {
+            let nonce = pda::deploy_nonce_address(&self.authority).0;
+            metas.push(AccountMeta::new(nonce, false));
+        }
+        if flags & FLAG_ROUND_RECORD != 0 {
+            let record = pda::round_record_address(&self.authority, self.round_id).0;
+            metas.push(AccountMeta::new(record, false));
+        }
+        if flags & FLAG_STORE_PLAN != 0 {
+            metas.push(AccountMeta::new(
+                pda::plan_address(&self.authority).0,
+                false,
+            ));
+        }
+        if self.event_cpi {
+            metas.push(AccountMeta::new_readonly(
+                pda::event_authority_address().0,
+                false,
+            ));
+            metas.push(AccountMeta::new_readonly(PROGRAM_ID, false));
+        }
+
+        metas
+    }
+}
+
+/// OreDeploy, sizing and placing a plan in one transaction
+pub fn ore_deploy(accounts: &DeployAccounts, data: impl Into<DeployData>) -> Instruction {
+    let data = data.into();
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: accounts.metas(data.base().flags, true),
+        data: with_discriminator(discriminator::ORE_DEPLOY, data.bytes()),
+    }
+}
+
+/// InitializeConfig, creating the GlobalConfig with `admin` as its admin
+pub fn initialize_config(admin: &Pubkey, data: impl Into<ConfigData>) -> Instruction {
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new(*admin, true),
+            AccountMeta::new(pda::config_address().0, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+        data: with_discriminator(discriminator::INITIALIZE_CONFIG, data.into().bytes()),
+    }
+}
+
+/// UpdateConfig, replacing the fields `data` carries
+pub fn update_config(admin: &Pubkey, data: impl Into<ConfigData>) -> Instruction {
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new_readonly(*admin, true),
+            AccountMeta::new(pda::config_address().0, false),
+        ],
+        data: with_discriminator(discriminator::UPDATE_CONFIG, data.into().bytes()),
+    }
+}
+
+/// SetBankroll, committing `capital` lamports for `authority`'s miner under
+/// `ore_program`
+pub fn set_bankroll(ore_program: &Pubkey, authority: &Pubkey, capital: u64) -> Instruction {
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new(*authority, true),
+            AccountMeta::new_readonly(*ore_program, false),
+            AccountMeta::new_readonly(pda::config_address().0, false),
+            AccountMeta::new_readonly(pda::miner_address(ore_program, authority).0, false),
+            AccountMeta::new(pda::bankroll_address(authority).0, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+        data: pod_data(discriminator::SET_BANKROLL, &SetBankrollIxData { capital }),
+    }
+}
+
+/// CreateSchedule, funding `rounds` deploys of `budget_per_round` with
+/// `strategy`
+pub fn create_schedule(
+    authority: &Pubkey,
+    budget_per_round: u64,
+    rounds: u32,
+    strategy: OreDeployIxData,
+) -> Instruction {
+    let schedule = pda::schedule_address(authority).0;
+    let data = CreateScheduleIxData {
+        budget_per_round,
+        rounds,
+        _padding: [0; 4],
+        strategy,
+    };
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new(*authority, true),
+            AccountMeta::new(schedule, false),
+            AccountMeta::new(pda::schedule_vault_address(&schedule).0, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+        data: pod_data(discriminator::CREATE_SCHEDULE, &data),
+    }
+}
+
+/// DepositSchedule, funding `rounds` more deploys of `authority`'s schedule
+/// at its `budget_per_round`
+pub fn deposit_schedule(authority: &Pubkey, rounds: u32) -> Instruction {
+    let schedule = pda::schedule_address(authority).0;
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new(*authority, true),
+            AccountMeta::new(schedule, false),
+            AccountMeta::new(pda::schedule_vault_address(&schedule).0, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+        data: pod_data(
+            discriminator::DEPOSIT_SCHEDULE,
+            &DepositScheduleIxData { rounds },
+        ),
+    }
+}
+
+/// ExecuteSchedule, cranking one round of `authority`'s schedule with
+/// `strategy_flags` (the schedule's `strategy.flags`)
+///
+/// `deploy` describes the round and deployment; its `signer` and `authority`
+/// are replaced by the schedule's vault.
+pub fn execute_schedule(
+    keeper: &Pubkey,
+    authority: &Pubkey,
+    deploy: &DeployAccounts,
+    strategy_flags: u32,
+) -> Instruction {
+    let schedule = pda::schedule_address(authority).0;
+    let vault = pda::schedule_vault_address(&schedule).0;
+    let deploy = DeployAccounts {
+        signer: vault,
+        authority: vault,
+        ..*deploy
+    };
+
+    let mut accounts = vec![
+        AccountMeta::new(*keeper, true),
+        AccountMeta::new(schedule, false),
+    ];
+    accounts.extend(deploy.metas(strategy_flags, false));
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts,
+        data: vec![discriminator::EXECUTE_SCHEDULE],
+    }
+}
+
+/// ClaimSchedule, paying what the vault's miner under `ore_program` has
+/// been credited out to `authority`
+///
+/// `flags` are `SCHEDULE_CLAIM_FLAG_*` bits; with the ORE flag the ORE goes
+/// to `authority`'s ORE token account, which must already exist.
+pub fn claim_schedule(ore_program: &Pubkey, authority: &Pubkey, flags: u8) -> Instruction {
+    let schedule = pda::schedule_address(authority).0;
+    let vault = pda::schedule_vault_address(&schedule).0;
+    let mut accounts = schedule_vault_metas(ore_program, authority);
+    if flags & SCHEDULE_CLAIM_FLAG_ORE != 0 {
+        let treasury = pda::treasury_address(ore_program).0;
+        accounts.extend([
+            AccountMeta::new(ORE_MINT, false),
+            AccountMeta::new(pda::associated_token_address(&vault, &ORE_MINT).0, false),
+            AccountMeta::new(treasury, false),
+            AccountMeta::new(pda::associated_token_address(&treasury, &ORE_MINT).0, false),
+            AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+            AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),
+            AccountMeta::new(pda::associated_token_address(authority, &ORE_MINT).0, false),
+        ]);
+    }
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts,
+        data: pod_data(
+            discriminator::CLAIM_SCHEDULE,
+            &ClaimScheduleIxData { flags },
+        ),
+    }
+}
+
+/// CloseSchedule, returning the vault and the schedule's rent to `authority`;
+/// the vault's miner under `ore_program` must have nothing left to claim
+pub fn close_schedule(ore_program: &Pubkey, authority: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: schedule_vault_metas(ore_program, authority),
+        data: vec![discriminator::CLOSE_SCHEDULE],
+    }
+}
+
+/// The accounts ClaimSchedule and CloseSchedule lead with: the schedule and
+/// its vault, and the vault's ORE miner
+fn schedule_vault_metas(ore_program: &Pubkey, authority: &Pubkey) -> Vec<AccountMeta> {
+    let schedule = pda::schedule_address(authority).0;
+    let vault = pda::schedule_vault_address(&schedule).0;
+    vec![
+        AccountMeta::new(*authority, true),
+        AccountMeta::new(schedule, false),
+        AccountMeta::new(vault, false),
+        AccountMeta::new_readonly(system_program::ID, false),
+        AccountMeta::new_readonly(*ore_program, false),
+        AccountMeta::new_readonly(pda::config_address().0, false),
+        AccountMeta::new(pda::miner_address(ore_program, &vault).0, false),
+    ]
+}
+
+/// SettleRound, filling in `authority`'s RoundRecord of a drawn round
+///
+/// `flags` are `SETTLE_FLAG_*` bits; `leaderboard` passes the Leaderboard,
+/// which the program requires when the round opted into it.
+pub fn settle_round(
+    ore_program: &Pubkey,
+    authority: &Pubkey,
+    round_id: u64,
+    flags: u8,
+    leaderboard: bool,
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new_readonly(*ore_program, false),
+        AccountMeta::new_readonly(pda::config_address().0, false),
+        AccountMeta::new_readonly(pda::round_address(ore_program, round_id).0, false),
+        AccountMeta::new_readonly(*authority, false),
+        AccountMeta::new(pda::round_record_address(authority, round_id).0, false),
+    ];
+    if flags & (SETTLE_FLAG_USER_STATS | SETTLE_FLAG_BANKROLL) != 0 {
+        let miner = pda::miner_address(ore_program, authority).0;
+        accounts.push(AccountMeta::new_readonly(miner, false));
+    }
+    if flags & SETTLE_FLAG_USER_STATS != 0 {
+        let stats = pda::user_stats_address(authority).0;
+        accounts.push(AccountMeta::new(stats, false));
+    }
+    if flags & SETTLE_FLAG_BANKROLL != 0 {
+        let bankroll = pda::bankroll_address(authority).0;
+        accounts.push(AccountMeta::new(bankroll, false));
+    }
+    if leaderboard {
+        accounts.push(AccountMeta::new(pda::leaderboard_address().0, false));
+    }
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts,
+        data: pod_data(discriminator::SETTLE_ROUND, &SettleRoundIxData { flags }),
+    }
+}
+
+/// InitializeLeaderboard, creating the global Leaderboard paid by `payer`
+pub fn initialize_leaderboard(payer: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new(*payer, true),
+            AccountMeta::new(pda::leaderboard_address().0, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+        data: vec![discriminator::INITIALIZE_LEADERBOARD],
+    }
+}
+
+/// MigrateAccount, upgrading `account` to its current layout with `payer`
+/// funding any growth
+pub fn migrate_account(payer: &Pubkey, account: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new(*payer, true),
+            AccountMeta::new(*account, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+        data: vec![discriminator::MIGRATE_ACCOUNT],
+    }
+}
+
+fn pod_data<T: Pod>(discriminator: u8, data: &T) -> Vec<u8> {
+    with_discriminator(discriminator, bytemuck::bytes_of(data))
+}
+
+fn with_discriminator(discriminator: u8, data: &[u8]) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(1 + data.len());
+    bytes.push(discriminator);
+    bytes.extend_from_slice(data);
+    bytes
+}
@@ -0,0 +1,176 @@
+//! Submitting deploys as Jito bundles: a tip transfer to one of the block
+//! engine's tip accounts rides in the deploy transaction, and the bundle is
+//! sent to block engines rather than RPC, which lands more reliably in the
+//! last slots of a round
+
+use std::fmt;
+
+use base64::{engine::general_purpose::STANDARD, Engine};
+use serde_json::{json, Value};
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey,
+    pubkey::Pubkey,
+    system_program,
+    transaction::VersionedTransaction,
+};
+
+/// Most transactions a block engine accepts in one bundle
+pub const MAX_BUNDLE_TRANSACTIONS: usize = 5;
+
+/// Least tip a block engine accepts, in lamports
+pub const MIN_TIP_LAMPORTS: u64 = 1_000;
+
+/// Mainnet block engine
+pub const MAINNET_BLOCK_ENGINE_URL: &str = "https://mainnet.block-engine.jito.wtf";
+
+/// Accounts tips are paid to; spreading tips across them avoids contending
+/// with other searchers for one write lock
+pub const TIP_ACCOUNTS: [Pubkey; 8] = [
+    pubkey!("96gYZGLnJYVFmbjzopPSU6QiEV5fGqZNyN9nmNhvrZU5"),
+    pubkey!("HFqU5x63VTqvQss8hp11i4wVV8bD44PvwucfZ2bU7gRe"),
+    pubkey!("Cw8CFyM9FkoMi7K7Crf6HNQqf4uEMzpKw6QNghXLvLkY"),
+    pubkey!("ADaUMid9yfUytqMBgopwjb2DTLSokTSzL1zt6iGPaS49"),
+    pubkey!("DfXygSm4jCyNCybVYYK6DwvWqjKee8pbDmJGcLWNDXjh"),
+    pubkey!("ADuUkR4vqLUMWXxW9gh6D6L8pMSawimctcNZ5pGwDcEt"),
+    pubkey!("DttWaMuVvTiduZRnguLF7jNxTgiMBZ1hyAumKUiL2KRL"),
+    pubkey!("3AVi9Tg9Uo68tJfuvoKvqKNWKkC5wPdSSdeBnizKZ6jT"),
+];
+
+/// Tip account for the `index`th bundle, cycling through all of them
+pub fn tip_account(index: u64) -> Pubkey {
+    TIP_ACCOUNTS[(index % TIP_ACCOUNTS.len() as u64) as usize]
+}
+
+/// Transfer of `lamports` from `payer` to `tip_account`, appended to the
+/// deploy so the tip is only paid if the deploy lands
+pub fn tip_instruction(payer: &Pubkey, tip_account: &Pubkey, lamports: u64) -> Instruction {
+    // SystemInstruction::Transfer: u32 variant index 2, then the lamports
+    let mut data = Vec::with_capacity(12);
+    data.extend_from_slice(&2u32.to_le_bytes());
+    data.extend_from_slice(&lamports.to_le_bytes());
+    Instruction {
+        program_id: system_program::ID,
+        accounts: vec![
+            AccountMeta::new(*payer, true),
+            AccountMeta::new(*tip_account, false),
+        ],
+        data,
+    }
+}
+
+/// Why a bundle was not accepted
+#[derive(Debug)]
+pub enum JitoError {
+    /// A bundle holds 1 to `MAX_BUNDLE_TRANSACTIONS` transactions
+    BundleSize(usize),
+
+    /// A transaction did not serialize
+    Serialize(bincode::Error),
+
+    /// The block engine could not be reached or answered garbage
+    Http(reqwest::Error),
+
+    /// The block engine rejected the bundle
+    Rejected { code: i64, message: String },
+}
+
+impl fmt::Display for JitoError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            JitoError::BundleSize(len) => write!(f, "bundle of {len} transactions"),
+            JitoError::Serialize(e) => write!(f, "transaction did not serialize: {e}"),
+            JitoError::Http(e) => write!(f, "block engine request failed: {e}"),
+            JitoError::Rejected { code, message } => {
+                write!(f, "bundle rejected ({code}): {message}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for JitoError {}
+
+impl From<reqwest::Error> for JitoError {
+    fn from(e: reqwest::Error) -> Self {
+        JitoError::Http(e)
+    }
+}
+
+/// Base64 wire encodings of `transactions`, executed in order as one bundle
+pub fn encode_bundle(transactions: &[VersionedTransaction]) -> Result<Vec<String>, JitoError> {
+    if transactions.is_empty() || transactions.len() > MAX_BUNDLE_TRANSACTIONS {
+        return Err(JitoError::BundleSize(transactions.len()));
+    }
+    transactions
+        .iter()
+        .map(|tx| {
+            bincode::serialize(tx)
+                .map(|bytes| STANDARD.encode(bytes))
+                .map_err(JitoError::Serialize)
+        })
+        .collect()
+}
+
+/// A block engine's JSON-RPC bundle endpoint
+#[derive(Clone, Debug)]
+pub struct BlockEngine {
+    url: String,
+    http: reqwest::blocking::Client,
+}
+
+impl BlockEngine {
+    /// The block engine at `url` (e.g. `MAINNET_BLOCK_ENGINE_URL` or a
+    /// regional one)
+    pub fn new(url: impl Into<String>) -> Self {
+        BlockEngine {
+            url: url.into(),
+            http: reqwest::blocking::Client::new(),
+        }
+    }
+
+    /// Submit `transactions` as one bundle and return its id
+    pub fn send_bundle(&self, transactions: &[VersionedTransaction]) -> Result<String, JitoError> {
+        let params = json!([encode_bundle(transactions)?, { "encoding": "base64" }]);
+        let result = self.call("sendBundle", params)?;
+        Ok(result.as_str().unwrap_or_default().to_string())
+    }
+
+    /// The bundle's landing status, `None` until it has landed
+    pub fn bundle_status(&self, bundle_id: &str) -> Result<Option<Value>, JitoError> {
+        let result = self.call("getBundleStatuses", json!([[bundle_id]]))?;
+        Ok(result["value"]
+            .as_array()
+            .and_then(|statuses| statuses.first())
+            .filter(|status| !status.is_null())
+            .cloned())
+    }
+
+    fn call(&self, method: &str, params: Value) -> Result<Value, JitoError> {
+        let body = json!({ "jsonrpc": "2.0", "id": 1, "method": method, "params": params });
+        let response: Value = self
+            .http
+            .post(format!("{}/api/v1/bundles", self.url))
+            .json(&body)
+            .send()?
+            .json()?;
+        if let Some(error) = response.get("error") {
+            return Err(JitoError::Rejected {
+                code: error["code"].as_i64().unwrap_or_default(),
+                message: error["message"].as_str().unwrap_or_default().to_string(),
+            });
+        }
+        Ok(response["result"].clone())
+    }
+}
+
+/// Submit the same bundle to every engine in `engines`; the bundle lands at
+/// most once, so only one acceptance is needed
+pub fn send_bundle_to_all(
+    engines: &[BlockEngine],
+    transactions: &[VersionedTransaction],
+) -> Vec<Result<String, JitoError>> {
+    engines
+        .iter()
+        .map(|engine| engine.send_bundle(transactions))
+        .collect()
+}
@@ -0,0 +1,48 @@
+//! Off-chain tooling for the ORE EV program, built on the same allocation
+//! math the program runs (`ore-ev-math`): typed instruction builders, PDA
+//! derivation, account decoding and fetching, transaction assembly with
+//! priority fees, a WebSocket round watcher and a round simulator.
+
+pub mod accounts;
+pub mod fee;
+pub mod instructions;
+#[cfg(feature = "jito")]
+pub mod jito;
+pub mod ore;
+pub mod pda;
+#[cfg(feature = "python")]
+pub mod python;
+pub mod quote;
+pub mod sim;
+pub mod stats;
+pub mod transaction;
+pub mod watch;
+
+use solana_sdk::pubkey::Pubkey;
+
+/// Instruction discriminators, the first byte of the program's instruction data
+pub use ore_ev_math::instruction;
+pub use quote::{quote, QuoteState};
+pub use sim::{simulate, SimConfig, SimReport};
+
+/// The program's instruction data and account layouts
+pub use solana_pinocchio_starter::{instruction as ix_data, state};
+
+/// This program
+pub const PROGRAM_ID: Pubkey = Pubkey::new_from_array(solana_pinocchio_starter::ID);
+
+/// ORE v3
+pub const ORE_PROGRAM_ID: Pubkey = Pubkey::new_from_array(state::ORE_PROGRAM_ID);
+
+/// Entropy program ORE v3 draws each round's randomness from
+pub const ENTROPY_PROGRAM_ID: Pubkey = Pubkey::new_from_array(state::ENTROPY_PROGRAM_ID);
+
+/// The ORE token mint
+pub const ORE_MINT: Pubkey = solana_sdk::pubkey!("oreoU2P8bN6jkk3jbaiVxYnG1dCXcYxwhwyK9jSybcp");
+
+/// SPL Token program, which holds ORE
+pub const TOKEN_PROGRAM_ID: Pubkey = Pubkey::new_from_array(state::TOKEN_PROGRAM_ID);
+
+/// SPL Associated Token Account program, deriving each wallet's ORE account
+pub const ASSOCIATED_TOKEN_PROGRAM_ID: Pubkey =
+    solana_sdk::pubkey!("ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL");
@@ -0,0 +1,79 @@
+//! ORE's own miner instructions, for settling and claiming what deploys
+//! through the program won: Checkpoint credits a finished round's winnings
+//! to the miner and ClaimSOL pays them out
+
+use solana_pinocchio_starter::state::{OreBoard, OreMiner};
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    system_program,
+};
+
+use crate::pda;
+
+/// ORE's Checkpoint instruction discriminator
+pub const ORE_CHECKPOINT: u8 = 2;
+
+/// ORE's ClaimSOL instruction discriminator
+pub const ORE_CLAIM_SOL: u8 = 3;
+
+/// Checkpoint, crediting `authority`'s winnings from `round_id` to its miner
+pub fn checkpoint(
+    ore_program: &Pubkey,
+    signer: &Pubkey,
+    authority: &Pubkey,
+    round_id: u64,
+) -> Instruction {
+    Instruction {
+        program_id: *ore_program,
+        accounts: vec![
+            AccountMeta::new(*signer, true),
+            AccountMeta::new(pda::board_address(ore_program).0, false),
+            AccountMeta::new(pda::miner_address(ore_program, authority).0, false),
+            AccountMeta::new(pda::round_address(ore_program, round_id).0, false),
+            AccountMeta::new(pda::treasury_address(ore_program).0, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+        data: vec![ORE_CHECKPOINT],
+    }
+}
+
+/// ClaimSOL, paying `authority`'s claimable SOL to it
+pub fn claim_sol(ore_program: &Pubkey, authority: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: *ore_program,
+        accounts: vec![
+            AccountMeta::new(*authority, true),
+            AccountMeta::new(pda::miner_address(ore_program, authority).0, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+        data: vec![ORE_CLAIM_SOL],
+    }
+}
+
+/// The round `miner` still has to checkpoint: the last it played, once the
+/// board has moved past it
+pub fn pending_checkpoint(miner: &OreMiner, board: &OreBoard) -> Option<u64> {
+    let played = miner.round_id;
+    (miner.checkpoint_id < played && played < board.round_id).then_some(played)
+}
+
+/// Instructions settling and claiming `miner`'s winnings: a Checkpoint of
+/// its last round if pending, then ClaimSOL if there is anything to claim
+/// or the checkpoint may credit some
+pub fn claim_instructions(
+    ore_program: &Pubkey,
+    authority: &Pubkey,
+    miner: &OreMiner,
+    board: &OreBoard,
+) -> Vec<Instruction> {
+    let mut instructions = Vec::new();
+    let pending = pending_checkpoint(miner, board);
+    if let Some(round_id) = pending {
+        instructions.push(checkpoint(ore_program, authority, authority, round_id));
+    }
+    if pending.is_some() || miner.rewards_sol != 0 {
+        instructions.push(claim_sol(ore_program, authority));
+    }
+    instructions
+}
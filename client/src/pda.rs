@@ -0,0 +1,133 @@
+//! Addresses of the program's PDAs and the ORE accounts a deploy touches,
+//! each returned with its bump as `find_program_address` does
+
+use solana_pinocchio_starter::state::{
+    AUTOMATION_SEED, BANKROLL_SEED, BOARD_SEED, DEPLOY_NONCE_SEED, ENTROPY_VAR_SEED,
+    GLOBAL_CONFIG_SEED, LEADERBOARD_SEED, MINER_SEED, ORE_ENTROPY_VAR_ID, PLAN_SEED,
+    ROUND_EXPOSURE_SEED, ROUND_RECORD_SEED, ROUND_SEED, SCHEDULE_SEED, SCHEDULE_VAULT_SEED,
+    USER_STATS_SEED,
+};
+use solana_sdk::pubkey::Pubkey;
+
+use crate::{ASSOCIATED_TOKEN_PROGRAM_ID, PROGRAM_ID, TOKEN_PROGRAM_ID};
+
+/// Seed of the event authority PDA (`events::cpi::EVENT_AUTHORITY_SEED`,
+/// compiled only into `event-cpi` builds)
+const EVENT_AUTHORITY_SEED: &[u8] = b"__event_authority";
+
+/// Seed of ORE's treasury, which pays out claimed ORE
+const TREASURY_SEED: &[u8] = b"treasury";
+
+/// The GlobalConfig, `["config"]`
+pub fn config_address() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[GLOBAL_CONFIG_SEED], &PROGRAM_ID)
+}
+
+/// The global Leaderboard, `["leaderboard"]`
+pub fn leaderboard_address() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[LEADERBOARD_SEED], &PROGRAM_ID)
+}
+
+/// `authority`'s UserStats, `["stats", authority]`
+pub fn user_stats_address(authority: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[USER_STATS_SEED, authority.as_ref()], &PROGRAM_ID)
+}
+
+/// `authority`'s Bankroll, `["bankroll", authority]`
+pub fn bankroll_address(authority: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[BANKROLL_SEED, authority.as_ref()], &PROGRAM_ID)
+}
+
+/// `authority`'s DeployNonce, `["nonce", authority]`
+pub fn deploy_nonce_address(authority: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[DEPLOY_NONCE_SEED, authority.as_ref()], &PROGRAM_ID)
+}
+
+/// `authority`'s stored Plan, `["plan", authority]`
+pub fn plan_address(authority: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[PLAN_SEED, authority.as_ref()], &PROGRAM_ID)
+}
+
+/// `authority`'s Schedule, `["schedule", authority]`
+pub fn schedule_address(authority: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[SCHEDULE_SEED, authority.as_ref()], &PROGRAM_ID)
+}
+
+/// The vault a schedule deploys from, `["vault", schedule]`
+pub fn schedule_vault_address(schedule: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[SCHEDULE_VAULT_SEED, schedule.as_ref()], &PROGRAM_ID)
+}
+
+/// `authority`'s RoundExposure in a round, `["exposure", authority, round_id]`
+pub fn round_exposure_address(authority: &Pubkey, round_id: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            ROUND_EXPOSURE_SEED,
+            authority.as_ref(),
+            &round_id.to_le_bytes(),
+        ],
+        &PROGRAM_ID,
+    )
+}
+
+/// `authority`'s RoundRecord of a round, `["record", authority, round_id]`
+pub fn round_record_address(authority: &Pubkey, round_id: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            ROUND_RECORD_SEED,
+            authority.as_ref(),
+            &round_id.to_le_bytes(),
+        ],
+        &PROGRAM_ID,
+    )
+}
+
+/// The event authority signing event self-CPIs, `["__event_authority"]`
+pub fn event_authority_address() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[EVENT_AUTHORITY_SEED], &PROGRAM_ID)
+}
+
+/// ORE's board, `["board"]` under `ore_program`
+pub fn board_address(ore_program: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[BOARD_SEED], ore_program)
+}
+
+/// ORE's round account, `["round", round_id]` under `ore_program`
+pub fn round_address(ore_program: &Pubkey, round_id: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[ROUND_SEED, &round_id.to_le_bytes()], ore_program)
+}
+
+/// `authority`'s ORE miner, `["miner", authority]` under `ore_program`
+pub fn miner_address(ore_program: &Pubkey, authority: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[MINER_SEED, authority.as_ref()], ore_program)
+}
+
+/// ORE's treasury, `["treasury"]` under `ore_program`
+pub fn treasury_address(ore_program: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[TREASURY_SEED], ore_program)
+}
+
+/// `owner`'s associated token account for `mint`
+pub fn associated_token_address(owner: &Pubkey, mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[owner.as_ref(), TOKEN_PROGRAM_ID.as_ref(), mint.as_ref()],
+        &ASSOCIATED_TOKEN_PROGRAM_ID,
+    )
+}
+
+/// `authority`'s ORE automation, `["automation", authority]` under `ore_program`
+pub fn automation_address(ore_program: &Pubkey, authority: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[AUTOMATION_SEED, authority.as_ref()], ore_program)
+}
+
+/// The board's entropy var, `["var", board, 0u64]` under `entropy_program`
+pub fn entropy_var_address(entropy_program: &Pubkey, board: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            ENTROPY_VAR_SEED,
+            board.as_ref(),
+            &ORE_ENTROPY_VAR_ID.to_le_bytes(),
+        ],
+        entropy_program,
+    )
+}
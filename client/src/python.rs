@@ -0,0 +1,216 @@
+//! Python bindings for research and backtesting: ORE round and program
+//! config decoding, the EV math and deploy quotes
+//!
+//! Built as the `ore_ev_client` extension module with maturin (see
+//! `pyproject.toml`); amounts are lamports.
+
+use bytemuck::Zeroable;
+use ore_ev_math::Allocation;
+use pyo3::{exceptions::PyValueError, prelude::*};
+use solana_pinocchio_starter::{
+    instruction::OreDeployIxData,
+    state::{GlobalConfig, OreRound},
+};
+use solana_sdk::{account::Account, pubkey::Pubkey};
+
+use crate::{
+    accounts::{decode_ore_account, decode_program_account},
+    quote::{quote as quote_deploy, QuoteState},
+    ORE_PROGRAM_ID, PROGRAM_ID,
+};
+
+/// An ORE round account
+#[pyclass(name = "OreRound", frozen)]
+#[derive(Clone, Copy)]
+pub struct PyOreRound(pub OreRound);
+
+#[pymethods]
+impl PyOreRound {
+    /// Decode an ORE round account's data
+    #[staticmethod]
+    pub fn decode(data: &[u8]) -> PyResult<Self> {
+        decode_ore_account(&ORE_PROGRAM_ID, &account(data, ORE_PROGRAM_ID))
+            .map(PyOreRound)
+            .map_err(|e| PyValueError::new_err(e.to_string()))
+    }
+
+    #[getter]
+    pub fn id(&self) -> u64 {
+        self.0.id
+    }
+
+    /// Lamports deployed per square
+    #[getter]
+    pub fn deployed(&self) -> Vec<u64> {
+        self.0.deployed.to_vec()
+    }
+
+    /// Miners per square
+    #[getter]
+    pub fn count(&self) -> Vec<u64> {
+        self.0.count.to_vec()
+    }
+
+    #[getter]
+    pub fn motherlode(&self) -> u64 {
+        self.0.motherlode
+    }
+
+    #[getter]
+    pub fn total_deployed(&self) -> u64 {
+        self.0.total_deployed
+    }
+
+    #[getter]
+    pub fn total_winnings(&self) -> u64 {
+        self.0.total_winnings
+    }
+
+    /// Square the round drew, `None` before the draw or after a reset
+    /// without a winner
+    pub fn winning_square(&self) -> Option<usize> {
+        self.0.winning_square()
+    }
+}
+
+/// The program's config account
+#[pyclass(name = "Config", frozen)]
+#[derive(Clone, Copy)]
+pub struct PyConfig(pub GlobalConfig);
+
+#[pymethods]
+impl PyConfig {
+    /// Decode the config account's data
+    #[staticmethod]
+    pub fn decode(data: &[u8]) -> PyResult<Self> {
+        decode_program_account(&account(data, PROGRAM_ID))
+            .map(PyConfig)
+            .map_err(|e| PyValueError::new_err(e.to_string()))
+    }
+}
+
+/// A deploy's plan, in selection order
+#[pyclass(name = "Plan", frozen)]
+#[derive(Clone, Copy)]
+pub struct PyPlan(pub Allocation);
+
+#[pymethods]
+impl PyPlan {
+    /// Square of each planned block
+    #[getter]
+    pub fn squares(&self) -> Vec<u8> {
+        self.0.indices[..self.0.count as usize].to_vec()
+    }
+
+    /// Lamports per block
+    #[getter]
+    pub fn amounts(&self) -> Vec<u64> {
+        self.0.amounts[..self.0.count as usize].to_vec()
+    }
+
+    /// Modeled EV in lamports per block
+    #[getter]
+    pub fn evs(&self) -> Vec<i64> {
+        self.0.evs[..self.0.count as usize].to_vec()
+    }
+
+    /// Modeled EV of the whole plan
+    #[getter]
+    pub fn portfolio_ev(&self) -> i64 {
+        self.0.portfolio_ev
+    }
+
+    /// Standard deviation of the whole plan's PnL
+    #[getter]
+    pub fn portfolio_std_dev(&self) -> u64 {
+        self.0.portfolio_std_dev
+    }
+}
+
+/// Modeled EV in lamports of deploying `deploy_amount` on a square holding
+/// `block_size` of a `total_pool` board, with ORE worth `ore_value`
+#[pyfunction]
+pub fn calculate_ev(
+    block_size: u64,
+    deploy_amount: u64,
+    total_pool: u64,
+    ore_value: u64,
+    config: &PyConfig,
+) -> i64 {
+    ore_ev_math::calculate_ev(
+        block_size,
+        deploy_amount,
+        total_pool,
+        ore_value,
+        &config.0.params,
+    )
+}
+
+/// Lamport value of winning `round`'s ORE at `ore_price_lamports`,
+/// motherlode included, after the refining fee
+#[pyfunction]
+pub fn ore_value(ore_price_lamports: u64, round: &PyOreRound, config: &PyConfig) -> u64 {
+    let params = &config.0.params;
+    let motherlode = ore_ev_math::motherlode_value(ore_price_lamports, round.0.motherlode, params);
+    ore_ev_math::ore_value(ore_price_lamports, motherlode, params)
+}
+
+/// The plan an OreDeploy of `total_amount` lamports without mode flags
+/// would execute in `round`, or the error it would fail with
+#[allow(clippy::too_many_arguments)]
+#[pyfunction]
+#[pyo3(signature = (
+    round,
+    config,
+    total_amount,
+    ore_price_lamports,
+    num_blocks = 5,
+    min_ev_threshold = 0,
+    max_block_share_bps = 0,
+    objective = 0
+))]
+pub fn quote(
+    round: &PyOreRound,
+    config: &PyConfig,
+    total_amount: u64,
+    ore_price_lamports: u64,
+    num_blocks: u8,
+    min_ev_threshold: i32,
+    max_block_share_bps: u16,
+    objective: u8,
+) -> PyResult<PyPlan> {
+    let data = OreDeployIxData {
+        total_amount,
+        ore_price_lamports,
+        min_ev_threshold,
+        num_blocks,
+        max_block_share_bps,
+        objective,
+        ..OreDeployIxData::zeroed()
+    };
+    quote_deploy(&round.0, &config.0, &data.into(), &QuoteState::default())
+        .map(PyPlan)
+        .map_err(|e| PyValueError::new_err(e.to_string()))
+}
+
+#[pymodule]
+fn ore_ev_client(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<PyOreRound>()?;
+    m.add_class::<PyConfig>()?;
+    m.add_class::<PyPlan>()?;
+    m.add_function(wrap_pyfunction!(calculate_ev, m)?)?;
+    m.add_function(wrap_pyfunction!(ore_value, m)?)?;
+    m.add_function(wrap_pyfunction!(quote, m)?)?;
+    Ok(())
+}
+
+/// Account data as owned by `owner`, for the decoders
+fn account(data: &[u8], owner: Pubkey) -> Account {
+    Account {
+        lamports: 0,
+        data: data.to_vec(),
+        owner,
+        executable: false,
+        rent_epoch: 0,
+    }
+}
@@ -0,0 +1,113 @@
+//! Off-chain quotes of an OreDeploy, sized by the program's own input
+//! derivation and planning code (`plan_inputs`, `plan_deploy`) so a preview
+//! matches the executed plan exactly
+
+use bytemuck::Zeroable;
+use ore_ev_math::Allocation;
+use solana_pinocchio_starter::{
+    error::MyProgramError,
+    instruction::{check_round_pool, plan_deploy, plan_inputs, PlanAccounts, FLAG_BANKROLL_SIZING},
+    state::{Bankroll, GlobalConfig, OreMiner, OreRound, RoundExposure, UserStats},
+    target::MinerPosition,
+};
+use solana_sdk::program_error::ProgramError;
+
+use crate::instructions::DeployData;
+
+/// The accounts a deploy reads besides the round and config, as last
+/// fetched; `None` for accounts that don't exist yet
+#[derive(Clone, Copy, Debug, Default)]
+pub struct QuoteState {
+    /// The authority's ORE miner
+    pub miner: Option<OreMiner>,
+
+    /// The authority's RoundExposure in the round
+    pub round_exposure: Option<RoundExposure>,
+
+    /// The authority's UserStats
+    pub user_stats: Option<UserStats>,
+
+    /// The authority's Bankroll
+    pub bankroll: Option<Bankroll>,
+
+    /// Lamports `total_amount` converts to with `FLAG_USD_BUDGET`, at the
+    /// SOL/USD price the deploy will read
+    pub usd_budget_lamports: Option<u64>,
+}
+
+/// The plan a deploy with `data` would execute in `round`, or the error it
+/// would fail with
+///
+/// An empty plan means the deploy succeeds without deploying
+/// (`FLAG_NOOP_ON_SMALL_POOL`). The plan is the one before the program
+/// truncates it to the compute units left, so the transaction should budget
+/// enough for every block.
+pub fn quote(
+    round: &OreRound,
+    config: &GlobalConfig,
+    data: &DeployData,
+    state: &QuoteState,
+) -> Result<Allocation, ProgramError> {
+    let ix_data = data.base();
+    if !check_round_pool(round, &config.params, ix_data).map_err(convert)? {
+        return Ok(Allocation::default());
+    }
+
+    // A deploy creates missing stats checkpointed at the miner's rewards, and
+    // fails to load a Bankroll that was never set
+    let mut user_stats = state.user_stats.unwrap_or(UserStats {
+        rewards_checkpoint: state.miner.map_or(0, |miner| miner.lifetime_rewards_sol),
+        ..UserStats::zeroed()
+    });
+    let mut bankroll = state.bankroll;
+    if ix_data.flags & FLAG_BANKROLL_SIZING != 0 && bankroll.is_none() {
+        return Err(error(MyProgramError::InvalidOwner));
+    }
+    let (inputs, _) = plan_inputs(
+        round.id,
+        &config.params,
+        ix_data,
+        PlanAccounts {
+            miner: state.miner.as_ref().map(MinerPosition::from),
+            usd_budget_lamports: state.usd_budget_lamports,
+            round_deployed: state.round_exposure.map_or(0, |e| e.total_deployed),
+            user_stats: Some(&mut user_stats),
+            bankroll: bankroll.as_mut(),
+        },
+    )
+    .map_err(convert)?;
+    let (rank_thresholds, block_quote) = match data {
+        DeployData::V1(_) => (&[][..], None),
+        DeployData::V2(data) => (
+            rank_thresholds(data.num_rank_thresholds, &data.rank_thresholds),
+            None,
+        ),
+        DeployData::V3(data) => (
+            rank_thresholds(data.v2.num_rank_thresholds, &data.v2.rank_thresholds),
+            Some(&data.quote),
+        ),
+    };
+    plan_deploy(
+        round,
+        config,
+        ix_data,
+        rank_thresholds,
+        block_quote,
+        &inputs,
+    )
+    .map_err(convert)
+}
+
+/// The leading `count` rank thresholds, as the program reads them
+fn rank_thresholds(count: u8, thresholds: &[i32]) -> &[i32] {
+    &thresholds[..(count as usize).min(thresholds.len())]
+}
+
+fn error(e: MyProgramError) -> ProgramError {
+    ProgramError::Custom(e as u32)
+}
+
+/// The program's error as the one a simulated transaction reports
+fn convert(e: impl Into<u64>) -> ProgramError {
+    ProgramError::from(e.into())
+}
@@ -0,0 +1,201 @@
+use ore_ev_math::{
+    ore_value, pot_value, top_miner_value, Allocation, ConfigParams, OreRound,
+    MOTHERLODE_SPLIT_PER_MINER,
+};
+
+/// Number of squares on the board (exactly one wins per round)
+const NUM_SQUARES: u64 = 25;
+
+/// Monte Carlo settings
+#[derive(Clone, Copy, Debug)]
+pub struct SimConfig {
+    /// Rounds to sample
+    pub trials: u32,
+
+    /// PRNG seed; the same seed reproduces the same report
+    pub seed: u64,
+
+    /// ORE price in lamports, as passed to the deploy
+    pub ore_price_lamports: u64,
+
+    /// Count the top-miner reward when our stake outweighs everyone else's
+    /// on the winning square
+    pub top_miner: bool,
+}
+
+impl Default for SimConfig {
+    fn default() -> Self {
+        SimConfig {
+            trials: 100_000,
+            seed: 0x0ae0_5eed,
+            ore_price_lamports: 0,
+            top_miner: false,
+        }
+    }
+}
+
+/// Distribution of a plan's realized PnL over the sampled rounds, in lamports
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct SimReport {
+    /// Rounds sampled
+    pub trials: u32,
+
+    /// Mean realized PnL
+    pub mean: f64,
+
+    /// Variance of realized PnL
+    pub variance: f64,
+
+    /// Standard deviation of realized PnL
+    pub std_dev: f64,
+
+    /// Share of rounds that ended with a loss
+    pub probability_of_loss: f64,
+
+    /// Worst and best realized PnL
+    pub min: i64,
+    pub max: i64,
+
+    /// 5th, 50th and 95th percentile PnL
+    pub p5: i64,
+    pub p50: i64,
+    pub p95: i64,
+
+    /// Rounds in which the motherlode triggered
+    pub motherlode_hits: u32,
+
+    /// Rounds won while the motherlode triggered
+    pub motherlode_captures: u32,
+
+    /// The plan's own modeled EV, for comparison with `mean`
+    pub modeled_ev: i64,
+}
+
+/// Sample `config.trials` rounds of `plan` against the `round` snapshot
+///
+/// Each trial draws the winning square uniformly and triggers the motherlode
+/// with probability 1 / `motherlode_odds`. Our stakes on the losing squares
+/// join the winning square's pot, which pays our share of it after the
+/// protocol fee plus the refined ORE reward; the admin and wrapper fees are
+/// paid in every round.
+pub fn simulate(
+    round: &OreRound,
+    plan: &Allocation,
+    params: &ConfigParams,
+    config: &SimConfig,
+) -> SimReport {
+    let count = plan.count as usize;
+    let mut stakes = [0u64; NUM_SQUARES as usize];
+    for (index, amount) in plan.indices[..count].iter().zip(&plan.amounts[..count]) {
+        stakes[*index as usize] += amount;
+    }
+    let total_ours: u64 = stakes.iter().sum();
+    let pool = round.total_deployed.saturating_add(total_ours);
+    let fees = (total_ours as u128 * params.fee_load_bps() as u128 / 10_000) as i64;
+
+    // Refined lamport values of the ORE reward, the whole motherlode and the
+    // top-miner reward (top_miner_value converts any ORE amount)
+    let ore = ore_value(config.ore_price_lamports, 0, params);
+    let motherlode = top_miner_value(config.ore_price_lamports, round.motherlode, params);
+    let top_miner_reward =
+        top_miner_value(config.ore_price_lamports, round.top_miner_reward, params);
+
+    // PnL per winning square, without and with the motherlode
+    let mut pnl = [(0i64, 0i64); NUM_SQUARES as usize];
+    for (square, outcome) in pnl.iter_mut().enumerate() {
+        let ours = stakes[square];
+        let lost = (total_ours - ours) as i64 + fees;
+        if ours == 0 {
+            *outcome = (-lost, -lost);
+            continue;
+        }
+
+        let size = round.deployed[square];
+        let block = size + ours;
+        let pot = pot_value(block, pool, ore, params);
+        let mut won = share(pot, ours, block);
+        if config.top_miner && ours > size {
+            won += top_miner_reward as u128;
+        }
+
+        let motherlode_share = if params.motherlode_split == MOTHERLODE_SPLIT_PER_MINER {
+            motherlode as u128 / (round.count[square] as u128 + 1)
+        } else {
+            share(motherlode, ours, block)
+        };
+
+        let base = narrow(won) - lost;
+        *outcome = (base, base.saturating_add(narrow(motherlode_share)));
+    }
+
+    let mut rng = SplitMix64(config.seed);
+    let mut samples = Vec::with_capacity(config.trials as usize);
+    let mut report = SimReport {
+        trials: config.trials,
+        modeled_ev: plan.portfolio_ev,
+        ..SimReport::default()
+    };
+
+    for _ in 0..config.trials {
+        let square = rng.below(NUM_SQUARES) as usize;
+        let hit = rng.below(params.motherlode_odds.max(1) as u64) == 0;
+        if hit {
+            report.motherlode_hits += 1;
+            if stakes[square] > 0 {
+                report.motherlode_captures += 1;
+            }
+        }
+        samples.push(if hit { pnl[square].1 } else { pnl[square].0 });
+    }
+
+    if samples.is_empty() {
+        return report;
+    }
+
+    let n = samples.len() as f64;
+    report.mean = samples.iter().map(|s| *s as f64).sum::<f64>() / n;
+    report.variance = samples
+        .iter()
+        .map(|s| (*s as f64 - report.mean).powi(2))
+        .sum::<f64>()
+        / n;
+    report.std_dev = report.variance.sqrt();
+    report.probability_of_loss = samples.iter().filter(|s| **s < 0).count() as f64 / n;
+
+    samples.sort_unstable();
+    let percentile = |p: usize| samples[(samples.len() - 1) * p / 100];
+    report.min = samples[0];
+    report.max = samples[samples.len() - 1];
+    report.p5 = percentile(5);
+    report.p50 = percentile(50);
+    report.p95 = percentile(95);
+
+    report
+}
+
+/// Our share `ours / block` of `value`
+fn share(value: u64, ours: u64, block: u64) -> u128 {
+    value as u128 * ours as u128 / block as u128
+}
+
+fn narrow(value: u128) -> i64 {
+    value.min(i64::MAX as u128) as i64
+}
+
+/// SplitMix64, enough for sampling outcomes and reproducible from a seed
+struct SplitMix64(u64);
+
+impl SplitMix64 {
+    fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform value in `0..n`
+    fn below(&mut self, n: u64) -> u64 {
+        ((self.next() as u128 * n as u128) >> 64) as u64
+    }
+}
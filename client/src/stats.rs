@@ -0,0 +1,68 @@
+//! Realized results against the model, summed from RoundRecords
+
+use solana_pinocchio_starter::state::RoundRecord;
+
+/// Totals over an authority's settled RoundRecords
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct RecordSummary {
+    /// Settled rounds
+    pub rounds: u64,
+
+    /// Settled rounds that won more SOL than was deployed into them
+    pub rounds_won: u64,
+
+    /// Lamports deployed in the settled rounds
+    pub deployed: u64,
+
+    /// SOL won from the settled rounds
+    pub won: u64,
+
+    /// Modeled EV of the settled rounds' plans, in lamports
+    pub modeled_ev: i128,
+
+    /// Sum of the plans' modeled PnL variances, in lamports squared
+    pub modeled_variance: u128,
+}
+
+impl RecordSummary {
+    /// Sum the settled records among `records`; unsettled ones have no
+    /// realized result yet
+    pub fn new<'a>(records: impl IntoIterator<Item = &'a RoundRecord>) -> Self {
+        let mut summary = RecordSummary::default();
+        for record in records.into_iter().filter(|record| record.settled != 0) {
+            summary.rounds += 1;
+            summary.rounds_won += (record.won > record.total_deployed) as u64;
+            summary.deployed = summary.deployed.saturating_add(record.total_deployed);
+            summary.won = summary.won.saturating_add(record.won);
+            summary.modeled_ev += record.modeled_ev as i128;
+            summary.modeled_variance +=
+                record.modeled_std_dev as u128 * record.modeled_std_dev as u128;
+        }
+        summary
+    }
+
+    /// SOL won minus SOL deployed, in lamports
+    pub fn realized_pnl(&self) -> i128 {
+        self.won as i128 - self.deployed as i128
+    }
+
+    /// How far realized PnL ran from the modeled EV, in lamports; persistent
+    /// drift means the model misprices the rounds played
+    pub fn ev_drift(&self) -> i128 {
+        self.realized_pnl() - self.modeled_ev
+    }
+
+    /// `ev_drift` in modeled standard deviations, treating rounds as
+    /// independent; `None` without modeled variance
+    pub fn drift_sigmas(&self) -> Option<f64> {
+        if self.modeled_variance == 0 {
+            return None;
+        }
+        Some(self.ev_drift() as f64 / (self.modeled_variance as f64).sqrt())
+    }
+
+    /// Share of settled rounds won, in bps
+    pub fn win_rate_bps(&self) -> Option<u64> {
+        (self.rounds != 0).then(|| self.rounds_won * 10_000 / self.rounds)
+    }
+}
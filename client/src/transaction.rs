@@ -0,0 +1,262 @@
+//! Assembling deploy transactions: compute budget sized to the plan,
+//! address lookup tables for the accounts every deploy repeats, and a v0
+//! message with the fee payer first
+
+use std::fmt;
+
+use solana_client::{client_error::ClientError, rpc_client::RpcClient};
+use solana_pinocchio_starter::instruction::{
+    DEFAULT_MAX_DEPLOY_CPIS, DEPLOY_CPI_COMPUTE_UNITS, PLAN_COMPUTE_UNITS,
+    POST_DEPLOY_COMPUTE_UNITS,
+};
+use solana_sdk::{
+    address_lookup_table::{
+        self,
+        instruction::{create_lookup_table, extend_lookup_table},
+        state::AddressLookupTable,
+        AddressLookupTableAccount,
+    },
+    compute_budget::{self, ComputeBudgetInstruction},
+    hash::Hash,
+    instruction::Instruction,
+    message::{v0, CompileError, VersionedMessage},
+    pubkey::Pubkey,
+    signer::{signers::Signers, SignerError},
+    system_program,
+    transaction::VersionedTransaction,
+};
+
+use crate::{
+    accounts::{DecodeError, FetchError},
+    fee::{self, FeePolicy, DEFAULT_INSTRUCTION_COMPUTE_UNITS},
+    instructions::{ore_deploy, DeployAccounts, DeployData},
+    pda, PROGRAM_ID,
+};
+
+/// Most compute units a transaction may request
+pub const MAX_COMPUTE_UNIT_LIMIT: u32 = 1_400_000;
+
+/// Compute-unit limit covering an OreDeploy of up to `num_blocks` blocks:
+/// planning, one deploy CPI per block and the bookkeeping after
+pub fn deploy_compute_units(num_blocks: u8) -> u32 {
+    let units = PLAN_COMPUTE_UNITS
+        + DEPLOY_CPI_COMPUTE_UNITS * num_blocks as u64
+        + POST_DEPLOY_COMPUTE_UNITS;
+    units.min(MAX_COMPUTE_UNIT_LIMIT as u64) as u32
+}
+
+/// Blocks an OreDeploy with `data` deploys at most, `num_blocks` capped by
+/// `max_cpis` as the program caps it
+pub fn max_deploy_blocks(data: &DeployData) -> u8 {
+    let base = data.base();
+    let max_cpis = match base.max_cpis {
+        0 => DEFAULT_MAX_DEPLOY_CPIS,
+        n => n,
+    };
+    base.num_blocks.min(max_cpis)
+}
+
+/// Deploy accounts that are the same in every round: the ORE deployment's
+/// shared accounts, this program and config, and `accounts.authority`'s ORE
+/// miner and automation and program PDAs
+///
+/// Per-round accounts (the round, RoundExposure and RoundRecord) are left
+/// out, so one table serves an authority across rounds.
+pub fn deploy_lookup_addresses(accounts: &DeployAccounts) -> Vec<Pubkey> {
+    let board = pda::board_address(&accounts.ore_program).0;
+    vec![
+        accounts.ore_program,
+        board,
+        system_program::ID,
+        pda::entropy_var_address(&accounts.entropy_program, &board).0,
+        accounts.entropy_program,
+        pda::config_address().0,
+        PROGRAM_ID,
+        compute_budget::ID,
+        pda::automation_address(&accounts.ore_program, &accounts.authority).0,
+        pda::miner_address(&accounts.ore_program, &accounts.authority).0,
+        pda::user_stats_address(&accounts.authority).0,
+        pda::bankroll_address(&accounts.authority).0,
+        pda::deploy_nonce_address(&accounts.authority).0,
+    ]
+}
+
+/// Instructions creating a lookup table owned by `authority` holding
+/// `addresses`, and the table's address; `recent_slot` must be a recent
+/// finalized slot
+pub fn create_lookup_table_instructions(
+    authority: &Pubkey,
+    payer: &Pubkey,
+    recent_slot: u64,
+    addresses: Vec<Pubkey>,
+) -> (Pubkey, Vec<Instruction>) {
+    let (create, table) = create_lookup_table(*authority, *payer, recent_slot);
+    let extend = extend_lookup_table(table, *authority, Some(*payer), addresses);
+    (table, vec![create, extend])
+}
+
+/// Fetch a lookup table's addresses for compiling messages against it
+pub fn fetch_lookup_table(
+    rpc: &RpcClient,
+    address: &Pubkey,
+) -> Result<AddressLookupTableAccount, FetchError> {
+    let account = rpc.get_account(address)?;
+    if account.owner != address_lookup_table::program::ID {
+        return Err(FetchError::Decode(
+            *address,
+            DecodeError::WrongOwner(account.owner),
+        ));
+    }
+    let table = AddressLookupTable::deserialize(&account.data)
+        .map_err(|_| FetchError::Decode(*address, DecodeError::WrongDiscriminator))?;
+    Ok(AddressLookupTableAccount {
+        key: *address,
+        addresses: table.addresses.to_vec(),
+    })
+}
+
+/// Why a transaction could not be built
+#[derive(Debug)]
+pub enum BuildError {
+    /// The message did not compile (e.g. too many accounts)
+    Compile(CompileError),
+
+    /// A required signer is missing or signing failed
+    Sign(SignerError),
+}
+
+impl fmt::Display for BuildError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BuildError::Compile(e) => write!(f, "message did not compile: {e}"),
+            BuildError::Sign(e) => write!(f, "signing failed: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for BuildError {}
+
+/// A v0 transaction under construction: compute-budget instructions come
+/// first, then the instructions in the order added
+#[derive(Clone, Debug)]
+pub struct TransactionBuilder {
+    payer: Pubkey,
+    instructions: Vec<Instruction>,
+    compute_unit_limit: Option<u32>,
+    compute_unit_price: Option<u64>,
+    lookup_tables: Vec<AddressLookupTableAccount>,
+}
+
+impl TransactionBuilder {
+    /// An empty transaction paid for by `payer`
+    pub fn new(payer: Pubkey) -> Self {
+        TransactionBuilder {
+            payer,
+            instructions: Vec::new(),
+            compute_unit_limit: None,
+            compute_unit_price: None,
+            lookup_tables: Vec::new(),
+        }
+    }
+
+    /// An OreDeploy paid for by `accounts.signer`, with a compute-unit limit
+    /// covering every block its data may deploy
+    pub fn deploy(accounts: &DeployAccounts, data: impl Into<DeployData>) -> Self {
+        let data = data.into();
+        TransactionBuilder::new(accounts.signer)
+            .compute_unit_limit(deploy_compute_units(max_deploy_blocks(&data)))
+            .instruction(ore_deploy(accounts, data))
+    }
+
+    /// Append `instruction`
+    pub fn instruction(mut self, instruction: Instruction) -> Self {
+        self.instructions.push(instruction);
+        self
+    }
+
+    /// Request `units` compute units for the transaction, replacing any
+    /// earlier limit
+    pub fn compute_unit_limit(mut self, units: u32) -> Self {
+        self.compute_unit_limit = Some(units.min(MAX_COMPUTE_UNIT_LIMIT));
+        self
+    }
+
+    /// Pay `micro_lamports` per compute unit as a priority fee
+    pub fn compute_unit_price(mut self, micro_lamports: u64) -> Self {
+        self.compute_unit_price = Some(micro_lamports);
+        self
+    }
+
+    /// Price compute units by `policy` from the fees recent transactions paid
+    /// to write-lock the accounts this one does
+    pub fn priority_fee(self, rpc: &RpcClient, policy: &FeePolicy) -> Result<Self, ClientError> {
+        let recent_fees = fee::recent_fees(rpc, &self.writable_accounts())?;
+        let price = policy.compute_unit_price(&recent_fees, self.compute_units());
+        Ok(self.compute_unit_price(price))
+    }
+
+    /// Resolve accounts through `table` where it holds them
+    pub fn lookup_table(mut self, table: AddressLookupTableAccount) -> Self {
+        self.lookup_tables.push(table);
+        self
+    }
+
+    /// Compute units the transaction may use: the limit set, or the
+    /// runtime's default per instruction
+    pub fn compute_units(&self) -> u32 {
+        self.compute_unit_limit.unwrap_or_else(|| {
+            let count = self.instructions.len() as u32;
+            (DEFAULT_INSTRUCTION_COMPUTE_UNITS * count).min(MAX_COMPUTE_UNIT_LIMIT)
+        })
+    }
+
+    /// Accounts the instructions write-lock besides their signers, the ones
+    /// other deploys contend for
+    pub fn writable_accounts(&self) -> Vec<Pubkey> {
+        let mut accounts: Vec<Pubkey> = Vec::new();
+        for meta in self.instructions.iter().flat_map(|ix| &ix.accounts) {
+            if meta.is_writable && !meta.is_signer && !accounts.contains(&meta.pubkey) {
+                accounts.push(meta.pubkey);
+            }
+        }
+        accounts
+    }
+
+    /// The transaction's instructions, compute-budget ones first
+    pub fn instructions(&self) -> Vec<Instruction> {
+        let mut instructions = Vec::with_capacity(self.instructions.len() + 2);
+        if let Some(units) = self.compute_unit_limit {
+            instructions.push(ComputeBudgetInstruction::set_compute_unit_limit(units));
+        }
+        if let Some(micro_lamports) = self.compute_unit_price {
+            instructions.push(ComputeBudgetInstruction::set_compute_unit_price(
+                micro_lamports,
+            ));
+        }
+        instructions.extend(self.instructions.iter().cloned());
+        instructions
+    }
+
+    /// The v0 message, unsigned; the payer is its first signer and the
+    /// signatures follow the order of its signer keys
+    pub fn message(&self, recent_blockhash: Hash) -> Result<VersionedMessage, BuildError> {
+        let message = v0::Message::try_compile(
+            &self.payer,
+            &self.instructions(),
+            &self.lookup_tables,
+            recent_blockhash,
+        )
+        .map_err(BuildError::Compile)?;
+        Ok(VersionedMessage::V0(message))
+    }
+
+    /// The transaction signed by `signers`, in any order
+    pub fn sign<T: Signers + ?Sized>(
+        &self,
+        recent_blockhash: Hash,
+        signers: &T,
+    ) -> Result<VersionedTransaction, BuildError> {
+        VersionedTransaction::try_new(self.message(recent_blockhash)?, signers)
+            .map_err(BuildError::Sign)
+    }
+}
@@ -0,0 +1,265 @@
+//! Following the board and its current round over WebSocket account
+//! subscriptions, turning each update into round events for a handler
+
+use std::{fmt, sync::mpsc, thread};
+
+use solana_account_decoder::{UiAccount, UiAccountEncoding};
+use solana_client::{
+    pubsub_client::{PubsubAccountClientSubscription, PubsubClient, PubsubClientError},
+    rpc_client::RpcClient,
+    rpc_config::RpcAccountInfoConfig,
+};
+use solana_pinocchio_starter::state::{OreBoard, OreRound, BOARD_SQUARES};
+use solana_sdk::{account::Account, pubkey::Pubkey};
+
+use crate::{
+    accounts::{decode_ore_account, fetch_current_round, DecodeError, FetchError},
+    pda,
+};
+
+/// What a round update changed since the last one seen
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RoundDiff {
+    /// The round as updated
+    pub round: OreRound,
+
+    /// Lamports deployed per square since the last update
+    pub deployed: [u64; BOARD_SQUARES],
+
+    /// Miners joining each square since the last update
+    pub miners: [u64; BOARD_SQUARES],
+
+    /// Lamports deployed on the whole board since the last update
+    pub total_deployed: u64,
+}
+
+impl RoundDiff {
+    /// The change from `previous` to `round`, from an empty round when
+    /// `previous` is another round or none
+    pub fn new(previous: Option<&OreRound>, round: &OreRound) -> Self {
+        let previous = previous.filter(|previous| previous.id == round.id);
+        let (deployed, count, total_deployed) = previous
+            .map_or(([0; BOARD_SQUARES], [0; BOARD_SQUARES], 0), |previous| {
+                (previous.deployed, previous.count, previous.total_deployed)
+            });
+        RoundDiff {
+            round: *round,
+            deployed: core::array::from_fn(|i| round.deployed[i].saturating_sub(deployed[i])),
+            miners: core::array::from_fn(|i| round.count[i].saturating_sub(count[i])),
+            total_deployed: round.total_deployed.saturating_sub(total_deployed),
+        }
+    }
+}
+
+/// A change to the board or its current round
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum RoundEvent {
+    /// The board moved to a new round
+    NewRound(OreBoard),
+
+    /// Lamports were deployed into the current round
+    PoolGrowth(RoundDiff),
+
+    /// The current round's slot hash was set, drawing its winning square
+    SlotHashSet(OreRound),
+}
+
+/// Callbacks for round events; each defaults to ignoring the event
+pub trait RoundHandler {
+    fn new_round(&mut self, _board: &OreBoard) {}
+
+    fn pool_growth(&mut self, _diff: &RoundDiff) {}
+
+    fn slot_hash_set(&mut self, _round: &OreRound) {}
+
+    /// Dispatch `event` to its callback
+    fn handle(&mut self, event: &RoundEvent) {
+        match event {
+            RoundEvent::NewRound(board) => self.new_round(board),
+            RoundEvent::PoolGrowth(diff) => self.pool_growth(diff),
+            RoundEvent::SlotHashSet(round) => self.slot_hash_set(round),
+        }
+    }
+}
+
+/// The board and current round as last seen, diffing each update against
+/// them
+#[derive(Clone, Copy, Debug, Default)]
+pub struct RoundWatcher {
+    board: Option<OreBoard>,
+    round: Option<OreRound>,
+}
+
+impl RoundWatcher {
+    pub fn new() -> Self {
+        RoundWatcher::default()
+    }
+
+    /// The board as last seen
+    pub fn board(&self) -> Option<&OreBoard> {
+        self.board.as_ref()
+    }
+
+    /// The current round as last seen, `None` before its first update
+    pub fn round(&self) -> Option<&OreRound> {
+        self.round.as_ref()
+    }
+
+    /// Record a board update, a `NewRound` if it moved to another round
+    pub fn update_board(&mut self, board: OreBoard) -> Option<RoundEvent> {
+        let moved = self
+            .board
+            .is_none_or(|last| last.round_id != board.round_id);
+        self.board = Some(board);
+        if !moved {
+            return None;
+        }
+        if self.round.is_some_and(|round| round.id != board.round_id) {
+            self.round = None;
+        }
+        Some(RoundEvent::NewRound(board))
+    }
+
+    /// Record an update of the current round; updates of other rounds, and
+    /// of any round before the board is seen, are ignored
+    pub fn update_round(&mut self, round: OreRound) -> Vec<RoundEvent> {
+        if self.board.is_none_or(|board| board.round_id != round.id) {
+            return Vec::new();
+        }
+        let mut events = Vec::new();
+        let diff = RoundDiff::new(self.round.as_ref(), &round);
+        if diff.total_deployed != 0 || diff.deployed.iter().any(|&d| d != 0) {
+            events.push(RoundEvent::PoolGrowth(diff));
+        }
+        let hash_was_set = self.round.is_some_and(|last| last.slot_hash != [0; 32]);
+        if !hash_was_set && round.slot_hash != [0; 32] {
+            events.push(RoundEvent::SlotHashSet(round));
+        }
+        self.round = Some(round);
+        events
+    }
+}
+
+/// Why watching stopped
+#[derive(Debug)]
+pub enum WatchError {
+    /// The initial fetch failed
+    Fetch(FetchError),
+
+    /// A subscription could not be opened
+    Pubsub(Box<PubsubClientError>),
+
+    /// An update did not decode
+    Decode(Pubkey, DecodeError),
+
+    /// The WebSocket closed
+    Closed,
+}
+
+impl fmt::Display for WatchError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WatchError::Fetch(e) => write!(f, "{e}"),
+            WatchError::Pubsub(e) => write!(f, "subscription failed: {e}"),
+            WatchError::Decode(address, e) => write!(f, "{address}: {e}"),
+            WatchError::Closed => write!(f, "subscription closed"),
+        }
+    }
+}
+
+impl std::error::Error for WatchError {}
+
+impl From<FetchError> for WatchError {
+    fn from(e: FetchError) -> Self {
+        WatchError::Fetch(e)
+    }
+}
+
+impl From<PubsubClientError> for WatchError {
+    fn from(e: PubsubClientError) -> Self {
+        WatchError::Pubsub(Box::new(e))
+    }
+}
+
+/// Follow `ore_program`'s board and current round until the WebSocket at
+/// `ws_url` closes, passing every event to `handler`
+///
+/// The board and round are fetched through `rpc` first, so `handler` sees
+/// the current round and its pool before any update. The round subscription
+/// moves to each new round as the board does.
+pub fn watch(
+    rpc: &RpcClient,
+    ws_url: &str,
+    ore_program: &Pubkey,
+    handler: &mut impl RoundHandler,
+) -> Result<(), WatchError> {
+    let mut watcher = RoundWatcher::new();
+    if let Some((board, round)) = fetch_current_round(rpc, ore_program)? {
+        watcher
+            .update_board(board)
+            .into_iter()
+            .chain(watcher.update_round(round))
+            .for_each(|event| handler.handle(&event));
+    }
+
+    let (sender, updates) = mpsc::channel();
+    let board_address = pda::board_address(ore_program).0;
+    let _board_subscription = subscribe(ws_url, &board_address, rpc, sender.clone())?;
+    let mut round_subscription = match watcher.board() {
+        Some(board) => {
+            let round_address = pda::round_address(ore_program, board.round_id).0;
+            Some(subscribe(ws_url, &round_address, rpc, sender.clone())?)
+        }
+        None => None,
+    };
+
+    while let Ok((address, ui_account)) = updates.recv() {
+        let Some(account) = ui_account.decode::<Account>() else {
+            continue;
+        };
+        let decode_error = |e| WatchError::Decode(address, e);
+        if address == board_address {
+            let board = decode_ore_account(ore_program, &account).map_err(decode_error)?;
+            let Some(event) = watcher.update_board(board) else {
+                continue;
+            };
+            if let Some(mut subscription) = round_subscription.take() {
+                let _ = subscription.shutdown();
+            }
+            let round_address = pda::round_address(ore_program, board.round_id).0;
+            round_subscription = Some(subscribe(ws_url, &round_address, rpc, sender.clone())?);
+            handler.handle(&event);
+        } else {
+            let round = decode_ore_account(ore_program, &account).map_err(decode_error)?;
+            for event in watcher.update_round(round) {
+                handler.handle(&event);
+            }
+        }
+    }
+    Err(WatchError::Closed)
+}
+
+/// Subscribe to `address`, forwarding its updates to `sender` until the
+/// subscription shuts down
+fn subscribe(
+    ws_url: &str,
+    address: &Pubkey,
+    rpc: &RpcClient,
+    sender: mpsc::Sender<(Pubkey, UiAccount)>,
+) -> Result<PubsubAccountClientSubscription, WatchError> {
+    let config = RpcAccountInfoConfig {
+        encoding: Some(UiAccountEncoding::Base64),
+        commitment: Some(rpc.commitment()),
+        ..RpcAccountInfoConfig::default()
+    };
+    let (subscription, receiver) = PubsubClient::account_subscribe(ws_url, address, Some(config))?;
+    let address = *address;
+    thread::spawn(move || {
+        while let Ok(update) = receiver.recv() {
+            if sender.send((address, update.value)).is_err() {
+                break;
+            }
+        }
+    });
+    Ok(subscription)
+}
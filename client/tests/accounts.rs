@@ -0,0 +1,74 @@
+use ore_ev_client::{
+    accounts::{decode_ore_account, decode_program_account, DecodeError},
+    state::{AccountDiscriminator, OreAccount, OreBoard, UserStats, Versioned, USER_STATS_V0_LEN},
+    ORE_PROGRAM_ID, PROGRAM_ID,
+};
+use solana_sdk::{account::Account, pubkey::Pubkey};
+
+fn account(data: Vec<u8>, owner: Pubkey) -> Account {
+    Account {
+        lamports: 1,
+        data,
+        owner,
+        executable: false,
+        rent_epoch: 0,
+    }
+}
+
+fn board_bytes() -> Vec<u8> {
+    let board = OreBoard {
+        _disc: OreAccount::Board.discriminator(),
+        round_id: 9,
+        start_slot: 100,
+        end_slot: 250,
+    };
+    bytemuck::bytes_of(&board).to_vec()
+}
+
+#[test]
+fn ore_account_decodes_past_a_longer_layout() {
+    // ORE may append fields; the known prefix still decodes
+    let mut data = board_bytes();
+    data.extend([0u8; 16]);
+    let board: OreBoard =
+        decode_ore_account(&ORE_PROGRAM_ID, &account(data, ORE_PROGRAM_ID)).unwrap();
+    assert_eq!((board.round_id, board.end_slot), (9, 250));
+}
+
+#[test]
+fn ore_account_checks_owner_and_discriminator() {
+    let other = Pubkey::new_unique();
+    assert_eq!(
+        decode_ore_account::<OreBoard>(&ORE_PROGRAM_ID, &account(board_bytes(), other)),
+        Err(DecodeError::WrongOwner(other))
+    );
+
+    let mut data = board_bytes();
+    data[0] = OreAccount::Round as u8;
+    assert_eq!(
+        decode_ore_account::<OreBoard>(&ORE_PROGRAM_ID, &account(data, ORE_PROGRAM_ID)),
+        Err(DecodeError::WrongDiscriminator)
+    );
+}
+
+#[test]
+fn legacy_program_account_reads_missing_fields_as_zero() {
+    let mut stats: UserStats = bytemuck::Zeroable::zeroed();
+    stats.discriminator = AccountDiscriminator::UserStats as u8;
+    stats.version = UserStats::VERSION;
+    stats.rounds_participated = 12;
+    let bytes = bytemuck::bytes_of(&stats).to_vec();
+
+    let current: UserStats = decode_program_account(&account(bytes.clone(), PROGRAM_ID)).unwrap();
+    assert_eq!(current.rounds_participated, 12);
+
+    let legacy = bytes[..USER_STATS_V0_LEN].to_vec();
+    let migrated: UserStats = decode_program_account(&account(legacy, PROGRAM_ID)).unwrap();
+    assert_eq!(migrated.rounds_participated, 0);
+
+    let truncated = bytes[..USER_STATS_V0_LEN - 1].to_vec();
+    assert_eq!(
+        decode_program_account::<UserStats>(&account(truncated, PROGRAM_ID)),
+        Err(DecodeError::WrongLength(USER_STATS_V0_LEN - 1))
+    );
+}
@@ -0,0 +1,51 @@
+use ore_ev_client::{
+    fee::{percentile, priority_fee_lamports, FeePolicy},
+    instructions::DeployAccounts,
+    ix_data::OreDeployIxData,
+    pda,
+    transaction::TransactionBuilder,
+    ORE_PROGRAM_ID,
+};
+use solana_sdk::pubkey::Pubkey;
+
+#[test]
+fn percentile_takes_the_nearest_rank_below() {
+    let fees = [50, 10, 40, 0, 30, 20];
+    assert_eq!(percentile(&fees, 0), 0);
+    assert_eq!(percentile(&fees, 5_000), 20);
+    assert_eq!(percentile(&fees, 7_500), 30);
+    assert_eq!(percentile(&fees, 10_000), 50);
+    assert_eq!(percentile(&[], 7_500), 0);
+}
+
+#[test]
+fn price_is_floored_then_capped_by_the_total_fee() {
+    let policy = FeePolicy {
+        percentile_bps: 10_000,
+        min_micro_lamports: 1_000,
+        max_fee_lamports: 100_000,
+    };
+    // Uncontended slots pay the floor
+    assert_eq!(policy.compute_unit_price(&[0, 0], 400_000), 1_000);
+    assert_eq!(policy.compute_unit_price(&[5_000], 400_000), 5_000);
+
+    // 100_000 lamports over 400_000 units is 250_000 micro-lamports a unit
+    let capped = policy.compute_unit_price(&[10_000_000], 400_000);
+    assert_eq!(capped, 250_000);
+    assert!(priority_fee_lamports(capped, 400_000) <= policy.max_fee_lamports);
+}
+
+#[test]
+fn fees_are_estimated_on_the_contended_deploy_accounts() {
+    let authority = Pubkey::new_unique();
+    let data = OreDeployIxData {
+        num_blocks: 5,
+        ..bytemuck::Zeroable::zeroed()
+    };
+    let builder = TransactionBuilder::deploy(&DeployAccounts::new(authority, 7), data);
+
+    let accounts = builder.writable_accounts();
+    assert!(accounts.contains(&pda::board_address(&ORE_PROGRAM_ID).0));
+    assert!(accounts.contains(&pda::round_address(&ORE_PROGRAM_ID, 7).0));
+    assert!(!accounts.contains(&authority));
+}
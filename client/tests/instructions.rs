@@ -0,0 +1,115 @@
+use ore_ev_client::{
+    instruction,
+    instructions::{execute_schedule, ore_deploy, settle_round, DeployAccounts},
+    ix_data::{
+        OreDeployIxData, OreDeployIxDataV2, FLAG_NONCE, FLAG_ROUND_RECORD, FLAG_STORE_PLAN,
+        FLAG_TRACK_STATS, FLAG_USD_BUDGET, SETTLE_FLAG_BANKROLL,
+    },
+    pda, ORE_PROGRAM_ID, PROGRAM_ID,
+};
+use solana_sdk::pubkey::Pubkey;
+
+fn deploy_data(flags: u32) -> OreDeployIxData {
+    OreDeployIxData {
+        total_amount: 1_000_000_000,
+        ore_price_lamports: 1_000_000_000,
+        num_blocks: 5,
+        flags,
+        ..bytemuck::Zeroable::zeroed()
+    }
+}
+
+#[test]
+fn deploy_lists_the_ore_accounts_then_the_config() {
+    let authority = Pubkey::new_unique();
+    let ix = ore_deploy(&DeployAccounts::new(authority, 7), deploy_data(0));
+
+    assert_eq!(ix.program_id, PROGRAM_ID);
+    assert_eq!(ix.data[0], instruction::ORE_DEPLOY);
+    assert_eq!(ix.data.len(), 1 + size_of::<OreDeployIxData>());
+
+    let keys: Vec<Pubkey> = ix.accounts.iter().map(|meta| meta.pubkey).collect();
+    assert_eq!(keys.len(), 11);
+    assert_eq!(keys[0], ORE_PROGRAM_ID);
+    assert_eq!(keys[1..3], [authority, authority]);
+    assert_eq!(keys[4], pda::board_address(&ORE_PROGRAM_ID).0);
+    assert_eq!(keys[5], pda::miner_address(&ORE_PROGRAM_ID, &authority).0);
+    assert_eq!(keys[6], pda::round_address(&ORE_PROGRAM_ID, 7).0);
+    assert_eq!(keys[10], pda::config_address().0);
+    assert!(ix.accounts[1].is_signer && ix.accounts[2].is_signer);
+}
+
+#[test]
+fn deploy_appends_mode_accounts_in_program_order() {
+    let authority = Pubkey::new_unique();
+    let price_feed = Pubkey::new_unique();
+    let accounts = DeployAccounts {
+        price_feed: Some(price_feed),
+        round_exposure: true,
+        ..DeployAccounts::new(authority, 7)
+    };
+    let flags =
+        FLAG_USD_BUDGET | FLAG_TRACK_STATS | FLAG_NONCE | FLAG_ROUND_RECORD | FLAG_STORE_PLAN;
+    let ix = ore_deploy(&accounts, deploy_data(flags));
+
+    let keys: Vec<Pubkey> = ix.accounts[11..].iter().map(|meta| meta.pubkey).collect();
+    assert_eq!(
+        keys,
+        [
+            price_feed,
+            pda::round_exposure_address(&authority, 7).0,
+            pda::user_stats_address(&authority).0,
+            pda::deploy_nonce_address(&authority).0,
+            pda::round_record_address(&authority, 7).0,
+            pda::plan_address(&authority).0,
+        ]
+    );
+}
+
+#[test]
+fn deploy_data_length_selects_the_layout() {
+    let data = OreDeployIxDataV2 {
+        base: deploy_data(0),
+        ..bytemuck::Zeroable::zeroed()
+    };
+    let ix = ore_deploy(&DeployAccounts::new(Pubkey::new_unique(), 7), data);
+    assert_eq!(ix.data.len(), 1 + size_of::<OreDeployIxDataV2>());
+}
+
+#[test]
+fn execute_schedule_deploys_from_the_unsigned_vault() {
+    let keeper = Pubkey::new_unique();
+    let authority = Pubkey::new_unique();
+    let ix = execute_schedule(&keeper, &authority, &DeployAccounts::new(authority, 7), 0);
+
+    let schedule = pda::schedule_address(&authority).0;
+    let vault = pda::schedule_vault_address(&schedule).0;
+    assert_eq!(ix.accounts[0].pubkey, keeper);
+    assert_eq!(ix.accounts[1].pubkey, schedule);
+    assert_eq!(
+        (ix.accounts[3].pubkey, ix.accounts[4].pubkey),
+        (vault, vault)
+    );
+    assert!(!ix.accounts[3].is_signer && !ix.accounts[4].is_signer);
+    assert_eq!(
+        ix.accounts[7].pubkey,
+        pda::miner_address(&ORE_PROGRAM_ID, &vault).0
+    );
+}
+
+#[test]
+fn settle_passes_the_miner_before_the_synced_accounts() {
+    let authority = Pubkey::new_unique();
+    let ix = settle_round(&ORE_PROGRAM_ID, &authority, 7, SETTLE_FLAG_BANKROLL, true);
+
+    let keys: Vec<Pubkey> = ix.accounts[5..].iter().map(|meta| meta.pubkey).collect();
+    assert_eq!(
+        keys,
+        [
+            pda::miner_address(&ORE_PROGRAM_ID, &authority).0,
+            pda::bankroll_address(&authority).0,
+            pda::leaderboard_address().0,
+        ]
+    );
+    assert_eq!(ix.data, [instruction::SETTLE_ROUND, SETTLE_FLAG_BANKROLL]);
+}
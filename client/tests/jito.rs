@@ -0,0 +1,66 @@
+use base64::{engine::general_purpose::STANDARD, Engine};
+use ore_ev_client::{
+    instructions::DeployAccounts,
+    ix_data::OreDeployIxData,
+    jito::{encode_bundle, tip_account, tip_instruction, JitoError, TIP_ACCOUNTS},
+    transaction::TransactionBuilder,
+};
+use solana_sdk::{
+    hash::Hash, signature::Keypair, signer::Signer, system_program,
+    transaction::VersionedTransaction,
+};
+
+fn tipped_deploy(authority: &Keypair, tip_lamports: u64) -> VersionedTransaction {
+    let data = OreDeployIxData {
+        num_blocks: 5,
+        ..bytemuck::Zeroable::zeroed()
+    };
+    TransactionBuilder::deploy(&DeployAccounts::new(authority.pubkey(), 7), data)
+        .instruction(tip_instruction(
+            &authority.pubkey(),
+            &tip_account(7),
+            tip_lamports,
+        ))
+        .sign(Hash::new_unique(), &[authority])
+        .unwrap()
+}
+
+#[test]
+fn tip_is_a_system_transfer_to_a_tip_account() {
+    let payer = Keypair::new().pubkey();
+    let ix = tip_instruction(&payer, &tip_account(9), 10_000);
+
+    assert_eq!(ix.program_id, system_program::ID);
+    assert_eq!(ix.accounts[0].pubkey, payer);
+    assert!(ix.accounts[0].is_signer && ix.accounts[1].is_writable);
+    assert!(TIP_ACCOUNTS.contains(&ix.accounts[1].pubkey));
+    assert_eq!(ix.data[..4], 2u32.to_le_bytes());
+    assert_eq!(ix.data[4..], 10_000u64.to_le_bytes());
+}
+
+#[test]
+fn bundle_encodes_each_transaction_in_order() {
+    let authority = Keypair::new();
+    let bundle = [
+        tipped_deploy(&authority, 1_000),
+        tipped_deploy(&authority, 2_000),
+    ];
+    let encoded = encode_bundle(&bundle).unwrap();
+
+    assert_eq!(encoded.len(), 2);
+    for (tx, encoded) in bundle.iter().zip(&encoded) {
+        let decoded: VersionedTransaction =
+            bincode::deserialize(&STANDARD.decode(encoded).unwrap()).unwrap();
+        assert_eq!(&decoded, tx);
+    }
+}
+
+#[test]
+fn bundle_holds_one_to_five_transactions() {
+    let tx = tipped_deploy(&Keypair::new(), 1_000);
+    assert!(matches!(encode_bundle(&[]), Err(JitoError::BundleSize(0))));
+    assert!(matches!(
+        encode_bundle(&vec![tx; 6]),
+        Err(JitoError::BundleSize(6))
+    ));
+}
@@ -0,0 +1,61 @@
+use bytemuck::Zeroable;
+use ore_ev_client::{
+    ore::{claim_instructions, pending_checkpoint, ORE_CHECKPOINT, ORE_CLAIM_SOL},
+    pda,
+    state::{OreBoard, OreMiner},
+    ORE_PROGRAM_ID,
+};
+use solana_sdk::pubkey::Pubkey;
+
+fn board(round_id: u64) -> OreBoard {
+    OreBoard {
+        round_id,
+        ..Zeroable::zeroed()
+    }
+}
+
+fn miner(round_id: u64, checkpoint_id: u64, rewards_sol: u64) -> OreMiner {
+    OreMiner {
+        round_id,
+        checkpoint_id,
+        rewards_sol,
+        ..Zeroable::zeroed()
+    }
+}
+
+#[test]
+fn checkpoint_is_pending_once_the_played_round_is_over() {
+    // Still playing the current round
+    assert_eq!(pending_checkpoint(&miner(8, 7, 0), &board(8)), None);
+    assert_eq!(pending_checkpoint(&miner(8, 7, 0), &board(9)), Some(8));
+    assert_eq!(pending_checkpoint(&miner(8, 8, 0), &board(9)), None);
+}
+
+#[test]
+fn claim_checkpoints_the_last_round_before_claiming() {
+    let authority = Pubkey::new_unique();
+    let ixs = claim_instructions(&ORE_PROGRAM_ID, &authority, &miner(8, 7, 0), &board(9));
+
+    let data: Vec<u8> = ixs.iter().map(|ix| ix.data[0]).collect();
+    assert_eq!(data, [ORE_CHECKPOINT, ORE_CLAIM_SOL]);
+    assert_eq!(
+        ixs[0].accounts[3].pubkey,
+        pda::round_address(&ORE_PROGRAM_ID, 8).0
+    );
+    assert_eq!(
+        ixs[1].accounts[1].pubkey,
+        pda::miner_address(&ORE_PROGRAM_ID, &authority).0
+    );
+}
+
+#[test]
+fn claim_skips_what_there_is_none_of() {
+    let authority = Pubkey::new_unique();
+    let settled = miner(8, 8, 0);
+    assert!(claim_instructions(&ORE_PROGRAM_ID, &authority, &settled, &board(9)).is_empty());
+
+    let credited = miner(8, 8, 5_000);
+    let ixs = claim_instructions(&ORE_PROGRAM_ID, &authority, &credited, &board(9));
+    assert_eq!(ixs.len(), 1);
+    assert_eq!(ixs[0].data, [ORE_CLAIM_SOL]);
+}
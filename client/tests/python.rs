@@ -0,0 +1,64 @@
+use bytemuck::Zeroable;
+use ore_ev_client::{
+    ix_data::OreDeployIxData,
+    python::{quote, PyConfig, PyOreRound},
+    quote as quote_deploy,
+    state::{AccountDiscriminator, GlobalConfig, OreAccount, OreRound},
+    QuoteState,
+};
+use ore_ev_math::test_utils::{self, config_params};
+
+fn config() -> GlobalConfig {
+    GlobalConfig {
+        discriminator: AccountDiscriminator::GlobalConfig as u8,
+        params: config_params(),
+        ..Zeroable::zeroed()
+    }
+}
+
+/// The shared round, as ORE stores it
+fn round() -> OreRound {
+    OreRound {
+        _disc: OreAccount::Round.discriminator(),
+        ..test_utils::round()
+    }
+}
+
+#[test]
+fn accounts_decode_from_their_data() {
+    let round = PyOreRound::decode(bytemuck::bytes_of(&round())).unwrap();
+    assert_eq!(round.id(), 3);
+    assert_eq!(round.deployed()[5], 20_000_000);
+    assert_eq!(round.winning_square(), None);
+
+    let config = PyConfig::decode(bytemuck::bytes_of(&config())).unwrap();
+    assert_eq!(config.0, self::config());
+}
+
+#[test]
+fn quote_is_the_clients_quote() {
+    let (round, config) = (round(), config());
+    let plan = quote(
+        &PyOreRound(round),
+        &PyConfig(config),
+        500_000_000,
+        1_000_000_000,
+        5,
+        -10_000,
+        0,
+        0,
+    )
+    .unwrap();
+
+    let data = OreDeployIxData {
+        total_amount: 500_000_000,
+        ore_price_lamports: 1_000_000_000,
+        min_ev_threshold: -10_000,
+        num_blocks: 5,
+        ..OreDeployIxData::zeroed()
+    };
+    let direct = quote_deploy(&round, &config, &data.into(), &QuoteState::default()).unwrap();
+    assert!(direct.count > 0);
+    assert_eq!(plan.0, direct);
+    assert_eq!(plan.squares(), direct.indices[..direct.count as usize]);
+}
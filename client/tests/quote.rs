@@ -0,0 +1,290 @@
+use bytemuck::Zeroable;
+use ore_ev_client::{
+    instructions::DeployData,
+    ix_data::{
+        plan_deploy, plan_inputs, OreDeployIxData, OreDeployIxDataV2, PlanAccounts, PlanInputs,
+        FLAG_DERIVED_KELLY_C, FLAG_DRAWDOWN_SIZING, FLAG_LOG_EV_SURFACE, FLAG_NOOP_ON_SMALL_POOL,
+        FLAG_SKIP_HELD_SQUARES, FLAG_TRACK_STATS,
+    },
+    quote,
+    state::{GlobalConfig, OreMiner, UserStats},
+    QuoteState,
+};
+use ore_ev_math::{
+    calculate_optimal_deployments,
+    test_utils::{config_params, round},
+    AllocationParams, Objective, Selection,
+};
+use solana_pinocchio_starter::{error::MyProgramError, target::MinerPosition};
+use solana_sdk::program_error::ProgramError;
+
+const ORE_PRICE: u64 = 1_000_000_000;
+
+fn config() -> GlobalConfig {
+    GlobalConfig {
+        params: config_params(),
+        ..Zeroable::zeroed()
+    }
+}
+
+fn deploy_data() -> OreDeployIxData {
+    OreDeployIxData {
+        total_amount: 500_000_000,
+        ore_price_lamports: ORE_PRICE,
+        min_ev_threshold: -10_000,
+        num_blocks: 5,
+        ..Zeroable::zeroed()
+    }
+}
+
+#[test]
+fn quote_runs_the_allocator_on_the_deploy_parameters() {
+    let (config, round) = (config(), round());
+    let plan = quote(
+        &round,
+        &config,
+        &deploy_data().into(),
+        &QuoteState::default(),
+    )
+    .unwrap();
+    assert!(plan.count > 0);
+
+    let direct = calculate_optimal_deployments(&AllocationParams {
+        round: &round,
+        config: &config.params,
+        total_budget: 500_000_000,
+        max_blocks: 5,
+        ore_price_lamports: ORE_PRICE,
+        min_ev_threshold: -10_000,
+        rank_thresholds: &[],
+        absolute_threshold: false,
+        max_block_share_bps: 0,
+        objective: Objective::Kelly,
+        selection: Selection::EvPerLamport,
+        max_std_dev_bps: 0,
+        portfolio: false,
+        eligible_squares: 0x1ff_ffff,
+        crowd_penalty_bps: 0,
+        held: [0; 25],
+        top_miner: false,
+        motherlode_weight_bps: 10_000,
+        kelly_fraction_bps: 10_000,
+        payout_multipliers_bps: None,
+    })
+    .unwrap();
+    assert_eq!(plan, direct);
+}
+
+#[test]
+fn quote_skips_squares_the_miner_holds() {
+    let (config, round) = (config(), round());
+    let mut miner = OreMiner::zeroed();
+    miner.round_id = round.id;
+    miner.deployed[5] = 1_000_000;
+    let state = QuoteState {
+        miner: Some(miner),
+        ..QuoteState::default()
+    };
+    let data = OreDeployIxData {
+        flags: FLAG_SKIP_HELD_SQUARES,
+        ..deploy_data()
+    };
+
+    let plan = quote(&round, &config, &data.into(), &state).unwrap();
+    assert!(plan.count > 0);
+    assert!(!plan.indices[..plan.count as usize].contains(&5));
+}
+
+#[test]
+fn plan_inputs_read_the_miner_only_for_the_flags_that_use_it() {
+    let round = round();
+    let mut miner = OreMiner::zeroed();
+    miner.round_id = round.id;
+    miner.deployed[5] = 1_000_000;
+    miner.lifetime_rewards_sol = 3_000_000_000;
+    let position = MinerPosition::from(&miner);
+    let inputs = |flags, stats: &mut UserStats| {
+        plan_inputs(
+            round.id,
+            &config_params(),
+            &OreDeployIxData {
+                flags,
+                ..deploy_data()
+            },
+            PlanAccounts {
+                miner: Some(position),
+                user_stats: Some(stats),
+                ..PlanAccounts::default()
+            },
+        )
+        .unwrap()
+        .0
+    };
+
+    let mut stats = UserStats::zeroed();
+    assert_eq!(inputs(0, &mut stats).held, [0; 25]);
+    assert_eq!(stats.rewards_checkpoint, 0);
+
+    // Tracked stats sync against the miner's winnings, not against none
+    inputs(FLAG_TRACK_STATS, &mut stats);
+    assert_eq!(
+        (stats.rewards_checkpoint, stats.total_won),
+        (3_000_000_000, 3_000_000_000)
+    );
+    assert_eq!(
+        inputs(FLAG_SKIP_HELD_SQUARES, &mut stats).held[5],
+        1_000_000
+    );
+}
+
+#[test]
+fn quote_plans_the_same_while_logging_the_ev_surface() {
+    let (config, round) = (config(), round());
+    let logged = OreDeployIxData {
+        flags: FLAG_LOG_EV_SURFACE,
+        ..deploy_data()
+    };
+    assert_eq!(
+        quote(&round, &config, &logged.into(), &QuoteState::default()),
+        quote(
+            &round,
+            &config,
+            &deploy_data().into(),
+            &QuoteState::default()
+        )
+    );
+}
+
+#[test]
+fn derived_kelly_c_follows_the_board_not_the_funded_squares() {
+    // 13 funded squares still derive the board's C = 24, whatever the config holds
+    let mut round = round();
+    round.deployed[13..].fill(0);
+    round.count[13..].fill(0);
+    round.total_deployed = round.deployed.iter().sum();
+    let mut configured = config();
+    configured.params.kelly_c_scaled = 12_000_000_000;
+    let derived = OreDeployIxData {
+        flags: FLAG_DERIVED_KELLY_C,
+        ..deploy_data()
+    };
+    let plan = quote(&round, &configured, &derived.into(), &QuoteState::default()).unwrap();
+
+    let expected = quote(
+        &round,
+        &config(),
+        &deploy_data().into(),
+        &QuoteState::default(),
+    )
+    .unwrap();
+    assert_eq!(plan, expected);
+
+    let undervalued = quote(
+        &round,
+        &configured,
+        &deploy_data().into(),
+        &QuoteState::default(),
+    )
+    .unwrap();
+    assert_ne!(plan, undervalued);
+}
+
+#[test]
+fn quote_limits_rank_thresholds_to_those_in_use() {
+    let (config, round) = (config(), round());
+    let mut data = OreDeployIxDataV2 {
+        base: deploy_data(),
+        ..Zeroable::zeroed()
+    };
+    // Unused entries would reject every block past the first
+    data.rank_thresholds = [i32::MAX; 25];
+    data.rank_thresholds[0] = -10_000;
+    data.num_rank_thresholds = 1;
+
+    let v1 = quote(
+        &round,
+        &config,
+        &deploy_data().into(),
+        &QuoteState::default(),
+    )
+    .unwrap();
+    let v2 = quote(
+        &round,
+        &config,
+        &DeployData::V2(data),
+        &QuoteState::default(),
+    )
+    .unwrap();
+    assert_eq!(v1, v2);
+}
+
+#[test]
+fn quote_reports_the_deploy_outcome_on_a_small_pool() {
+    let mut config = config();
+    config.params.min_total_deployed = u64::MAX;
+    let round = round();
+
+    assert_eq!(
+        quote(
+            &round,
+            &config,
+            &deploy_data().into(),
+            &QuoteState::default()
+        ),
+        Err(ProgramError::Custom(MyProgramError::PoolTooSmall as u32))
+    );
+
+    let noop = OreDeployIxData {
+        flags: FLAG_NOOP_ON_SMALL_POOL,
+        ..deploy_data()
+    };
+    let plan = quote(&round, &config, &noop.into(), &QuoteState::default()).unwrap();
+    assert_eq!(plan.count, 0);
+}
+
+#[test]
+fn quote_fails_where_the_deploy_would() {
+    let data = OreDeployIxData {
+        flags: FLAG_DRAWDOWN_SIZING,
+        ..deploy_data()
+    };
+    assert_eq!(
+        quote(&round(), &config(), &data.into(), &QuoteState::default()),
+        Err(ProgramError::Custom(
+            MyProgramError::MissingBankrollLamports as u32
+        ))
+    );
+}
+
+#[test]
+fn ev_thresholds_are_bounded_after_the_auto_adjustment() {
+    let inputs = PlanInputs {
+        threshold_adjust_bps: -100,
+        ..PlanInputs::new(500_000_000)
+    };
+    let invalid = Err(ProgramError::Custom(
+        MyProgramError::InvalidEvThreshold as u32,
+    ));
+    let data = OreDeployIxData {
+        min_ev_threshold: -9_950,
+        ..deploy_data()
+    };
+    assert_eq!(
+        plan_deploy(&round(), &config(), &data, &[], None, &inputs).map(|plan| plan.count),
+        invalid.clone()
+    );
+
+    // A rank threshold pushed past the bound fails the same way
+    let data = OreDeployIxData {
+        min_ev_threshold: 0,
+        ..deploy_data()
+    };
+    assert_eq!(
+        plan_deploy(&round(), &config(), &data, &[0, -9_950], None, &inputs).map(|plan| plan.count),
+        invalid.clone()
+    );
+    assert_ne!(
+        plan_deploy(&round(), &config(), &data, &[0, -9_900], None, &inputs).map(|plan| plan.count),
+        invalid
+    );
+}
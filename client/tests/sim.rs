@@ -0,0 +1,135 @@
+use ore_ev_client::{simulate, SimConfig};
+use ore_ev_math::{
+    calculate_optimal_deployments, test_utils::config_params as params, Allocation,
+    AllocationParams, ConfigParams, Objective, OreRound, Selection,
+};
+
+const ORE_PRICE: u64 = 1_000_000_000;
+
+/// A round with a few thin squares among well-funded ones
+fn round(motherlode: u64) -> OreRound {
+    let mut deployed = [2_000_000_000u64; 25];
+    deployed[3] = 50_000_000;
+    deployed[11] = 80_000_000;
+    deployed[19] = 120_000_000;
+    OreRound {
+        _disc: [0; 8],
+        id: 7,
+        deployed,
+        slot_hash: [0; 32],
+        count: [40; 25],
+        expires_at: 0,
+        motherlode,
+        rent_payer: [0; 32],
+        top_miner: [0; 32],
+        top_miner_reward: 0,
+        total_deployed: deployed.iter().sum(),
+        total_vaulted: 0,
+        total_winnings: 0,
+    }
+}
+
+fn kelly_plan(round: &OreRound, config: &ConfigParams) -> Allocation {
+    calculate_optimal_deployments(&AllocationParams {
+        round,
+        config,
+        total_budget: 1_000_000_000,
+        max_blocks: 5,
+        ore_price_lamports: ORE_PRICE,
+        min_ev_threshold: 0,
+        rank_thresholds: &[],
+        absolute_threshold: false,
+        max_block_share_bps: 0,
+        objective: Objective::Kelly,
+        selection: Selection::EvPerLamport,
+        max_std_dev_bps: 0,
+        portfolio: false,
+        eligible_squares: 0x1ff_ffff,
+        crowd_penalty_bps: 0,
+        held: [0; 25],
+        top_miner: false,
+        motherlode_weight_bps: 10_000,
+        kelly_fraction_bps: 10_000,
+        payout_multipliers_bps: None,
+    })
+    .unwrap()
+}
+
+fn sim_config() -> SimConfig {
+    SimConfig {
+        ore_price_lamports: ORE_PRICE,
+        ..SimConfig::default()
+    }
+}
+
+#[test]
+fn empty_plan_never_loses() {
+    let report = simulate(&round(0), &Allocation::default(), &params(), &sim_config());
+    assert_eq!(report.mean, 0.0);
+    assert_eq!(report.probability_of_loss, 0.0);
+    assert_eq!((report.min, report.max), (0, 0));
+}
+
+#[test]
+fn mean_converges_to_modeled_ev() {
+    let config = params();
+    let round = round(0);
+    let plan = kelly_plan(&round, &config);
+    assert!(plan.count > 0, "expected the thin squares to be funded");
+
+    let report = simulate(&round, &plan, &config, &sim_config());
+    let standard_error = report.std_dev / (report.trials as f64).sqrt();
+    let gap = (report.mean - plan.portfolio_ev as f64).abs();
+    assert!(
+        gap <= 4.0 * standard_error + 25.0,
+        "mean {} vs modeled EV {} (standard error {standard_error})",
+        report.mean,
+        plan.portfolio_ev
+    );
+
+    // Sampled spread matches the modeled plan standard deviation
+    let modeled = plan.portfolio_std_dev as f64;
+    assert!((report.std_dev - modeled).abs() <= modeled * 0.02);
+}
+
+#[test]
+fn loss_probability_counts_unheld_squares() {
+    let config = params();
+    let round = round(0);
+    let plan = kelly_plan(&round, &config);
+    let report = simulate(&round, &plan, &config, &sim_config());
+
+    // Losing every round we don't hold a winning square, at least
+    let unheld = (25 - plan.count as u32) as f64 / 25.0;
+    assert!(report.probability_of_loss >= unheld - 0.01);
+    assert!(report.p5 < 0 && report.p5 <= report.p50 && report.p50 <= report.p95);
+}
+
+#[test]
+fn motherlode_triggers_at_configured_odds() {
+    let config = params();
+    let round = round(50_000_000_000_000);
+    let plan = kelly_plan(&round, &config);
+    let report = simulate(&round, &plan, &config, &sim_config());
+
+    let expected = report.trials as f64 / config.motherlode_odds as f64;
+    assert!((report.motherlode_hits as f64 - expected).abs() <= 4.0 * expected.sqrt());
+    assert!(report.motherlode_captures <= report.motherlode_hits);
+    assert!(report.max > 0);
+}
+
+#[test]
+fn same_seed_reproduces_the_report() {
+    let config = params();
+    let round = round(50_000_000_000_000);
+    let plan = kelly_plan(&round, &config);
+    let first = simulate(&round, &plan, &config, &sim_config());
+    let second = simulate(&round, &plan, &config, &sim_config());
+    assert_eq!(first, second);
+
+    let reseeded = SimConfig {
+        seed: 42,
+        ..sim_config()
+    };
+    assert_ne!(first, simulate(&round, &plan, &config, &reseeded));
+}
@@ -0,0 +1,46 @@
+use bytemuck::Zeroable;
+use ore_ev_client::{state::RoundRecord, stats::RecordSummary};
+
+fn record(deployed: u64, won: u64, modeled_ev: i64, std_dev: u64) -> RoundRecord {
+    RoundRecord {
+        settled: 1,
+        total_deployed: deployed,
+        won,
+        modeled_ev,
+        modeled_std_dev: std_dev,
+        ..RoundRecord::zeroed()
+    }
+}
+
+#[test]
+fn summary_counts_only_settled_rounds() {
+    let unsettled = RoundRecord {
+        settled: 0,
+        ..record(9_000, 0, 100, 50)
+    };
+    let records = [
+        record(1_000, 3_000, 50, 300),
+        record(1_000, 0, 50, 400),
+        unsettled,
+    ];
+    let summary = RecordSummary::new(&records);
+
+    assert_eq!((summary.rounds, summary.rounds_won), (2, 1));
+    assert_eq!((summary.deployed, summary.won), (2_000, 3_000));
+    assert_eq!(summary.realized_pnl(), 1_000);
+    assert_eq!(summary.win_rate_bps(), Some(5_000));
+}
+
+#[test]
+fn drift_is_realized_pnl_less_modeled_ev_in_sigmas() {
+    let records = [record(1_000, 3_000, 50, 300), record(1_000, 0, 50, 400)];
+    let summary = RecordSummary::new(&records);
+
+    assert_eq!(summary.modeled_ev, 100);
+    assert_eq!(summary.ev_drift(), 900);
+    // sqrt(300² + 400²) = 500
+    assert_eq!(summary.drift_sigmas(), Some(1.8));
+
+    let empty = RecordSummary::new(std::iter::empty());
+    assert_eq!((empty.win_rate_bps(), empty.drift_sigmas()), (None, None));
+}
@@ -0,0 +1,114 @@
+use ore_ev_client::{
+    instructions::DeployAccounts,
+    ix_data::{OreDeployIxData, DEPLOY_CPI_COMPUTE_UNITS},
+    transaction::{deploy_compute_units, deploy_lookup_addresses, TransactionBuilder},
+    PROGRAM_ID,
+};
+use solana_sdk::{
+    address_lookup_table::AddressLookupTableAccount,
+    compute_budget::{self, ComputeBudgetInstruction},
+    hash::Hash,
+    pubkey::Pubkey,
+    signature::Keypair,
+    signer::Signer,
+};
+
+fn deploy_data(num_blocks: u8, max_cpis: u8) -> OreDeployIxData {
+    OreDeployIxData {
+        total_amount: 1_000_000_000,
+        ore_price_lamports: 1_000_000_000,
+        num_blocks,
+        max_cpis,
+        ..bytemuck::Zeroable::zeroed()
+    }
+}
+
+#[test]
+fn deploy_budgets_one_cpi_per_block_up_to_max_cpis() {
+    let accounts = DeployAccounts::new(Pubkey::new_unique(), 7);
+    let limit = |data| TransactionBuilder::deploy(&accounts, data).instructions()[0].clone();
+
+    assert_eq!(
+        deploy_compute_units(6) - deploy_compute_units(5),
+        DEPLOY_CPI_COMPUTE_UNITS as u32
+    );
+    assert_eq!(
+        limit(deploy_data(5, 0)),
+        ComputeBudgetInstruction::set_compute_unit_limit(deploy_compute_units(5))
+    );
+    assert_eq!(
+        limit(deploy_data(5, 2)),
+        ComputeBudgetInstruction::set_compute_unit_limit(deploy_compute_units(2))
+    );
+    // max_cpis 0 is the program's default of 8
+    assert_eq!(
+        limit(deploy_data(25, 0)),
+        ComputeBudgetInstruction::set_compute_unit_limit(deploy_compute_units(8))
+    );
+}
+
+#[test]
+fn deploy_message_puts_the_budget_first_and_the_payer_first() {
+    let authority = Keypair::new();
+    let tx = TransactionBuilder::deploy(
+        &DeployAccounts::new(authority.pubkey(), 7),
+        deploy_data(5, 0),
+    )
+    .compute_unit_price(10_000)
+    .sign(Hash::new_unique(), &[&authority])
+    .unwrap();
+
+    let message = &tx.message;
+    let keys = message.static_account_keys();
+    assert_eq!(keys[0], authority.pubkey());
+    assert_eq!(message.header().num_required_signatures, 1);
+    assert!(tx.verify_with_results().iter().all(|ok| *ok));
+
+    let programs: Vec<Pubkey> = message
+        .instructions()
+        .iter()
+        .map(|ix| keys[ix.program_id_index as usize])
+        .collect();
+    assert_eq!(
+        programs,
+        [compute_budget::ID, compute_budget::ID, PROGRAM_ID]
+    );
+}
+
+#[test]
+fn deploy_message_loads_repeated_accounts_from_a_lookup_table() {
+    let authority = Keypair::new();
+    let accounts = DeployAccounts::new(authority.pubkey(), 7);
+    let builder = TransactionBuilder::deploy(&accounts, deploy_data(5, 0));
+    let table = AddressLookupTableAccount {
+        key: Pubkey::new_unique(),
+        addresses: deploy_lookup_addresses(&accounts),
+    };
+
+    let direct = builder.message(Hash::default()).unwrap();
+    let looked_up = builder
+        .lookup_table(table)
+        .message(Hash::default())
+        .unwrap();
+    assert!(looked_up.static_account_keys().len() < direct.static_account_keys().len());
+    assert_eq!(looked_up.static_account_keys()[0], authority.pubkey());
+
+    // Signers and invoked programs stay static
+    assert!(
+        looked_up.static_account_keys().contains(&PROGRAM_ID)
+            && looked_up
+                .static_account_keys()
+                .contains(&compute_budget::ID)
+    );
+    assert!(looked_up.serialize().len() < direct.serialize().len());
+}
+
+#[test]
+fn sign_fails_without_the_payer() {
+    let tx = TransactionBuilder::deploy(
+        &DeployAccounts::new(Pubkey::new_unique(), 7),
+        deploy_data(5, 0),
+    )
+    .sign(Hash::default(), &[&Keypair::new()]);
+    assert!(tx.is_err());
+}
@@ -0,0 +1,114 @@
+use bytemuck::Zeroable;
+use ore_ev_client::{
+    state::{OreBoard, OreRound},
+    watch::{RoundDiff, RoundEvent, RoundHandler, RoundWatcher},
+};
+
+fn board(round_id: u64) -> OreBoard {
+    OreBoard {
+        round_id,
+        ..Zeroable::zeroed()
+    }
+}
+
+fn round(id: u64, deployed: &[(usize, u64)]) -> OreRound {
+    let mut round = OreRound::zeroed();
+    round.id = id;
+    for &(square, lamports) in deployed {
+        round.deployed[square] += lamports;
+        round.count[square] += 1;
+    }
+    round.total_deployed = round.deployed.iter().sum();
+    round
+}
+
+#[test]
+fn first_round_update_reports_the_whole_pool() {
+    let mut watcher = RoundWatcher::new();
+    assert_eq!(
+        watcher.update_board(board(4)),
+        Some(RoundEvent::NewRound(board(4)))
+    );
+    assert_eq!(watcher.update_board(board(4)), None);
+
+    let current = round(4, &[(2, 500), (7, 300)]);
+    let events = watcher.update_round(current);
+    let [RoundEvent::PoolGrowth(diff)] = events[..] else {
+        panic!("expected pool growth, got {events:?}");
+    };
+    assert_eq!(diff.total_deployed, 800);
+    assert_eq!((diff.deployed[2], diff.miners[7]), (500, 1));
+}
+
+#[test]
+fn round_updates_diff_against_the_last_one() {
+    let mut watcher = RoundWatcher::new();
+    watcher.update_board(board(4));
+    let before = round(4, &[(2, 500)]);
+    watcher.update_round(before);
+
+    let after = round(4, &[(2, 500), (2, 100), (9, 50)]);
+    assert_eq!(
+        watcher.update_round(after),
+        [RoundEvent::PoolGrowth(RoundDiff::new(
+            Some(&before),
+            &after
+        ))]
+    );
+    let diff = RoundDiff::new(Some(&before), &after);
+    assert_eq!((diff.deployed[2], diff.deployed[9]), (100, 50));
+    assert_eq!(diff.total_deployed, 150);
+
+    // An unchanged pool reports nothing
+    assert!(watcher.update_round(after).is_empty());
+}
+
+#[test]
+fn slot_hash_is_reported_once_and_stale_rounds_ignored() {
+    let mut watcher = RoundWatcher::new();
+    assert!(watcher.update_round(round(4, &[(2, 500)])).is_empty());
+
+    watcher.update_board(board(4));
+    watcher.update_round(round(4, &[(2, 500)]));
+    let mut drawn = round(4, &[(2, 500)]);
+    drawn.slot_hash = [7; 32];
+    assert_eq!(
+        watcher.update_round(drawn),
+        [RoundEvent::SlotHashSet(drawn)]
+    );
+    assert!(watcher.update_round(drawn).is_empty());
+
+    // The next round replaces the last; late updates of the old one are dropped
+    watcher.update_board(board(5));
+    assert!(watcher.round().is_none());
+    assert!(watcher.update_round(drawn).is_empty());
+}
+
+#[test]
+fn handler_receives_each_event_by_kind() {
+    #[derive(Default)]
+    struct Counts {
+        rounds: u32,
+        growth: u64,
+        draws: u32,
+    }
+    impl RoundHandler for Counts {
+        fn new_round(&mut self, _board: &OreBoard) {
+            self.rounds += 1;
+        }
+        fn pool_growth(&mut self, diff: &RoundDiff) {
+            self.growth += diff.total_deployed;
+        }
+    }
+
+    let mut counts = Counts::default();
+    let mut watcher = RoundWatcher::new();
+    let mut events: Vec<RoundEvent> = watcher.update_board(board(4)).into_iter().collect();
+    events.extend(watcher.update_round(round(4, &[(1, 250)])));
+    let mut drawn = round(4, &[(1, 250)]);
+    drawn.slot_hash = [1; 32];
+    events.extend(watcher.update_round(drawn));
+    events.iter().for_each(|event| counts.handle(event));
+
+    assert_eq!((counts.rounds, counts.growth, counts.draws), (1, 250, 0));
+}
@@ -0,0 +1,103 @@
+//! Minimal stand-in for the ORE program's deploy and ClaimSOL instructions,
+//! for integration tests: a deploy moves the SOL into the round account and
+//! adds it to the round's per-square totals, and a claim pays the miner's
+//! `rewards_sol` out of its account, so callers see the same state as on ORE.
+
+#![no_std]
+
+use ore_ev_math::OreRound;
+use pinocchio::{
+    account_info::AccountInfo, program_error::ProgramError, pubkey::Pubkey, ProgramResult,
+};
+use pinocchio_system::instructions::Transfer;
+
+#[cfg(not(feature = "no-entrypoint"))]
+mod entrypoint {
+    use pinocchio::{default_panic_handler, no_allocator, program_entrypoint};
+
+    program_entrypoint!(crate::process_instruction);
+    no_allocator!();
+    default_panic_handler!();
+}
+
+/// Discriminator of ORE's deploy instruction
+pub const DEPLOY_IX_DISCRIMINATOR: u8 = 6;
+
+/// Discriminator of ORE's ClaimSOL instruction
+pub const CLAIM_SOL_IX_DISCRIMINATOR: u8 = 3;
+
+/// Offset of `rewards_sol` in ORE's miner account
+pub const MINER_REWARDS_SOL_OFFSET: usize = 488;
+
+pub fn process_instruction(
+    _program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    instruction_data: &[u8],
+) -> ProgramResult {
+    match instruction_data.first() {
+        Some(&DEPLOY_IX_DISCRIMINATOR) => process_deploy(accounts, instruction_data),
+        Some(&CLAIM_SOL_IX_DISCRIMINATOR) => process_claim_sol(accounts),
+        _ => Err(ProgramError::InvalidInstructionData),
+    }
+}
+
+fn process_claim_sol(accounts: &[AccountInfo]) -> ProgramResult {
+    // signer, miner, system program
+    let [signer, miner, ..] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    if !signer.is_signer() {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let mut data = miner.try_borrow_mut_data()?;
+    let rewards = data
+        .get_mut(MINER_REWARDS_SOL_OFFSET..MINER_REWARDS_SOL_OFFSET + 8)
+        .ok_or(ProgramError::InvalidAccountData)?;
+    let amount = u64::from_le_bytes((&*rewards).try_into().unwrap());
+    rewards.fill(0);
+    drop(data);
+
+    *miner.try_borrow_mut_lamports()? -= amount;
+    *signer.try_borrow_mut_lamports()? += amount;
+    Ok(())
+}
+
+fn process_deploy(accounts: &[AccountInfo], instruction_data: &[u8]) -> ProgramResult {
+    // signer, authority, automation, board, miner, round, system program, entropy var, entropy program
+    let [signer, _authority, _automation, _board, _miner, round, _system_program, ..] = accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    let [DEPLOY_IX_DISCRIMINATOR, amount @ .., s0, s1, s2, s3] = instruction_data else {
+        return Err(ProgramError::InvalidInstructionData);
+    };
+    let amount = u64::from_le_bytes(
+        amount
+            .try_into()
+            .map_err(|_| ProgramError::InvalidInstructionData)?,
+    );
+    let squares = u32::from_le_bytes([*s0, *s1, *s2, *s3]);
+    if !signer.is_signer() || squares == 0 || squares >> 25 != 0 {
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    Transfer {
+        from: signer,
+        to: round,
+        lamports: amount * squares.count_ones() as u64,
+    }
+    .invoke()?;
+
+    let mut data = round.try_borrow_mut_data()?;
+    let round_data: &mut OreRound = bytemuck::try_from_bytes_mut(&mut data[..])
+        .map_err(|_| ProgramError::InvalidAccountData)?;
+    for square in (0..25).filter(|i| squares & (1 << i) != 0) {
+        round_data.deployed[square] += amount;
+        round_data.count[square] += 1;
+        round_data.total_deployed += amount;
+    }
+
+    Ok(())
+}
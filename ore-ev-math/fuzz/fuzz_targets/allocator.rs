@@ -0,0 +1,131 @@
+#![no_main]
+
+use arbitrary::Arbitrary;
+use libfuzzer_sys::fuzz_target;
+use ore_ev_math::{
+    calculate_optimal_deployments, AllocationParams, ConfigParams, Error, Objective, OreRound,
+    Selection,
+};
+
+/// Raw allocator inputs; out-of-range configs and objectives are rejected
+/// the same way the program rejects them
+#[derive(Arbitrary, Debug)]
+struct Input {
+    deployed: [u64; 25],
+    count: [u64; 25],
+    held: [u64; 25],
+    motherlode: u64,
+    top_miner_reward: u64,
+    fees_bps: [u16; 4],
+    kelly_c_scaled: u64,
+    min_deploy_per_block: u64,
+    motherlode_odds: u32,
+    motherlode_split: u8,
+    total_budget: u64,
+    max_blocks: u8,
+    ore_price_lamports: u64,
+    min_ev_threshold: i32,
+    rank_thresholds: [i32; 4],
+    absolute_threshold: bool,
+    max_block_share_bps: u16,
+    objective: u8,
+    selection: u8,
+    max_std_dev_bps: u16,
+    portfolio: bool,
+    eligible_squares: u32,
+    crowd_penalty_bps: u16,
+    top_miner: bool,
+    motherlode_weight_bps: u32,
+    kelly_fraction_bps: u16,
+}
+
+fuzz_target!(|input: Input| {
+    let config = ConfigParams {
+        protocol_fee_bps: input.fees_bps[0],
+        refining_fee_bps: input.fees_bps[1],
+        admin_fee_bps: input.fees_bps[2],
+        wrapper_fee_bps: input.fees_bps[3],
+        kelly_c_scaled: input.kelly_c_scaled,
+        min_ore_price_lamports: 0,
+        max_ore_price_lamports: u64::MAX,
+        max_round_deploy_lamports: 0,
+        min_total_deployed: 0,
+        min_deploy_per_block: input.min_deploy_per_block,
+        motherlode_odds: input.motherlode_odds,
+        motherlode_split: input.motherlode_split,
+        _padding2: [0; 3],
+        drawdown_trigger_bps: 0,
+        drawdown_scale_bps: 10_000,
+        max_threshold_adjust_bps: 0,
+        _padding3: [0; 2],
+    };
+    if config.validate().is_err() || !(1..=25).contains(&input.max_blocks) {
+        return;
+    }
+    let (Ok(objective), Ok(selection)) = (
+        Objective::try_from(input.objective),
+        Selection::try_from(input.selection),
+    ) else {
+        return;
+    };
+
+    let round = OreRound {
+        _disc: [0; 8],
+        id: 1,
+        deployed: input.deployed,
+        slot_hash: [0; 32],
+        count: input.count,
+        expires_at: 0,
+        motherlode: input.motherlode,
+        rent_payer: [0; 32],
+        top_miner: [0; 32],
+        top_miner_reward: input.top_miner_reward,
+        total_deployed: input
+            .deployed
+            .iter()
+            .fold(0u64, |t, d| t.saturating_add(*d)),
+        total_vaulted: 0,
+        total_winnings: 0,
+    };
+
+    let p = AllocationParams {
+        round: &round,
+        config: &config,
+        total_budget: input.total_budget,
+        max_blocks: input.max_blocks,
+        ore_price_lamports: input.ore_price_lamports,
+        min_ev_threshold: input.min_ev_threshold,
+        rank_thresholds: &input.rank_thresholds,
+        absolute_threshold: input.absolute_threshold,
+        max_block_share_bps: input.max_block_share_bps,
+        objective,
+        selection,
+        max_std_dev_bps: input.max_std_dev_bps,
+        portfolio: input.portfolio,
+        eligible_squares: input.eligible_squares,
+        crowd_penalty_bps: input.crowd_penalty_bps,
+        held: input.held,
+        top_miner: input.top_miner,
+        motherlode_weight_bps: input.motherlode_weight_bps,
+        kelly_fraction_bps: input.kelly_fraction_bps,
+        payout_multipliers_bps: None,
+    };
+
+    // Any input must either size a plan within budget or report overflow
+    match calculate_optimal_deployments(&p) {
+        Ok(plan) => {
+            let count = plan.count as usize;
+            let total: u128 = plan.amounts[..count].iter().map(|a| *a as u128).sum();
+            assert!(
+                total <= p.total_budget as u128,
+                "plan overspends the budget"
+            );
+            for i in 0..count {
+                assert!(plan.amounts[i] >= config.min_deploy_per_block);
+                assert!(p.eligible_squares & (1 << plan.indices[i]) != 0);
+            }
+        }
+        Err(Error::MathOverflow) => {}
+        Err(e) => panic!("unexpected error {e:?}"),
+    }
+});
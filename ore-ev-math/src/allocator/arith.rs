@@ -0,0 +1,77 @@
+//! Arithmetic the allocator can overflow in
+//!
+//! By default an overflow saturates, as the allocator always has. With the
+//! `strict-math` feature it is an `Error::ArithmeticOverflow` instead: a
+//! saturated product passes for a plausible Kelly size or EV, so strict
+//! builds refuse to plan with one.
+//!
+//! Clamps that are part of the model (a Kelly root below the block, a pot
+//! shrunk past zero by our own stake) stay saturating in both modes, as do
+//! totals of planned amounts, which never exceed the budget they're checked
+//! against.
+
+use crate::Error;
+
+/// `saturated` by default, the overflow error with `strict-math`
+#[inline(always)]
+fn overflow<T>(saturated: T) -> Result<T, Error> {
+    if cfg!(feature = "strict-math") {
+        Err(Error::ArithmeticOverflow)
+    } else {
+        Ok(saturated)
+    }
+}
+
+/// a + b
+#[inline(always)]
+pub fn add(a: u64, b: u64) -> Result<u64, Error> {
+    match a.checked_add(b) {
+        Some(sum) => Ok(sum),
+        None => overflow(u64::MAX),
+    }
+}
+
+/// a × b
+#[inline(always)]
+pub fn mul(a: u64, b: u64) -> Result<u64, Error> {
+    match a.checked_mul(b) {
+        Some(product) => Ok(product),
+        None => overflow(u64::MAX),
+    }
+}
+
+/// a × b in u128
+#[inline(always)]
+pub fn mul_u128(a: u128, b: u128) -> Result<u128, Error> {
+    match a.checked_mul(b) {
+        Some(product) => Ok(product),
+        None => overflow(u128::MAX),
+    }
+}
+
+/// a - b for signed lamports
+#[inline(always)]
+pub fn sub_i64(a: i64, b: i64) -> Result<i64, Error> {
+    match a.checked_sub(b) {
+        Some(difference) => Ok(difference),
+        None => overflow(if b > 0 { i64::MIN } else { i64::MAX }),
+    }
+}
+
+/// Narrow a widened amount back to lamports
+#[inline(always)]
+pub fn to_u64(value: u128) -> Result<u64, Error> {
+    match u64::try_from(value) {
+        Ok(value) => Ok(value),
+        Err(_) => overflow(u64::MAX),
+    }
+}
+
+/// Narrow a widened EV back to signed lamports
+#[inline(always)]
+pub fn to_i64(value: i128) -> Result<i64, Error> {
+    match i64::try_from(value) {
+        Ok(value) => Ok(value),
+        Err(_) => overflow(if value < 0 { i64::MIN } else { i64::MAX }),
+    }
+}
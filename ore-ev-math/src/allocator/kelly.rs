@@ -0,0 +1,107 @@
+use crate::allocator::{
+    apply_fraction, arith, block_ev, marginal_ev, top_miner_threshold,
+    try_calculate_kelly_optimal_scaled, AllocationParams, Candidate, Selection,
+};
+use crate::{Error, BOARD_SQUARES};
+
+/// Kelly-optimal size (capped by the share limit) for the `max_blocks`
+/// candidates chosen by the selection rule
+pub fn prepare(
+    candidates: &mut [Candidate],
+    p: &AllocationParams,
+    ore_value: u64,
+) -> Result<(), Error> {
+    // Only the leading candidates arrive sorted by size (smallest or largest
+    // first); the rest can't be selected
+    for c in candidates.iter_mut().take(p.candidate_pool()) {
+        target(c, p, ore_value)?;
+    }
+
+    if p.selection == Selection::EvPerLamport {
+        rank_by_ev_per_lamport(candidates, p, ore_value)?;
+    }
+
+    for c in candidates.iter_mut().take(p.max_blocks as usize) {
+        c.active = c.optimal > 0;
+    }
+    Ok(())
+}
+
+/// Kelly-optimal size of a candidate against its current pool, capped by the
+/// share limit
+pub fn target(c: &mut Candidate, p: &AllocationParams, ore_value: u64) -> Result<(), Error> {
+    // Calculate Kelly-optimal deployment: y* = √(V × O / C) - O
+    let kelly =
+        try_calculate_kelly_optimal_scaled(c.size, c.pool, ore_value, p.config, c.payout_bps)?;
+    c.optimal = apply_fraction(kelly, p.kelly_fraction_bps).min(c.share_cap);
+
+    // Topping up to the top-miner threshold can beat the Kelly size once
+    // the reward is counted
+    if p.top_miner {
+        let threshold = top_miner_threshold(c, p);
+        if threshold > c.optimal
+            && threshold <= c.share_cap
+            && threshold <= p.total_budget
+            && block_ev(c, threshold, p, ore_value)? > block_ev(c, c.optimal, p, ore_value)?
+        {
+            c.optimal = threshold;
+        }
+    }
+
+    // Budget re-solves never push a block past its Kelly optimum
+    c.cap = c.optimal;
+    Ok(())
+}
+
+/// Reorder candidates by EV per lamport at the amount we'd actually deploy
+/// (Kelly optimum, capped by share limit and budget), net of the crowd
+/// discount, best first
+fn rank_by_ev_per_lamport(
+    candidates: &mut [Candidate],
+    p: &AllocationParams,
+    ore_value: u64,
+) -> Result<(), Error> {
+    let mut scores: [i64; BOARD_SQUARES] = [i64::MIN; BOARD_SQUARES];
+    for (score, c) in scores.iter_mut().zip(candidates.iter()) {
+        let amount = c.optimal.min(p.total_budget);
+        if amount == 0 {
+            continue;
+        }
+        let ev = block_ev(c, amount, p, ore_value)?;
+        let crowd_penalty = c.crowd_penalty_bps as i128 * 100_000; // bps -> 1e9 scale
+        *score = arith::to_i64((ev * 1_000_000_000) / amount as i128 - crowd_penalty)?;
+    }
+
+    // Insertion sort descending; stable, so equal scores keep smallest-first order
+    for i in 1..candidates.len() {
+        let mut j = i;
+        while j > 0 && scores[j] > scores[j - 1] {
+            scores.swap(j, j - 1);
+            candidates.swap(j, j - 1);
+            j -= 1;
+        }
+    }
+    Ok(())
+}
+
+/// Deploy the active Kelly optima, or re-solve under the budget if they don't fit
+///
+/// Shaving every block proportionally is suboptimal with a tight budget;
+/// instead marginal EV is equalized across the active blocks (as in the
+/// marginal-EV objective), each capped at its Kelly optimum, so the budget
+/// concentrates where each lamport earns the most.
+pub fn size(candidates: &mut [Candidate], budget: u64, c_scaled: u64) {
+    let total_optimal = candidates
+        .iter()
+        .filter(|c| c.active)
+        .fold(0u64, |total, c| total.saturating_add(c.optimal));
+
+    if total_optimal > budget {
+        marginal_ev::size(candidates, budget, c_scaled);
+        return;
+    }
+
+    for c in candidates.iter_mut() {
+        c.amount = if c.active { c.optimal } else { 0 };
+    }
+}
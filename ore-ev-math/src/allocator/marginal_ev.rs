@@ -0,0 +1,115 @@
+use crate::{
+    allocator::{
+        apply_fraction, kelly_amount, try_all_in_kelly_c_scaled, AllocationParams, Candidate,
+    },
+    Error,
+};
+
+/// Bisection steps when solving for the shared marginal EV
+const BISECTION_STEPS: u32 = 24;
+
+/// Consider every non-empty square in the candidate pool, then keep the `max_blocks` squares that
+/// receive the largest allocations at the budget-constrained optimum
+pub fn prepare(candidates: &mut [Candidate], p: &AllocationParams) -> Result<(), Error> {
+    let c_scaled = try_all_in_kelly_c_scaled(p.config)?;
+    let pool = p.candidate_pool();
+    for (i, c) in candidates.iter_mut().enumerate() {
+        c.active = i < pool && c.size > 0 && c.pot_value > 0;
+
+        // Fractional Kelly caps each square at that fraction of its Kelly size
+        if p.kelly_fraction_bps < 10_000 {
+            let kelly = kelly_amount(c.pot_value, c.size, c_scaled);
+            c.cap = c.share_cap.min(apply_fraction(kelly, p.kelly_fraction_bps));
+        }
+    }
+
+    size(candidates, p.total_budget, c_scaled);
+    keep_largest(candidates, p.max_blocks as usize);
+    Ok(())
+}
+
+/// Deactivate unfunded candidates and all but the `max_blocks` largest allocations
+pub fn keep_largest(candidates: &mut [Candidate], max_blocks: usize) {
+    let mut active = 0;
+    for c in candidates.iter_mut() {
+        c.active = c.active && c.amount > 0;
+        if c.active {
+            active += 1;
+        }
+    }
+
+    while active > max_blocks {
+        let mut smallest: Option<usize> = None;
+        for (i, c) in candidates.iter().enumerate() {
+            if c.active && smallest.is_none_or(|s| c.amount < candidates[s].amount) {
+                smallest = Some(i);
+            }
+        }
+        if let Some(i) = smallest {
+            candidates[i].active = false;
+        }
+        active -= 1;
+    }
+}
+
+/// Size the active candidates so marginal EV per lamport is equal across them
+///
+/// The marginal EV of y on a square is V·O / (N·(O + y)²) - (N - 1)/N - fee,
+/// so equal marginal EV means (O + y)² = V·O / C' for one shared C' >= C.
+/// C' = C is the unconstrained Kelly optimum; C' is raised by bisection until
+/// the allocation fits the budget. Share caps clamp individual squares.
+pub fn size(candidates: &mut [Candidate], budget: u64, c_scaled: u64) {
+    let mut solved = c_scaled;
+
+    if total_at(candidates, c_scaled) > budget {
+        // Bracket the solution by doubling C'
+        let mut lo = c_scaled;
+        let mut hi = c_scaled.saturating_mul(2);
+        while total_at(candidates, hi) > budget && hi < u64::MAX / 2 {
+            lo = hi;
+            hi *= 2;
+        }
+
+        for _ in 0..BISECTION_STEPS {
+            let mid = lo + (hi - lo) / 2;
+            if total_at(candidates, mid) > budget {
+                lo = mid;
+            } else {
+                hi = mid;
+            }
+        }
+
+        solved = hi;
+    }
+
+    for c in candidates.iter_mut() {
+        c.amount = if c.active { amount_at(c, solved) } else { 0 };
+    }
+    fit_budget(candidates, budget);
+}
+
+/// Trim allocations in order until they fit the budget
+///
+/// Bracketing stops at C' = u64::MAX / 2, where a tiny square under a large
+/// pot is still funded a few lamports, so a budget of a few hundred lamports
+/// can stay overspent after the solve.
+pub fn fit_budget(candidates: &mut [Candidate], budget: u64) {
+    let mut remaining = budget;
+    for c in candidates.iter_mut() {
+        c.amount = c.amount.min(remaining);
+        remaining -= c.amount;
+    }
+}
+
+pub fn amount_at(c: &Candidate, c_scaled: u64) -> u64 {
+    kelly_amount(c.pot_value, c.size, c_scaled).min(c.cap)
+}
+
+pub fn total_at(candidates: &[Candidate], c_scaled: u64) -> u64 {
+    candidates
+        .iter()
+        .filter(|c| c.active)
+        .fold(0u64, |total, c| {
+            total.saturating_add(amount_at(c, c_scaled))
+        })
+}
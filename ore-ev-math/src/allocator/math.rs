@@ -0,0 +1,385 @@
+use crate::{
+    allocator::arith, ConfigParams, Error, BOARD_SQUARES, MOTHERLODE_SPLIT_PROPORTIONAL, Q64,
+};
+
+/// One ORE in base units (11 decimals)
+const ONE_ORE: u128 = 100_000_000_000;
+
+/// ORE value of a win in lamports, after refining fee. Includes the expected
+/// motherlode value (see `motherlode_value`) when it is split in proportion to
+/// stake, like the rest of the pot
+pub fn ore_value(ore_price_lamports: u64, motherlode_value: u64, params: &ConfigParams) -> u64 {
+    try_ore_value(ore_price_lamports, motherlode_value, params).unwrap_or(u64::MAX)
+}
+
+/// `ore_value`, failing on overflow with `strict-math`
+pub fn try_ore_value(
+    ore_price_lamports: u64,
+    motherlode_value: u64,
+    params: &ConfigParams,
+) -> Result<u64, Error> {
+    let refined_bps = 10_000 - params.refining_fee_bps as u128;
+    let base = (ore_price_lamports as u128 * refined_bps / 10_000) as u64;
+    if params.motherlode_split != MOTHERLODE_SPLIT_PROPORTIONAL {
+        return Ok(base);
+    }
+    arith::add(base, motherlode_value)
+}
+
+/// Expected lamport value of the motherlode to the winning square's miners:
+/// the ORE balance at `ore_price_lamports`, after refining, times the hit chance
+pub fn motherlode_value(ore_price_lamports: u64, motherlode: u64, params: &ConfigParams) -> u64 {
+    try_motherlode_value(ore_price_lamports, motherlode, params).unwrap_or(u64::MAX)
+}
+
+/// `motherlode_value`, failing on overflow with `strict-math`
+pub fn try_motherlode_value(
+    ore_price_lamports: u64,
+    motherlode: u64,
+    params: &ConfigParams,
+) -> Result<u64, Error> {
+    let refined_bps = 10_000 - params.refining_fee_bps as u128;
+    let value = arith::mul_u128(motherlode as u128 * ore_price_lamports as u128, refined_bps)?
+        / (ONE_ORE * 10_000 * params.motherlode_odds as u128);
+    arith::to_u64(value)
+}
+
+/// Lamport value of the round's top-miner reward (ORE, 11 decimals) after the refining fee
+pub fn top_miner_value(
+    ore_price_lamports: u64,
+    top_miner_reward: u64,
+    params: &ConfigParams,
+) -> u64 {
+    try_top_miner_value(ore_price_lamports, top_miner_reward, params).unwrap_or(u64::MAX)
+}
+
+/// `top_miner_value`, failing on overflow with `strict-math`
+pub fn try_top_miner_value(
+    ore_price_lamports: u64,
+    top_miner_reward: u64,
+    params: &ConfigParams,
+) -> Result<u64, Error> {
+    let refined_bps = 10_000 - params.refining_fee_bps as u128;
+    let value = arith::mul_u128(
+        top_miner_reward as u128 * ore_price_lamports as u128,
+        refined_bps,
+    )? / (ONE_ORE * 10_000);
+    arith::to_u64(value)
+}
+
+/// Payout multiplier of a square paying out its pot as is, in bps
+pub const FLAT_PAYOUT_BPS: u64 = 10_000;
+
+/// Scale a square's pot by its payout multiplier in bps
+pub fn scale_payout(pot: u64, payout_bps: u64) -> u64 {
+    try_scale_payout(pot, payout_bps).unwrap_or(u64::MAX)
+}
+
+/// `scale_payout`, failing on overflow with `strict-math`
+pub fn try_scale_payout(pot: u64, payout_bps: u64) -> Result<u64, Error> {
+    if payout_bps == FLAT_PAYOUT_BPS {
+        return Ok(pot);
+    }
+    arith::to_u64(pot as u128 * payout_bps as u128 / 10_000)
+}
+
+/// Pot value V if the block of `block_size` wins: the losing pool after the
+/// protocol fee plus the ORE reward
+pub fn pot_value(block_size: u64, total_pool: u64, ore_value: u64, params: &ConfigParams) -> u64 {
+    try_pot_value(block_size, total_pool, ore_value, params).unwrap_or(u64::MAX)
+}
+
+/// `pot_value`, failing on overflow with `strict-math`
+pub fn try_pot_value(
+    block_size: u64,
+    total_pool: u64,
+    ore_value: u64,
+    params: &ConfigParams,
+) -> Result<u64, Error> {
+    let losing_pool = total_pool.saturating_sub(block_size);
+    arith::add(payout(losing_pool, params), ore_value)
+}
+
+/// Share of a losing pool paid out to winners after the protocol fee
+fn payout(losing_pool: u64, params: &ConfigParams) -> u64 {
+    let payout_bps = 10_000 - params.protocol_fee_bps as u128;
+    (losing_pool as u128 * payout_bps / 10_000) as u64
+}
+
+/// a × b / c without overflowing the product, exact while b × c fits in a u128
+pub fn mul_div(a: u128, b: u128, c: u128) -> u128 {
+    a / c * b + a % c * b / c
+}
+
+/// √(V × O / C) for C scaled by 1e9, computed in Q64.64 as √(V / C) × √O
+///
+/// With C >= 1, V / C fits the 64 integer bits and the root is at most
+/// √(V × O), so it fits a u64.
+fn kelly_root(pot_value: u64, block_size: u64, c_scaled: u64) -> u64 {
+    let c = Q64::from_scaled(c_scaled, 1_000_000_000);
+    let root = (Q64::from_int(pot_value) / c).sqrt() * Q64::from_int(block_size).sqrt();
+    root.to_int()
+}
+
+/// y = √(V × O / C) - O for an arbitrary C (scaled by 1e9), without refinement
+pub fn kelly_amount(pot_value: u64, block_size: u64, c_scaled: u64) -> u64 {
+    kelly_root(pot_value, block_size, c_scaled).saturating_sub(block_size)
+}
+
+/// Calculate Kelly-optimal deployment for a single block
+/// Formula: y* = √(V × O / C) - O, with C taken from the config
+/// With iterative refinement to account for pot impact
+pub fn calculate_kelly_optimal(
+    block_size: u64,
+    total_pool: u64,
+    ore_value: u64,
+    params: &ConfigParams,
+) -> u64 {
+    calculate_kelly_optimal_scaled(block_size, total_pool, ore_value, params, FLAT_PAYOUT_BPS)
+}
+
+/// `calculate_kelly_optimal` for a square whose pot is scaled by a payout
+/// multiplier in bps
+pub fn calculate_kelly_optimal_scaled(
+    block_size: u64,
+    total_pool: u64,
+    ore_value: u64,
+    params: &ConfigParams,
+    payout_bps: u64,
+) -> u64 {
+    try_calculate_kelly_optimal_scaled(block_size, total_pool, ore_value, params, payout_bps)
+        .unwrap_or(0)
+}
+
+/// `calculate_kelly_optimal_scaled`, failing on overflow with `strict-math`
+pub fn try_calculate_kelly_optimal_scaled(
+    block_size: u64,
+    total_pool: u64,
+    ore_value: u64,
+    params: &ConfigParams,
+    payout_bps: u64,
+) -> Result<u64, Error> {
+    let c_scaled = try_all_in_kelly_c_scaled(params)?;
+
+    if block_size == 0 || total_pool <= block_size {
+        return Ok(0);
+    }
+
+    // Initial pot value if this block wins
+    let losing_pool = total_pool - block_size;
+    let winnings = payout(losing_pool, params); // After protocol fee
+    let v = try_scale_payout(arith::add(winnings, ore_value)?, payout_bps)?;
+
+    if v == 0 {
+        return Ok(0);
+    }
+
+    // Calculate y* = √(V × O / C) - O
+    let mut y_star = kelly_amount(v, block_size, c_scaled);
+
+    // Iterative refinement (accounts for deployment reducing pot)
+    for _ in 0..5 {
+        if y_star == 0 {
+            break;
+        }
+
+        // Recalculate V with your deployment factored in
+        let adjusted_pool = losing_pool.saturating_sub(y_star);
+        let adjusted_winnings = payout(adjusted_pool, params);
+        let new_v = try_scale_payout(arith::add(adjusted_winnings, ore_value)?, payout_bps)?;
+
+        if new_v == 0 {
+            return Ok(0);
+        }
+
+        // Recalculate y*
+        let new_y_star = kelly_amount(new_v, block_size, c_scaled);
+
+        // Check convergence (within 100 lamports)
+        if new_y_star.abs_diff(y_star) < 100 {
+            y_star = new_y_star;
+            break;
+        }
+
+        y_star = new_y_star;
+    }
+
+    Ok(y_star)
+}
+
+/// Largest deployment keeping our share of the block's post-deploy total
+/// at or below `max_share_bps`, counting the `held` lamports of the block
+/// that are already ours: (h + y) / (O + y) <= s  =>  y <= (O·s - h) / (1 - s)
+pub fn max_amount_for_share(block_size: u64, held: u64, max_share_bps: u16) -> u64 {
+    try_max_amount_for_share(block_size, held, max_share_bps).unwrap_or(u64::MAX)
+}
+
+/// `max_amount_for_share`, failing on overflow with `strict-math`
+pub fn try_max_amount_for_share(
+    block_size: u64,
+    held: u64,
+    max_share_bps: u16,
+) -> Result<u64, Error> {
+    if max_share_bps == 0 || max_share_bps >= 10_000 {
+        return Ok(u64::MAX);
+    }
+    let allowed =
+        (block_size as u128 * max_share_bps as u128).saturating_sub(held as u128 * 10_000);
+    arith::to_u64(allowed / (10_000 - max_share_bps) as u128)
+}
+
+/// Fee-free Kelly constant implied by the board geometry, scaled by 1e9
+///
+/// Maximizing EV(y) = V·y / (N·(O + y)) - y·(N - 1)/N - y·fee over y gives
+/// (O + y)² = V·O / C' with C' = (N - 1) + N·fee; this is the N - 1 part, the
+/// fee part is added by `all_in_kelly_c_scaled`.
+pub fn derive_kelly_c_scaled(num_squares: u64) -> u64 {
+    (num_squares - 1) * 1_000_000_000
+}
+
+/// Kelly constant used for sizing, scaled by 1e9: the configured fee-free C
+/// plus the all-in fee load, C' = C + N·(admin fee + wrapper fee)
+/// (24.2525 for 25 squares and a 1.01% admin fee)
+pub fn all_in_kelly_c_scaled(params: &ConfigParams) -> u64 {
+    try_all_in_kelly_c_scaled(params).unwrap_or(u64::MAX)
+}
+
+/// `all_in_kelly_c_scaled`, failing on overflow with `strict-math`
+pub fn try_all_in_kelly_c_scaled(params: &ConfigParams) -> Result<u64, Error> {
+    let fee_load = arith::mul(BOARD_SQUARES as u64 * 100_000, params.fee_load_bps())?;
+    arith::add(params.kelly_c_scaled, fee_load)
+}
+
+/// Integer square root, floor(√n) (Newton's method from above)
+///
+/// Starting at 2^ceil(bits/2) >= √n, every step strictly decreases until it
+/// reaches floor(√n), so the loop always terminates with the exact root.
+#[inline(always)]
+pub fn isqrt(n: u64) -> u64 {
+    if n < 2 {
+        return n;
+    }
+
+    let mut x = 1u64 << (64 - n.leading_zeros()).div_ceil(2);
+    loop {
+        let y = (x + n / x) >> 1;
+        if y >= x {
+            return x;
+        }
+        x = y;
+    }
+}
+
+/// Integer square root of a u128 (Newton's method from above)
+pub fn isqrt_u128(n: u128) -> u128 {
+    if n < 2 {
+        return n;
+    }
+
+    // 2^ceil(bits/2) is at least sqrt(n), so the iteration decreases monotonically
+    let mut x = 1u128 << (128 - n.leading_zeros()).div_ceil(2);
+    loop {
+        let y = (x + n / x) >> 1;
+        if y >= x {
+            return x;
+        }
+        x = y;
+    }
+}
+
+/// Calculate expected value for a deployment
+pub fn calculate_ev(
+    block_size: u64,
+    deploy_amount: u64,
+    total_pool: u64,
+    ore_value: u64,
+    params: &ConfigParams,
+) -> i64 {
+    calculate_ev_scaled(
+        block_size,
+        deploy_amount,
+        total_pool,
+        ore_value,
+        params,
+        FLAT_PAYOUT_BPS,
+    )
+}
+
+/// `calculate_ev` for a square whose pot is scaled by a payout multiplier in
+/// bps
+pub fn calculate_ev_scaled(
+    block_size: u64,
+    deploy_amount: u64,
+    total_pool: u64,
+    ore_value: u64,
+    params: &ConfigParams,
+    payout_bps: u64,
+) -> i64 {
+    try_calculate_ev_scaled(
+        block_size,
+        deploy_amount,
+        total_pool,
+        ore_value,
+        params,
+        payout_bps,
+    )
+    .unwrap_or(i64::MIN)
+}
+
+/// `calculate_ev_scaled`, failing on overflow with `strict-math`
+pub fn try_calculate_ev_scaled(
+    block_size: u64,
+    deploy_amount: u64,
+    total_pool: u64,
+    ore_value: u64,
+    params: &ConfigParams,
+    payout_bps: u64,
+) -> Result<i64, Error> {
+    let ev = try_calculate_ev_wide(
+        block_size,
+        deploy_amount,
+        total_pool,
+        ore_value,
+        params,
+        payout_bps,
+    )?;
+    arith::to_i64(ev)
+}
+
+/// `calculate_ev_scaled` before narrowing to an i64, for callers that scale
+/// or sum EVs; `i64::MIN` for an empty block or deployment
+pub fn try_calculate_ev_wide(
+    block_size: u64,
+    deploy_amount: u64,
+    total_pool: u64,
+    ore_value: u64,
+    params: &ConfigParams,
+    payout_bps: u64,
+) -> Result<i128, Error> {
+    if deploy_amount == 0 || block_size == 0 {
+        return Ok(i64::MIN as i128);
+    }
+
+    let total_block = arith::add(block_size, deploy_amount)?;
+
+    // Your share of the block, y / (O + y)
+    let share = Q64::from_ratio(deploy_amount as u128, total_block as u128);
+
+    // Pot value if you win
+    let losing_pool = total_pool.saturating_sub(block_size);
+    let pot = arith::add(payout(losing_pool, params), ore_value)?; // After protocol fee
+    let pot = try_scale_payout(pot, payout_bps)?;
+
+    // EV calculation, kept wide for the caller to narrow
+    let squares = BOARD_SQUARES as u128;
+    let expected_win = share.mul_int(pot) / squares;
+    let expected_loss = deploy_amount as u128 * (squares - 1) / squares;
+    let admin_fee = deploy_amount as u128 * params.fee_load_bps() as u128 / 10_000;
+
+    Ok(expected_win as i128 - expected_loss as i128 - admin_fee as i128)
+}
+
+/// Scale a lamport amount by a Kelly fraction in bps
+pub fn apply_fraction(amount: u64, fraction_bps: u16) -> u64 {
+    (amount as u128 * fraction_bps as u128 / 10_000) as u64
+}
@@ -0,0 +1,51 @@
+use crate::allocator::{
+    marginal_ev::{amount_at, fit_budget, keep_largest, total_at},
+    AllocationParams, Candidate,
+};
+
+/// Bisection steps when solving for the full-spend constant
+const BISECTION_STEPS: u32 = 40;
+
+/// Consider every non-empty square in the candidate pool, then keep the `max_blocks` squares that
+/// receive the largest allocations when the whole budget is spent
+pub fn prepare(candidates: &mut [Candidate], p: &AllocationParams) {
+    let pool = p.candidate_pool();
+    for (i, c) in candidates.iter_mut().enumerate() {
+        c.active = i < pool && c.size > 0 && c.pot_value > 0;
+    }
+
+    size(candidates, p.total_budget);
+    keep_largest(candidates, p.max_blocks as usize);
+}
+
+/// Spend the whole budget (or every share cap, if smaller) where marginal EV
+/// is highest, ignoring Kelly sizing
+///
+/// Marginal EV is equalized as in the marginal-EV objective, but the shared
+/// constant C' may fall below the Kelly C, so squares are pushed past their
+/// Kelly optimum until the budget is exhausted.
+pub fn size(candidates: &mut [Candidate], budget: u64) {
+    // Bracket the smallest C' whose allocation fits the budget
+    let mut hi = 1_000_000_000u64;
+    while total_at(candidates, hi) > budget && hi < u64::MAX / 2 {
+        hi *= 2;
+    }
+
+    let mut lo = 0u64;
+    for _ in 0..BISECTION_STEPS {
+        let mid = lo + (hi - lo) / 2;
+        if mid == 0 || total_at(candidates, mid) <= budget {
+            hi = mid.max(1);
+        } else {
+            lo = mid;
+        }
+        if hi - lo <= 1 {
+            break;
+        }
+    }
+
+    for c in candidates.iter_mut() {
+        c.amount = if c.active { amount_at(c, hi) } else { 0 };
+    }
+    fit_budget(candidates, budget);
+}
@@ -0,0 +1,48 @@
+use crate::{
+    allocator::{
+        evaluate_candidates, marginal_ev, try_all_in_kelly_c_scaled, AllocationParams, Candidate,
+    },
+    Error,
+};
+
+/// Bisection steps when solving for the spend that meets the risk cap
+const BISECTION_STEPS: u32 = 20;
+
+/// Maximize EV subject to a cap on the plan's PnL standard deviation
+///
+/// The marginal-EV allocation is the EV-maximizing way to spend any given
+/// amount, and its standard deviation grows with the amount spent, so the
+/// constrained optimum is the marginal-EV allocation at the largest spend
+/// whose standard deviation fits under `max_std_dev_bps` of the budget.
+pub fn size(
+    candidates: &mut [Candidate],
+    p: &AllocationParams,
+    ore_value: u64,
+) -> Result<(), Error> {
+    let c_scaled = try_all_in_kelly_c_scaled(p.config)?;
+    let max_std_dev = (p.total_budget as u128 * p.max_std_dev_bps as u128 / 10_000) as u64;
+
+    marginal_ev::size(candidates, p.total_budget, c_scaled);
+    if std_dev(candidates, p, ore_value)? <= max_std_dev {
+        return Ok(());
+    }
+
+    let mut lo = 0u64;
+    let mut hi = p.total_budget;
+    for _ in 0..BISECTION_STEPS {
+        let mid = lo + (hi - lo) / 2;
+        marginal_ev::size(candidates, mid, c_scaled);
+        if std_dev(candidates, p, ore_value)? <= max_std_dev {
+            lo = mid;
+        } else {
+            hi = mid;
+        }
+    }
+
+    marginal_ev::size(candidates, lo, c_scaled);
+    Ok(())
+}
+
+fn std_dev(candidates: &[Candidate], p: &AllocationParams, ore_value: u64) -> Result<u64, Error> {
+    Ok(evaluate_candidates(candidates, None, ore_value, p)?.std_dev())
+}
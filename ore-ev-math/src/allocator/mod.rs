@@ -0,0 +1,712 @@
+use crate::{ConfigParams, Error, OreRound, BOARD_SQUARES, MOTHERLODE_SPLIT_PER_MINER};
+
+pub mod arith;
+pub mod kelly;
+pub mod marginal_ev;
+pub mod math;
+pub mod max_ev;
+pub mod mean_variance;
+pub mod portfolio;
+
+pub use math::*;
+pub use portfolio::{PortfolioStats, Position};
+
+/// Re-sizing passes against pools updated with our own stakes
+const POT_IMPACT_PASSES: u32 = 3;
+
+/// Most blocks a single plan can deploy to (every square on the board)
+pub const MAX_PLAN_BLOCKS: usize = BOARD_SQUARES;
+
+/// How the budget is sized across blocks
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Objective {
+    /// Independent Kelly optimum on the selected blocks, re-solved to fit the budget
+    Kelly = 0,
+    /// Equalize marginal EV per lamport across all squares under the budget
+    MarginalEv = 1,
+    /// Maximize EV subject to a cap on the plan's PnL standard deviation
+    MeanVariance = 2,
+    /// Spend the full budget where marginal EV is highest, ignoring Kelly sizing
+    MaxEv = 3,
+    /// Max-EV sizing with the motherlode payoff weighted up, to maximize
+    /// expected motherlode capture
+    MotherlodeHunt = 4,
+}
+
+impl TryFrom<u8> for Objective {
+    type Error = Error;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(Objective::Kelly),
+            1 => Ok(Objective::MarginalEv),
+            2 => Ok(Objective::MeanVariance),
+            3 => Ok(Objective::MaxEv),
+            4 => Ok(Objective::MotherlodeHunt),
+            _ => Err(Error::InvalidObjective),
+        }
+    }
+}
+
+/// Which squares may receive budget
+///
+/// The Kelly objective sizes the first `max_blocks` squares in this order. The
+/// marginal objectives consider every square under `EvPerLamport` and only the
+/// first `max_blocks` squares by size under `Smallest` / `Largest`.
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Selection {
+    /// Highest EV per lamport at the candidate deployment size
+    EvPerLamport = 0,
+    /// Smallest blocks first
+    Smallest = 1,
+    /// Largest blocks first (top-miner hunting, avoiding sniped small blocks)
+    Largest = 2,
+}
+
+impl TryFrom<u8> for Selection {
+    type Error = Error;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(Selection::EvPerLamport),
+            1 => Ok(Selection::Smallest),
+            2 => Ok(Selection::Largest),
+            _ => Err(Error::InvalidSelection),
+        }
+    }
+}
+
+/// Inputs to the allocator
+pub struct AllocationParams<'a> {
+    pub round: &'a OreRound,
+
+    pub config: &'a ConfigParams,
+
+    /// Total lamports available
+    pub total_budget: u64,
+
+    /// Maximum number of blocks to deploy to (1-25)
+    pub max_blocks: u8,
+
+    /// ORE price in lamports
+    pub ore_price_lamports: u64,
+
+    /// Minimum EV threshold, in bps of the deployment (or lamports if `absolute_threshold`)
+    pub min_ev_threshold: i32,
+
+    /// Per-rank thresholds, by position in selection order; ranks past the
+    /// end fall back to `min_ev_threshold`
+    pub rank_thresholds: &'a [i32],
+
+    /// Thresholds are absolute EV floors in lamports per block, not bps
+    pub absolute_threshold: bool,
+
+    /// Cap on our share of a block's post-deploy total (0 = no cap)
+    pub max_block_share_bps: u16,
+
+    pub objective: Objective,
+
+    /// Square selection rule
+    pub selection: Selection,
+
+    /// Cap on the plan's PnL standard deviation, in bps of the budget (MeanVariance)
+    pub max_std_dev_bps: u16,
+
+    /// Apply the EV threshold to the whole plan (mutually exclusive outcomes)
+    /// instead of to each block independently
+    pub portfolio: bool,
+
+    /// Squares the plan may use (bit i = square i)
+    pub eligible_squares: u32,
+
+    /// EV discount per miner already on a square, in bps (0 = ignore crowding)
+    pub crowd_penalty_bps: u16,
+
+    /// Lamports we already hold per square, counted toward the share cap
+    pub held: [u64; BOARD_SQUARES],
+
+    /// Add the top-miner reward to the EV of squares where we'd be top miner
+    pub top_miner: bool,
+
+    /// Weight on the motherlode payoff in bps (10000 = its expected value)
+    pub motherlode_weight_bps: u32,
+
+    /// Fraction of the Kelly size to target, in bps (10000 = full Kelly)
+    pub kelly_fraction_bps: u16,
+
+    /// Multiplier on each square's pot, in bps (10000 = as is); `None` pays
+    /// every square its pot as is
+    pub payout_multipliers_bps: Option<&'a [u16; BOARD_SQUARES]>,
+}
+
+impl AllocationParams<'_> {
+    /// Number of leading candidates (in size order) the objectives may fund
+    pub fn candidate_pool(&self) -> usize {
+        match self.selection {
+            Selection::EvPerLamport => BOARD_SQUARES,
+            Selection::Smallest | Selection::Largest => self.max_blocks as usize,
+        }
+    }
+
+    /// Payout multiplier of `square`, in bps
+    pub fn payout_bps(&self, square: usize) -> u64 {
+        self.payout_multipliers_bps
+            .map_or(FLAT_PAYOUT_BPS, |multipliers| multipliers[square] as u64)
+    }
+}
+
+/// Deployment plan, in selection order
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Allocation {
+    /// Number of blocks selected
+    pub count: u8,
+
+    /// Lamports to deploy per selected block
+    pub amounts: [u64; MAX_PLAN_BLOCKS],
+
+    /// Square index per selected block
+    pub indices: [u8; MAX_PLAN_BLOCKS],
+
+    /// Modeled EV in lamports per selected block
+    pub evs: [i64; MAX_PLAN_BLOCKS],
+
+    /// EV of the whole plan, accounting for only one square winning
+    pub portfolio_ev: i64,
+
+    /// Standard deviation of the whole plan's PnL
+    pub portfolio_std_dev: u64,
+}
+
+impl Allocation {
+    /// Keep only the `max_blocks` highest-EV blocks, in their original order
+    ///
+    /// The plan's EV becomes the sum of the kept blocks' EVs and its standard
+    /// deviation is scaled by the share of lamports kept; both are estimates,
+    /// as the blocks were sized against each other's stakes.
+    pub fn truncate(&mut self, max_blocks: usize) {
+        let count = self.count as usize;
+        if max_blocks >= count {
+            return;
+        }
+
+        let mut keep = [false; MAX_PLAN_BLOCKS];
+        for _ in 0..max_blocks {
+            let mut best: Option<usize> = None;
+            for (i, kept) in keep.iter().enumerate().take(count) {
+                if !kept && best.is_none_or(|b| self.evs[i] > self.evs[b]) {
+                    best = Some(i);
+                }
+            }
+            if let Some(i) = best {
+                keep[i] = true;
+            }
+        }
+
+        let planned: u64 = self.amounts[..count].iter().sum();
+        let mut plan = Allocation::default();
+        let mut portfolio_ev: i128 = 0;
+        for i in (0..count).filter(|&i| keep[i]) {
+            let n = plan.count as usize;
+            plan.amounts[n] = self.amounts[i];
+            plan.indices[n] = self.indices[i];
+            plan.evs[n] = self.evs[i];
+            portfolio_ev += self.evs[i] as i128;
+            plan.count += 1;
+        }
+        plan.portfolio_ev = portfolio_ev.clamp(i64::MIN as i128, i64::MAX as i128) as i64;
+        let kept: u64 = plan.amounts[..plan.count as usize].iter().sum();
+        if planned > 0 {
+            plan.portfolio_std_dev =
+                (self.portfolio_std_dev as u128 * kept as u128 / planned as u128) as u64;
+        }
+
+        *self = plan;
+    }
+}
+
+/// A square considered by the allocator
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Candidate {
+    /// Square index (0-24)
+    pub index: u8,
+
+    /// Lamports already deployed on the square
+    pub size: u64,
+
+    /// Total pool the square's pot is drawn from, including our stakes on
+    /// the other squares
+    pub pool: u64,
+
+    /// Pot value V if the square wins, scaled by its payout multiplier
+    pub pot_value: u64,
+
+    /// Multiplier on the square's pot, in bps (`FLAT_PAYOUT_BPS` = as is)
+    pub payout_bps: u64,
+
+    /// Most we may deploy on the square under the share limit
+    pub share_cap: u64,
+
+    /// Most the current objective may size the square to
+    pub cap: u64,
+
+    /// Miners already on the square
+    pub miners: u64,
+
+    /// EV discount for crowding, in bps of the deployment
+    pub crowd_penalty_bps: i64,
+
+    /// EV threshold for the square's rank (bps, or lamports if absolute)
+    pub min_ev: i64,
+
+    /// Unconstrained target amount (Kelly objective)
+    pub optimal: u64,
+
+    /// Amount sized by the current pass
+    pub amount: u64,
+
+    /// Still eligible for budget
+    pub active: bool,
+}
+
+/// Calculate optimal deployment amounts under the requested objective
+pub fn calculate_optimal_deployments(p: &AllocationParams) -> Result<Allocation, Error> {
+    let round = p.round;
+    let ore_value = try_ore_value(p.ore_price_lamports, try_expected_motherlode(p)?, p.config)?;
+
+    // The math widens to u128 and narrows at the end; with every pot and
+    // deployment bounded by i64::MAX lamports each narrowing is exact
+    let bound = round
+        .total_deployed
+        .checked_add(p.total_budget)
+        .and_then(|total| total.checked_add(ore_value));
+    if bound.is_none_or(|total| total > i64::MAX as u64) {
+        return Err(Error::MathOverflow);
+    }
+
+    // Keep the eligible squares
+    let mut blocks: [(u8, u64); BOARD_SQUARES] = [(0, 0); BOARD_SQUARES];
+    let mut n = 0;
+    for i in 0..BOARD_SQUARES {
+        if p.eligible_squares & (1 << i) != 0 {
+            blocks[n] = (i as u8, round.deployed[i]);
+            n += 1;
+        }
+    }
+
+    // Only the leading candidates in size order can be funded, so a partial
+    // selection pass (O(n·k) rather than a full sort) puts the k smallest
+    // (or largest) first. Rotating instead of swapping keeps ties in index order.
+    let k = p.candidate_pool().min(n);
+    let largest = p.selection == Selection::Largest;
+    for i in 0..k {
+        let mut best = i;
+        for j in i + 1..n {
+            let better = if largest {
+                blocks[j].1 > blocks[best].1
+            } else {
+                blocks[j].1 < blocks[best].1
+            };
+            if better {
+                best = j;
+            }
+        }
+        blocks[i..=best].rotate_right(1);
+    }
+
+    // Step 1: Pick the candidate squares and their targets
+    let mut candidates = [Candidate::default(); BOARD_SQUARES];
+    let candidates = &mut candidates[..n];
+    for (candidate, (index, _)) in candidates.iter_mut().zip(blocks) {
+        *candidate = candidate_for(index, p, ore_value)?;
+    }
+
+    match p.objective {
+        Objective::Kelly => kelly::prepare(candidates, p, ore_value)?,
+        Objective::MarginalEv | Objective::MeanVariance => marginal_ev::prepare(candidates, p)?,
+        Objective::MaxEv | Objective::MotherlodeHunt => max_ev::prepare(candidates, p),
+    }
+
+    // Per-rank thresholds follow the selection order of the prepared candidates
+    let mut rank = 0;
+    for c in candidates.iter_mut() {
+        c.min_ev = p.min_ev_threshold as i64;
+        if c.active {
+            if let Some(threshold) = p.rank_thresholds.get(rank) {
+                c.min_ev = *threshold as i64;
+            }
+            rank += 1;
+        }
+    }
+
+    // Step 2: Water-fill the budget across the surviving blocks. Each pass
+    // sizes the active candidates to fit the budget, then drops one block
+    // that falls below ORE's minimum deploy or fails the EV threshold. Its
+    // share flows to the survivors (never past their optimum) on the next
+    // pass, so filtered blocks don't shrink the total deployed.
+    loop {
+        size(candidates, p, ore_value)?;
+
+        // Our stakes on the other squares join each square's losing pool, so
+        // re-size against the updated pools until they settle
+        for _ in 0..POT_IMPACT_PASSES {
+            if !apply_pot_impact(candidates, p, ore_value)? {
+                break;
+            }
+            size(candidates, p, ore_value)?;
+        }
+
+        let mut evs: [i64; BOARD_SQUARES] = [0; BOARD_SQUARES];
+        let mut dust: Option<usize> = None;
+        let mut worst_ev: Option<(usize, i64)> = None;
+
+        for (i, c) in candidates.iter().enumerate() {
+            if !c.active || c.amount == 0 {
+                continue;
+            }
+
+            if c.amount < p.config.min_deploy_per_block {
+                if dust.is_none_or(|d| c.amount < candidates[d].amount) {
+                    dust = Some(i);
+                }
+                continue;
+            }
+
+            // Calculate EV with final amount
+            let ev = block_ev(c, c.amount, p, ore_value)?;
+
+            // Check EV threshold (after the crowd discount)
+            let margin = threshold_margin(c, ev, p)?;
+            if margin < 0 && worst_ev.is_none_or(|(_, worst)| margin < worst) {
+                worst_ev = Some((i, margin));
+            }
+
+            evs[i] = arith::to_i64(ev)?;
+        }
+
+        // In portfolio mode the threshold applies to the plan as a whole; a
+        // failing plan sheds the block contributing least to portfolio EV
+        if p.portfolio {
+            worst_ev = match dust {
+                Some(_) => None,
+                None => weakest_if_below_threshold(candidates, p, ore_value)?,
+            };
+        }
+
+        // Drop dust first, then the block furthest below the EV threshold
+        if let Some(i) = dust.or(worst_ev.map(|(i, _)| i)) {
+            candidates[i].active = false;
+            continue;
+        }
+
+        // Step 3: Every surviving block fits the budget and meets the threshold
+        let mut plan = Allocation::default();
+        for (i, c) in candidates.iter().enumerate() {
+            if !c.active || c.amount == 0 {
+                continue;
+            }
+            let n = plan.count as usize;
+            if n == MAX_PLAN_BLOCKS {
+                break;
+            }
+            plan.amounts[n] = c.amount;
+            plan.indices[n] = c.index;
+            plan.evs[n] = evs[i];
+            plan.count += 1;
+        }
+
+        let stats = evaluate_candidates(candidates, None, ore_value, p)?;
+        plan.portfolio_ev = stats.ev;
+        plan.portfolio_std_dev = stats.std_dev();
+
+        return Ok(plan);
+    }
+}
+
+/// Size the active candidates under the requested objective
+fn size(candidates: &mut [Candidate], p: &AllocationParams, ore_value: u64) -> Result<(), Error> {
+    match p.objective {
+        Objective::Kelly => kelly::size(
+            candidates,
+            p.total_budget,
+            try_all_in_kelly_c_scaled(p.config)?,
+        ),
+        Objective::MarginalEv => marginal_ev::size(
+            candidates,
+            p.total_budget,
+            try_all_in_kelly_c_scaled(p.config)?,
+        ),
+        Objective::MeanVariance => return mean_variance::size(candidates, p, ore_value),
+        Objective::MaxEv | Objective::MotherlodeHunt => max_ev::size(candidates, p.total_budget),
+    }
+    Ok(())
+}
+
+/// Refresh each candidate's pool (and pot value, and Kelly target) with our
+/// current stakes on the other squares; returns whether any pool changed
+fn apply_pot_impact(
+    candidates: &mut [Candidate],
+    p: &AllocationParams,
+    ore_value: u64,
+) -> Result<bool, Error> {
+    let ours = candidates
+        .iter()
+        .filter(|c| c.active)
+        .fold(0u64, |total, c| total.saturating_add(c.amount));
+
+    let mut changed = false;
+    for c in candidates.iter_mut() {
+        let own = if c.active { c.amount } else { 0 };
+        let pool = arith::add(p.round.total_deployed, ours - own)?;
+        if pool == c.pool {
+            continue;
+        }
+        c.pool = pool;
+        c.pot_value = try_scale_payout(
+            try_pot_value(c.size, pool, ore_value, p.config)?,
+            c.payout_bps,
+        )?;
+        if p.objective == Objective::Kelly && c.active {
+            kelly::target(c, p, ore_value)?;
+        }
+        changed = true;
+    }
+    Ok(changed)
+}
+
+/// Marginal EV probe on a square for `ev_surface`, in lamports: small
+/// enough to read the slope at the start of the square's EV curve
+pub const MARGINAL_PROBE_LAMPORTS: u64 = 1_000_000;
+
+/// EV diagnostics of one square, whether or not a plan funds it
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct SquareEv {
+    /// Lamports already deployed on the square
+    pub deployed: u64,
+
+    /// Kelly-optimal deployment, after the Kelly fraction and share cap
+    pub kelly: u64,
+
+    /// EV of deploying `kelly`, in lamports
+    pub ev: i64,
+
+    /// EV of the first `MARGINAL_PROBE_LAMPORTS`, in bps, net of the crowd
+    /// discount
+    pub marginal_ev_bps: i64,
+}
+
+/// Kelly size and EV of every square under `p`, ignoring the budget, block
+/// limit and square masks, for seeing why a plan skipped a square
+///
+/// Empty squares can't be sized and read zero besides `deployed`.
+pub fn ev_surface(p: &AllocationParams) -> Result<[SquareEv; BOARD_SQUARES], Error> {
+    let round = p.round;
+    let ore_value = try_ore_value(p.ore_price_lamports, try_expected_motherlode(p)?, p.config)?;
+    let bound = round.total_deployed.checked_add(ore_value);
+    if bound.is_none_or(|total| total > i64::MAX as u64) {
+        return Err(Error::MathOverflow);
+    }
+
+    let mut surface = [SquareEv::default(); BOARD_SQUARES];
+    for (index, square) in surface.iter_mut().enumerate() {
+        let mut c = candidate_for(index as u8, p, ore_value)?;
+        square.deployed = c.size;
+        if c.size == 0 {
+            continue;
+        }
+
+        kelly::target(&mut c, p, ore_value)?;
+        square.kelly = c.optimal;
+        if c.optimal > 0 {
+            square.ev = arith::to_i64(block_ev(&c, c.optimal, p, ore_value)?)?;
+        }
+
+        let probe = MARGINAL_PROBE_LAMPORTS;
+        let ev = block_ev(&c, probe, p, ore_value)?;
+        square.marginal_ev_bps =
+            arith::to_i64((ev * 10_000) / probe as i128 - c.crowd_penalty_bps as i128)?;
+    }
+    Ok(surface)
+}
+
+/// The allocator's view of `index` before any objective sizes it
+fn candidate_for(index: u8, p: &AllocationParams, ore_value: u64) -> Result<Candidate, Error> {
+    let round = p.round;
+    let size = round.deployed[index as usize];
+    let payout_bps = p.payout_bps(index as usize);
+    let share_cap = try_max_amount_for_share(size, p.held[index as usize], p.max_block_share_bps)?;
+    Ok(Candidate {
+        index,
+        size,
+        pool: round.total_deployed,
+        pot_value: try_scale_payout(
+            try_pot_value(size, round.total_deployed, ore_value, p.config)?,
+            payout_bps,
+        )?,
+        payout_bps,
+        share_cap,
+        cap: share_cap,
+        miners: round.count[index as usize],
+        crowd_penalty_bps: crowd_penalty_bps(round.count[index as usize], p)?,
+        ..Candidate::default()
+    })
+}
+
+/// EV of deploying `amount` on a candidate, including the fixed winnings
+/// collected if the square wins
+///
+/// Kept in i128 so thresholds and rankings scale it without overflowing;
+/// it is narrowed to lamports only where a plan records it.
+pub fn block_ev(
+    c: &Candidate,
+    amount: u64,
+    p: &AllocationParams,
+    ore_value: u64,
+) -> Result<i128, Error> {
+    let ev = try_calculate_ev_wide(c.size, amount, c.pool, ore_value, p.config, c.payout_bps)?;
+    let fixed = fixed_winnings(c, amount, p)?;
+
+    // Paid only if this square wins
+    Ok(ev + (fixed / BOARD_SQUARES as u64) as i128)
+}
+
+/// Winnings on top of our pot share if the square wins: the per-miner
+/// motherlode share and, when enabled and earned, the top-miner reward
+pub fn fixed_winnings(c: &Candidate, amount: u64, p: &AllocationParams) -> Result<u64, Error> {
+    if amount == 0 {
+        return Ok(0);
+    }
+    let mut fixed = 0u64;
+
+    // Equal split between the square's miners, counting us if we're new to it
+    if p.config.motherlode_split == MOTHERLODE_SPLIT_PER_MINER {
+        let new_miner = p.held[c.index as usize] == 0;
+        let winners = arith::add(c.miners, new_miner as u64)?;
+        fixed = arith::add(fixed, try_expected_motherlode(p)? / winners.max(1))?;
+    }
+
+    if p.top_miner && amount >= top_miner_threshold(c, p) {
+        let reward = try_top_miner_value(p.ore_price_lamports, p.round.top_miner_reward, p.config)?;
+        fixed = arith::add(fixed, reward)?;
+    }
+
+    Ok(fixed)
+}
+
+/// Expected motherlode value for the winning square's miners, weighted by
+/// `motherlode_weight_bps`
+pub fn expected_motherlode(p: &AllocationParams) -> u64 {
+    try_expected_motherlode(p).unwrap_or(u64::MAX)
+}
+
+/// `expected_motherlode`, failing on overflow with `strict-math`
+pub fn try_expected_motherlode(p: &AllocationParams) -> Result<u64, Error> {
+    let value = try_motherlode_value(p.ore_price_lamports, p.round.motherlode, p.config)?;
+    arith::to_u64(value as u128 * p.motherlode_weight_bps as u128 / 10_000)
+}
+
+/// Smallest deployment that guarantees we're the square's top miner: our
+/// stake must exceed everyone else's combined, h + y > O - h
+pub fn top_miner_threshold(c: &Candidate, p: &AllocationParams) -> u64 {
+    let held = p.held[c.index as usize];
+    c.size
+        .saturating_sub(held.saturating_mul(2))
+        .saturating_add(1)
+}
+
+/// How far a block's EV clears its threshold: in bps of the deployment, or in
+/// lamports for an absolute floor
+fn threshold_margin(c: &Candidate, ev: i128, p: &AllocationParams) -> Result<i64, Error> {
+    let crowd_penalty_bps = c.crowd_penalty_bps as i128;
+    let margin = if p.absolute_threshold {
+        ev - c.min_ev as i128 - c.amount as i128 * crowd_penalty_bps / 10_000
+    } else {
+        (ev * 10_000) / c.amount as i128 - c.min_ev as i128 - crowd_penalty_bps
+    };
+    arith::to_i64(margin)
+}
+
+/// Crowding discount for a square holding `miners` miners
+fn crowd_penalty_bps(miners: u64, p: &AllocationParams) -> Result<i64, Error> {
+    let penalty = arith::mul(miners, p.crowd_penalty_bps as u64)?;
+    arith::to_i64(penalty as i128)
+}
+
+/// Portfolio stats of the active, funded candidates, optionally leaving one out
+fn evaluate_candidates(
+    candidates: &[Candidate],
+    skip: Option<usize>,
+    ore_value: u64,
+    p: &AllocationParams,
+) -> Result<PortfolioStats, Error> {
+    let mut positions: [Position; BOARD_SQUARES] = [(0, 0, 0, 0); BOARD_SQUARES];
+    let mut n = 0;
+    for (i, c) in candidates.iter().enumerate() {
+        if !c.active || c.amount == 0 || skip == Some(i) {
+            continue;
+        }
+        positions[n] = (
+            c.size,
+            c.amount,
+            fixed_winnings(c, c.amount, p)?,
+            c.payout_bps,
+        );
+        n += 1;
+    }
+    portfolio::try_evaluate(&positions[..n], p.round.total_deployed, ore_value, p.config)
+}
+
+/// If the plan's portfolio EV misses the threshold, the block whose removal
+/// costs the least portfolio EV, with its contribution
+fn weakest_if_below_threshold(
+    candidates: &[Candidate],
+    p: &AllocationParams,
+    ore_value: u64,
+) -> Result<Option<(usize, i64)>, Error> {
+    let deployed: u64 = candidates
+        .iter()
+        .filter(|c| c.active)
+        .map(|c| c.amount)
+        .sum();
+    if deployed == 0 {
+        return Ok(None);
+    }
+
+    // Crowd discount of the plan, weighted by deployment (lamports × 1e4)
+    let crowd_penalty: i128 = candidates
+        .iter()
+        .filter(|c| c.active)
+        .map(|c| c.amount as i128 * c.crowd_penalty_bps as i128)
+        .sum();
+
+    let stats = evaluate_candidates(candidates, None, ore_value, p)?;
+    let passes = if p.absolute_threshold {
+        // The plan must clear the sum of its blocks' floors
+        let floor: i128 = candidates
+            .iter()
+            .filter(|c| c.active && c.amount > 0)
+            .map(|c| c.min_ev as i128)
+            .sum();
+        stats.ev as i128 - crowd_penalty / 10_000 >= floor
+    } else {
+        let ev_bps = (stats.ev as i128 * 10_000) / deployed as i128;
+        ev_bps - crowd_penalty / deployed as i128 >= p.min_ev_threshold as i128
+    };
+    if passes {
+        return Ok(None);
+    }
+
+    let mut weakest: Option<(usize, i64)> = None;
+    for (i, c) in candidates.iter().enumerate() {
+        if !c.active || c.amount == 0 {
+            continue;
+        }
+        let without = evaluate_candidates(candidates, Some(i), ore_value, p)?;
+        let contribution = arith::sub_i64(stats.ev, without.ev)?;
+        if weakest.is_none_or(|(_, worst)| contribution < worst) {
+            weakest = Some((i, contribution));
+        }
+    }
+    Ok(weakest)
+}
@@ -0,0 +1,100 @@
+use crate::{
+    allocator::{arith, isqrt_u128},
+    ConfigParams, Error, BOARD_SQUARES,
+};
+
+/// Number of squares on the board (exactly one wins per round)
+const NUM_SQUARES: i128 = BOARD_SQUARES as i128;
+
+/// A position in the plan: (lamports already on the square, lamports we
+/// deploy, fixed lamports we collect on top of our pot share if it wins, the
+/// square's payout multiplier in bps)
+pub type Position = (u64, u64, u64, u64);
+
+/// Outcome distribution of a whole plan, in lamports
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct PortfolioStats {
+    /// Expected PnL across all winning squares
+    pub ev: i64,
+
+    /// Variance of PnL
+    pub variance: u128,
+}
+
+impl PortfolioStats {
+    pub fn std_dev(&self) -> u64 {
+        isqrt_u128(self.variance) as u64
+    }
+}
+
+/// Evaluate a multi-block plan over the N mutually exclusive outcomes
+///
+/// If square w wins and we hold y_w of it, we win our share of its pot, which
+/// includes our own stakes on the other squares (they join the losing pool)
+/// and is scaled by the square's payout multiplier, plus any fixed winnings
+/// (motherlode per-miner share, top-miner reward), and lose those other
+/// stakes. If an unheld square wins we lose everything.
+/// The admin and wrapper fees are paid in every outcome.
+pub fn evaluate(
+    positions: &[Position],
+    total_pool: u64,
+    ore_value: u64,
+    params: &ConfigParams,
+) -> PortfolioStats {
+    try_evaluate(positions, total_pool, ore_value, params).unwrap_or_default()
+}
+
+/// `evaluate`, failing with `strict-math` when the plan's EV overflows
+/// (the variance saturates in every build)
+pub fn try_evaluate(
+    positions: &[Position],
+    total_pool: u64,
+    ore_value: u64,
+    params: &ConfigParams,
+) -> Result<PortfolioStats, Error> {
+    let total_ours: u128 = positions.iter().map(|(_, y, _, _)| *y as u128).sum();
+    if total_ours == 0 {
+        return Ok(PortfolioStats::default());
+    }
+
+    let pool_payout_bps = 10_000 - params.protocol_fee_bps as u128;
+    let admin_fee = (total_ours * params.fee_load_bps() as u128 / 10_000) as i128;
+    let pool_with_ours = total_pool as u128 + total_ours;
+
+    // Squared PnL of a whale-sized plan can pass u128, so the second moment
+    // saturates (an effectively unbounded variance) instead of wrapping
+    let mut sum: i128 = 0;
+    let mut sum_sq: u128 = 0;
+
+    for &(size, amount, fixed, payout_bps) in positions {
+        let (size, amount) = (size as u128, amount as u128);
+        if amount == 0 {
+            continue;
+        }
+        let losing_pool = pool_with_ours.saturating_sub(size + amount);
+        let pot = losing_pool * pool_payout_bps / 10_000 + ore_value as u128;
+        let pot = pot * payout_bps as u128 / 10_000;
+        let won = pot * amount / (size + amount) + fixed as u128;
+        let pnl = won as i128 - (total_ours - amount) as i128 - admin_fee;
+        sum += pnl;
+        sum_sq = sum_sq.saturating_add(square(pnl));
+    }
+
+    // Every square we don't hold loses the whole stake
+    let held = positions.iter().filter(|(_, y, _, _)| *y > 0).count() as i128;
+    let loss = -(total_ours as i128) - admin_fee;
+    sum += (NUM_SQUARES - held) * loss;
+    sum_sq = sum_sq.saturating_add(square(loss).saturating_mul((NUM_SQUARES - held) as u128));
+
+    let ev = sum / NUM_SQUARES;
+    let variance = (sum_sq / NUM_SQUARES as u128).saturating_sub(square(ev));
+
+    Ok(PortfolioStats {
+        ev: arith::to_i64(ev)?,
+        variance,
+    })
+}
+
+fn square(x: i128) -> u128 {
+    x.unsigned_abs().saturating_mul(x.unsigned_abs())
+}
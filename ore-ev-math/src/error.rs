@@ -0,0 +1,15 @@
+/// Errors from parameter validation and allocation
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Error {
+    /// A `ConfigParams` field is out of range
+    InvalidParams,
+    /// Unknown `Objective` value
+    InvalidObjective,
+    /// Unknown `Selection` value
+    InvalidSelection,
+    /// Round pool, budget and ORE value exceed the allocator's lamport bounds
+    MathOverflow,
+    /// An allocator operation overflowed (`strict-math` builds only; other
+    /// builds saturate)
+    ArithmeticOverflow,
+}
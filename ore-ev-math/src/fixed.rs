@@ -0,0 +1,121 @@
+use core::ops::{Div, Mul};
+
+use crate::allocator::isqrt_u128;
+
+/// Unsigned Q64.64 fixed-point number: 64 integer bits, 64 fractional bits
+///
+/// Used for the allocator's ratios and roots so formulas read as written
+/// (`√(V / C) × √O`, `pot × y / (O + y)`) instead of juggling ad-hoc scale
+/// factors and truncating integer divisions.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Q64(pub u128);
+
+const FRAC_BITS: u32 = 64;
+
+impl Q64 {
+    pub const ZERO: Q64 = Q64(0);
+    pub const ONE: Q64 = Q64(1 << FRAC_BITS);
+
+    /// Integer value
+    pub const fn from_int(value: u64) -> Q64 {
+        Q64((value as u128) << FRAC_BITS)
+    }
+
+    /// `num / den`, rounded down; saturates if the quotient needs more than
+    /// 64 integer bits
+    pub fn from_ratio(num: u128, den: u128) -> Q64 {
+        Q64::from_raw_div(num, den).unwrap_or(Q64(u128::MAX))
+    }
+
+    /// Value of an integer carrying `scale` (e.g. bps with 10_000, or the
+    /// 1e9-scaled Kelly constant)
+    pub fn from_scaled(value: u64, scale: u64) -> Q64 {
+        Q64::from_ratio(value as u128, scale as u128)
+    }
+
+    /// Integer part, rounded down
+    pub const fn to_int(self) -> u64 {
+        (self.0 >> FRAC_BITS) as u64
+    }
+
+    /// `self × rhs`, or `None` on overflow
+    pub fn checked_mul(self, rhs: Q64) -> Option<Q64> {
+        // (ah·2^64 + al)(bh·2^64 + bl) / 2^64, using only 128-bit products
+        let (ah, al) = (self.0 >> FRAC_BITS, self.0 & u64::MAX as u128);
+        let (bh, bl) = (rhs.0 >> FRAC_BITS, rhs.0 & u64::MAX as u128);
+        let high = ah.checked_mul(bh)?.checked_mul(1 << FRAC_BITS)?;
+        high.checked_add(ah * bl)?
+            .checked_add(al * bh)?
+            .checked_add((al * bl) >> FRAC_BITS)
+            .map(Q64)
+    }
+
+    /// `self × rhs` for an integer `rhs`, rounded down to an integer
+    pub fn mul_int(self, rhs: u64) -> u128 {
+        let (high, low) = (self.0 >> FRAC_BITS, self.0 & u64::MAX as u128);
+        high * rhs as u128 + ((low * rhs as u128) >> FRAC_BITS)
+    }
+
+    /// `self / rhs`, or `None` on division by zero or overflow
+    pub fn checked_div(self, rhs: Q64) -> Option<Q64> {
+        Q64::from_raw_div(self.0, rhs.0)
+    }
+
+    /// Square root, rounded down
+    pub fn sqrt(self) -> Q64 {
+        // √(raw · 2^64) = √(raw · 4^s) · 2^(32 - s); shifting by as much as
+        // fits before the integer root keeps ~64 significant bits
+        let s = (self.0.leading_zeros() / 2).min(FRAC_BITS / 2);
+        Q64(isqrt_u128(self.0 << (2 * s)) << (FRAC_BITS / 2 - s))
+    }
+
+    /// Q64.64 quotient of two raw integers, `num · 2^64 / den`
+    fn from_raw_div(num: u128, den: u128) -> Option<Q64> {
+        if den == 0 {
+            return None;
+        }
+        let int = num / den;
+        if int > u64::MAX as u128 {
+            return None;
+        }
+        let mut rem = num % den;
+
+        let frac = if den <= u64::MAX as u128 {
+            // rem < den < 2^64, so the shift can't overflow
+            (rem << FRAC_BITS) / den
+        } else {
+            // Long division one bit at a time; `carry` holds the bit shifted out of rem
+            let mut frac = 0u128;
+            for _ in 0..FRAC_BITS {
+                let carry = rem >> 127;
+                rem <<= 1;
+                frac <<= 1;
+                if carry == 1 || rem >= den {
+                    rem = rem.wrapping_sub(den);
+                    frac |= 1;
+                }
+            }
+            frac
+        };
+
+        Some(Q64((int << FRAC_BITS) | frac))
+    }
+}
+
+/// Saturating multiplication
+impl Mul for Q64 {
+    type Output = Q64;
+
+    fn mul(self, rhs: Q64) -> Q64 {
+        self.checked_mul(rhs).unwrap_or(Q64(u128::MAX))
+    }
+}
+
+/// Saturating division; dividing by zero saturates too
+impl Div for Q64 {
+    type Output = Q64;
+
+    fn div(self, rhs: Q64) -> Q64 {
+        self.checked_div(rhs).unwrap_or(Q64(u128::MAX))
+    }
+}
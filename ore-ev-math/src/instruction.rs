@@ -0,0 +1,16 @@
+//! Instruction discriminators of the ORE EV program: the first byte of its
+//! instruction data, shared so clients build the same bytes the program
+//! dispatches on.
+
+pub const ORE_DEPLOY: u8 = 1;
+pub const INITIALIZE_CONFIG: u8 = 2;
+pub const UPDATE_CONFIG: u8 = 3;
+pub const SET_BANKROLL: u8 = 4;
+pub const CREATE_SCHEDULE: u8 = 5;
+pub const EXECUTE_SCHEDULE: u8 = 7;
+pub const CLOSE_SCHEDULE: u8 = 8;
+pub const SETTLE_ROUND: u8 = 9;
+pub const INITIALIZE_LEADERBOARD: u8 = 10;
+pub const MIGRATE_ACCOUNT: u8 = 11;
+pub const CLAIM_SCHEDULE: u8 = 12;
+pub const DEPOSIT_SCHEDULE: u8 = 13;
@@ -0,0 +1,19 @@
+//! EV and Kelly allocation math for ORE rounds, shared by the on-chain
+//! program and off-chain clients so both size deployments identically.
+
+#![no_std]
+
+pub mod allocator;
+pub mod error;
+pub mod fixed;
+pub mod instruction;
+pub mod params;
+pub mod round;
+#[cfg(feature = "test-utils")]
+pub mod test_utils;
+
+pub use allocator::*;
+pub use error::Error;
+pub use fixed::Q64;
+pub use params::*;
+pub use round::*;
@@ -0,0 +1,99 @@
+use bytemuck::{Pod, Zeroable};
+
+use crate::Error;
+
+/// Bounds on the Kelly constant C (scaled by 1e9)
+pub const MIN_KELLY_C_SCALED: u64 = 1_000_000_000; // C = 1
+pub const MAX_KELLY_C_SCALED: u64 = 100_000_000_000; // C = 100
+
+/// Motherlode split rules (`ConfigParams::motherlode_split`)
+/// Winners of the square share the motherlode in proportion to their stake
+pub const MOTHERLODE_SPLIT_PROPORTIONAL: u8 = 0;
+/// Every miner on the winning square gets an equal share of the motherlode
+pub const MOTHERLODE_SPLIT_PER_MINER: u8 = 1;
+
+/// Tunable strategy parameters, set by the admin via InitializeConfig/UpdateConfig
+#[repr(C)]
+#[derive(Pod, Zeroable, Clone, Copy, Debug, PartialEq)]
+pub struct ConfigParams {
+    /// Share of the losing pool withheld by ORE before paying winners (ORE: 1000 = 10%).
+    /// ORE v3 hard-codes this and the refining fee rather than storing them in
+    /// an account, so both are mirrored here and kept current via UpdateConfig
+    pub protocol_fee_bps: u16,
+
+    /// Fee charged when claiming mined ORE (ORE: 1000 = 10%)
+    pub refining_fee_bps: u16,
+
+    /// Admin fee charged on every deployment (ORE: 101 = 1.01%)
+    pub admin_fee_bps: u16,
+
+    /// Extra fee charged on every deployment by a wrapping program (0 = none)
+    pub wrapper_fee_bps: u16,
+
+    /// Fee-free Kelly constant C scaled by 1e9 (default: 24_000_000_000 = N - 1).
+    /// Sizing uses y* = √(V × O / C') - O with C' = C + N × (admin + wrapper fee)
+    pub kelly_c_scaled: u64,
+
+    /// Lowest accepted `ore_price_lamports`
+    pub min_ore_price_lamports: u64,
+
+    /// Highest accepted `ore_price_lamports`
+    pub max_ore_price_lamports: u64,
+
+    /// Cap on lamports deployed per (authority, round) across calls (0 = no cap)
+    pub max_round_deploy_lamports: u64,
+
+    /// Rounds with less than this many lamports deployed in total are skipped
+    pub min_total_deployed: u64,
+
+    /// Smallest amount ORE accepts per square; smaller allocations are dropped
+    pub min_deploy_per_block: u64,
+
+    /// Motherlode hits once every this many rounds on average (ORE: 625)
+    pub motherlode_odds: u32,
+
+    /// How the motherlode is split among the winning square's miners (`MOTHERLODE_SPLIT_*`)
+    pub motherlode_split: u8,
+
+    /// Padding (3 bytes)
+    pub _padding2: [u8; 3],
+
+    /// Drawdown from peak PnL, in bps of the bankroll, that triggers reduced sizing (0 = off)
+    pub drawdown_trigger_bps: u16,
+
+    /// Kelly fraction in bps applied while in drawdown (5000 = half Kelly)
+    pub drawdown_scale_bps: u16,
+
+    /// Most the auto-tuned EV threshold may be tightened, in bps
+    pub max_threshold_adjust_bps: u16,
+
+    /// Padding (2 bytes)
+    pub _padding3: [u8; 2],
+}
+
+impl ConfigParams {
+    /// All-in fee charged on every deployment, in bps
+    pub fn fee_load_bps(&self) -> u64 {
+        self.admin_fee_bps as u64 + self.wrapper_fee_bps as u64
+    }
+
+    /// Check every parameter is in range
+    pub fn validate(&self) -> Result<(), Error> {
+        if self.protocol_fee_bps > 10_000
+            || self.refining_fee_bps > 10_000
+            || self.fee_load_bps() > 10_000
+            || self.kelly_c_scaled < MIN_KELLY_C_SCALED
+            || self.kelly_c_scaled > MAX_KELLY_C_SCALED
+            || self.max_ore_price_lamports == 0
+            || self.min_ore_price_lamports > self.max_ore_price_lamports
+            || self.motherlode_odds == 0
+            || self.motherlode_split > MOTHERLODE_SPLIT_PER_MINER
+            || self.drawdown_trigger_bps > 10_000
+            || self.drawdown_scale_bps > 10_000
+            || self.max_threshold_adjust_bps > 10_000
+        {
+            return Err(Error::InvalidParams);
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,80 @@
+use bytemuck::{Pod, Zeroable};
+
+/// Number of squares on the ORE board; exactly one wins each round. Every
+/// per-square array and the win probability derive from it, so a board
+/// change is a one-line edit here (account layouts still change with it)
+pub const BOARD_SQUARES: usize = 25;
+
+// Square masks are u32, one bit per square
+const _: () = assert!(BOARD_SQUARES <= 32);
+
+/// ORE round account layout
+#[repr(C)]
+#[derive(Pod, Zeroable, Clone, Copy, Debug, PartialEq)]
+pub struct OreRound {
+    pub _disc: [u8; 8],
+
+    /// The round number.
+    pub id: u64,
+
+    /// The amount of SOL deployed in each square.
+    pub deployed: [u64; BOARD_SQUARES],
+
+    /// The hash of the end slot, provided by solana, used for random number generation.
+    pub slot_hash: [u8; 32],
+
+    /// The count of miners on each square.
+    pub count: [u64; BOARD_SQUARES],
+
+    /// The slot at which claims for this round account end.
+    pub expires_at: u64,
+
+    /// The amount of ORE in the motherlode.
+    pub motherlode: u64,
+
+    /// The account to which rent should be returned when this account is closed.
+    pub rent_payer: [u8; 32],
+
+    /// The top miner of the round.
+    pub top_miner: [u8; 32],
+
+    /// The amount of ORE to distribute to the top miner.
+    pub top_miner_reward: u64,
+
+    /// The total amount of SOL deployed in the round.
+    pub total_deployed: u64,
+
+    /// The total amount of SOL put in the ORE vault.
+    pub total_vaulted: u64,
+
+    /// The total amount of SOL won by miners for the round.
+    pub total_winnings: u64,
+}
+
+impl OreRound {
+    /// Square the round's slot hash drew, or `None` before it is drawn and
+    /// when ORE reset the round without a winner (slot hash all `0xff`)
+    pub fn winning_square(&self) -> Option<usize> {
+        if self.slot_hash == [0; 32] || self.slot_hash == [u8::MAX; 32] {
+            return None;
+        }
+        let mut rng = 0u64;
+        for word in self.slot_hash.chunks_exact(8) {
+            let mut bytes = [0u8; 8];
+            bytes.copy_from_slice(word);
+            rng ^= u64::from_le_bytes(bytes);
+        }
+        Some((rng % BOARD_SQUARES as u64) as usize)
+    }
+
+    /// SOL paid out for `amount` lamports deployed on `square` once the
+    /// round is drawn: the stake plus its pro-rata share of `total_winnings`
+    /// on the winning square, nothing on the others
+    pub fn sol_payout(&self, square: usize, amount: u64) -> u64 {
+        if self.winning_square() != Some(square) || self.deployed[square] == 0 {
+            return 0;
+        }
+        let share = self.total_winnings as u128 * amount as u128 / self.deployed[square] as u128;
+        amount.saturating_add(share as u64)
+    }
+}
@@ -0,0 +1,39 @@
+//! Fixtures shared by the tests of the crates built on this one, so the
+//! client, Python and wasm tests all quote the same round under the same fees
+
+use bytemuck::Zeroable;
+
+use crate::{ConfigParams, OreRound, BOARD_SQUARES};
+
+/// Squares of `round()` holding a thin pool
+pub const THIN_SQUARES: [usize; 5] = [1, 5, 9, 13, 17];
+
+/// ORE's fees with the fee-free Kelly constant for 25 squares
+pub fn config_params() -> ConfigParams {
+    ConfigParams {
+        protocol_fee_bps: 1000,
+        refining_fee_bps: 1000,
+        admin_fee_bps: 101,
+        kelly_c_scaled: 24_000_000_000,
+        min_ore_price_lamports: 1,
+        max_ore_price_lamports: u64::MAX,
+        min_deploy_per_block: 10_000,
+        motherlode_odds: 625,
+        drawdown_scale_bps: 10_000,
+        ..ConfigParams::zeroed()
+    }
+}
+
+/// Round 3, with thin squares among well-funded ones
+pub fn round() -> OreRound {
+    let mut round = OreRound::zeroed();
+    round.id = 3;
+    round.deployed = [2_000_000_000; BOARD_SQUARES];
+    round.count = [50; BOARD_SQUARES];
+    for square in THIN_SQUARES {
+        round.deployed[square] = 20_000_000;
+        round.count[square] = 3;
+    }
+    round.total_deployed = round.deployed.iter().sum();
+    round
+}
@@ -0,0 +1,388 @@
+use proptest::prelude::*;
+
+use ore_ev_math::{
+    calculate_kelly_optimal, calculate_optimal_deployments, ev_surface, expected_motherlode,
+    ore_value, AllocationParams, ConfigParams, Error, Objective, OreRound, Selection,
+    MAX_KELLY_C_SCALED, MIN_KELLY_C_SCALED, MOTHERLODE_SPLIT_PER_MINER,
+};
+
+/// Everything the allocator reads, drawn at random
+#[derive(Clone, Debug)]
+struct Input {
+    deployed: [u64; 25],
+    count: [u64; 25],
+    motherlode: u64,
+    top_miner_reward: u64,
+    config: ConfigParams,
+    total_budget: u64,
+    max_blocks: u8,
+    ore_price_lamports: u64,
+    min_ev_threshold: i32,
+    objective: u8,
+    selection: u8,
+    max_std_dev_bps: u16,
+    portfolio: bool,
+    eligible_squares: u32,
+    crowd_penalty_bps: u16,
+    held: [u64; 25],
+    top_miner: bool,
+    motherlode_weight_bps: u32,
+    kelly_fraction_bps: u16,
+}
+
+fn round(input: &Input) -> OreRound {
+    OreRound {
+        _disc: [0; 8],
+        id: 1,
+        deployed: input.deployed,
+        slot_hash: [0; 32],
+        count: input.count,
+        expires_at: 0,
+        motherlode: input.motherlode,
+        rent_payer: [0; 32],
+        top_miner: [0; 32],
+        top_miner_reward: input.top_miner_reward,
+        total_deployed: input
+            .deployed
+            .iter()
+            .fold(0u64, |t, d| t.saturating_add(*d)),
+        total_vaulted: 0,
+        total_winnings: 0,
+    }
+}
+
+fn allocation_params<'a>(input: &'a Input, round: &'a OreRound) -> AllocationParams<'a> {
+    AllocationParams {
+        round,
+        config: &input.config,
+        total_budget: input.total_budget,
+        max_blocks: input.max_blocks,
+        ore_price_lamports: input.ore_price_lamports,
+        min_ev_threshold: input.min_ev_threshold,
+        rank_thresholds: &[],
+        absolute_threshold: false,
+        max_block_share_bps: 0,
+        objective: Objective::try_from(input.objective).unwrap(),
+        selection: Selection::try_from(input.selection).unwrap(),
+        max_std_dev_bps: input.max_std_dev_bps,
+        portfolio: input.portfolio,
+        eligible_squares: input.eligible_squares,
+        crowd_penalty_bps: input.crowd_penalty_bps,
+        held: input.held,
+        top_miner: input.top_miner,
+        motherlode_weight_bps: input.motherlode_weight_bps,
+        kelly_fraction_bps: input.kelly_fraction_bps,
+        payout_multipliers_bps: None,
+    }
+}
+
+/// Invariants every plan must satisfy, whatever the round
+fn check_invariants(input: &Input) -> Result<(), String> {
+    let round = round(input);
+    let p = allocation_params(input, &round);
+    let ore = ore_value(p.ore_price_lamports, expected_motherlode(&p), p.config);
+
+    let plan = match calculate_optimal_deployments(&p) {
+        Ok(plan) => plan,
+        // Overflow is reported only for rounds past the documented bound
+        Err(Error::MathOverflow) => {
+            let total = round.total_deployed as u128 + p.total_budget as u128 + ore as u128;
+            if total <= i64::MAX as u128 {
+                return Err(format!("MathOverflow within bounds ({total})"));
+            }
+            return Ok(());
+        }
+        // Strict builds stop at the first overflow instead of saturating
+        Err(Error::ArithmeticOverflow) if cfg!(feature = "strict-math") => return Ok(()),
+        Err(e) => return Err(format!("unexpected error {e:?}")),
+    };
+    let count = plan.count as usize;
+
+    // Total never exceeds the budget
+    let total: u128 = plan.amounts[..count].iter().map(|a| *a as u128).sum();
+    if total > p.total_budget as u128 {
+        return Err(format!("deployed {total} over budget {}", p.total_budget));
+    }
+
+    for i in 0..count {
+        let (index, amount) = (plan.indices[i] as usize, plan.amounts[i]);
+        if amount == 0 || index >= 25 || p.eligible_squares & (1 << index) == 0 {
+            return Err(format!("block {i}: square {index} with {amount} lamports"));
+        }
+        if plan.indices[..i].contains(&plan.indices[i]) {
+            return Err(format!("square {index} selected twice"));
+        }
+        if amount < p.config.min_deploy_per_block {
+            return Err(format!("square {index}: {amount} below the minimum deploy"));
+        }
+
+        // EV of every selected block meets the threshold (when applied per block)
+        if !p.portfolio && p.crowd_penalty_bps == 0 {
+            let ev_bps = plan.evs[i] as i128 * 10_000 / amount as i128;
+            if ev_bps < p.min_ev_threshold as i128 {
+                return Err(format!(
+                    "square {index}: EV {ev_bps} bps under threshold {}",
+                    p.min_ev_threshold
+                ));
+            }
+        }
+
+        // Kelly sizing never exceeds the Kelly optimum; the optimum is taken
+        // against the largest pool our other stakes can make, plus the
+        // refinement's convergence tolerance
+        if p.objective == Objective::Kelly && !p.top_miner && p.kelly_fraction_bps <= 10_000 {
+            let size = round.deployed[index];
+            let pool = round.total_deployed.saturating_add(p.total_budget);
+            let optimum = calculate_kelly_optimal(size, pool, ore, p.config);
+            if amount > optimum.saturating_add(100) {
+                return Err(format!(
+                    "square {index}: {amount} over Kelly optimum {optimum}"
+                ));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Value spread across magnitudes: a bit width, then a value below 2^width
+fn magnitude(max_bits: u32) -> impl Strategy<Value = u64> {
+    (1..=max_bits).prop_flat_map(|bits| 0..=u64::MAX >> (64 - bits))
+}
+
+prop_compose! {
+    /// A config that passes `ConfigParams::validate`
+    fn config()(
+        protocol_fee_bps in 0u16..3_000,
+        refining_fee_bps in 0u16..3_000,
+        admin_fee_bps in 0u16..500,
+        wrapper_fee_bps in 0u16..200,
+        kelly_c_scaled in MIN_KELLY_C_SCALED..=MAX_KELLY_C_SCALED,
+        min_deploy_per_block in magnitude(20),
+        motherlode_odds in 1u32..2_000,
+        motherlode_split in 0u8..=MOTHERLODE_SPLIT_PER_MINER,
+    ) -> ConfigParams {
+        ConfigParams {
+            protocol_fee_bps,
+            refining_fee_bps,
+            admin_fee_bps,
+            wrapper_fee_bps,
+            kelly_c_scaled,
+            min_ore_price_lamports: 0,
+            max_ore_price_lamports: u64::MAX,
+            max_round_deploy_lamports: 0,
+            min_total_deployed: 0,
+            min_deploy_per_block,
+            motherlode_odds,
+            motherlode_split,
+            _padding2: [0; 3],
+            drawdown_trigger_bps: 0,
+            drawdown_scale_bps: 10_000,
+            max_threshold_adjust_bps: 0,
+            _padding3: [0; 2],
+        }
+    }
+}
+
+prop_compose! {
+    fn input()(
+        (deployed, count, held_share) in (
+            prop::array::uniform25(magnitude(45)),
+            prop::array::uniform25(0u64..500),
+            prop::array::uniform25(0u64..4),
+        ),
+        (motherlode, top_miner_reward, ore_price_lamports) in
+            (magnitude(50), magnitude(45), magnitude(42)),
+        config in config(),
+        (total_budget, max_blocks) in (magnitude(50), 1u8..=25),
+        (objective, selection) in (0u8..5, 0u8..3),
+        (min_ev_threshold, portfolio) in (-2_000i32..2_000, any::<bool>()),
+        (max_std_dev_bps, kelly_fraction_bps) in (0u16..20_000, 0u16..12_000),
+        eligible_squares in prop_oneof![Just(0x1ff_ffffu32), 0u32..0x200_0000],
+        crowd_penalty_bps in prop_oneof![Just(0u16), 0u16..100],
+        (top_miner, motherlode_weight_bps) in (any::<bool>(), 0u32..20_000),
+    ) -> Input {
+        // Hold none, all, a half or a third of each square
+        let mut held = [0u64; 25];
+        for ((h, d), share) in held.iter_mut().zip(deployed).zip(held_share) {
+            *h = d.checked_div(share).unwrap_or(0);
+        }
+        Input {
+            deployed,
+            count,
+            motherlode,
+            top_miner_reward,
+            config,
+            total_budget,
+            max_blocks,
+            ore_price_lamports,
+            min_ev_threshold,
+            objective,
+            selection,
+            max_std_dev_bps,
+            portfolio,
+            eligible_squares,
+            crowd_penalty_bps,
+            held,
+            top_miner,
+            motherlode_weight_bps,
+            kelly_fraction_bps,
+        }
+    }
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(256))]
+
+    #[test]
+    fn allocator_invariants(input in input()) {
+        if let Err(failure) = check_invariants(&input) {
+            return Err(TestCaseError::fail(failure));
+        }
+    }
+
+    /// Rounds sized near the u64 limit must fail cleanly, never panic
+    #[test]
+    fn allocator_handles_whale_rounds(
+        input in input(),
+        whale in prop::array::uniform25(magnitude(64)),
+        budget in magnitude(64),
+    ) {
+        let mut input = input;
+        input.deployed = whale;
+        input.total_budget = budget;
+        if let Err(failure) = check_invariants(&input) {
+            return Err(TestCaseError::fail(failure));
+        }
+    }
+}
+
+/// Two identical 0.1 SOL squares, every other square 1 SOL, sized under Kelly
+fn twin_squares() -> Input {
+    let mut deployed = [1_000_000_000; 25];
+    deployed[..2].copy_from_slice(&[100_000_000; 2]);
+    Input {
+        deployed,
+        count: [10; 25],
+        motherlode: 0,
+        top_miner_reward: 0,
+        config: ConfigParams {
+            protocol_fee_bps: 1_000,
+            refining_fee_bps: 1_000,
+            admin_fee_bps: 101,
+            wrapper_fee_bps: 0,
+            kelly_c_scaled: 24_000_000_000,
+            min_ore_price_lamports: 0,
+            max_ore_price_lamports: u64::MAX,
+            max_round_deploy_lamports: 0,
+            min_total_deployed: 0,
+            min_deploy_per_block: 0,
+            motherlode_odds: 625,
+            motherlode_split: 0,
+            _padding2: [0; 3],
+            drawdown_trigger_bps: 0,
+            drawdown_scale_bps: 10_000,
+            max_threshold_adjust_bps: 0,
+            _padding3: [0; 2],
+        },
+        total_budget: 100_000_000_000,
+        max_blocks: 2,
+        ore_price_lamports: 0,
+        min_ev_threshold: -10_000,
+        objective: Objective::Kelly as u8,
+        selection: Selection::Smallest as u8,
+        max_std_dev_bps: 0,
+        portfolio: false,
+        eligible_squares: 0b11,
+        crowd_penalty_bps: 0,
+        held: [0; 25],
+        top_miner: false,
+        motherlode_weight_bps: 0,
+        kelly_fraction_bps: 10_000,
+    }
+}
+
+#[test]
+fn flat_multipliers_leave_the_plan_unchanged() {
+    let input = twin_squares();
+    let round = round(&input);
+    let flat = calculate_optimal_deployments(&allocation_params(&input, &round)).unwrap();
+
+    let multipliers = [10_000u16; 25];
+    let p = AllocationParams {
+        payout_multipliers_bps: Some(&multipliers),
+        ..allocation_params(&input, &round)
+    };
+    assert_eq!(calculate_optimal_deployments(&p).unwrap(), flat);
+}
+
+#[test]
+fn a_boosted_square_draws_more_of_the_budget() {
+    let input = twin_squares();
+    let round = round(&input);
+    let mut multipliers = [10_000u16; 25];
+    multipliers[1] = 20_000;
+    let p = AllocationParams {
+        payout_multipliers_bps: Some(&multipliers),
+        ..allocation_params(&input, &round)
+    };
+    let plan = calculate_optimal_deployments(&p).unwrap();
+
+    let amount_on = |square: u8| {
+        let i = plan.indices[..plan.count as usize]
+            .iter()
+            .position(|&index| index == square)
+            .unwrap();
+        (plan.amounts[i], plan.evs[i])
+    };
+    let (flat_amount, flat_ev) = amount_on(0);
+    let (boosted_amount, boosted_ev) = amount_on(1);
+    assert!(boosted_amount > flat_amount);
+    assert!(boosted_ev > flat_ev);
+}
+
+#[test]
+fn ev_surface_matches_the_kelly_plan_and_covers_skipped_squares() {
+    // One block, so no stake of ours on another square moves its pot
+    let input = Input {
+        max_blocks: 1,
+        ..twin_squares()
+    };
+    let round = round(&input);
+    let p = allocation_params(&input, &round);
+    let plan = calculate_optimal_deployments(&p).unwrap();
+    let surface = ev_surface(&p).unwrap();
+
+    // The budget covers the Kelly optimum, so the plan deploys it exactly
+    assert_eq!(plan.count, 1);
+    let square = surface[plan.indices[0] as usize];
+    assert_eq!((square.kelly, square.ev), (plan.amounts[0], plan.evs[0]));
+    assert!(square.marginal_ev_bps > 0);
+
+    // Squares outside the mask are still sized
+    let skipped = surface[2];
+    assert_eq!(skipped.deployed, 1_000_000_000);
+    assert!(skipped.kelly > 0);
+    assert!(skipped.marginal_ev_bps < surface[0].marginal_ev_bps);
+}
+
+#[test]
+fn a_budget_past_18_sol_is_shared_without_overflow() {
+    // (budget × 1e9) no longer fits a u64 here, and the Kelly optima of the
+    // deep twins far exceed the budget, so it is solved for
+    let mut input = twin_squares();
+    for deployed in input.deployed.iter_mut() {
+        *deployed *= 1_000;
+    }
+    input.total_budget = 20_000_000_000;
+    let round = round(&input);
+    let plan = calculate_optimal_deployments(&allocation_params(&input, &round)).unwrap();
+
+    assert_eq!(plan.count, 2);
+    let total: u64 = plan.amounts[..2].iter().sum();
+    assert!(total <= input.total_budget);
+    assert!(total >= input.total_budget / 100 * 99);
+    assert!(plan.amounts[..2]
+        .iter()
+        .all(|&a| a > input.total_budget / 3));
+}
@@ -0,0 +1,143 @@
+use ore_ev_math::{calculate_ev, kelly_amount, ConfigParams, Q64};
+
+const CASES: usize = 10_000;
+const TWO_64: f64 = 18_446_744_073_709_551_616.0;
+
+/// xorshift64*, so the property tests are reproducible without extra deps
+struct Rng(u64);
+
+impl Rng {
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 >> 12;
+        self.0 ^= self.0 << 25;
+        self.0 ^= self.0 >> 27;
+        self.0.wrapping_mul(0x2545_f491_4f6c_dd1d)
+    }
+
+    /// Log-uniform value in [1, 2^bits), so small and huge magnitudes are both covered
+    fn magnitude(&mut self, bits: u32) -> u64 {
+        let width = 1 + self.next() % bits as u64;
+        (self.next() >> (64 - width)).max(1)
+    }
+}
+
+fn to_f64(q: Q64) -> f64 {
+    q.0 as f64 / TWO_64
+}
+
+fn assert_close(actual: f64, expected: f64, what: &str) {
+    let tolerance = expected.abs() * 1e-12 + 1e-15;
+    assert!(
+        (actual - expected).abs() <= tolerance,
+        "{what}: {actual} vs f64 reference {expected}"
+    );
+}
+
+#[test]
+fn from_ratio_matches_f64() {
+    let mut rng = Rng(0x9e37_79b9_7f4a_7c15);
+    for _ in 0..CASES {
+        let num = rng.magnitude(63);
+        let den = rng.magnitude(63);
+        let q = Q64::from_ratio(num as u128, den as u128);
+        assert_close(to_f64(q), num as f64 / den as f64, "from_ratio");
+    }
+}
+
+#[test]
+fn mul_matches_f64() {
+    let mut rng = Rng(0x0123_4567_89ab_cdef);
+    for _ in 0..CASES {
+        // Operands below 2^31 keep the product inside the 64 integer bits
+        let a = Q64::from_ratio(rng.magnitude(31) as u128, rng.magnitude(20) as u128);
+        let b = Q64::from_ratio(rng.magnitude(31) as u128, rng.magnitude(20) as u128);
+        assert_close(to_f64(a * b), to_f64(a) * to_f64(b), "mul");
+    }
+}
+
+#[test]
+fn div_matches_f64() {
+    let mut rng = Rng(0xdead_beef_cafe_f00d);
+    for _ in 0..CASES {
+        let a = Q64::from_ratio(rng.magnitude(40) as u128, rng.magnitude(20) as u128);
+        let b = Q64::from_ratio(rng.magnitude(20) as u128, rng.magnitude(20) as u128);
+        assert_close(to_f64(a / b), to_f64(a) / to_f64(b), "div");
+    }
+}
+
+#[test]
+fn sqrt_matches_f64() {
+    let mut rng = Rng(0x5eed_5eed_5eed_5eed);
+    for _ in 0..CASES {
+        let a = Q64::from_ratio(rng.magnitude(63) as u128, rng.magnitude(63) as u128);
+        assert_close(to_f64(a.sqrt()), to_f64(a).sqrt(), "sqrt");
+    }
+}
+
+#[test]
+fn overflow_is_reported() {
+    let max = Q64::from_int(u64::MAX);
+    assert_eq!(max.checked_mul(Q64::from_int(2)), None);
+    assert_eq!(Q64::ONE.checked_div(Q64::ZERO), None);
+    assert_eq!(max.checked_div(Q64::from_ratio(1, 2)), None);
+    assert_eq!(Q64::ONE.checked_mul(Q64::ONE), Some(Q64::ONE));
+}
+
+#[test]
+fn kelly_amount_matches_f64() {
+    let mut rng = Rng(0x1357_9bdf_2468_ace0);
+    for _ in 0..CASES {
+        let v = rng.magnitude(60);
+        let o = rng.magnitude(60);
+        let c_scaled = 1_000_000_000 + rng.next() % 99_000_000_000;
+        let c = c_scaled as f64 / 1e9;
+        let expected = ((v as f64 * o as f64 / c).sqrt() - o as f64).max(0.0);
+        let actual = kelly_amount(v, o, c_scaled) as f64;
+        // Floor at each step can cost a lamport or two on top of f64's own error
+        assert!(
+            (actual - expected).abs() <= expected * 1e-12 + 2.0,
+            "kelly_amount({v}, {o}, {c_scaled}) = {actual}, f64 reference {expected}"
+        );
+    }
+}
+
+#[test]
+fn calculate_ev_matches_f64() {
+    let params = ConfigParams {
+        protocol_fee_bps: 1000,
+        refining_fee_bps: 1000,
+        admin_fee_bps: 101,
+        wrapper_fee_bps: 0,
+        kelly_c_scaled: 24_000_000_000,
+        min_ore_price_lamports: 0,
+        max_ore_price_lamports: u64::MAX,
+        max_round_deploy_lamports: 0,
+        min_total_deployed: 0,
+        min_deploy_per_block: 0,
+        motherlode_odds: 625,
+        motherlode_split: 0,
+        _padding2: [0; 3],
+        drawdown_trigger_bps: 0,
+        drawdown_scale_bps: 10_000,
+        max_threshold_adjust_bps: 0,
+        _padding3: [0; 2],
+    };
+    let payout = (10_000 - params.protocol_fee_bps) as f64 / 10_000.0;
+    let fee = params.fee_load_bps() as f64 / 10_000.0;
+    let mut rng = Rng(0xfeed_face_0bad_f00d);
+    for _ in 0..CASES {
+        let block = rng.magnitude(50);
+        let total = block + rng.magnitude(50);
+        let y = rng.magnitude(50);
+        let ore = rng.magnitude(40);
+
+        let pot = ((total - block) as f64 * payout).floor() + ore as f64;
+        let share = y as f64 / (block + y) as f64;
+        let expected = pot * share / 25.0 - y as f64 * 24.0 / 25.0 - y as f64 * fee;
+        let actual = calculate_ev(block, y, total, ore, &params) as f64;
+        assert!(
+            (actual - expected).abs() <= expected.abs() * 1e-12 + 3.0,
+            "calculate_ev({block}, {y}, {total}, {ore}) = {actual}, f64 reference {expected}"
+        );
+    }
+}
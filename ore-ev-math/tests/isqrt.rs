@@ -0,0 +1,88 @@
+use ore_ev_math::{isqrt, isqrt_u128};
+
+fn assert_root_u64(n: u64) {
+    let r = isqrt(n) as u128;
+    let n = n as u128;
+    assert!(r * r <= n && (r + 1) * (r + 1) > n, "isqrt({n}) = {r}");
+}
+
+fn assert_root_u128(n: u128) {
+    let r = isqrt_u128(n);
+    assert!(r * r <= n, "isqrt_u128({n}) = {r} is too large");
+    if let Some(next) = (r + 1).checked_mul(r + 1) {
+        assert!(next > n, "isqrt_u128({n}) = {r} is too small");
+    }
+}
+
+#[test]
+fn isqrt_small_values_exhaustive() {
+    for n in 0..=1_000_000u64 {
+        assert_root_u64(n);
+    }
+}
+
+#[test]
+fn isqrt_around_powers_of_two() {
+    for k in 0..64 {
+        let p = 1u64 << k;
+        for n in [p - 1, p, p.saturating_add(1)] {
+            assert_root_u64(n);
+        }
+    }
+    assert_root_u64(u64::MAX);
+}
+
+#[test]
+fn isqrt_around_perfect_squares() {
+    let roots = (1..=4_096u64).chain((0..32).map(|k| 1u64 << k)).chain([
+        65_535,
+        65_536,
+        3_037_000_499,
+        u32::MAX as u64 - 1,
+        u32::MAX as u64,
+    ]);
+    for r in roots {
+        let square = r * r;
+        assert_eq!(isqrt(square), r);
+        assert_eq!(isqrt(square - 1), r - 1);
+        assert_eq!(isqrt(square.saturating_add(1)), r);
+        if let Some(next) = (r + 1).checked_mul(r + 1) {
+            assert_eq!(isqrt(next - 1), r);
+        }
+    }
+}
+
+#[test]
+fn isqrt_u128_around_powers_of_two() {
+    for k in 0..128 {
+        let p = 1u128 << k;
+        for n in [p - 1, p, p.saturating_add(1)] {
+            assert_root_u128(n);
+        }
+    }
+    assert_root_u128(u128::MAX);
+}
+
+#[test]
+fn isqrt_u128_around_perfect_squares() {
+    let roots = (1..=4_096u128)
+        .chain((0..64).map(|k| 1u128 << k))
+        .chain([u64::MAX as u128 - 1, u64::MAX as u128]);
+    for r in roots {
+        let square = r * r;
+        assert_eq!(isqrt_u128(square), r);
+        assert_eq!(isqrt_u128(square - 1), r - 1);
+        assert_eq!(isqrt_u128(square.saturating_add(1)), r);
+    }
+}
+
+#[test]
+fn isqrt_matches_u128_variant() {
+    let mut n = 1u64;
+    while let Some(next) = n.checked_mul(3) {
+        for m in [n - 1, n, n + 1] {
+            assert_eq!(isqrt(m) as u128, isqrt_u128(m as u128));
+        }
+        n = next;
+    }
+}
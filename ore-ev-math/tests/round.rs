@@ -0,0 +1,41 @@
+use ore_ev_math::OreRound;
+
+fn drawn(rng: u64) -> OreRound {
+    let mut round = OreRound {
+        _disc: [0; 8],
+        id: 1,
+        deployed: [1_000; 25],
+        slot_hash: [0; 32],
+        count: [1; 25],
+        expires_at: 0,
+        motherlode: 0,
+        rent_payer: [0; 32],
+        top_miner: [0; 32],
+        top_miner_reward: 0,
+        total_deployed: 25_000,
+        total_vaulted: 0,
+        total_winnings: 20_000,
+    };
+    round.slot_hash[..8].copy_from_slice(&rng.to_le_bytes());
+    round
+}
+
+#[test]
+fn the_slot_hash_words_pick_the_winning_square() {
+    assert_eq!(drawn(7).winning_square(), Some(7));
+    assert_eq!(drawn(25 + 3).winning_square(), Some(3));
+
+    let mut round = drawn(0);
+    round.slot_hash = [0; 32];
+    assert_eq!(round.winning_square(), None);
+    round.slot_hash = [u8::MAX; 32];
+    assert_eq!(round.winning_square(), None);
+}
+
+#[test]
+fn only_the_winning_square_pays_its_stake_and_share() {
+    let round = drawn(7);
+    // A quarter of the winning square's pool takes a quarter of the winnings
+    assert_eq!(round.sol_payout(7, 250), 250 + 5_000);
+    assert_eq!(round.sol_payout(8, 250), 0);
+}
@@ -0,0 +1,71 @@
+use bytemuck::Zeroable;
+use ore_ev_math::{
+    calculate_optimal_deployments, motherlode_value, try_motherlode_value, try_scale_payout,
+    AllocationParams, ConfigParams, Error, Objective, OreRound, Selection,
+};
+
+fn config() -> ConfigParams {
+    ConfigParams {
+        protocol_fee_bps: 1_000,
+        refining_fee_bps: 1_000,
+        admin_fee_bps: 101,
+        kelly_c_scaled: 24_000_000_000,
+        max_ore_price_lamports: u64::MAX,
+        motherlode_odds: 625,
+        drawdown_scale_bps: 10_000,
+        ..Zeroable::zeroed()
+    }
+}
+
+#[test]
+fn overflow_is_an_error_instead_of_a_saturated_value() {
+    let params = config();
+    assert_eq!(
+        try_motherlode_value(u64::MAX, u64::MAX, &params),
+        Err(Error::ArithmeticOverflow)
+    );
+    assert_eq!(
+        try_scale_payout(u64::MAX, 20_000),
+        Err(Error::ArithmeticOverflow)
+    );
+
+    // The saturating forms are unchanged for callers that want them
+    assert_eq!(motherlode_value(u64::MAX, u64::MAX, &params), u64::MAX);
+}
+
+#[test]
+fn the_allocator_fails_on_an_overflowing_motherlode() {
+    let params = config();
+    let mut round = OreRound::zeroed();
+    round.deployed = [1_000_000_000; 25];
+    round.count = [10; 25];
+    round.total_deployed = 25_000_000_000;
+    round.motherlode = u64::MAX;
+
+    let p = AllocationParams {
+        round: &round,
+        config: &params,
+        total_budget: 1_000_000_000,
+        max_blocks: 5,
+        ore_price_lamports: u64::MAX,
+        min_ev_threshold: -10_000,
+        rank_thresholds: &[],
+        absolute_threshold: false,
+        max_block_share_bps: 0,
+        objective: Objective::Kelly,
+        selection: Selection::EvPerLamport,
+        max_std_dev_bps: 0,
+        portfolio: false,
+        eligible_squares: 0x1ff_ffff,
+        crowd_penalty_bps: 0,
+        held: [0; 25],
+        top_miner: false,
+        motherlode_weight_bps: 10_000,
+        kelly_fraction_bps: 10_000,
+        payout_multipliers_bps: None,
+    };
+    assert_eq!(
+        calculate_optimal_deployments(&p),
+        Err(Error::ArithmeticOverflow)
+    );
+}
@@ -0,0 +1,29 @@
+//! Compute units per plan size; writes a markdown report to `target/benches`
+
+#[path = "../tests/common/mod.rs"]
+mod common;
+
+use common::Fixture;
+use mollusk_svm_bencher::MolluskComputeUnitBencher;
+
+fn main() {
+    let fixture = Fixture::new();
+    let accounts = fixture.accounts();
+    let plans: Vec<_> = [1u8, 2, 4, 8, 16, 25]
+        .into_iter()
+        .map(|n| {
+            (
+                format!("ore_deploy_{n}_blocks"),
+                fixture.deploy_ix(1_000_000_000, n),
+            )
+        })
+        .collect();
+
+    let mut bencher = MolluskComputeUnitBencher::new(fixture.mollusk)
+        .must_pass(true)
+        .out_dir("../target/benches");
+    for (name, ix) in &plans {
+        bencher = bencher.bench((name.as_str(), ix, &accounts));
+    }
+    bencher.execute();
+}
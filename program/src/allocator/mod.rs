@@ -0,0 +1,6 @@
+//! Allocation math lives in the shared `ore-ev-math` crate; this module
+//! re-exports it alongside the program-state-aware risk rules.
+
+pub mod risk;
+
+pub use ore_ev_math::allocator::*;
@@ -0,0 +1,52 @@
+use crate::{
+    allocator::isqrt_u128,
+    state::{ConfigParams, UserStats},
+};
+
+/// Kelly fraction in bps under the drawdown rule
+///
+/// While PnL sits more than `drawdown_trigger_bps` of the bankroll below its
+/// peak, sizing is scaled to `drawdown_scale_bps`; once PnL recovers above
+/// the trigger, full size (10000) is restored.
+pub fn kelly_fraction_bps(stats: &UserStats, bankroll: u64, params: &ConfigParams) -> u16 {
+    if params.drawdown_trigger_bps == 0 || bankroll == 0 {
+        return 10_000;
+    }
+
+    let drawdown = (stats.peak_pnl as i128 - stats.pnl() as i128).max(0);
+    let trigger = bankroll as i128 * params.drawdown_trigger_bps as i128 / 10_000;
+    if drawdown > trigger {
+        params.drawdown_scale_bps
+    } else {
+        10_000
+    }
+}
+
+/// Extra EV threshold in bps while realized returns trail modeled EV
+///
+/// Over the settled rounds in the stats window, the shortfall of realized
+/// PnL against modeled EV beyond one modeled standard deviation (ordinary
+/// variance) is read as adverse selection or late sniping, and the threshold
+/// is tightened by that excess per lamport deployed, up to
+/// `max_threshold_adjust_bps`. Once realized returns match the model again
+/// the adjustment falls back to zero.
+pub fn threshold_adjustment_bps(stats: &UserStats, params: &ConfigParams) -> i32 {
+    let mut deployed: i128 = 0;
+    let mut shortfall: i128 = 0;
+    let mut variance: u128 = 0;
+    for round in stats.settled_rounds() {
+        let realized = round.won as i128 - round.deployed as i128;
+        deployed += round.deployed as i128;
+        shortfall += round.modeled_ev as i128 - realized;
+        variance += round.std_dev as u128 * round.std_dev as u128;
+    }
+    if deployed == 0 {
+        return 0;
+    }
+
+    let excess = shortfall - isqrt_u128(variance) as i128;
+    if excess <= 0 {
+        return 0;
+    }
+    (excess * 10_000 / deployed).min(params.max_threshold_adjust_bps as i128) as i32
+}
@@ -1,9 +1,9 @@
 #![allow(unexpected_cfgs)]
 
-use crate::instruction::{self, MyProgramInstruction};
+use crate::processor;
 use pinocchio::{
-    account_info::AccountInfo, default_panic_handler, msg, no_allocator, program_entrypoint,
-    program_error::ProgramError, pubkey::Pubkey, ProgramResult,
+    account_info::AccountInfo, default_panic_handler, no_allocator, program_entrypoint,
+    pubkey::Pubkey, ProgramResult,
 };
 
 // This is the entrypoint for the program.
@@ -19,13 +19,5 @@ fn process_instruction(
     accounts: &[AccountInfo],
     instruction_data: &[u8],
 ) -> ProgramResult {
-    let (ix_disc, instruction_data) = instruction_data
-        .split_first()
-        .ok_or(ProgramError::InvalidInstructionData)?;
-
-    match MyProgramInstruction::try_from(ix_disc)? {
-        MyProgramInstruction::OreDeploy => {
-            instruction::process_ore_deploy(accounts, instruction_data)
-        }
-    }
+    processor::process_instruction(accounts, instruction_data)
 }
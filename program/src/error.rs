@@ -1,17 +1,130 @@
 use pinocchio::program_error::ProgramError;
 
+/// Custom program errors, surfaced as `ProgramError::Custom(code)`; codes
+/// are stable, new variants are only ever appended
 #[derive(Clone, PartialEq, shank::ShankType)]
+#[repr(u32)]
 pub enum MyProgramError {
-    // overflow error
-    WriteOverflow,
-    // invalid instruction data
-    InvalidInstructionData,
-    // pda mismatch
-    PdaMismatch,
-    // Invalid Owner
-    InvalidOwner,
-    // ORE NoPositiveEvBlocks
-    NoPositiveEvBlocks,
+    /// 0: A count or deposit overflowed
+    WriteOverflow = 0,
+    /// 1: `ConfigParams` out of bounds
+    InvalidInstructionData = 1,
+    /// 2: Account is not the expected PDA
+    PdaMismatch = 2,
+    /// 3: Account is not owned by this program
+    InvalidOwner = 3,
+    /// 4: No block meets the EV threshold
+    NoPositiveEvBlocks = 4,
+    /// 5: Oracle account is not a verified SOL/USD price
+    InvalidOracle = 5,
+    /// 6: Oracle price is older than the allowed age
+    StaleOracle = 6,
+    /// 7: Signer is not the config admin
+    InvalidAdmin = 7,
+    /// 8: ORE price outside the configured bounds
+    OrePriceOutOfBounds = 8,
+    /// 9: Per-round deployment cap already reached
+    RoundCapExceeded = 9,
+    /// 10: Round total deployed below the configured minimum
+    PoolTooSmall = 10,
+    /// 11: Motherlode below the motherlode-hunt threshold
+    MotherlodeTooSmall = 11,
+    /// 12: Bankroll has no capital left to deploy
+    BankrollExhausted = 12,
+    /// 13: Signer is not the account's authority
+    InvalidAuthority = 13,
+    /// 14: Schedule has no rounds or funds left
+    ScheduleComplete = 14,
+    /// 15: Schedule already executed in this ORE round
+    ScheduleAlreadyExecuted = 15,
+    /// 16: Not enough compute units left for a single deploy CPI
+    InsufficientComputeUnits = 16,
+    /// 17: Round pool, budget and ORE value exceed the allocator's lamport bounds
+    MathOverflow = 17,
+    /// 18: Instruction data is not the exact size of the instruction's layout
+    InvalidIxDataLength = 18,
+    /// 19: Round account is not an ORE round owned by the ORE program
+    InvalidRoundAccount = 19,
+    /// 20: Board account is not an ORE board owned by the ORE program
+    InvalidBoardAccount = 20,
+    /// 21: Miner account is not an ORE miner owned by the ORE program
+    InvalidMinerAccount = 21,
+    /// 22: Automation account is not an ORE automation owned by the ORE program
+    InvalidAutomationAccount = 22,
+    /// 23: ORE program is neither a known ORE program id nor the config's override
+    InvalidOreProgram = 23,
+    /// 24: Entropy program is not the known entropy program id
+    InvalidEntropyProgram = 24,
+    /// 25: Entropy var is not the board's entropy var PDA, owned by the entropy program
+    InvalidEntropyVar = 25,
+    /// 26: Round is not the board's current round
+    StaleRound = 26,
+    /// 27: OreDeploy signer account did not sign the transaction
+    SignerNotSigned = 27,
+    /// 28: OreDeploy authority account did not sign the transaction
+    AuthorityNotSigned = 28,
+    /// 29: Round's slot hash is already set
+    RoundAlreadyEnded = 29,
+    /// 30: Current slot is at or past the board's end slot
+    RoundExpiring = 30,
+    /// 31: Round pool grew past the instruction's max_total_deployed
+    PoolSlippageExceeded = 31,
+    /// 32: A quoted square grew past the instruction's block slippage bound
+    BlockSlippageExceeded = 32,
+    /// 33: Deploy's expected_nonce is not the authority's current DeployNonce
+    NonceMismatch = 33,
+    /// 34: `num_blocks` is not between 1 and 25
+    InvalidNumBlocks = 34,
+    /// 35: `max_block_share_bps` is above 10000
+    InvalidBlockShareBps = 35,
+    /// 36: `bankroll_share_bps` is above 10000
+    InvalidBankrollShareBps = 36,
+    /// 37: Drawdown sizing without `bankroll_lamports`
+    MissingBankrollLamports = 37,
+    /// 38: Mean-variance objective without `max_std_dev_bps`
+    MissingStdDevCap = 38,
+    /// 39: `motherlode_weight_bps` is below 10000
+    InvalidMotherlodeWeight = 39,
+    /// 40: Square masks exclude every square
+    NoEligibleSquares = 40,
+    /// 41: `ore_price_lamports` is zero
+    MissingOrePrice = 41,
+    /// 42: Auto EV threshold with absolute (lamport) thresholds
+    AutoThresholdRequiresBps = 42,
+    /// 43: A bps EV threshold is below -10000 (a loss of more than the stake)
+    InvalidEvThreshold = 43,
+    /// 44: Schedule without `budget_per_round` or `rounds`
+    InvalidScheduleParams = 44,
+    /// 45: Schedule strategy asks for a USD budget
+    UsdBudgetNotSupported = 45,
+    /// 46: Signer holds fewer lamports than the plan deploys
+    InsufficientBalance = 46,
+    /// 47: Instruction data is empty or starts with an unknown discriminator
+    UnknownInstruction = 47,
+    /// 48: `objective` is not a known allocator objective
+    InvalidObjective = 48,
+    /// 49: `selection` is not a known square selection
+    InvalidSelection = 49,
+    /// 50: An ORE deploy CPI failed; the log names the block, square mask and amount
+    DeployCpiFailed = 50,
+    /// 51: Creating one of this program's PDAs failed
+    AccountCreationFailed = 51,
+    /// 52: Event authority account is not this program's event authority PDA
+    InvalidEventAuthority = 52,
+    /// 53: SettleRound before the round's slot hash is drawn
+    RoundNotEnded = 53,
+    /// 54: The round record has already been settled
+    RoundAlreadySettled = 54,
+    /// 55: Account layout version is newer than this program knows
+    UnsupportedAccountVersion = 55,
+    /// 56: Account predates its current layout; run MigrateAccount first
+    AccountNeedsMigration = 56,
+    /// 57: An allocator operation overflowed (`strict-math` builds)
+    ArithmeticOverflow = 57,
+    /// 58: Token account is not an SPL Token account of the expected owner
+    InvalidTokenAccount = 58,
+    /// 59: The schedule vault's miner holds rewards not yet claimed or checkpointed
+    ScheduleRewardsUnclaimed = 59,
 }
 
 impl From<MyProgramError> for ProgramError {
@@ -19,3 +132,14 @@ impl From<MyProgramError> for ProgramError {
         Self::Custom(e as u32)
     }
 }
+
+/// Map an `ore-ev-math` error to its program error
+pub fn math_error(e: ore_ev_math::Error) -> ProgramError {
+    match e {
+        ore_ev_math::Error::InvalidParams => MyProgramError::InvalidInstructionData.into(),
+        ore_ev_math::Error::InvalidObjective => MyProgramError::InvalidObjective.into(),
+        ore_ev_math::Error::InvalidSelection => MyProgramError::InvalidSelection.into(),
+        ore_ev_math::Error::MathOverflow => MyProgramError::MathOverflow.into(),
+        ore_ev_math::Error::ArithmeticOverflow => MyProgramError::ArithmeticOverflow.into(),
+    }
+}
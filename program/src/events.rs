@@ -0,0 +1,207 @@
+use bytemuck::{Pod, Zeroable};
+use pinocchio::{log::sol_log_data, pubkey::Pubkey};
+
+use crate::allocator::MAX_PLAN_BLOCKS;
+
+/// First byte of every event, identifying its layout
+#[repr(u8)]
+pub enum EventDiscriminator {
+    Deploy = 1,
+    Settle = 2,
+    Claim = 3,
+}
+
+/// One executed deployment plan
+#[repr(C)]
+#[derive(Pod, Zeroable, Clone, Copy, Debug, PartialEq)]
+pub struct DeployEvent {
+    pub discriminator: u8,
+
+    /// Number of blocks deployed to
+    pub num_blocks: u8,
+
+    /// Padding (6 bytes)
+    pub _padding: [u8; 6],
+
+    /// ORE miner authority the plan deployed for
+    pub authority: Pubkey,
+
+    /// ORE round id
+    pub round_id: u64,
+
+    /// Lamports deployed across all blocks
+    pub total_deployed: u64,
+
+    /// Modeled EV of the whole plan in lamports
+    pub portfolio_ev: i64,
+
+    /// Modeled standard deviation of the plan's PnL in lamports
+    pub portfolio_std_dev: u64,
+
+    /// Lamports deployed per block (first `num_blocks` entries)
+    pub amounts: [u64; MAX_PLAN_BLOCKS],
+
+    /// Square index per block (first `num_blocks` entries)
+    pub indices: [u8; MAX_PLAN_BLOCKS],
+
+    /// Padding (7 bytes)
+    pub _padding2: [u8; 7],
+}
+
+/// A round's realized result, recorded once ORE has credited it
+#[repr(C)]
+#[derive(Pod, Zeroable, Clone, Copy, Debug, PartialEq)]
+pub struct SettleEvent {
+    pub discriminator: u8,
+
+    /// Padding (7 bytes)
+    pub _padding: [u8; 7],
+
+    /// ORE miner authority the round settled for
+    pub authority: Pubkey,
+
+    /// ORE round id
+    pub round_id: u64,
+
+    /// Lamports deployed in the round
+    pub deployed: u64,
+
+    /// SOL won from the round
+    pub won: u64,
+
+    /// Modeled plan EV in lamports
+    pub modeled_ev: i64,
+}
+
+/// Rewards claimed from an ORE miner
+#[repr(C)]
+#[derive(Pod, Zeroable, Clone, Copy, Debug, PartialEq)]
+pub struct ClaimEvent {
+    pub discriminator: u8,
+
+    /// Padding (7 bytes)
+    pub _padding: [u8; 7],
+
+    /// ORE miner authority that claimed
+    pub authority: Pubkey,
+
+    /// Lamports of SOL claimed
+    pub rewards_sol: u64,
+
+    /// ORE claimed, in base units
+    pub rewards_ore: u64,
+}
+
+/// Log `event` with `sol_log_data`, as one `Program data:` entry holding its
+/// Pod bytes
+#[inline(always)]
+pub fn emit<T: Pod>(event: &T) {
+    sol_log_data(&[bytemuck::bytes_of(event)]);
+}
+
+/// Anchor's discriminator for an event: `sha256("event:<Name>")[..8]`
+pub trait Event: Pod {
+    const DISCRIMINATOR: [u8; 8];
+}
+
+impl Event for DeployEvent {
+    const DISCRIMINATOR: [u8; 8] = [38, 80, 216, 152, 93, 117, 66, 0];
+}
+
+impl Event for SettleEvent {
+    const DISCRIMINATOR: [u8; 8] = [14, 166, 206, 248, 35, 1, 134, 48];
+}
+
+impl Event for ClaimEvent {
+    const DISCRIMINATOR: [u8; 8] = [93, 15, 70, 170, 48, 140, 212, 219];
+}
+
+/// Anchor event CPI: each event is also sent to this program in a self-invoke
+/// signed by the event authority PDA, where Anchor indexers pick it up from
+/// the inner instructions
+#[cfg(feature = "event-cpi")]
+pub mod cpi {
+    use pinocchio::{
+        account_info::AccountInfo,
+        cpi::invoke_signed,
+        instruction::{AccountMeta, Instruction, Seed, Signer},
+        program_error::ProgramError,
+        pubkey::find_program_address,
+        ProgramResult,
+    };
+
+    use super::{DeployEvent, Event};
+    use crate::error::MyProgramError;
+
+    pub const EVENT_AUTHORITY_SEED: &[u8] = b"__event_authority";
+
+    /// Leading bytes of a self-invoke carrying an event (Anchor's
+    /// `EVENT_IX_TAG_LE`)
+    pub const EVENT_IX_TAG_LE: [u8; 8] = [228, 69, 165, 46, 81, 203, 154, 29];
+
+    /// Largest self-invoke: the tag, the event discriminator and the largest event
+    const MAX_EVENT_IX_LEN: usize = 16 + core::mem::size_of::<DeployEvent>();
+
+    /// The event authority PDA and this program, passed last to instructions
+    /// that emit events
+    pub struct EventAccounts<'a> {
+        authority: &'a AccountInfo,
+        program: &'a AccountInfo,
+        bump: u8,
+    }
+
+    impl<'a> EventAccounts<'a> {
+        /// Take the event authority and program accounts from `accounts`
+        pub fn next(
+            accounts: &mut impl Iterator<Item = &'a AccountInfo>,
+        ) -> Result<Self, ProgramError> {
+            let (Some(authority), Some(program)) = (accounts.next(), accounts.next()) else {
+                return Err(ProgramError::NotEnoughAccountKeys);
+            };
+            let (expected, bump) = find_program_address(&[EVENT_AUTHORITY_SEED], &crate::ID);
+            if authority.key() != &expected || program.key() != &crate::ID {
+                return Err(MyProgramError::InvalidEventAuthority.into());
+            }
+            Ok(EventAccounts {
+                authority,
+                program,
+                bump,
+            })
+        }
+
+        /// Self-invoke with `event`, signed by the event authority
+        pub fn emit<T: Event>(&self, event: &T) -> ProgramResult {
+            let payload = bytemuck::bytes_of(event);
+            let len = 16 + payload.len();
+            let mut data = [0u8; MAX_EVENT_IX_LEN];
+            data[..8].copy_from_slice(&EVENT_IX_TAG_LE);
+            data[8..16].copy_from_slice(&T::DISCRIMINATOR);
+            data[16..len].copy_from_slice(payload);
+
+            let bump_seed = [self.bump];
+            let seeds = [Seed::from(EVENT_AUTHORITY_SEED), Seed::from(&bump_seed)];
+            invoke_signed(
+                &Instruction {
+                    program_id: &crate::ID,
+                    accounts: &[AccountMeta::readonly_signer(self.authority.key())],
+                    data: &data[..len],
+                },
+                &[self.authority, self.program],
+                &[Signer::from(&seeds)],
+            )
+        }
+    }
+
+    /// Accept a self-invoked event: it must be signed by the event authority,
+    /// so only this program can emit one
+    pub fn process_event(accounts: &[AccountInfo]) -> ProgramResult {
+        let [authority, ..] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+        let (expected, _) = find_program_address(&[EVENT_AUTHORITY_SEED], &crate::ID);
+        if !authority.is_signer() || authority.key() != &expected {
+            return Err(MyProgramError::InvalidEventAuthority.into());
+        }
+        Ok(())
+    }
+}
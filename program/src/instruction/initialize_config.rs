@@ -0,0 +1,165 @@
+use bytemuck::{Pod, Zeroable};
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::Seed,
+    program_error::ProgramError,
+    pubkey::{find_program_address, Pubkey},
+    ProgramResult,
+};
+
+use crate::{
+    error::{math_error, MyProgramError},
+    state::{
+        utils::{create_pda_account, load_acc_mut_unchecked, parse_ix_data, DataLen, Versioned},
+        AccountDiscriminator, ConfigParams, GlobalConfig, OreEnvironment, BOARD_SQUARES,
+        GLOBAL_CONFIG_SEED, MAX_ORE_ENVIRONMENTS,
+    },
+};
+
+/// `ConfigParams` followed by the ORE program override, selected by
+/// instruction data length; plain `ConfigParams` leaves the override as is
+/// (none for a new config)
+#[repr(C)]
+#[derive(Pod, Zeroable, Clone, Copy, Debug)]
+pub struct ConfigIxDataV2 {
+    pub params: ConfigParams,
+
+    /// See `GlobalConfig::ore_program_override` (all zeros = none)
+    pub ore_program_override: Pubkey,
+}
+
+impl DataLen for ConfigIxDataV2 {
+    const LEN: usize = core::mem::size_of::<ConfigIxDataV2>();
+}
+
+/// `ConfigIxDataV2` followed by the payout multiplier table; shorter data
+/// leaves the table as is (every square paying as is for a new config)
+#[repr(C)]
+#[derive(Pod, Zeroable, Clone, Copy, Debug)]
+pub struct ConfigIxDataV3 {
+    pub base: ConfigIxDataV2,
+
+    /// See `GlobalConfig::payout_multipliers_bps` (all zeros = as is)
+    pub payout_multipliers_bps: [u16; BOARD_SQUARES],
+
+    /// Padding (6 bytes)
+    pub _padding: [u8; 6],
+}
+
+impl DataLen for ConfigIxDataV3 {
+    const LEN: usize = core::mem::size_of::<ConfigIxDataV3>();
+}
+
+/// `ConfigIxDataV3` followed by the permitted ORE environments; shorter data
+/// leaves them as is (none for a new config)
+#[repr(C)]
+#[derive(Pod, Zeroable, Clone, Copy, Debug)]
+pub struct ConfigIxDataV4 {
+    pub base: ConfigIxDataV3,
+
+    /// See `GlobalConfig::ore_environments` (all zeros = unused entry)
+    pub ore_environments: [OreEnvironment; MAX_ORE_ENVIRONMENTS],
+}
+
+impl DataLen for ConfigIxDataV4 {
+    const LEN: usize = core::mem::size_of::<ConfigIxDataV4>();
+}
+
+/// InitializeConfig / UpdateConfig data, with the optional trailing fields
+/// that were present
+pub(crate) struct ConfigUpdate {
+    pub params: ConfigParams,
+    pub ore_program_override: Option<Pubkey>,
+    pub payout_multipliers_bps: Option<[u16; BOARD_SQUARES]>,
+    pub ore_environments: Option<[OreEnvironment; MAX_ORE_ENVIRONMENTS]>,
+}
+
+/// Parse InitializeConfig / UpdateConfig data, selected by its length
+pub(crate) fn parse_config_ix_data(data: &[u8]) -> Result<ConfigUpdate, ProgramError> {
+    let update = match data.len() {
+        ConfigIxDataV4::LEN => {
+            let v4 = parse_ix_data::<ConfigIxDataV4>(data)?;
+            ConfigUpdate {
+                params: v4.base.base.params,
+                ore_program_override: Some(v4.base.base.ore_program_override),
+                payout_multipliers_bps: Some(v4.base.payout_multipliers_bps),
+                ore_environments: Some(v4.ore_environments),
+            }
+        }
+        ConfigIxDataV3::LEN => {
+            let v3 = parse_ix_data::<ConfigIxDataV3>(data)?;
+            ConfigUpdate {
+                params: v3.base.params,
+                ore_program_override: Some(v3.base.ore_program_override),
+                payout_multipliers_bps: Some(v3.payout_multipliers_bps),
+                ore_environments: None,
+            }
+        }
+        ConfigIxDataV2::LEN => {
+            let v2 = parse_ix_data::<ConfigIxDataV2>(data)?;
+            ConfigUpdate {
+                params: v2.params,
+                ore_program_override: Some(v2.ore_program_override),
+                payout_multipliers_bps: None,
+                ore_environments: None,
+            }
+        }
+        _ => ConfigUpdate {
+            params: parse_ix_data::<ConfigParams>(data)?,
+            ore_program_override: None,
+            payout_multipliers_bps: None,
+            ore_environments: None,
+        },
+    };
+    update.params.validate().map_err(math_error)?;
+
+    // A permitted deployment is only as safe as the entropy it is checked against
+    let environments = update.ore_environments.unwrap_or_default();
+    if environments
+        .iter()
+        .any(|env| env.ore_program != [0; 32] && env.entropy_program == [0; 32])
+    {
+        return Err(MyProgramError::InvalidInstructionData.into());
+    }
+    Ok(update)
+}
+
+pub fn process_initialize_config(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let [admin, config, _system_program] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    if !admin.is_signer() {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let update = parse_config_ix_data(data)?;
+
+    let (expected, bump) = find_program_address(&[GLOBAL_CONFIG_SEED], &crate::ID);
+    if config.key() != &expected {
+        return Err(MyProgramError::PdaMismatch.into());
+    }
+
+    let bump_seed = [bump];
+    let seeds = [Seed::from(GLOBAL_CONFIG_SEED), Seed::from(&bump_seed)];
+    create_pda_account(admin, config, GlobalConfig::LEN, &seeds)?;
+
+    let mut data = config.try_borrow_mut_data()?;
+    let global_config = unsafe { load_acc_mut_unchecked::<GlobalConfig>(&mut data)? };
+    *global_config = GlobalConfig {
+        discriminator: AccountDiscriminator::GlobalConfig as u8,
+        bump,
+        version: GlobalConfig::VERSION,
+        _padding: [0; 5],
+        admin: *admin.key(),
+        params: update.params,
+        ore_program_override: update.ore_program_override.unwrap_or_default(),
+        payout_multipliers_bps: update.payout_multipliers_bps.unwrap_or_default(),
+        _padding2: [0; 6],
+        ore_environments: update.ore_environments.unwrap_or_default(),
+    };
+
+    info!("GlobalConfig initialized");
+
+    Ok(())
+}
@@ -0,0 +1,23 @@
+use pinocchio::{account_info::AccountInfo, program_error::ProgramError, ProgramResult};
+
+use crate::{instruction::discriminator, state::init_leaderboard};
+
+pub const INITIALIZE_LEADERBOARD_IX_DISCRIMINATOR: u8 = discriminator::INITIALIZE_LEADERBOARD;
+
+/// Create the global Leaderboard PDA; anyone may pay for it, since its only
+/// writer is SettleRound
+pub fn process_initialize_leaderboard(accounts: &[AccountInfo], _data: &[u8]) -> ProgramResult {
+    let [payer, leaderboard, _system_program] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    if !payer.is_signer() {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    init_leaderboard(payer, leaderboard)?;
+
+    info!("Leaderboard created");
+
+    Ok(())
+}
@@ -0,0 +1,84 @@
+use pinocchio::{account_info::AccountInfo, program_error::ProgramError, ProgramResult};
+use pinocchio_log::log;
+
+use crate::{
+    error::MyProgramError,
+    instruction::discriminator,
+    state::{
+        upgrade_global_config, upgrade_schedule, upgrade_user_stats,
+        utils::{load_acc_mut, DataLen, Initialized, Versioned},
+        AccountDiscriminator, Bankroll, DeployNonce, GlobalConfig, Leaderboard, Plan,
+        RoundExposure, RoundRecord, Schedule, UserStats,
+    },
+};
+
+pub const MIGRATE_ACCOUNT_IX_DISCRIMINATOR: u8 = discriminator::MIGRATE_ACCOUNT;
+
+/// Upgrade one of this program's accounts to its current layout version in
+/// place, growing it first if the new layout is longer; `payer` funds the
+/// extra rent. Permissionless, since migrating only preserves the account's
+/// contents. Accounts already at their current version are left as they are.
+///
+/// Accounts: `payer` (signer), `account`, `system_program`
+pub fn process_migrate_account(accounts: &[AccountInfo], _data: &[u8]) -> ProgramResult {
+    let [payer, account, _system_program] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    if !payer.is_signer() {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    if !account.is_owned_by(&crate::ID) {
+        return Err(MyProgramError::InvalidOwner.into());
+    }
+
+    let kind = {
+        let data = account.try_borrow_data()?;
+        let first = data.first().ok_or(ProgramError::InvalidAccountData)?;
+        AccountDiscriminator::try_from(*first)?
+    };
+
+    match kind {
+        AccountDiscriminator::GlobalConfig => {
+            upgrade_global_config(payer, account)?;
+            stamp_version::<GlobalConfig>(account)
+        }
+        AccountDiscriminator::RoundExposure => stamp_version::<RoundExposure>(account),
+        AccountDiscriminator::UserStats => {
+            upgrade_user_stats(payer, account)?;
+            stamp_version::<UserStats>(account)
+        }
+        AccountDiscriminator::Bankroll => stamp_version::<Bankroll>(account),
+        AccountDiscriminator::Schedule => {
+            upgrade_schedule(payer, account)?;
+            stamp_version::<Schedule>(account)
+        }
+        AccountDiscriminator::DeployNonce => stamp_version::<DeployNonce>(account),
+        AccountDiscriminator::RoundRecord => stamp_version::<RoundRecord>(account),
+        AccountDiscriminator::Leaderboard => stamp_version::<Leaderboard>(account),
+        AccountDiscriminator::Plan => stamp_version::<Plan>(account),
+    }
+}
+
+/// Mark an account whose layout matches `T` as being at `T::VERSION`
+fn stamp_version<T: DataLen + Initialized + Versioned>(account: &AccountInfo) -> ProgramResult {
+    let mut data = account.try_borrow_mut_data()?;
+    let version = unsafe { load_acc_mut::<T>(&mut data)? }.version_mut();
+    if *version > T::VERSION {
+        log!(
+            "Error: account version {} is newer than {}",
+            *version,
+            T::VERSION
+        );
+        return Err(MyProgramError::UnsupportedAccountVersion.into());
+    }
+    if *version < T::VERSION {
+        info!(
+            "Account migrated from version {} to {}",
+            *version,
+            T::VERSION
+        );
+        *version = T::VERSION;
+    }
+    Ok(())
+}
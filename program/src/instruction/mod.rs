@@ -1,12 +1,60 @@
 use pinocchio::program_error::ProgramError;
 
+use crate::error::MyProgramError;
+
+pub mod initialize_config;
+pub mod initialize_leaderboard;
+pub mod migrate_account;
 pub mod ore_deploy;
+pub mod schedule;
+pub mod set_bankroll;
+pub mod settle_round;
+pub mod update_config;
 
+pub use initialize_config::*;
+pub use initialize_leaderboard::*;
+pub use migrate_account::*;
 pub use ore_deploy::*;
+pub use schedule::*;
+pub use set_bankroll::*;
+pub use settle_round::*;
+pub use update_config::*;
+
+pub use ore_ev_math::instruction as discriminator;
 
 #[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub enum MyProgramInstruction {
-    OreDeploy = 6,
+    OreDeploy = discriminator::ORE_DEPLOY,
+    InitializeConfig = discriminator::INITIALIZE_CONFIG,
+    UpdateConfig = discriminator::UPDATE_CONFIG,
+    SetBankroll = discriminator::SET_BANKROLL,
+    CreateSchedule = discriminator::CREATE_SCHEDULE,
+    ExecuteSchedule = discriminator::EXECUTE_SCHEDULE,
+    CloseSchedule = discriminator::CLOSE_SCHEDULE,
+    SettleRound = discriminator::SETTLE_ROUND,
+    InitializeLeaderboard = discriminator::INITIALIZE_LEADERBOARD,
+    MigrateAccount = discriminator::MIGRATE_ACCOUNT,
+    ClaimSchedule = discriminator::CLAIM_SCHEDULE,
+    DepositSchedule = discriminator::DEPOSIT_SCHEDULE,
+}
+
+impl MyProgramInstruction {
+    /// Every instruction, in discriminator order
+    pub const ALL: [MyProgramInstruction; 12] = [
+        MyProgramInstruction::OreDeploy,
+        MyProgramInstruction::InitializeConfig,
+        MyProgramInstruction::UpdateConfig,
+        MyProgramInstruction::SetBankroll,
+        MyProgramInstruction::CreateSchedule,
+        MyProgramInstruction::ExecuteSchedule,
+        MyProgramInstruction::CloseSchedule,
+        MyProgramInstruction::SettleRound,
+        MyProgramInstruction::InitializeLeaderboard,
+        MyProgramInstruction::MigrateAccount,
+        MyProgramInstruction::ClaimSchedule,
+        MyProgramInstruction::DepositSchedule,
+    ];
 }
 
 impl TryFrom<&u8> for MyProgramInstruction {
@@ -14,8 +62,21 @@ impl TryFrom<&u8> for MyProgramInstruction {
 
     fn try_from(value: &u8) -> Result<Self, Self::Error> {
         match *value {
-            1 => Ok(MyProgramInstruction::OreDeploy),
-            _ => Err(ProgramError::InvalidInstructionData),
+            discriminator::ORE_DEPLOY => Ok(MyProgramInstruction::OreDeploy),
+            discriminator::INITIALIZE_CONFIG => Ok(MyProgramInstruction::InitializeConfig),
+            discriminator::UPDATE_CONFIG => Ok(MyProgramInstruction::UpdateConfig),
+            discriminator::SET_BANKROLL => Ok(MyProgramInstruction::SetBankroll),
+            discriminator::CREATE_SCHEDULE => Ok(MyProgramInstruction::CreateSchedule),
+            discriminator::EXECUTE_SCHEDULE => Ok(MyProgramInstruction::ExecuteSchedule),
+            discriminator::CLOSE_SCHEDULE => Ok(MyProgramInstruction::CloseSchedule),
+            discriminator::SETTLE_ROUND => Ok(MyProgramInstruction::SettleRound),
+            discriminator::INITIALIZE_LEADERBOARD => {
+                Ok(MyProgramInstruction::InitializeLeaderboard)
+            }
+            discriminator::MIGRATE_ACCOUNT => Ok(MyProgramInstruction::MigrateAccount),
+            discriminator::CLAIM_SCHEDULE => Ok(MyProgramInstruction::ClaimSchedule),
+            discriminator::DEPOSIT_SCHEDULE => Ok(MyProgramInstruction::DepositSchedule),
+            _ => Err(MyProgramError::UnknownInstruction.into()),
         }
     }
 }
@@ -1,29 +1,153 @@
 use pinocchio::{
     account_info::AccountInfo,
-    cpi::slice_invoke,
-    instruction::{AccountMeta, Instruction},
+    cpi::set_return_data,
+    instruction::Signer,
     program_error::ProgramError,
+    sysvars::{clock::Clock, Sysvar},
     ProgramResult,
 };
 
 use bytemuck::{Pod, Zeroable};
 use pinocchio_log::log;
 
+#[cfg(feature = "event-cpi")]
+use crate::events::cpi::EventAccounts;
+
 use crate::{
-    error::MyProgramError,
+    allocator::{
+        all_in_kelly_c_scaled, apply_fraction, calculate_optimal_deployments,
+        derive_kelly_c_scaled, ev_surface,
+        risk::{kelly_fraction_bps, threshold_adjustment_bps},
+        Allocation, AllocationParams, Objective, Selection, MAX_PLAN_BLOCKS,
+    },
+    error::{math_error, MyProgramError},
+    events::{emit, DeployEvent, EventDiscriminator, SettleEvent},
+    instruction::discriminator,
     state::{
-        read_ore_round_data,
-        utils::{load_ix_data, DataLen},
-        OreRound,
+        add_round_exposure, advance_deploy_nonce, load_bankroll, load_or_init_deploy_nonce,
+        load_or_init_round_exposure, load_or_init_round_record, load_or_init_user_stats,
+        read_global_config, read_pyth_price, record_bankroll, record_round_plan, record_user_stats,
+        utils::{parse_ix_data, DataLen},
+        write_plan, Bankroll, ConfigParams, GlobalConfig, OreRound, Plan, RoundResult, UserStats,
+        BOARD_SQUARES, MAX_PRICE_AGE_SECS, PYTH_RECEIVER_PROGRAM_ID,
     },
+    target::{DeployTarget, MinerPosition, OreTarget},
 };
 
-pub const ORE_DEPLOY_IX_DISCRIMINATOR: u8 = 6;
+pub const ORE_DEPLOY_IX_DISCRIMINATOR: u8 = discriminator::ORE_DEPLOY;
+
+/// Deploy CPIs executed per instruction when `max_cpis` is 0. Every block is
+/// one ORE deploy CPI, so this keeps a full plan well inside the transaction
+/// compute budget; raise it with a larger compute-unit limit.
+pub const DEFAULT_MAX_DEPLOY_CPIS: u8 = 8;
+
+/// Estimated compute units per deploy CPI into ORE v3, with headroom; the
+/// loop logs nothing per block, so this is the CPI's own cost
+pub const DEPLOY_CPI_COMPUTE_UNITS: u64 = 40_000;
+
+/// Compute units held back for the bookkeeping after the deploy CPIs
+pub const POST_DEPLOY_COMPUTE_UNITS: u64 = 15_000;
+
+/// Ceiling on reading the accounts and sizing the plan, before any CPI
+pub const PLAN_COMPUTE_UNITS: u64 = 150_000;
+
+/// `total_amount` is in USD cents, converted to lamports with the SOL/USD
+/// Pyth price account passed after the config account.
+pub const FLAG_USD_BUDGET: u32 = 1 << 0;
+
+/// Derive the fee-free Kelly constant from the round's square count (N - 1)
+/// instead of using the configured `kelly_c_scaled`.
+pub const FLAG_DERIVED_KELLY_C: u32 = 1 << 1;
+
+/// Return successfully without deploying (instead of failing with
+/// `PoolTooSmall`) when the round is below `min_total_deployed`.
+pub const FLAG_NOOP_ON_SMALL_POOL: u32 = 1 << 2;
+
+/// Apply the EV threshold to the plan as a whole, modeling that exactly one
+/// square wins, instead of to each block independently.
+pub const FLAG_PORTFOLIO_EV: u32 = 1 << 3;
+
+/// Exclude squares where the miner account already holds a position in this
+/// round, so repeated runs don't double down on the same square.
+pub const FLAG_SKIP_HELD_SQUARES: u32 = 1 << 4;
+
+/// Count the miner's existing positions in this round toward the block share
+/// cap and the per-round cap, so total exposure respects the limits.
+pub const FLAG_INCLUDE_HELD_EXPOSURE: u32 = 1 << 5;
+
+/// Add the round's top-miner reward to the EV of squares where the deployment
+/// makes us top miner, and let the Kelly objective size up to win it.
+pub const FLAG_TOP_MINER_EV: u32 = 1 << 6;
+
+/// Interpret `min_ev_threshold` (and per-rank thresholds) as an absolute EV
+/// floor in lamports per block instead of bps of the deployment.
+pub const FLAG_ABSOLUTE_EV_THRESHOLD: u32 = 1 << 7;
+
+/// Track PnL in the authority's UserStats PDA (passed after the optional
+/// accounts above) and cut the Kelly fraction while in drawdown against
+/// `bankroll_lamports`.
+pub const FLAG_DRAWDOWN_SIZING: u32 = 1 << 8;
+
+/// Size against the authority's Bankroll PDA (passed after the optional
+/// accounts above): the round budget is `bankroll_share_bps` of its capital,
+/// with `total_amount` only as a per-round ceiling.
+pub const FLAG_BANKROLL_SIZING: u32 = 1 << 9;
+
+/// Tighten the EV threshold while realized returns over the recent rounds in
+/// the authority's UserStats PDA trail modeled EV (bps thresholds only)
+pub const FLAG_AUTO_EV_THRESHOLD: u32 = 1 << 10;
+
+/// With a V3 block quote, drop squares that grew past their slippage bound
+/// from the plan instead of failing with `BlockSlippageExceeded`
+pub const FLAG_DROP_SLIPPED_BLOCKS: u32 = 1 << 11;
+
+/// Require `expected_nonce` to match the authority's DeployNonce PDA (passed
+/// after the optional accounts above) and advance it, so the transaction
+/// can't be replayed.
+pub const FLAG_NONCE: u32 = 1 << 12;
+
+/// Record the deploy in the authority's UserStats PDA (passed after the
+/// optional accounts above) without using it for sizing; implied by
+/// `FLAG_DRAWDOWN_SIZING` and `FLAG_AUTO_EV_THRESHOLD`.
+pub const FLAG_TRACK_STATS: u32 = 1 << 13;
+
+/// Flags whose plan inputs come from the authority's miner: its positions
+/// this round, or the winnings UserStats and the Bankroll sync against
+pub const MINER_FLAGS: u32 = FLAG_SKIP_HELD_SQUARES
+    | FLAG_INCLUDE_HELD_EXPOSURE
+    | FLAG_DRAWDOWN_SIZING
+    | FLAG_BANKROLL_SIZING
+    | FLAG_AUTO_EV_THRESHOLD
+    | FLAG_TRACK_STATS;
+
+/// Flags that load the authority's UserStats
+pub const USER_STATS_FLAGS: u32 = FLAG_DRAWDOWN_SIZING | FLAG_AUTO_EV_THRESHOLD | FLAG_TRACK_STATS;
+
+/// Merge the executed plan into the authority's RoundRecord PDA for the
+/// round (passed after the optional accounts above), kept for comparing the
+/// modeled EV against the realized result once the round settles.
+pub const FLAG_ROUND_RECORD: u32 = 1 << 14;
+
+/// Opt the round's RoundRecord into the Leaderboard, so SettleRound adds its
+/// result there (with `FLAG_ROUND_RECORD`)
+pub const FLAG_LEADERBOARD: u32 = 1 << 15;
+
+/// Log the Kelly size and EV of every square, funded or not, before
+/// planning; for simulating a deploy to see why a square was skipped
+pub const FLAG_LOG_EV_SURFACE: u32 = 1 << 16;
+
+/// Store the executed plan in the authority's Plan PDA (passed after the
+/// optional accounts above), valid until the round stops accepting deploys
+pub const FLAG_STORE_PLAN: u32 = 1 << 17;
+
+/// Square mask covering the whole board
+const ALL_SQUARES: u32 = (1 << BOARD_SQUARES) - 1;
 
 #[repr(C)]
 #[derive(Pod, Zeroable, Clone, Copy, Debug)]
 pub struct OreDeployIxData {
     /// Total SOL budget (will be allocated optimally across blocks)
+    /// In USD cents when `FLAG_USD_BUDGET` is set
     pub total_amount: u64,
 
     /// ORE price in lamports (for calculating optimal deployment)
@@ -31,393 +155,1046 @@ pub struct OreDeployIxData {
 
     /// Minimum EV threshold in basis points
     /// Examples: -500 = accept -5% EV, 0 = break-even+, 150 = +1.5%+
-    pub min_ev_threshold_bps: i16,
+    /// In lamports of EV per block when `FLAG_ABSOLUTE_EV_THRESHOLD` is set
+    pub min_ev_threshold: i32,
 
-    /// Number of smallest blocks to target (1-5)
+    /// Number of blocks to target (1-25)
     pub num_blocks: u8,
 
-    /// Padding (5 bytes)
-    pub _padding: [u8; 5],
+    /// Most deploy CPIs to execute (0 = `DEFAULT_MAX_DEPLOY_CPIS`); caps `num_blocks`
+    pub max_cpis: u8,
+
+    /// Maximum share of any block's post-deploy total we may own, in bps (0 = no cap)
+    pub max_block_share_bps: u16,
+
+    /// Mode flags (`FLAG_*`)
+    pub flags: u32,
+
+    /// Allocator objective (`Objective`: 0 = Kelly, 1 = marginal-EV equalization,
+    /// 2 = mean-variance, 3 = max-EV, 4 = motherlode hunt)
+    pub objective: u8,
+
+    /// Square selection (`Selection`: 0 = best EV per lamport, 1 = smallest first,
+    /// 2 = largest first)
+    pub selection: u8,
+
+    /// Cap on the plan's PnL standard deviation in bps of the budget (mean-variance only)
+    pub max_std_dev_bps: u16,
+
+    /// Squares the plan may use, bit i = square i (0 = all squares)
+    pub allowed_squares: u32,
+
+    /// Squares the plan must not use, bit i = square i
+    pub excluded_squares: u32,
+
+    /// EV discount per miner already on a square, in bps (0 = ignore crowding)
+    pub crowd_penalty_bps: u16,
+
+    /// Bankroll sizing only: share of the bankroll's capital to deploy this
+    /// round, in bps (0 = all of it)
+    pub bankroll_share_bps: u16,
+
+    /// Motherlode-hunt only: weight on the motherlode payoff in bps (>= 10000)
+    pub motherlode_weight_bps: u32,
+
+    /// Motherlode-hunt only: smallest motherlode (ORE base units) worth hunting
+    pub min_motherlode: u64,
+
+    /// Drawdown sizing only: bankroll the drawdown trigger is measured against
+    pub bankroll_lamports: u64,
+
+    /// Largest round pool the plan may land in, in lamports (0 = no bound)
+    pub max_total_deployed: u64,
+
+    /// Nonce check only: the authority's DeployNonce this deploy consumes
+    pub expected_nonce: u64,
 }
 
 impl DataLen for OreDeployIxData {
     const LEN: usize = core::mem::size_of::<OreDeployIxData>();
 }
 
+/// `OreDeployIxData` followed by optional per-rank EV thresholds, selected by
+/// instruction data length
+#[repr(C)]
+#[derive(Pod, Zeroable, Clone, Copy, Debug)]
+pub struct OreDeployIxDataV2 {
+    pub base: OreDeployIxData,
+
+    /// EV threshold for each rank in selection order (rank 0 = first selected),
+    /// in the same unit as `min_ev_threshold`
+    pub rank_thresholds: [i32; MAX_PLAN_BLOCKS],
+
+    /// Number of leading `rank_thresholds` entries in use; later ranks use
+    /// `min_ev_threshold`
+    pub num_rank_thresholds: u8,
+
+    /// Padding (3 bytes)
+    pub _padding: [u8; 3],
+}
+
+impl DataLen for OreDeployIxDataV2 {
+    const LEN: usize = core::mem::size_of::<OreDeployIxDataV2>();
+}
+
+/// Square sizes a plan was quoted against, bounding how far each may grow
+/// before the deploy lands
+#[repr(C)]
+#[derive(Pod, Zeroable, Clone, Copy, Debug)]
+pub struct BlockQuote {
+    /// Squares with a quoted size, bit i = square i
+    pub quoted_squares: u32,
+
+    /// Growth allowed over each quoted size, in bps
+    pub block_slippage_bps: u16,
+
+    /// Padding (2 bytes)
+    pub _padding: [u8; 2],
+
+    /// Lamports on each quoted square when the plan was quoted
+    pub quoted_deployed: [u64; BOARD_SQUARES],
+}
+
+impl BlockQuote {
+    /// Quoted squares that grew past `quoted × (1 + block_slippage_bps)`
+    pub fn slipped_squares(&self, deployed: &[u64; BOARD_SQUARES]) -> u32 {
+        let mut slipped = 0;
+        for (i, (current, quoted)) in deployed.iter().zip(self.quoted_deployed).enumerate() {
+            let bound = quoted as u128 * (10_000 + self.block_slippage_bps as u128) / 10_000;
+            if self.quoted_squares & (1 << i) != 0 && *current as u128 > bound {
+                slipped |= 1 << i;
+            }
+        }
+        slipped
+    }
+}
+
+/// `OreDeployIxDataV2` followed by a block quote, selected by instruction
+/// data length
+#[repr(C)]
+#[derive(Pod, Zeroable, Clone, Copy, Debug)]
+pub struct OreDeployIxDataV3 {
+    pub v2: OreDeployIxDataV2,
+    pub quote: BlockQuote,
+}
+
+impl DataLen for OreDeployIxDataV3 {
+    const LEN: usize = core::mem::size_of::<OreDeployIxDataV3>();
+}
+
+/// Executed plan, returned via `set_return_data`
+#[repr(C)]
+#[derive(Pod, Zeroable, Clone, Copy, Debug)]
+pub struct OreDeployResult {
+    /// Lamports deployed across all blocks
+    pub total_deployed: u64,
+
+    /// EV of the whole plan in lamports, accounting for only one square winning
+    pub portfolio_ev: i64,
+
+    /// Lamports deployed per block (first `num_blocks` entries)
+    pub amounts: [u64; MAX_PLAN_BLOCKS],
+
+    /// Modeled EV in lamports per block (first `num_blocks` entries)
+    pub evs: [i64; MAX_PLAN_BLOCKS],
+
+    /// Square index per block (first `num_blocks` entries)
+    pub indices: [u8; MAX_PLAN_BLOCKS],
+
+    /// Number of blocks deployed to
+    pub num_blocks: u8,
+
+    /// Padding (6 bytes)
+    pub _padding: [u8; 6],
+}
+
+impl DataLen for OreDeployResult {
+    const LEN: usize = core::mem::size_of::<OreDeployResult>();
+}
+
 pub fn process_ore_deploy(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
-    let [ore_program, signer, authority, automation, board, miner, round, system_program, entropy_var, entropy_program] =
-        accounts
-    else {
+    let Some(signer) = accounts.get(1) else {
         return Err(ProgramError::NotEnoughAccountKeys);
     };
 
-    let round_data = read_round_data(round)?;
-    let ix_data = unsafe { load_ix_data::<OreDeployIxData>(data)? };
+    // V1 data is V2 without rank thresholds, V2 is V3 without a block quote
+    let (ix_data, quote) = match data.len() {
+        OreDeployIxDataV3::LEN => {
+            let ix_data = parse_ix_data::<OreDeployIxDataV3>(data)?;
+            (ix_data.v2, Some(ix_data.quote))
+        }
+        OreDeployIxDataV2::LEN => (parse_ix_data::<OreDeployIxDataV2>(data)?, None),
+        _ => (
+            OreDeployIxDataV2 {
+                base: parse_ix_data::<OreDeployIxData>(data)?,
+                ..Zeroable::zeroed()
+            },
+            None,
+        ),
+    };
+    let count = (ix_data.num_rank_thresholds as usize).min(ix_data.rank_thresholds.len());
+    let rank_thresholds = &ix_data.rank_thresholds[..count];
 
-    // Validate inputs
-    if ix_data.num_blocks == 0 || ix_data.num_blocks > 5 {
-        log!("Error: num_blocks must be between 1 and 5");
-        return Err(ProgramError::InvalidInstructionData);
+    deploy::<OreTarget>(
+        accounts,
+        signer,
+        &ix_data.base,
+        rank_thresholds,
+        quote.as_ref(),
+        &[],
+    )?;
+    Ok(())
+}
+
+/// Size and execute a deployment over the OreDeploy accounts, returning the
+/// lamports deployed
+///
+/// `payer` funds any program PDAs created along the way; `quote` bounds the
+/// growth of the squares the plan was quoted against; `signers` are the
+/// seeds that sign the ORE deploy CPIs when `signer` is a PDA.
+pub(crate) fn deploy<'a, T: DeployTarget<'a>>(
+    accounts: &'a [AccountInfo],
+    payer: &AccountInfo,
+    ix_data: &OreDeployIxData,
+    rank_thresholds: &[i32],
+    quote: Option<&BlockQuote>,
+    signers: &[Signer],
+) -> Result<u64, ProgramError> {
+    let (target, rest) = T::from_accounts(accounts)?;
+    let [config, remaining @ ..] = rest else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    let (signer, authority) = (target.signer(), target.authority());
+
+    // Both sign the deploy CPIs; PDA signers sign through `signers` instead
+    if signers.is_empty() {
+        if !signer.is_signer() {
+            log!("Error: signer did not sign");
+            return Err(MyProgramError::SignerNotSigned.into());
+        }
+        if !authority.is_signer() {
+            log!("Error: authority did not sign");
+            return Err(MyProgramError::AuthorityNotSigned.into());
+        }
     }
 
-    if ix_data.ore_price_lamports == 0 {
-        log!("Error: ore_price_lamports required");
-        return Err(ProgramError::InvalidInstructionData);
+    let config = read_global_config(config)?;
+
+    target.validate(&config)?;
+    let slot = Clock::get()?.slot;
+    let round_data = target.read_open_round(slot)?;
+    telemetry!("decode");
+
+    let params = config.params;
+    if !check_round_pool(&round_data, &params, ix_data)? {
+        return Ok(0);
     }
 
-    log!("═══ ORE OPTIMAL DEPLOYMENT ═══");
-    log!(
-        "Total budget: {}.{} SOL",
-        ix_data.total_amount / 1_000_000_000,
-        (ix_data.total_amount % 1_000_000_000) / 1_000_000
-    );
-    log!(
-        "ORE price: {}.{} SOL",
-        ix_data.ore_price_lamports / 1_000_000_000,
-        (ix_data.ore_price_lamports % 1_000_000_000) / 1_000_000
-    );
-    log!(
-        "EV threshold: {} bps ({}.{}%)",
-        ix_data.min_ev_threshold_bps,
-        ix_data.min_ev_threshold_bps / 100,
-        ix_data.min_ev_threshold_bps.abs() % 100
-    );
-    log!("Target blocks: up to {}", ix_data.num_blocks);
+    // Existing positions from earlier deploys this round (any program or
+    // wallet run) and the winnings the stats and bankroll sync against
+    let miner = if ix_data.flags & MINER_FLAGS != 0 {
+        target.read_position()?
+    } else {
+        None
+    };
 
-    // Calculate optimal deployment for smallest blocks
-    let (num_selected, amounts, indices, evs) = calculate_optimal_deployments(
-        &round_data,
-        ix_data.total_amount,
-        ix_data.num_blocks,
-        ix_data.ore_price_lamports,
-        ix_data.min_ev_threshold_bps,
-    )?;
+    // Mode-specific accounts follow the config account, in the order they are consumed here
+    let mut remaining = remaining.iter();
 
-    if num_selected == 0 {
-        log!(
-            "✗ No blocks meet EV threshold of {} bps",
-            ix_data.min_ev_threshold_bps
+    let usd_budget_lamports = if ix_data.flags & FLAG_USD_BUDGET != 0 {
+        let Some(oracle) = remaining.next() else {
+            log!("Error: USD budget requires a SOL/USD oracle account");
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+        let lamports = usd_budget_to_lamports(oracle, ix_data.total_amount)?;
+        info!(
+            "USD budget: ${}.{} → {} lamports",
+            ix_data.total_amount / 100,
+            ix_data.total_amount % 100,
+            lamports
         );
-        return Err(MyProgramError::NoPositiveEvBlocks.into());
+        Some(lamports)
+    } else {
+        None
+    };
+
+    // Per-round cap across repeated calls, tracked in a (authority, round) PDA
+    let exposure_account = if params.max_round_deploy_lamports != 0 {
+        let Some(account) = remaining.next() else {
+            log!("Error: round cap requires the RoundExposure account");
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+        let exposure = load_or_init_round_exposure(payer, authority, account, round_data.id)?;
+        Some((account, exposure.total_deployed))
+    } else {
+        None
+    };
+
+    // Cumulative PnL and recent round history per authority
+    let mut user_stats = if ix_data.flags & USER_STATS_FLAGS != 0 {
+        let Some(account) = remaining.next() else {
+            log!("Error: UserStats account required");
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+        let lifetime_rewards_sol = miner.map_or(0, |m| m.lifetime_rewards_sol);
+        let stats = load_or_init_user_stats(payer, authority, account, lifetime_rewards_sol)?;
+        Some((account, stats))
+    } else {
+        None
+    };
+
+    // Capital committed across rounds; total_amount only caps this round
+    let mut bankroll = if ix_data.flags & FLAG_BANKROLL_SIZING != 0 {
+        let Some(account) = remaining.next() else {
+            log!("Error: bankroll sizing requires the Bankroll account");
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+        Some((account, load_bankroll(authority, account)?))
+    } else {
+        None
+    };
+
+    // Derived exactly as an off-chain quote derives them
+    let (inputs, settled) = plan_inputs(
+        round_data.id,
+        &params,
+        ix_data,
+        PlanAccounts {
+            miner,
+            usd_budget_lamports,
+            round_deployed: exposure_account.map_or(0, |(_, deployed)| deployed),
+            user_stats: user_stats.as_mut().map(|(_, stats)| stats),
+            bankroll: bankroll.as_mut().map(|(_, bankroll)| bankroll),
+        },
+    )?;
+    let settle_event = settled.map(|settled| SettleEvent {
+        discriminator: EventDiscriminator::Settle as u8,
+        _padding: [0; 7],
+        authority: *authority.key(),
+        round_id: settled.round_id,
+        deployed: settled.deployed,
+        won: settled.won,
+        modeled_ev: settled.modeled_ev,
+    });
+    if let Some(event) = &settle_event {
+        emit(event);
     }
 
-    log!("Deploying to {} blocks with optimal sizing:", num_selected);
+    // A captured or re-broadcast deploy carries a nonce that's already used
+    let nonce_account = if ix_data.flags & FLAG_NONCE != 0 {
+        let Some(account) = remaining.next() else {
+            log!("Error: nonce check requires the DeployNonce account");
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+        let nonce = load_or_init_deploy_nonce(payer, authority, account)?;
+        if nonce.nonce != ix_data.expected_nonce {
+            log!(
+                "Error: expected nonce {}, deploy carries {}",
+                nonce.nonce,
+                ix_data.expected_nonce
+            );
+            return Err(MyProgramError::NonceMismatch.into());
+        }
+        Some(account)
+    } else {
+        None
+    };
 
-    // Execute deployments
-    for i in 0..num_selected as usize {
-        let block_size = round_data.deployed[indices[i] as usize];
+    let record_account = if ix_data.flags & FLAG_ROUND_RECORD != 0 {
+        let Some(account) = remaining.next() else {
+            log!("Error: round record requires the RoundRecord account");
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+        let leaderboard = ix_data.flags & FLAG_LEADERBOARD != 0;
+        load_or_init_round_record(payer, authority, account, round_data.id, leaderboard)?;
+        Some(account)
+    } else {
+        None
+    };
 
-        // Calculate EV percentage
-        let ev_bps = (evs[i] * 10_000) / amounts[i] as i64;
-        let is_positive = ev_bps >= 0;
-        let abs_ev_bps = ev_bps.abs() as u64;
+    let plan_account = if ix_data.flags & FLAG_STORE_PLAN != 0 {
+        let Some(account) = remaining.next() else {
+            log!("Error: storing the plan requires the Plan account");
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+        Some(account)
+    } else {
+        None
+    };
 
-        log!(
-            "  Block #{} (size: {} mSOL):",
-            indices[i],
-            block_size / 1_000_000
+    let mut plan = plan_deploy(
+        &round_data,
+        &config,
+        ix_data,
+        rank_thresholds,
+        quote,
+        &inputs,
+    )?;
+    telemetry!("plan");
+
+    // A CPI that runs out of compute fails the whole transaction, so only
+    // plan as many deploys as the remaining compute units cover
+    let fits = remaining_compute_units().saturating_sub(POST_DEPLOY_COMPUTE_UNITS)
+        / DEPLOY_CPI_COMPUTE_UNITS;
+    if fits == 0 {
+        log!("Error: not enough compute units left for a deploy CPI");
+        return Err(MyProgramError::InsufficientComputeUnits.into());
+    }
+    if (fits as usize) < plan.count as usize {
+        info!(
+            "Plan truncated from {} to {} blocks to fit remaining compute units",
+            plan.count, fits
         );
+        plan.truncate(fits as usize);
+    }
+    let Allocation {
+        count: num_selected,
+        amounts,
+        indices,
+        evs,
+        portfolio_ev,
+        portfolio_std_dev,
+    } = plan;
+
+    let planned: u64 = amounts[..num_selected as usize].iter().sum();
+    if signer.lamports() < planned {
         log!(
-            "    → Deploying {} mSOL (EV: {}{}.{}%)",
-            amounts[i] / 1_000_000,
-            if is_positive { "+" } else { "-" },
-            abs_ev_bps / 100,
-            abs_ev_bps % 100
+            "Error: signer holds {} lamports, plan deploys {}",
+            signer.lamports(),
+            planned
         );
+        return Err(MyProgramError::InsufficientBalance.into());
+    }
 
+    #[cfg(not(feature = "structured-logs"))]
+    {
+        info!("Deploying to {} blocks with optimal sizing:", num_selected);
+        info!(
+            "Portfolio EV: {} lamports (σ {} lamports)",
+            portfolio_ev, portfolio_std_dev
+        );
+    }
+
+    // Execute deployments. Nothing is logged per block: the DeployEvent and
+    // the return data carry each block's square, amount and EV, so the loop
+    // spends its compute units on the CPIs alone
+    for i in 0..num_selected as usize {
         let mask = 1u32 << indices[i];
-        execute_deploy(
-            ore_program,
-            signer,
-            authority,
-            automation,
-            board,
-            miner,
-            round,
-            system_program,
-            entropy_var,
-            entropy_program,
-            amounts[i],
-            mask,
-        )?;
+        target.deploy(amounts[i], mask, signers).map_err(|e| {
+            log!(
+                "Error: deploy CPI failed at block {} (square mask {}, {} lamports): error {}",
+                i,
+                mask,
+                amounts[i],
+                u64::from(e)
+            );
+            ProgramError::from(MyProgramError::DeployCpiFailed)
+        })?;
+        telemetry!("deploy", i);
     }
 
     let total_deployed: u64 = amounts[..num_selected as usize].iter().sum();
-    log!(
+
+    if let Some((account, _)) = exposure_account {
+        add_round_exposure(account, total_deployed)?;
+    }
+    if let Some((account, stats)) = user_stats {
+        record_user_stats(
+            account,
+            &stats,
+            round_data.id,
+            total_deployed,
+            portfolio_ev,
+            portfolio_std_dev,
+        )?;
+    }
+    if let Some((account, bankroll)) = bankroll {
+        record_bankroll(account, &bankroll, total_deployed)?;
+    }
+    if let Some(account) = nonce_account {
+        advance_deploy_nonce(account)?;
+    }
+    if let Some(account) = record_account {
+        record_round_plan(
+            account,
+            &amounts,
+            &evs,
+            &indices,
+            num_selected as usize,
+            portfolio_ev,
+            portfolio_std_dev,
+        )?;
+    }
+    if let Some(account) = plan_account {
+        let stored = Plan::new(
+            authority.key(),
+            round_data.id,
+            slot,
+            target.round_end_slot()?,
+            ix_data.flags,
+            &plan,
+        );
+        write_plan(payer, account, &stored)?;
+    }
+    // Event authority and this program come last, after every optional account
+    #[cfg(feature = "event-cpi")]
+    let event_accounts = EventAccounts::next(&mut remaining)?;
+    #[cfg(feature = "structured-logs")]
+    info!(
+        "deployed round={} total={} blocks={} ev={} std={}",
+        round_data.id, total_deployed, num_selected, portfolio_ev, portfolio_std_dev
+    );
+
+    #[cfg(not(feature = "structured-logs"))]
+    info!(
         "✓ Total deployed: {} SOL across {} blocks",
         total_deployed / 1_000_000_000,
         num_selected
     );
 
-    Ok(())
+    let deploy_event = DeployEvent {
+        discriminator: EventDiscriminator::Deploy as u8,
+        num_blocks: num_selected,
+        _padding: [0; 6],
+        authority: *authority.key(),
+        round_id: round_data.id,
+        total_deployed,
+        portfolio_ev,
+        portfolio_std_dev,
+        amounts,
+        indices,
+        _padding2: [0; 7],
+    };
+    emit(&deploy_event);
+    #[cfg(feature = "event-cpi")]
+    {
+        if let Some(event) = &settle_event {
+            event_accounts.emit(event)?;
+        }
+        event_accounts.emit(&deploy_event)?;
+    }
+
+    set_return_data(bytemuck::bytes_of(&OreDeployResult {
+        total_deployed,
+        portfolio_ev,
+        amounts,
+        evs,
+        indices,
+        num_blocks: num_selected,
+        _padding: [0; 6],
+    }));
+    telemetry!("done");
+
+    Ok(total_deployed)
 }
 
-/// Calculate optimal deployment amounts for smallest blocks
-/// Returns (num_blocks, amounts[], block_indices[], evs[])
-fn calculate_optimal_deployments(
-    round: &OreRound,
-    total_budget: u64,
-    max_blocks: u8,
-    ore_price_lamports: u64,
-    min_ev_threshold_bps: i16,
-) -> Result<(u8, [u64; 5], [u8; 5], [i64; 5]), ProgramError> {
-    // Calculate ORE value (includes motherlode, after refining fee)
-    let ore_value = {
-        let base = (ore_price_lamports * 9) / 10; // 10% refining fee
-        let motherlode_ev = (round.motherlode * 9) / 6250; // (motherlode/625) * 0.9
-        base + motherlode_ev
-    };
+/// Inputs to a plan that `plan_inputs` derives from a deploy's accounts:
+/// the budget after the USD conversion and the round and bankroll caps, the
+/// miner's positions in the round, and the drawdown and auto-threshold
+/// adjustments
+#[derive(Clone, Copy, Debug)]
+pub struct PlanInputs {
+    /// Lamports the plan may deploy
+    pub total_amount: u64,
 
-    // Sort blocks by size (smallest first)
-    let mut blocks: [(u8, u64); 25] = [(0, 0); 25];
-    for i in 0..25 {
-        blocks[i] = (i as u8, round.deployed[i]);
-    }
+    /// The authority's existing positions per square this round
+    pub held: [u64; BOARD_SQUARES],
 
-    // Bubble sort ascending
-    for i in 0..24 {
-        for j in 0..(24 - i) {
-            if blocks[j].1 > blocks[j + 1].1 {
-                blocks.swap(j, j + 1);
-            }
+    /// Kelly fraction applied to sizing, in bps (10000 = full Kelly)
+    pub kelly_fraction_bps: u16,
+
+    /// Added to every bps EV threshold
+    pub threshold_adjust_bps: i32,
+}
+
+impl PlanInputs {
+    /// Inputs of a deploy that reads no accounts beyond the round: the
+    /// budget as given, no held positions and no adjustments
+    pub fn new(total_amount: u64) -> Self {
+        PlanInputs {
+            total_amount,
+            held: [0; BOARD_SQUARES],
+            kelly_fraction_bps: 10_000,
+            threshold_adjust_bps: 0,
         }
     }
+}
 
-    // Step 1: Calculate optimal deployment for each of the smallest blocks
-    let mut optimal_amounts: [u64; 5] = [0; 5];
-    let mut total_optimal = 0u64;
+/// The accounts a deploy derives its plan inputs from, as it decoded
+/// them: UserStats already created and the Bankroll already loaded
+#[derive(Debug, Default)]
+pub struct PlanAccounts<'a> {
+    /// The authority's position, used only for flags in `MINER_FLAGS`
+    pub miner: Option<MinerPosition>,
 
-    for i in 0..max_blocks as usize {
-        let (_, block_size) = blocks[i];
+    /// Lamports `total_amount` converts to with `FLAG_USD_BUDGET`
+    pub usd_budget_lamports: Option<u64>,
 
-        // Calculate Kelly-optimal deployment: y* = √(V × O / C) - O
-        let optimal = calculate_kelly_optimal(block_size, round.total_deployed, ore_value);
+    /// Lamports the authority's RoundExposure has tracked this round
+    pub round_deployed: u64,
 
-        optimal_amounts[i] = optimal;
-        total_optimal = total_optimal.saturating_add(optimal);
-    }
+    /// The authority's UserStats, with `USER_STATS_FLAGS`
+    pub user_stats: Option<&'a mut UserStats>,
 
-    // Step 2: Scale to fit within budget (if needed)
-    let scale_factor = if total_optimal > total_budget && total_optimal > 0 {
-        (total_budget * 1_000_000_000) / total_optimal
+    /// The authority's Bankroll, with `FLAG_BANKROLL_SIZING`
+    pub bankroll: Option<&'a mut Bankroll>,
+}
+
+/// Derive the inputs a deploy with `ix_data` plans with from its accounts,
+/// syncing UserStats and the Bankroll to the miner's winnings; pure, so
+/// off-chain quotes derive them exactly as `deploy` does. Also returns the
+/// history round the sync credited winnings to, if any
+pub fn plan_inputs(
+    round_id: u64,
+    params: &ConfigParams,
+    ix_data: &OreDeployIxData,
+    accounts: PlanAccounts,
+) -> Result<(PlanInputs, Option<RoundResult>), ProgramError> {
+    let miner = accounts.miner.filter(|_| ix_data.flags & MINER_FLAGS != 0);
+    let held = miner.map_or([0; BOARD_SQUARES], |m| m.deployed_in(round_id));
+    let (lifetime_rewards_sol, checkpoint_id) =
+        miner.map_or((0, 0), |m| (m.lifetime_rewards_sol, m.checkpoint_id));
+
+    let mut total_amount = if ix_data.flags & FLAG_USD_BUDGET != 0 {
+        accounts
+            .usd_budget_lamports
+            .ok_or(ProgramError::NotEnoughAccountKeys)?
     } else {
-        1_000_000_000 // No scaling needed
+        ix_data.total_amount
     };
 
-    // Step 3: Apply scaling and filter by EV threshold
-    let mut count: u8 = 0;
-    let mut amounts: [u64; 5] = [0; 5];
-    let mut indices: [u8; 5] = [255; 5];
-    let mut evs: [i64; 5] = [0; 5];
-
-    for i in 0..max_blocks as usize {
-        if optimal_amounts[i] == 0 {
-            continue;
+    if params.max_round_deploy_lamports != 0 {
+        // The miner's positions include our tracked deploys, so take the larger of the two
+        let already_deployed = if ix_data.flags & FLAG_INCLUDE_HELD_EXPOSURE != 0 {
+            accounts.round_deployed.max(held.iter().sum())
+        } else {
+            accounts.round_deployed
+        };
+        let allowance = params
+            .max_round_deploy_lamports
+            .saturating_sub(already_deployed);
+        if allowance == 0 {
+            log!(
+                "Error: round cap of {} lamports reached",
+                params.max_round_deploy_lamports
+            );
+            return Err(MyProgramError::RoundCapExceeded.into());
         }
+        total_amount = total_amount.min(allowance);
+    }
 
-        let (block_idx, block_size) = blocks[i];
-
-        // Apply scaling
-        let scaled_amount = (optimal_amounts[i] * scale_factor) / 1_000_000_000;
+    let drawdown_sizing = ix_data.flags & FLAG_DRAWDOWN_SIZING != 0;
+    let auto_threshold = ix_data.flags & FLAG_AUTO_EV_THRESHOLD != 0;
+    let mut settled = None;
+    let stats = if ix_data.flags & USER_STATS_FLAGS != 0 {
+        let Some(stats) = accounts.user_stats else {
+            log!("Error: UserStats account required");
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+        settled = stats.sync_winnings(lifetime_rewards_sol, checkpoint_id);
+        Some(&*stats)
+    } else {
+        None
+    };
 
-        if scaled_amount == 0 {
-            continue;
+    // Sizing is cut while in drawdown
+    let kelly_fraction_bps = match stats {
+        Some(stats) if drawdown_sizing => {
+            if ix_data.bankroll_lamports == 0 {
+                log!("Error: drawdown sizing requires bankroll_lamports");
+                return Err(MyProgramError::MissingBankrollLamports.into());
+            }
+            let fraction = kelly_fraction_bps(stats, ix_data.bankroll_lamports, params);
+            info!(
+                "PnL: {} lamports (peak {}), Kelly fraction {} bps",
+                stats.pnl(),
+                stats.peak_pnl,
+                fraction
+            );
+            fraction
         }
+        _ => 10_000,
+    };
 
-        // Calculate EV with final amount
-        let ev = calculate_ev(block_size, scaled_amount, round.total_deployed, ore_value);
-
-        // Check EV threshold
-        let min_ev_lamports = (scaled_amount as i64 * min_ev_threshold_bps as i64) / 10_000;
+    // The EV threshold tightens while realized returns trail the model
+    let threshold_adjust_bps = match stats {
+        Some(stats) if auto_threshold => {
+            if ix_data.flags & FLAG_ABSOLUTE_EV_THRESHOLD != 0 {
+                log!("Error: auto threshold requires bps thresholds");
+                return Err(MyProgramError::AutoThresholdRequiresBps.into());
+            }
+            let adjust = threshold_adjustment_bps(stats, params);
+            info!("Auto threshold: +{} bps", adjust);
+            adjust
+        }
+        _ => 0,
+    };
 
-        if ev >= min_ev_lamports {
-            amounts[count as usize] = scaled_amount;
-            indices[count as usize] = block_idx;
-            evs[count as usize] = ev;
-            count += 1;
-        } else {
-            // Smallest blocks have best EV, so if one fails threshold, stop
-            break;
+    if ix_data.flags & FLAG_BANKROLL_SIZING != 0 {
+        if ix_data.bankroll_share_bps > 10_000 {
+            log!("Error: bankroll_share_bps must be at most 10000");
+            return Err(MyProgramError::InvalidBankrollShareBps.into());
+        }
+        let Some(bankroll) = accounts.bankroll else {
+            log!("Error: bankroll sizing requires the Bankroll account");
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+        bankroll.sync_winnings(lifetime_rewards_sol);
+        let budget = bankroll.round_budget(ix_data.bankroll_share_bps);
+        info!(
+            "Bankroll: {} lamports, round budget {} lamports",
+            bankroll.capital, budget
+        );
+        if budget == 0 {
+            log!("Error: bankroll has no capital left to deploy");
+            return Err(MyProgramError::BankrollExhausted.into());
         }
+        total_amount = total_amount.min(budget);
     }
 
-    Ok((count, amounts, indices, evs))
+    Ok((
+        PlanInputs {
+            total_amount,
+            held,
+            kelly_fraction_bps,
+            threshold_adjust_bps,
+        },
+        settled,
+    ))
 }
 
-/// Calculate Kelly-optimal deployment for a single block
-/// Formula: y* = √(V × O / C) - O
-/// With iterative refinement to account for pot impact
-fn calculate_kelly_optimal(block_size: u64, total_pool: u64, ore_value: u64) -> u64 {
-    const C_SCALED: u64 = 24_252_500_000; // C = 24.2525 * 1e9
+/// Whether a deploy with `ix_data` goes ahead in `round`: `false` (deploy
+/// nothing) on a pool below `min_total_deployed` with
+/// `FLAG_NOOP_ON_SMALL_POOL`, an error without it or past the quoted
+/// `max_total_deployed`
+pub fn check_round_pool(
+    round: &OreRound,
+    params: &ConfigParams,
+    ix_data: &OreDeployIxData,
+) -> Result<bool, ProgramError> {
+    if round.total_deployed < params.min_total_deployed {
+        log!(
+            "Round pool {} below minimum {} lamports",
+            round.total_deployed,
+            params.min_total_deployed
+        );
+        if ix_data.flags & FLAG_NOOP_ON_SMALL_POOL != 0 {
+            return Ok(false);
+        }
+        return Err(MyProgramError::PoolTooSmall.into());
+    }
 
-    if block_size == 0 || total_pool <= block_size {
-        return 0;
+    // A plan quoted against a smaller pool may have lost its edge
+    if ix_data.max_total_deployed != 0 && round.total_deployed > ix_data.max_total_deployed {
+        log!(
+            "Error: round pool {} grew past the quoted maximum {} lamports",
+            round.total_deployed,
+            ix_data.max_total_deployed
+        );
+        return Err(MyProgramError::PoolSlippageExceeded.into());
     }
 
-    // Initial pot value if this block wins
-    let losing_pool = total_pool.saturating_sub(block_size);
-    let winnings = (losing_pool * 9000) / 10_000; // After protocol fee
-    let v = winnings.saturating_add(ore_value);
+    Ok(true)
+}
 
-    if v == 0 {
-        return 0;
+/// Size the plan a deploy with `ix_data` executes in `round_data`, before it
+/// is truncated to the compute units left; pure, so off-chain quotes run the
+/// exact code the program does
+pub fn plan_deploy(
+    round_data: &OreRound,
+    config: &GlobalConfig,
+    ix_data: &OreDeployIxData,
+    rank_thresholds: &[i32],
+    quote: Option<&BlockQuote>,
+    inputs: &PlanInputs,
+) -> Result<Allocation, ProgramError> {
+    let mut params = config.params;
+    if ix_data.flags & FLAG_DERIVED_KELLY_C != 0 {
+        params.kelly_c_scaled = derive_kelly_c_scaled(BOARD_SQUARES as u64);
+    }
+    let mut total_amount = inputs.total_amount;
+    let held = inputs.held;
+    let include_held = ix_data.flags & FLAG_INCLUDE_HELD_EXPOSURE != 0;
+    let kelly_fraction_bps = inputs.kelly_fraction_bps;
+
+    let min_ev_threshold = ix_data
+        .min_ev_threshold
+        .saturating_add(inputs.threshold_adjust_bps);
+    let mut adjusted_thresholds = [0i32; MAX_PLAN_BLOCKS];
+    for (adjusted, threshold) in adjusted_thresholds.iter_mut().zip(rank_thresholds) {
+        *adjusted = threshold.saturating_add(inputs.threshold_adjust_bps);
     }
+    let rank_thresholds = &adjusted_thresholds[..rank_thresholds.len()];
 
-    // Calculate y* = √(V × O / C) - O
-    let mut y_star = {
-        let product = v.saturating_mul(block_size);
-        let scaled = product.saturating_mul(1_000_000_000) / C_SCALED;
-        isqrt(scaled).saturating_sub(block_size)
+    // Validate inputs
+    if ix_data.num_blocks == 0 || ix_data.num_blocks as usize > MAX_PLAN_BLOCKS {
+        log!(
+            "Error: num_blocks must be between 1 and {}",
+            MAX_PLAN_BLOCKS
+        );
+        return Err(MyProgramError::InvalidNumBlocks.into());
+    }
+
+    // One CPI per block, so the CPI limit caps how many blocks get budget
+    let max_cpis = match ix_data.max_cpis {
+        0 => DEFAULT_MAX_DEPLOY_CPIS,
+        n => n,
     };
+    let max_blocks = ix_data.num_blocks.min(max_cpis);
 
-    // Iterative refinement (accounts for deployment reducing pot)
-    for _ in 0..5 {
-        if y_star == 0 {
-            break;
-        }
+    let objective = Objective::try_from(ix_data.objective).map_err(math_error)?;
+    let selection = Selection::try_from(ix_data.selection).map_err(math_error)?;
 
-        // Recalculate V with your deployment factored in
-        let adjusted_pool = losing_pool.saturating_sub(y_star);
-        let adjusted_winnings = (adjusted_pool * 9000) / 10_000;
-        let new_v = adjusted_winnings.saturating_add(ore_value);
+    if objective == Objective::MeanVariance && ix_data.max_std_dev_bps == 0 {
+        log!("Error: mean-variance objective requires max_std_dev_bps");
+        return Err(MyProgramError::MissingStdDevCap.into());
+    }
 
-        if new_v == 0 {
-            return 0;
+    let motherlode_weight_bps = if objective == Objective::MotherlodeHunt {
+        if ix_data.motherlode_weight_bps < 10_000 {
+            log!("Error: motherlode_weight_bps must be at least 10000");
+            return Err(MyProgramError::InvalidMotherlodeWeight.into());
         }
+        if round_data.motherlode < ix_data.min_motherlode {
+            log!(
+                "Motherlode {} below hunt threshold {}",
+                round_data.motherlode,
+                ix_data.min_motherlode
+            );
+            return Err(MyProgramError::MotherlodeTooSmall.into());
+        }
+        ix_data.motherlode_weight_bps
+    } else {
+        10_000
+    };
 
-        // Recalculate y*
-        let product = new_v.saturating_mul(block_size);
-        let scaled = product.saturating_mul(1_000_000_000) / C_SCALED;
-        let new_y_star = isqrt(scaled).saturating_sub(block_size);
-
-        // Check convergence (within 100 lamports)
-        let diff = if new_y_star > y_star {
-            new_y_star - y_star
-        } else {
-            y_star - new_y_star
-        };
+    // Objectives that ignore Kelly sizing take the drawdown cut on the budget
+    if matches!(objective, Objective::MaxEv | Objective::MotherlodeHunt) {
+        total_amount = apply_fraction(total_amount, kelly_fraction_bps);
+    }
 
-        if diff < 100 {
-            y_star = new_y_star;
-            break;
+    let allowed_squares = match ix_data.allowed_squares {
+        0 => ALL_SQUARES,
+        mask => mask & ALL_SQUARES,
+    };
+    let mut eligible_squares = allowed_squares & !ix_data.excluded_squares;
+    if ix_data.flags & FLAG_SKIP_HELD_SQUARES != 0 {
+        for (i, deployed) in held.iter().enumerate() {
+            if *deployed > 0 {
+                eligible_squares &= !(1 << i);
+            }
         }
+    }
+    if let Some(quote) = quote {
+        let slipped = quote.slipped_squares(&round_data.deployed) & eligible_squares;
+        if slipped != 0 {
+            if ix_data.flags & FLAG_DROP_SLIPPED_BLOCKS == 0 {
+                log!(
+                    "Error: quoted squares {} grew past their slippage bound",
+                    slipped
+                );
+                return Err(MyProgramError::BlockSlippageExceeded.into());
+            }
+            info!(
+                "Dropping quoted squares {} past their slippage bound",
+                slipped
+            );
+            eligible_squares &= !slipped;
+        }
+    }
+    if eligible_squares == 0 {
+        log!("Error: square masks exclude every square");
+        return Err(MyProgramError::NoEligibleSquares.into());
+    }
 
-        y_star = new_y_star;
+    if ix_data.max_block_share_bps > 10_000 {
+        log!("Error: max_block_share_bps must be at most 10000");
+        return Err(MyProgramError::InvalidBlockShareBps.into());
     }
 
-    y_star
-}
+    if ix_data.ore_price_lamports == 0 {
+        log!("Error: ore_price_lamports required");
+        return Err(MyProgramError::MissingOrePrice.into());
+    }
 
-/// Integer square root (Newton's method)
-#[inline(always)]
-fn isqrt(n: u64) -> u64 {
-    if n == 0 {
-        return 0;
+    if ix_data.ore_price_lamports < params.min_ore_price_lamports
+        || ix_data.ore_price_lamports > params.max_ore_price_lamports
+    {
+        log!(
+            "Error: ORE price {} outside [{}, {}] lamports",
+            ix_data.ore_price_lamports,
+            params.min_ore_price_lamports,
+            params.max_ore_price_lamports
+        );
+        return Err(MyProgramError::OrePriceOutOfBounds.into());
     }
-    if n <= 3 {
-        return 1;
+
+    // A bps threshold below -100% would accept losing more than the stake;
+    // checked after the auto-threshold adjustment, on what ranking uses
+    if ix_data.flags & FLAG_ABSOLUTE_EV_THRESHOLD == 0
+        && core::iter::once(&min_ev_threshold)
+            .chain(rank_thresholds)
+            .any(|threshold| *threshold < -10_000)
+    {
+        log!("Error: bps EV thresholds must be at least -10000");
+        return Err(MyProgramError::InvalidEvThreshold.into());
     }
 
-    let mut x = n >> 1;
-    let mut y = (x + n / x) >> 1;
+    let absolute_threshold = ix_data.flags & FLAG_ABSOLUTE_EV_THRESHOLD != 0;
+    let kelly_c_scaled = all_in_kelly_c_scaled(&params);
 
-    for _ in 0..6 {
-        if y >= x {
-            return x;
+    #[cfg(feature = "structured-logs")]
+    info!(
+        "plan round={} budget={} ore_price={} threshold={} absolute={} blocks={} kelly_c={}",
+        round_data.id,
+        total_amount,
+        ix_data.ore_price_lamports,
+        min_ev_threshold,
+        absolute_threshold as u8,
+        max_blocks,
+        kelly_c_scaled
+    );
+
+    #[cfg(not(feature = "structured-logs"))]
+    {
+        info!("═══ ORE OPTIMAL DEPLOYMENT ═══");
+        info!(
+            "Total budget: {}.{} SOL",
+            total_amount / 1_000_000_000,
+            (total_amount % 1_000_000_000) / 1_000_000
+        );
+        info!(
+            "ORE price: {}.{} SOL",
+            ix_data.ore_price_lamports / 1_000_000_000,
+            (ix_data.ore_price_lamports % 1_000_000_000) / 1_000_000
+        );
+        if absolute_threshold {
+            info!("EV floor: {} lamports per block", min_ev_threshold);
+        } else {
+            info!(
+                "EV threshold: {} bps ({}.{}%)",
+                min_ev_threshold,
+                min_ev_threshold / 100,
+                min_ev_threshold.unsigned_abs() % 100
+            );
+        }
+        info!("Target blocks: up to {}", max_blocks);
+        if max_blocks < ix_data.num_blocks {
+            info!(
+                "num_blocks {} capped to {} deploy CPIs",
+                ix_data.num_blocks, max_cpis
+            );
+        }
+        if ix_data.max_block_share_bps != 0 {
+            info!("Max block share: {} bps", ix_data.max_block_share_bps);
+        }
+        if ix_data.crowd_penalty_bps != 0 {
+            info!("Crowd penalty: {} bps per miner", ix_data.crowd_penalty_bps);
         }
-        x = y;
-        y = (x + n / x) >> 1;
+        info!(
+            "Kelly C: {}.{} (with fees)",
+            kelly_c_scaled / 1_000_000_000,
+            (kelly_c_scaled % 1_000_000_000) / 100_000
+        );
     }
 
-    x
-}
-
-/// Calculate expected value for a deployment
-fn calculate_ev(block_size: u64, deploy_amount: u64, total_pool: u64, ore_value: u64) -> i64 {
-    if deploy_amount == 0 || block_size == 0 {
-        return i64::MIN;
-    }
+    let allocation_params = AllocationParams {
+        round: round_data,
+        config: &params,
+        total_budget: total_amount,
+        max_blocks,
+        ore_price_lamports: ix_data.ore_price_lamports,
+        min_ev_threshold,
+        rank_thresholds,
+        absolute_threshold,
+        max_block_share_bps: ix_data.max_block_share_bps,
+        objective,
+        selection,
+        max_std_dev_bps: ix_data.max_std_dev_bps,
+        portfolio: ix_data.flags & FLAG_PORTFOLIO_EV != 0,
+        eligible_squares,
+        crowd_penalty_bps: ix_data.crowd_penalty_bps,
+        held: if include_held {
+            held
+        } else {
+            [0; BOARD_SQUARES]
+        },
+        top_miner: ix_data.flags & FLAG_TOP_MINER_EV != 0,
+        motherlode_weight_bps,
+        kelly_fraction_bps,
+        payout_multipliers_bps: config.payout_multipliers(),
+    };
 
-    let total_block = block_size.saturating_add(deploy_amount);
-    if total_block == 0 {
-        return i64::MIN;
+    if ix_data.flags & FLAG_LOG_EV_SURFACE != 0 {
+        log_ev_surface(&allocation_params)?;
     }
 
-    // Your share (in basis points)
-    let share_bps = (deploy_amount * 10_000) / total_block;
+    // Calculate optimal deployment under the requested objective
+    let plan = calculate_optimal_deployments(&allocation_params).map_err(math_error)?;
 
-    // Pot value if you win
-    let losing_pool = total_pool.saturating_sub(block_size);
-    let winnings = (losing_pool * 9000) / 10_000; // Protocol fee
-    let pot = winnings.saturating_add(ore_value);
+    if plan.count == 0 {
+        let unit = if absolute_threshold {
+            "lamports"
+        } else {
+            "bps"
+        };
+        log!(
+            "✗ No blocks meet EV threshold of {} {}",
+            min_ev_threshold,
+            unit
+        );
+        return Err(MyProgramError::NoPositiveEvBlocks.into());
+    }
 
-    // EV calculation
-    let expected_win = (pot * share_bps) / (25 * 10_000);
-    let expected_loss = (deploy_amount * 24) / 25;
-    let admin_fee = (deploy_amount * 101) / 10_000;
+    Ok(plan)
+}
 
-    (expected_win as i64)
-        .saturating_sub(expected_loss as i64)
-        .saturating_sub(admin_fee as i64)
+/// Log one line per square: whether the plan may use it, its pool, and its
+/// Kelly size, EV and marginal EV
+fn log_ev_surface(p: &AllocationParams) -> Result<(), ProgramError> {
+    let surface = ev_surface(p).map_err(math_error)?;
+    for (i, square) in surface.iter().enumerate() {
+        log!(
+            "square {} eligible={} deployed={} kelly={} ev={} marginal_bps={}",
+            i,
+            (p.eligible_squares >> i) & 1,
+            square.deployed,
+            square.kelly,
+            square.ev,
+            square.marginal_ev_bps
+        );
+    }
+    Ok(())
 }
 
-fn execute_deploy(
-    ore_program: &AccountInfo,
-    signer: &AccountInfo,
-    authority: &AccountInfo,
-    automation: &AccountInfo,
-    board: &AccountInfo,
-    miner: &AccountInfo,
-    round: &AccountInfo,
-    system_program: &AccountInfo,
-    entropy_var: &AccountInfo,
-    entropy_program: &AccountInfo,
-    sol_amount: u64,
-    squares: u32,
-) -> ProgramResult {
-    let mut instruction_data = [0u8; 13];
-    instruction_data[0..1].copy_from_slice(&ORE_DEPLOY_IX_DISCRIMINATOR.to_le_bytes());
-    instruction_data[1..9].copy_from_slice(&sol_amount.to_le_bytes());
-    instruction_data[9..13].copy_from_slice(&squares.to_le_bytes());
-
-    let account_metas: [AccountMeta; 9] = [
-        AccountMeta::writable_signer(signer.key()),
-        AccountMeta::writable_signer(authority.key()),
-        AccountMeta::writable(automation.key()),
-        AccountMeta::writable(board.key()),
-        AccountMeta::writable(miner.key()),
-        AccountMeta::writable(round.key()),
-        AccountMeta::readonly(system_program.key()),
-        AccountMeta::writable(entropy_var.key()),
-        AccountMeta::readonly(entropy_program.key()),
-    ];
-
-    let instruction = Instruction {
-        program_id: ore_program.key(),
-        accounts: &account_metas,
-        data: &instruction_data,
+/// Convert a USD-cent budget to lamports using a fresh Pyth SOL/USD price
+fn usd_budget_to_lamports(oracle: &AccountInfo, cents: u64) -> Result<u64, ProgramError> {
+    if !oracle.is_owned_by(&PYTH_RECEIVER_PROGRAM_ID) {
+        return Err(MyProgramError::InvalidOracle.into());
+    }
+
+    let price = {
+        let data = oracle.try_borrow_data()?;
+        read_pyth_price(&data)?
     };
 
-    let account_refs: [&AccountInfo; 9] = [
-        signer,
-        authority,
-        automation,
-        board,
-        miner,
-        round,
-        system_program,
-        entropy_var,
-        entropy_program,
-    ];
-
-    slice_invoke(&instruction, &account_refs)?;
+    let now = Clock::get()?.unix_timestamp;
+    if now.saturating_sub(price.publish_time) > MAX_PRICE_AGE_SECS {
+        log!("Error: oracle price is stale");
+        return Err(MyProgramError::StaleOracle.into());
+    }
 
-    Ok(())
+    price.usd_cents_to_lamports(cents)
 }
 
-fn read_round_data(round: &AccountInfo) -> Result<OreRound, ProgramError> {
-    let data = round.try_borrow_data()?;
-    let decoded_round = read_ore_round_data(&data)?;
-    Ok(*decoded_round)
+/// Compute units left in the transaction (unbounded off-chain)
+pub(crate) fn remaining_compute_units() -> u64 {
+    #[cfg(target_os = "solana")]
+    unsafe {
+        pinocchio::syscalls::sol_remaining_compute_units()
+    }
+
+    #[cfg(not(target_os = "solana"))]
+    u64::MAX
 }
 
@@ -0,0 +1,517 @@
+use bytemuck::{Pod, Zeroable};
+use pinocchio::{
+    account_info::AccountInfo,
+    cpi::slice_invoke_signed,
+    instruction::{AccountMeta, Instruction, Seed, Signer},
+    program_error::ProgramError,
+    pubkey::find_program_address,
+    sysvars::{rent::Rent, Sysvar},
+    ProgramResult,
+};
+use pinocchio_log::log;
+use pinocchio_system::instructions::Transfer;
+
+use crate::{
+    error::MyProgramError,
+    events::{emit, ClaimEvent, EventDiscriminator},
+    instruction::{
+        discriminator,
+        ore_deploy::{deploy, OreDeployIxData, FLAG_USD_BUDGET},
+    },
+    state::{
+        check_ore_account, check_ore_pda, create_pda_account, load_acc_mut,
+        load_acc_mut_unchecked, read_global_config, read_ore_miner_data, read_schedule,
+        upgrade_schedule,
+        utils::{parse_ix_data, DataLen, Versioned},
+        AccountDiscriminator, OreAccount, OreMiner, Schedule, NO_ROUND, SCHEDULE_SEED,
+        SCHEDULE_VAULT_SEED, TOKEN_PROGRAM_ID,
+    },
+    target::{claim_ore, claim_sol, read_round_data, OreClaimAccounts, OreTarget},
+};
+
+pub const CREATE_SCHEDULE_IX_DISCRIMINATOR: u8 = discriminator::CREATE_SCHEDULE;
+pub const EXECUTE_SCHEDULE_IX_DISCRIMINATOR: u8 = discriminator::EXECUTE_SCHEDULE;
+pub const CLOSE_SCHEDULE_IX_DISCRIMINATOR: u8 = discriminator::CLOSE_SCHEDULE;
+pub const CLAIM_SCHEDULE_IX_DISCRIMINATOR: u8 = discriminator::CLAIM_SCHEDULE;
+pub const DEPOSIT_SCHEDULE_IX_DISCRIMINATOR: u8 = discriminator::DEPOSIT_SCHEDULE;
+
+/// Also claim the vault miner's ORE, passing ORE's claim accounts and the
+/// authority's ORE token account
+pub const SCHEDULE_CLAIM_FLAG_ORE: u8 = 1 << 0;
+
+/// SPL Token's Transfer instruction
+const TOKEN_TRANSFER_DISCRIMINATOR: u8 = 3;
+
+#[repr(C)]
+#[derive(Pod, Zeroable, Clone, Copy, Debug)]
+pub struct CreateScheduleIxData {
+    /// Lamports deployed per executed round (at most)
+    pub budget_per_round: u64,
+
+    /// Number of rounds to execute
+    pub rounds: u32,
+
+    /// Padding (4 bytes)
+    pub _padding: [u8; 4],
+
+    /// OreDeploy parameters for each execution; `total_amount` is ignored
+    pub strategy: OreDeployIxData,
+}
+
+impl DataLen for CreateScheduleIxData {
+    const LEN: usize = core::mem::size_of::<CreateScheduleIxData>();
+}
+
+#[repr(C)]
+#[derive(Pod, Zeroable, Clone, Copy, Debug)]
+pub struct ClaimScheduleIxData {
+    /// `SCHEDULE_CLAIM_FLAG_*` bits selecting the rewards claimed besides SOL
+    pub flags: u8,
+}
+
+impl DataLen for ClaimScheduleIxData {
+    const LEN: usize = core::mem::size_of::<ClaimScheduleIxData>();
+}
+
+#[repr(C)]
+#[derive(Pod, Zeroable, Clone, Copy, Debug)]
+pub struct DepositScheduleIxData {
+    /// Rounds added to the schedule, each funded with `budget_per_round`
+    pub rounds: u32,
+}
+
+impl DataLen for DepositScheduleIxData {
+    const LEN: usize = core::mem::size_of::<DepositScheduleIxData>();
+}
+
+/// Create the authority's Schedule PDA and fund its vault with
+/// `budget_per_round × rounds` (plus the vault's rent-exempt minimum)
+pub fn process_create_schedule(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let [authority, schedule, vault, _system_program] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    if !authority.is_signer() {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let ix_data = parse_ix_data::<CreateScheduleIxData>(data)?;
+    if ix_data.budget_per_round == 0 || ix_data.rounds == 0 {
+        log!("Error: schedule requires budget_per_round and rounds");
+        return Err(MyProgramError::InvalidScheduleParams.into());
+    }
+    // Budgets are in lamports; a USD budget would reinterpret them as cents
+    if ix_data.strategy.flags & FLAG_USD_BUDGET != 0 {
+        log!("Error: schedules do not support USD budgets");
+        return Err(MyProgramError::UsdBudgetNotSupported.into());
+    }
+
+    let (expected, bump) = find_program_address(&[SCHEDULE_SEED, authority.key()], &crate::ID);
+    if schedule.key() != &expected {
+        return Err(MyProgramError::PdaMismatch.into());
+    }
+    let (expected_vault, vault_bump) =
+        find_program_address(&[SCHEDULE_VAULT_SEED, schedule.key()], &crate::ID);
+    if vault.key() != &expected_vault {
+        return Err(MyProgramError::PdaMismatch.into());
+    }
+
+    let bump_seed = [bump];
+    let seeds = [
+        Seed::from(SCHEDULE_SEED),
+        Seed::from(authority.key()),
+        Seed::from(&bump_seed),
+    ];
+    create_pda_account(authority, schedule, Schedule::LEN, &seeds)?;
+
+    let mut data = schedule.try_borrow_mut_data()?;
+    *unsafe { load_acc_mut_unchecked::<Schedule>(&mut data)? } = Schedule {
+        discriminator: AccountDiscriminator::Schedule as u8,
+        bump,
+        vault_bump,
+        version: Schedule::VERSION,
+        _padding: [0; 4],
+        authority: *authority.key(),
+        budget_per_round: ix_data.budget_per_round,
+        last_round_id: NO_ROUND,
+        rounds_remaining: ix_data.rounds,
+        _padding2: [0; 4],
+        strategy: ix_data.strategy,
+    };
+
+    let rent = Rent::get()?.minimum_balance(0);
+    let deposit = ix_data
+        .budget_per_round
+        .checked_mul(ix_data.rounds as u64)
+        .and_then(|total| total.checked_add(rent))
+        .ok_or(MyProgramError::WriteOverflow)?;
+    Transfer {
+        from: authority,
+        to: vault,
+        lamports: deposit,
+    }
+    .invoke()?;
+
+    info!(
+        "Schedule created: {} rounds of {} lamports",
+        ix_data.rounds, ix_data.budget_per_round
+    );
+
+    Ok(())
+}
+
+/// Fund `rounds` more rounds of the authority's schedule: the vault is
+/// topped up with `budget_per_round × rounds` and the rounds are added to
+/// `rounds_remaining`
+///
+/// Accounts: `authority, schedule, vault, system_program`.
+pub fn process_deposit_schedule(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let [authority, schedule, vault, _system_program] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    let ix_data = parse_ix_data::<DepositScheduleIxData>(data)?;
+    let mut state = read_authorized_schedule(authority, schedule, vault)?;
+    if ix_data.rounds == 0 {
+        log!("Error: deposit requires rounds");
+        return Err(MyProgramError::InvalidScheduleParams.into());
+    }
+
+    let deposit = state
+        .budget_per_round
+        .checked_mul(ix_data.rounds as u64)
+        .ok_or(MyProgramError::WriteOverflow)?;
+    state.rounds_remaining = state
+        .rounds_remaining
+        .checked_add(ix_data.rounds)
+        .ok_or(MyProgramError::WriteOverflow)?;
+    Transfer {
+        from: authority,
+        to: vault,
+        lamports: deposit,
+    }
+    .invoke()?;
+
+    let mut data = schedule.try_borrow_mut_data()?;
+    *unsafe { load_acc_mut::<Schedule>(&mut data)? } = state;
+
+    info!(
+        "Schedule funded: {} lamports, {} rounds remaining",
+        deposit, state.rounds_remaining
+    );
+
+    Ok(())
+}
+
+/// Permissionless crank: deploy one round of a schedule from its vault
+///
+/// Accounts are the keeper (signer, pays for any PDAs the strategy creates),
+/// the schedule, then the OreDeploy accounts with the vault as both `signer`
+/// and `authority`.
+pub fn process_execute_schedule(accounts: &[AccountInfo], _data: &[u8]) -> ProgramResult {
+    let [keeper, schedule, deploy_accounts @ ..] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    let [ore_program, vault, authority, _, _, _, round, ..] = deploy_accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    if !keeper.is_signer() {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    if schedule.is_owned_by(&crate::ID) {
+        upgrade_schedule(keeper, schedule)?;
+    }
+    let mut state = read_schedule(schedule)?;
+    if vault.key() != &state.vault_address(schedule.key())? || authority.key() != vault.key() {
+        return Err(MyProgramError::PdaMismatch.into());
+    }
+    if state.rounds_remaining == 0 {
+        log!("Error: schedule has no rounds remaining");
+        return Err(MyProgramError::ScheduleComplete.into());
+    }
+
+    let round_id = read_round_data(ore_program, round)?.id;
+    if state.last_round_id == round_id {
+        log!("Error: schedule already executed in round {}", round_id);
+        return Err(MyProgramError::ScheduleAlreadyExecuted.into());
+    }
+
+    // The vault stays rent-exempt until it is closed
+    let available = vault
+        .lamports()
+        .saturating_sub(Rent::get()?.minimum_balance(0));
+    let mut strategy = state.strategy;
+    strategy.total_amount = state.budget_per_round.min(available);
+    if strategy.total_amount == 0 {
+        log!("Error: schedule vault is empty");
+        return Err(MyProgramError::ScheduleComplete.into());
+    }
+
+    let bump_seed = [state.vault_bump];
+    let seeds = [
+        Seed::from(SCHEDULE_VAULT_SEED),
+        Seed::from(schedule.key()),
+        Seed::from(&bump_seed),
+    ];
+    let deployed = deploy::<OreTarget>(
+        deploy_accounts,
+        keeper,
+        &strategy,
+        &[],
+        None,
+        &[Signer::from(&seeds)],
+    )?;
+
+    // A strategy that skips small pools deploys nothing; that round isn't
+    // used up, or any keeper could crank the schedule through its rounds
+    if deployed == 0 {
+        info!("Schedule skipped round {}: nothing deployed", round_id);
+        return Ok(());
+    }
+
+    state.rounds_remaining -= 1;
+    state.last_round_id = round_id;
+    let mut data = schedule.try_borrow_mut_data()?;
+    *unsafe { load_acc_mut::<Schedule>(&mut data)? } = state;
+
+    info!(
+        "Schedule executed: {} lamports, {} rounds remaining",
+        deployed, state.rounds_remaining
+    );
+
+    Ok(())
+}
+
+/// Pay the rewards the vault's ORE miner has been credited out to the
+/// schedule's authority: its SOL, and with `SCHEDULE_CLAIM_FLAG_ORE` its ORE
+///
+/// Accounts: `authority, schedule, vault, system_program, ore_program,
+/// config, miner` (the vault's miner), then with the ORE flag `mint,
+/// vault_tokens, treasury, treasury_tokens, token_program,
+/// associated_token_program, authority_tokens`. Only checkpointed rounds are
+/// credited, so the miner is checkpointed with ORE first. Emits a
+/// `ClaimEvent` when anything was paid out.
+pub fn process_claim_schedule(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let [authority, schedule, vault, system_program, ore_program, config, miner, remaining @ ..] =
+        accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    let ix_data = parse_ix_data::<ClaimScheduleIxData>(data)?;
+    let state = read_authorized_schedule(authority, schedule, vault)?;
+    let Some(miner_data) = read_vault_miner(ore_program, config, miner, vault)? else {
+        info!("Schedule vault has no miner to claim from");
+        return Ok(());
+    };
+
+    let bump_seed = [state.vault_bump];
+    let seeds = [
+        Seed::from(SCHEDULE_VAULT_SEED),
+        Seed::from(schedule.key()),
+        Seed::from(&bump_seed),
+    ];
+    let signers = [Signer::from(&seeds)];
+
+    let (rewards_sol, rewards_ore) = miner_data.unclaimed();
+    let claimed_sol = if rewards_sol > 0 {
+        let before = vault.lamports();
+        claim_sol(ore_program, vault, miner, system_program, &signers)?;
+        let claimed = vault.lamports().saturating_sub(before);
+        Transfer {
+            from: vault,
+            to: authority,
+            lamports: claimed,
+        }
+        .invoke_signed(&signers)?;
+        claimed
+    } else {
+        0
+    };
+
+    let claimed_ore = if ix_data.flags & SCHEDULE_CLAIM_FLAG_ORE != 0 {
+        let [mint, vault_tokens, treasury, treasury_tokens, token_program, associated_token_program, authority_tokens, ..] =
+            remaining
+        else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+        // The vault signs the transfer, so it must reach the real token program
+        if token_program.key() != &TOKEN_PROGRAM_ID {
+            log!("Error: token_program is not the SPL Token program");
+            return Err(MyProgramError::InvalidTokenAccount.into());
+        }
+        if rewards_ore > 0 {
+            let claim_accounts = OreClaimAccounts {
+                mint,
+                recipient: vault_tokens,
+                treasury,
+                treasury_tokens,
+                system_program,
+                token_program,
+                associated_token_program,
+            };
+            claim_ore(ore_program, vault, miner, &claim_accounts, &signers)?;
+        }
+        // Everything in the vault's token account came from its miner
+        let amount = vault_token_balance(vault_tokens, vault)?;
+        if amount > 0 {
+            transfer_tokens(
+                token_program,
+                vault_tokens,
+                authority_tokens,
+                vault,
+                amount,
+                &signers,
+            )?;
+        }
+        amount
+    } else {
+        0
+    };
+
+    info!(
+        "Schedule claimed: {} lamports, {} ORE base units",
+        claimed_sol, claimed_ore
+    );
+    if claimed_sol > 0 || claimed_ore > 0 {
+        emit(&ClaimEvent {
+            discriminator: EventDiscriminator::Claim as u8,
+            _padding: [0; 7],
+            authority: *vault.key(),
+            rewards_sol: claimed_sol,
+            rewards_ore: claimed_ore,
+        });
+    }
+
+    Ok(())
+}
+
+/// Close the schedule, returning the vault's remaining lamports and the
+/// schedule's rent to the authority
+///
+/// Accounts: `authority, schedule, vault, system_program, ore_program,
+/// config, miner` (the vault's miner). The vault is the only account that
+/// can claim from its miner, so closing fails with `ScheduleRewardsUnclaimed`
+/// while the miner has rewards left or a round not yet checkpointed.
+pub fn process_close_schedule(accounts: &[AccountInfo], _data: &[u8]) -> ProgramResult {
+    let [authority, schedule, vault, _system_program, ore_program, config, miner] = accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    let state = read_authorized_schedule(authority, schedule, vault)?;
+    if let Some(miner_data) = read_vault_miner(ore_program, config, miner, vault)? {
+        if miner_data.unclaimed() != (0, 0) || miner_data.checkpoint_id < miner_data.round_id {
+            log!("Error: claim the schedule's rewards before closing it");
+            return Err(MyProgramError::ScheduleRewardsUnclaimed.into());
+        }
+    }
+
+    let bump_seed = [state.vault_bump];
+    let seeds = [
+        Seed::from(SCHEDULE_VAULT_SEED),
+        Seed::from(schedule.key()),
+        Seed::from(&bump_seed),
+    ];
+    Transfer {
+        from: vault,
+        to: authority,
+        lamports: vault.lamports(),
+    }
+    .invoke_signed(&[Signer::from(&seeds)])?;
+
+    *authority.try_borrow_mut_lamports()? += schedule.lamports();
+    *schedule.try_borrow_mut_lamports()? = 0;
+    schedule.close()?;
+
+    info!("Schedule closed");
+
+    Ok(())
+}
+
+/// The schedule, checked to be `authority`'s (who must sign) with `vault`
+/// its vault
+fn read_authorized_schedule(
+    authority: &AccountInfo,
+    schedule: &AccountInfo,
+    vault: &AccountInfo,
+) -> Result<Schedule, ProgramError> {
+    if !authority.is_signer() {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let state = read_schedule(schedule)?;
+    if authority.key() != &state.authority {
+        return Err(MyProgramError::InvalidAuthority.into());
+    }
+    if vault.key() != &state.vault_address(schedule.key())? {
+        return Err(MyProgramError::PdaMismatch.into());
+    }
+    Ok(state)
+}
+
+/// The vault's ORE miner under an allowed ORE program, `None` before its
+/// first deploy
+fn read_vault_miner(
+    ore_program: &AccountInfo,
+    config: &AccountInfo,
+    miner: &AccountInfo,
+    vault: &AccountInfo,
+) -> Result<Option<OreMiner>, ProgramError> {
+    if !read_global_config(config)?.is_allowed_ore_program(ore_program.key()) {
+        log!("Error: ore_program is not an allowed ORE program");
+        return Err(MyProgramError::InvalidOreProgram.into());
+    }
+    check_ore_account(miner, ore_program.key(), OreAccount::Miner)?;
+    check_ore_pda(miner, vault, ore_program.key(), OreAccount::Miner)?;
+
+    if miner.data_len() == 0 {
+        return Ok(None);
+    }
+    let data = miner.try_borrow_data()?;
+    Ok(Some(*read_ore_miner_data(&data)?))
+}
+
+/// ORE held by the vault's token account, zero before ORE creates it
+fn vault_token_balance(account: &AccountInfo, vault: &AccountInfo) -> Result<u64, ProgramError> {
+    if account.data_len() == 0 {
+        return Ok(0);
+    }
+    // SPL Token account layout: mint, owner, then the amount
+    let data = account.try_borrow_data()?;
+    if !account.is_owned_by(&TOKEN_PROGRAM_ID)
+        || data.len() < 72
+        || data[32..64] != vault.key()[..]
+    {
+        log!("Error: vault_tokens is not a token account of the vault");
+        return Err(MyProgramError::InvalidTokenAccount.into());
+    }
+    Ok(u64::from_le_bytes(data[64..72].try_into().unwrap()))
+}
+
+/// SPL Token Transfer of `amount` from `source`, signed by its PDA `owner`
+fn transfer_tokens(
+    token_program: &AccountInfo,
+    source: &AccountInfo,
+    destination: &AccountInfo,
+    owner: &AccountInfo,
+    amount: u64,
+    signers: &[Signer],
+) -> ProgramResult {
+    let mut data = [0u8; 9];
+    data[0] = TOKEN_TRANSFER_DISCRIMINATOR;
+    data[1..9].copy_from_slice(&amount.to_le_bytes());
+    let account_metas = [
+        AccountMeta::writable(source.key()),
+        AccountMeta::writable(destination.key()),
+        AccountMeta::readonly_signer(owner.key()),
+    ];
+    let instruction = Instruction {
+        program_id: token_program.key(),
+        accounts: &account_metas,
+        data: &data,
+    };
+    slice_invoke_signed(&instruction, &[source, destination, owner], signers)
+}
@@ -0,0 +1,67 @@
+use pinocchio::{account_info::AccountInfo, program_error::ProgramError, ProgramResult};
+
+use bytemuck::{Pod, Zeroable};
+use pinocchio_log::log;
+
+use crate::{
+    error::MyProgramError,
+    instruction::discriminator,
+    state::{
+        check_ore_account, check_ore_pda, read_global_config, read_ore_miner_data, set_bankroll,
+        utils::parse_ix_data, DataLen, OreAccount,
+    },
+};
+
+pub const SET_BANKROLL_IX_DISCRIMINATOR: u8 = discriminator::SET_BANKROLL;
+
+#[repr(C)]
+#[derive(Pod, Zeroable, Clone, Copy, Debug)]
+pub struct SetBankrollIxData {
+    /// Lamports committed to deploying across rounds; replaces the current capital
+    pub capital: u64,
+}
+
+impl DataLen for SetBankrollIxData {
+    const LEN: usize = core::mem::size_of::<SetBankrollIxData>();
+}
+
+pub fn process_set_bankroll(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let [authority, ore_program, config, miner, bankroll, _system_program] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    if !authority.is_signer() {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let ix_data = parse_ix_data::<SetBankrollIxData>(data)?;
+
+    // The checkpoint is read from the miner, so it must be the authority's
+    // real one: fake rewards would shift every later winnings sync
+    if !read_global_config(config)?.is_allowed_ore_program(ore_program.key()) {
+        log!("Error: ore_program is not an allowed ORE program");
+        return Err(MyProgramError::InvalidOreProgram.into());
+    }
+    check_ore_account(miner, ore_program.key(), OreAccount::Miner)?;
+    check_ore_pda(miner, authority, ore_program.key(), OreAccount::Miner)?;
+
+    // Winnings already on the miner belong to the old bankroll
+    let lifetime_rewards_sol = if miner.data_len() == 0 {
+        0
+    } else {
+        let data = miner.try_borrow_data()?;
+        read_ore_miner_data(&data)?.lifetime_rewards_sol
+    };
+
+    set_bankroll(
+        authority,
+        authority,
+        bankroll,
+        ix_data.capital,
+        lifetime_rewards_sol,
+    )?;
+
+    info!("Bankroll set to {} lamports", ix_data.capital);
+
+    Ok(())
+}
@@ -0,0 +1,149 @@
+use pinocchio::{account_info::AccountInfo, program_error::ProgramError, ProgramResult};
+
+use bytemuck::{Pod, Zeroable};
+use pinocchio_log::log;
+
+use crate::{
+    error::MyProgramError,
+    events::{emit, EventDiscriminator, SettleEvent},
+    instruction::discriminator,
+    state::{
+        check_ore_account, check_ore_pda, load_bankroll, load_user_stats, read_global_config,
+        read_ore_miner_data, record_bankroll, record_leaderboard, settle_round_record,
+        store_user_stats,
+        utils::{parse_ix_data, DataLen},
+        OreAccount,
+    },
+    target::read_round_data,
+};
+
+pub const SETTLE_ROUND_IX_DISCRIMINATOR: u8 = discriminator::SETTLE_ROUND;
+
+/// Also sync the authority's UserStats PDA from its miner
+pub const SETTLE_FLAG_USER_STATS: u8 = 1 << 0;
+
+/// Also sync the authority's Bankroll PDA from its miner
+pub const SETTLE_FLAG_BANKROLL: u8 = 1 << 1;
+
+#[repr(C)]
+#[derive(Pod, Zeroable, Clone, Copy, Debug)]
+pub struct SettleRoundIxData {
+    /// `SETTLE_FLAG_*` bits selecting the accounts synced alongside the record
+    pub flags: u8,
+}
+
+impl DataLen for SettleRoundIxData {
+    const LEN: usize = core::mem::size_of::<SettleRoundIxData>();
+}
+
+/// Fill in a drawn round's realized result in the authority's RoundRecord,
+/// and optionally sync its UserStats and Bankroll with the SOL its miner has
+/// been credited. Permissionless: everything is read from ORE's accounts, so
+/// any keeper can crank it.
+///
+/// Accounts: `ore_program, config, round, authority, record`, then
+/// `miner, user_stats, bankroll` as selected by the flags (the miner when
+/// either is), then the Leaderboard if the round opted into it.
+pub fn process_settle_round(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let [ore_program, config, round, authority, record, remaining @ ..] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    let ix_data = parse_ix_data::<SettleRoundIxData>(data)?;
+
+    // A fake ORE program could draw any winner it likes
+    if !read_global_config(config)?.is_allowed_ore_program(ore_program.key()) {
+        log!("Error: ore_program is not an allowed ORE program");
+        return Err(MyProgramError::InvalidOreProgram.into());
+    }
+
+    let round_data = read_round_data(ore_program, round)?;
+    if round_data.slot_hash == [0; 32] {
+        log!("Error: round {} has not been drawn yet", round_data.id);
+        return Err(MyProgramError::RoundNotEnded.into());
+    }
+
+    let settled = settle_round_record(authority, record, &round_data)?;
+
+    info!(
+        "Round {} settled: {} lamports deployed, {} won, {} modeled EV",
+        settled.round_id, settled.total_deployed, settled.won, settled.modeled_ev
+    );
+    emit(&SettleEvent {
+        discriminator: EventDiscriminator::Settle as u8,
+        _padding: [0; 7],
+        authority: *authority.key(),
+        round_id: settled.round_id,
+        deployed: settled.total_deployed,
+        won: settled.won,
+        modeled_ev: settled.modeled_ev,
+    });
+
+    let mut remaining = remaining.iter();
+    let sync_stats = ix_data.flags & SETTLE_FLAG_USER_STATS != 0;
+    let sync_bankroll = ix_data.flags & SETTLE_FLAG_BANKROLL != 0;
+    if sync_stats || sync_bankroll {
+        sync_authority_accounts(
+            ore_program,
+            authority,
+            &mut remaining,
+            sync_stats,
+            sync_bankroll,
+        )?;
+    }
+
+    if settled.leaderboard != 0 {
+        let Some(account) = remaining.next() else {
+            log!("Error: round opted into the Leaderboard; pass its account");
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+        record_leaderboard(
+            account,
+            authority.key(),
+            settled.realized_pnl(),
+            settled.total_deployed,
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Sync the authority's UserStats and/or Bankroll with the SOL its miner has
+/// been credited, the same source deploys sync from
+fn sync_authority_accounts<'a>(
+    ore_program: &AccountInfo,
+    authority: &AccountInfo,
+    remaining: &mut impl Iterator<Item = &'a AccountInfo>,
+    sync_stats: bool,
+    sync_bankroll: bool,
+) -> ProgramResult {
+    let Some(miner) = remaining.next() else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    check_ore_account(miner, ore_program.key(), OreAccount::Miner)?;
+    check_ore_pda(miner, authority, ore_program.key(), OreAccount::Miner)?;
+    let (lifetime_rewards_sol, checkpoint_id) = {
+        let data = miner.try_borrow_data()?;
+        let miner_data = read_ore_miner_data(&data)?;
+        (miner_data.lifetime_rewards_sol, miner_data.checkpoint_id)
+    };
+
+    if sync_stats {
+        let Some(account) = remaining.next() else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+        let mut stats = load_user_stats(authority, account)?;
+        stats.sync_winnings(lifetime_rewards_sol, checkpoint_id);
+        store_user_stats(account, &stats)?;
+    }
+    if sync_bankroll {
+        let Some(account) = remaining.next() else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+        let mut bankroll = load_bankroll(authority, account)?;
+        bankroll.sync_winnings(lifetime_rewards_sol);
+        record_bankroll(account, &bankroll, 0)?;
+    }
+
+    Ok(())
+}
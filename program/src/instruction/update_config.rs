@@ -0,0 +1,47 @@
+use pinocchio::{account_info::AccountInfo, program_error::ProgramError, ProgramResult};
+use pinocchio_log::log;
+
+use crate::{
+    error::MyProgramError,
+    instruction::initialize_config::parse_config_ix_data,
+    state::{is_legacy_global_config_len, read_global_config, utils::load_acc_mut, GlobalConfig},
+};
+
+pub fn process_update_config(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let [admin, config] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    if !admin.is_signer() {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let current = read_global_config(config)?;
+    if admin.key() != &current.admin {
+        return Err(MyProgramError::InvalidAdmin.into());
+    }
+
+    let update = parse_config_ix_data(data)?;
+
+    if is_legacy_global_config_len(config.data_len()) {
+        log!("Error: config predates its current layout; migrate it first");
+        return Err(MyProgramError::AccountNeedsMigration.into());
+    }
+
+    let mut data = config.try_borrow_mut_data()?;
+    let global_config = unsafe { load_acc_mut::<GlobalConfig>(&mut data)? };
+    global_config.params = update.params;
+    if let Some(ore_program) = update.ore_program_override {
+        global_config.ore_program_override = ore_program;
+    }
+    if let Some(multipliers) = update.payout_multipliers_bps {
+        global_config.payout_multipliers_bps = multipliers;
+    }
+    if let Some(environments) = update.ore_environments {
+        global_config.ore_environments = environments;
+    }
+
+    info!("GlobalConfig updated");
+
+    Ok(())
+}
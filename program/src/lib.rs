@@ -6,8 +6,15 @@ mod entrypoint;
 #[cfg(feature = "std")]
 extern crate std;
 
+#[macro_use]
+mod macros;
+
+pub mod allocator;
 pub mod error;
+pub mod events;
 pub mod instruction;
+pub mod processor;
 pub mod state;
+pub mod target;
 
 pinocchio_pubkey::declare_id!("ENrRns55VechXJiq4bMbdx7idzQh7tvaEJoYeWxRNe7Y");
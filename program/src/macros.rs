@@ -0,0 +1,33 @@
+/// Informational log, compiled out unless the `verbose-logs` feature is on.
+/// Logs explaining a failure use `log!` directly and are always kept.
+macro_rules! info {
+    ($($arg:tt)*) => {
+        if cfg!(feature = "verbose-logs") {
+            pinocchio_log::log!($($arg)*);
+        }
+    };
+}
+
+/// Log the compute units left at a named phase of an instruction, compiled
+/// out unless the `telemetry` feature is on
+macro_rules! telemetry {
+    ($phase:expr) => {
+        if cfg!(feature = "telemetry") {
+            pinocchio_log::log!(
+                "cu phase={} remaining={}",
+                $phase,
+                crate::instruction::remaining_compute_units()
+            );
+        }
+    };
+    ($phase:expr, $block:expr) => {
+        if cfg!(feature = "telemetry") {
+            pinocchio_log::log!(
+                "cu phase={} block={} remaining={}",
+                $phase,
+                $block,
+                crate::instruction::remaining_compute_units()
+            );
+        }
+    };
+}
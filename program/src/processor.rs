@@ -0,0 +1,58 @@
+use pinocchio::{account_info::AccountInfo, ProgramResult};
+
+use crate::{
+    error::MyProgramError,
+    instruction::{self, MyProgramInstruction},
+};
+
+/// Route instruction data to its instruction by the leading discriminator
+#[inline(always)]
+pub fn process_instruction(accounts: &[AccountInfo], instruction_data: &[u8]) -> ProgramResult {
+    #[cfg(feature = "event-cpi")]
+    if instruction_data.starts_with(&crate::events::cpi::EVENT_IX_TAG_LE) {
+        return crate::events::cpi::process_event(accounts);
+    }
+
+    let (ix_disc, instruction_data) = instruction_data
+        .split_first()
+        .ok_or(MyProgramError::UnknownInstruction)?;
+
+    match MyProgramInstruction::try_from(ix_disc)? {
+        MyProgramInstruction::OreDeploy => {
+            instruction::process_ore_deploy(accounts, instruction_data)
+        }
+        MyProgramInstruction::InitializeConfig => {
+            instruction::process_initialize_config(accounts, instruction_data)
+        }
+        MyProgramInstruction::UpdateConfig => {
+            instruction::process_update_config(accounts, instruction_data)
+        }
+        MyProgramInstruction::SetBankroll => {
+            instruction::process_set_bankroll(accounts, instruction_data)
+        }
+        MyProgramInstruction::CreateSchedule => {
+            instruction::process_create_schedule(accounts, instruction_data)
+        }
+        MyProgramInstruction::ExecuteSchedule => {
+            instruction::process_execute_schedule(accounts, instruction_data)
+        }
+        MyProgramInstruction::CloseSchedule => {
+            instruction::process_close_schedule(accounts, instruction_data)
+        }
+        MyProgramInstruction::SettleRound => {
+            instruction::process_settle_round(accounts, instruction_data)
+        }
+        MyProgramInstruction::InitializeLeaderboard => {
+            instruction::process_initialize_leaderboard(accounts, instruction_data)
+        }
+        MyProgramInstruction::MigrateAccount => {
+            instruction::process_migrate_account(accounts, instruction_data)
+        }
+        MyProgramInstruction::ClaimSchedule => {
+            instruction::process_claim_schedule(accounts, instruction_data)
+        }
+        MyProgramInstruction::DepositSchedule => {
+            instruction::process_deposit_schedule(accounts, instruction_data)
+        }
+    }
+}
@@ -0,0 +1,145 @@
+use bytemuck::{Pod, Zeroable};
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::Seed,
+    program_error::ProgramError,
+    pubkey::{find_program_address, Pubkey},
+    ProgramResult,
+};
+
+use crate::{
+    error::MyProgramError,
+    state::{
+        create_pda_account, load_acc, load_acc_mut, load_acc_mut_unchecked, AccountDiscriminator,
+        DataLen, Initialized, Versioned,
+    },
+};
+
+pub const BANKROLL_SEED: &[u8] = b"bankroll";
+
+/// Capital one authority has committed to deploying, carried across rounds
+#[repr(C)]
+#[derive(Pod, Zeroable, Clone, Copy, Debug, PartialEq)]
+pub struct Bankroll {
+    pub discriminator: u8,
+
+    /// PDA bump for `[BANKROLL_SEED, authority]`
+    pub bump: u8,
+
+    /// Layout version (`Versioned::VERSION`); 0 for accounts created before versioning
+    pub version: u8,
+
+    /// Padding (5 bytes)
+    pub _padding: [u8; 5],
+
+    /// ORE miner authority the bankroll belongs to
+    pub authority: Pubkey,
+
+    /// Lamports available to deploy: committed capital plus SOL won minus
+    /// lamports deployed since
+    pub capital: u64,
+
+    /// Miner `lifetime_rewards_sol` when `capital` was last synced
+    pub rewards_checkpoint: u64,
+}
+
+impl DataLen for Bankroll {
+    const LEN: usize = core::mem::size_of::<Bankroll>();
+}
+
+impl Initialized for Bankroll {
+    fn is_initialized(&self) -> bool {
+        self.discriminator == AccountDiscriminator::Bankroll as u8
+    }
+}
+
+impl Versioned for Bankroll {
+    const VERSION: u8 = 1;
+
+    fn version_mut(&mut self) -> &mut u8 {
+        &mut self.version
+    }
+}
+
+impl Bankroll {
+    /// Credit SOL the miner has won since the last sync
+    pub fn sync_winnings(&mut self, lifetime_rewards_sol: u64) {
+        let won = lifetime_rewards_sol.saturating_sub(self.rewards_checkpoint);
+        self.capital = self.capital.saturating_add(won);
+        self.rewards_checkpoint = lifetime_rewards_sol;
+    }
+
+    /// Most this bankroll allows in one round: `share_bps` of the capital
+    /// (0 = all of it)
+    pub fn round_budget(&self, share_bps: u16) -> u64 {
+        match share_bps {
+            0 => self.capital,
+            bps => (self.capital as u128 * bps as u128 / 10_000) as u64,
+        }
+    }
+}
+
+/// Set the committed capital in the Bankroll PDA for `authority`, creating
+/// it on first use; winnings are counted from the miner's current
+/// `lifetime_rewards_sol`
+pub fn set_bankroll(
+    payer: &AccountInfo,
+    authority: &AccountInfo,
+    account: &AccountInfo,
+    capital: u64,
+    lifetime_rewards_sol: u64,
+) -> ProgramResult {
+    let (expected, bump) = find_program_address(&[BANKROLL_SEED, authority.key()], &crate::ID);
+    if account.key() != &expected {
+        return Err(MyProgramError::PdaMismatch.into());
+    }
+
+    if account.data_len() == 0 {
+        let bump_seed = [bump];
+        let seeds = [
+            Seed::from(BANKROLL_SEED),
+            Seed::from(authority.key()),
+            Seed::from(&bump_seed),
+        ];
+        create_pda_account(payer, account, Bankroll::LEN, &seeds)?;
+    } else if !account.is_owned_by(&crate::ID) {
+        return Err(MyProgramError::InvalidOwner.into());
+    }
+
+    let mut data = account.try_borrow_mut_data()?;
+    *unsafe { load_acc_mut_unchecked::<Bankroll>(&mut data)? } = Bankroll {
+        discriminator: AccountDiscriminator::Bankroll as u8,
+        bump,
+        version: Bankroll::VERSION,
+        _padding: [0; 5],
+        authority: *authority.key(),
+        capital,
+        rewards_checkpoint: lifetime_rewards_sol,
+    };
+    Ok(())
+}
+
+/// Load the Bankroll PDA for `authority`; it must have been set up with SetBankroll
+pub fn load_bankroll(
+    authority: &AccountInfo,
+    account: &AccountInfo,
+) -> Result<Bankroll, ProgramError> {
+    let (expected, _) = find_program_address(&[BANKROLL_SEED, authority.key()], &crate::ID);
+    if account.key() != &expected {
+        return Err(MyProgramError::PdaMismatch.into());
+    }
+    if !account.is_owned_by(&crate::ID) {
+        return Err(MyProgramError::InvalidOwner.into());
+    }
+    let data = account.try_borrow_data()?;
+    Ok(*unsafe { load_acc::<Bankroll>(&data)? })
+}
+
+/// Write back a synced bankroll with `deployed` lamports spent
+pub fn record_bankroll(account: &AccountInfo, bankroll: &Bankroll, deployed: u64) -> ProgramResult {
+    let mut data = account.try_borrow_mut_data()?;
+    let stored = unsafe { load_acc_mut::<Bankroll>(&mut data)? };
+    *stored = *bankroll;
+    stored.capital = stored.capital.saturating_sub(deployed);
+    Ok(())
+}
@@ -0,0 +1,108 @@
+use bytemuck::{Pod, Zeroable};
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::Seed,
+    program_error::ProgramError,
+    pubkey::{find_program_address, Pubkey},
+    ProgramResult,
+};
+
+use crate::{
+    error::MyProgramError,
+    state::{
+        create_pda_account, load_acc, load_acc_mut, load_acc_mut_unchecked, AccountDiscriminator,
+        DataLen, Initialized, Versioned,
+    },
+};
+
+pub const DEPLOY_NONCE_SEED: &[u8] = b"nonce";
+
+/// Count of nonce-checked deploys per authority, so a captured deploy
+/// transaction can't be replayed
+#[repr(C)]
+#[derive(Pod, Zeroable, Clone, Copy, Debug, PartialEq)]
+pub struct DeployNonce {
+    pub discriminator: u8,
+
+    /// PDA bump for `[DEPLOY_NONCE_SEED, authority]`
+    pub bump: u8,
+
+    /// Layout version (`Versioned::VERSION`); 0 for accounts created before versioning
+    pub version: u8,
+
+    /// Padding (5 bytes)
+    pub _padding: [u8; 5],
+
+    /// ORE miner authority the nonce belongs to
+    pub authority: Pubkey,
+
+    /// Nonce the next deploy must carry
+    pub nonce: u64,
+}
+
+impl DataLen for DeployNonce {
+    const LEN: usize = core::mem::size_of::<DeployNonce>();
+}
+
+impl Initialized for DeployNonce {
+    fn is_initialized(&self) -> bool {
+        self.discriminator == AccountDiscriminator::DeployNonce as u8
+    }
+}
+
+impl Versioned for DeployNonce {
+    const VERSION: u8 = 1;
+
+    fn version_mut(&mut self) -> &mut u8 {
+        &mut self.version
+    }
+}
+
+/// Load the DeployNonce PDA for `authority`, creating it on first use with
+/// nonce 0
+pub fn load_or_init_deploy_nonce(
+    payer: &AccountInfo,
+    authority: &AccountInfo,
+    account: &AccountInfo,
+) -> Result<DeployNonce, ProgramError> {
+    let (expected, bump) = find_program_address(&[DEPLOY_NONCE_SEED, authority.key()], &crate::ID);
+    if account.key() != &expected {
+        return Err(MyProgramError::PdaMismatch.into());
+    }
+
+    if account.data_len() == 0 {
+        let bump_seed = [bump];
+        let seeds = [
+            Seed::from(DEPLOY_NONCE_SEED),
+            Seed::from(authority.key()),
+            Seed::from(&bump_seed),
+        ];
+        create_pda_account(payer, account, DeployNonce::LEN, &seeds)?;
+
+        let nonce = DeployNonce {
+            discriminator: AccountDiscriminator::DeployNonce as u8,
+            bump,
+            version: DeployNonce::VERSION,
+            _padding: [0; 5],
+            authority: *authority.key(),
+            nonce: 0,
+        };
+        let mut data = account.try_borrow_mut_data()?;
+        *unsafe { load_acc_mut_unchecked::<DeployNonce>(&mut data)? } = nonce;
+        return Ok(nonce);
+    }
+
+    if !account.is_owned_by(&crate::ID) {
+        return Err(MyProgramError::InvalidOwner.into());
+    }
+    let data = account.try_borrow_data()?;
+    Ok(*unsafe { load_acc::<DeployNonce>(&data)? })
+}
+
+/// Advance the nonce past the deploy that just consumed it
+pub fn advance_deploy_nonce(account: &AccountInfo) -> ProgramResult {
+    let mut data = account.try_borrow_mut_data()?;
+    let stored = unsafe { load_acc_mut::<DeployNonce>(&mut data)? };
+    stored.nonce = stored.nonce.wrapping_add(1);
+    Ok(())
+}
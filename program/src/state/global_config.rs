@@ -0,0 +1,184 @@
+use bytemuck::{Pod, Zeroable};
+use pinocchio::{
+    account_info::AccountInfo,
+    program_error::ProgramError,
+    pubkey::{create_program_address, Pubkey},
+    ProgramResult,
+};
+
+use crate::{
+    error::MyProgramError,
+    state::{
+        load_acc, load_acc_mut, realloc_account, AccountDiscriminator, DataLen, Initialized,
+        Versioned, BOARD_SQUARES, ENTROPY_PROGRAM_ID, ORE_PROGRAM_IDS,
+    },
+};
+
+pub use ore_ev_math::{
+    ConfigParams, MAX_KELLY_C_SCALED, MIN_KELLY_C_SCALED, MOTHERLODE_SPLIT_PER_MINER,
+    MOTHERLODE_SPLIT_PROPORTIONAL,
+};
+
+pub const GLOBAL_CONFIG_SEED: &[u8] = b"config";
+
+/// Most ORE deployments a config can permit besides the built-in ones
+pub const MAX_ORE_ENVIRONMENTS: usize = 3;
+
+/// An ORE deployment the config permits, e.g. devnet or staging, with the
+/// entropy program its boards draw from
+#[repr(C)]
+#[derive(Pod, Zeroable, Clone, Copy, Debug, Default, PartialEq)]
+pub struct OreEnvironment {
+    /// ORE program id (all zeros = unused entry)
+    pub ore_program: Pubkey,
+
+    /// Entropy program the deployment's entropy vars are owned by
+    pub entropy_program: Pubkey,
+}
+
+impl DataLen for ConfigParams {
+    const LEN: usize = core::mem::size_of::<ConfigParams>();
+}
+
+#[repr(C)]
+#[derive(Pod, Zeroable, Clone, Copy, Debug, PartialEq)]
+pub struct GlobalConfig {
+    pub discriminator: u8,
+
+    /// PDA bump for `[GLOBAL_CONFIG_SEED]`
+    pub bump: u8,
+
+    /// Layout version (`Versioned::VERSION`); 0 for accounts created before versioning
+    pub version: u8,
+
+    /// Padding (5 bytes)
+    pub _padding: [u8; 5],
+
+    /// Authority allowed to update the config
+    pub admin: Pubkey,
+
+    pub params: ConfigParams,
+
+    /// Extra ORE program the deploy CPIs may target besides `ORE_PROGRAM_IDS`
+    /// (all zeros = none), e.g. a devnet or upgraded deployment
+    pub ore_program_override: Pubkey,
+
+    /// Multiplier on each square's expected payout, in bps (10000 = as is);
+    /// all zeros pays every square as is
+    pub payout_multipliers_bps: [u16; BOARD_SQUARES],
+
+    /// Padding (6 bytes)
+    pub _padding2: [u8; 6],
+
+    /// Further ORE deployments the deploy CPIs may target, each checked
+    /// against its own entropy program
+    pub ore_environments: [OreEnvironment; MAX_ORE_ENVIRONMENTS],
+}
+
+impl DataLen for GlobalConfig {
+    const LEN: usize = core::mem::size_of::<GlobalConfig>();
+}
+
+/// Length of a version 0 or 1 config, created before the payout multipliers
+pub const GLOBAL_CONFIG_V1_LEN: usize = core::mem::offset_of!(GlobalConfig, payout_multipliers_bps);
+
+/// Length of a version 2 config, created before the ORE environments
+pub const GLOBAL_CONFIG_V2_LEN: usize = core::mem::offset_of!(GlobalConfig, ore_environments);
+
+/// Whether `len` is the length of a config created at an older version
+pub fn is_legacy_global_config_len(len: usize) -> bool {
+    len == GLOBAL_CONFIG_V1_LEN || len == GLOBAL_CONFIG_V2_LEN
+}
+
+impl Initialized for GlobalConfig {
+    fn is_initialized(&self) -> bool {
+        self.discriminator == AccountDiscriminator::GlobalConfig as u8
+    }
+}
+
+impl Versioned for GlobalConfig {
+    const VERSION: u8 = 3;
+
+    fn version_mut(&mut self) -> &mut u8 {
+        &mut self.version
+    }
+}
+
+impl GlobalConfig {
+    /// Whether deploy CPIs may target `program`
+    pub fn is_allowed_ore_program(&self, program: &Pubkey) -> bool {
+        ORE_PROGRAM_IDS.contains(program)
+            || (self.ore_program_override != [0; 32] && program == &self.ore_program_override)
+            || self.ore_environment(program).is_some()
+    }
+
+    /// The configured environment deploying `program`, if any
+    pub fn ore_environment(&self, program: &Pubkey) -> Option<&OreEnvironment> {
+        self.ore_environments
+            .iter()
+            .find(|env| env.ore_program != [0; 32] && &env.ore_program == program)
+    }
+
+    /// Entropy program the boards of `program` draw from, or `None` when
+    /// deploys may not target it: a configured environment's own, else ORE
+    /// v3's, so an override bringing its own entropy program must also be
+    /// listed in `ore_environments`
+    pub fn entropy_program_for(&self, program: &Pubkey) -> Option<Pubkey> {
+        if let Some(env) = self.ore_environment(program) {
+            return Some(env.entropy_program);
+        }
+        self.is_allowed_ore_program(program).then_some(ENTROPY_PROGRAM_ID)
+    }
+
+    /// The payout multiplier table, or `None` when every square pays as is
+    pub fn payout_multipliers(&self) -> Option<&[u16; BOARD_SQUARES]> {
+        if self.payout_multipliers_bps == [0; BOARD_SQUARES] {
+            None
+        } else {
+            Some(&self.payout_multipliers_bps)
+        }
+    }
+}
+
+/// Read and validate the program's GlobalConfig PDA
+pub fn read_global_config(account: &AccountInfo) -> Result<GlobalConfig, ProgramError> {
+    if !account.is_owned_by(&crate::ID) {
+        return Err(MyProgramError::InvalidOwner.into());
+    }
+
+    let config = {
+        let data = account.try_borrow_data()?;
+        if is_legacy_global_config_len(data.len()) {
+            // Not migrated yet: the missing fields read as zeros, paying every
+            // square as is with no extra environments
+            let mut config = GlobalConfig::zeroed();
+            bytemuck::bytes_of_mut(&mut config)[..data.len()].copy_from_slice(&data);
+            if !config.is_initialized() {
+                return Err(ProgramError::UninitializedAccount);
+            }
+            config
+        } else {
+            *unsafe { load_acc::<GlobalConfig>(&data)? }
+        }
+    };
+
+    let expected = create_program_address(&[GLOBAL_CONFIG_SEED, &[config.bump]], &crate::ID)?;
+    if account.key() != &expected {
+        return Err(MyProgramError::PdaMismatch.into());
+    }
+
+    Ok(config)
+}
+
+/// Grow a config created at an older version to the current layout, the new
+/// fields starting at zero (every square paying as is, no extra
+/// environments); `payer` funds the extra rent
+pub fn upgrade_global_config(payer: &AccountInfo, account: &AccountInfo) -> ProgramResult {
+    if !is_legacy_global_config_len(account.data_len()) {
+        return Ok(());
+    }
+    realloc_account(payer, account, GlobalConfig::LEN)?;
+    let mut data = account.try_borrow_mut_data()?;
+    unsafe { load_acc_mut::<GlobalConfig>(&mut data)? }.version = GlobalConfig::VERSION;
+    Ok(())
+}
@@ -0,0 +1,160 @@
+use bytemuck::{Pod, Zeroable};
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::Seed,
+    pubkey::{find_program_address, Pubkey},
+    ProgramResult,
+};
+
+use crate::{
+    error::MyProgramError,
+    state::{
+        create_pda_account, load_acc_mut, load_acc_mut_unchecked, AccountDiscriminator, DataLen,
+        Initialized, Versioned,
+    },
+};
+
+pub const LEADERBOARD_SEED: &[u8] = b"leaderboard";
+
+/// Authorities ranked on the leaderboard
+pub const LEADERBOARD_SIZE: usize = 64;
+
+/// One authority's settled results on the leaderboard
+#[repr(C)]
+#[derive(Pod, Zeroable, Clone, Copy, Debug, PartialEq)]
+pub struct LeaderboardEntry {
+    /// ORE miner authority
+    pub authority: Pubkey,
+
+    /// Realized PnL of its settled rounds in lamports
+    pub settled_pnl: i64,
+
+    /// Lamports deployed in its settled rounds
+    pub volume: u64,
+
+    /// Rounds settled
+    pub rounds: u64,
+}
+
+/// Settled PnL and volume of the authorities that opted in, aggregated by
+/// SettleRound; entries are unsorted, front-ends rank them
+#[repr(C)]
+#[derive(Pod, Zeroable, Clone, Copy, Debug, PartialEq)]
+pub struct Leaderboard {
+    pub discriminator: u8,
+
+    /// PDA bump for `[LEADERBOARD_SEED]`
+    pub bump: u8,
+
+    /// Layout version (`Versioned::VERSION`); 0 for accounts created before versioning
+    pub version: u8,
+
+    /// Padding (1 bytes)
+    pub _padding: [u8; 1],
+
+    /// Number of `entries` in use
+    pub len: u32,
+
+    pub entries: [LeaderboardEntry; LEADERBOARD_SIZE],
+}
+
+impl DataLen for Leaderboard {
+    const LEN: usize = core::mem::size_of::<Leaderboard>();
+}
+
+impl Initialized for Leaderboard {
+    fn is_initialized(&self) -> bool {
+        self.discriminator == AccountDiscriminator::Leaderboard as u8
+    }
+}
+
+impl Versioned for Leaderboard {
+    const VERSION: u8 = 1;
+
+    fn version_mut(&mut self) -> &mut u8 {
+        &mut self.version
+    }
+}
+
+impl Leaderboard {
+    /// Add a settled round to `authority`'s entry, starting one if there is
+    /// room; once full, a newcomer takes the lowest-PnL entry's place only if
+    /// its round beat that entry's PnL
+    pub fn record(&mut self, authority: &Pubkey, pnl: i64, deployed: u64) {
+        let len = self.len as usize;
+        if let Some(entry) = self.entries[..len]
+            .iter_mut()
+            .find(|e| &e.authority == authority)
+        {
+            entry.settled_pnl = entry.settled_pnl.saturating_add(pnl);
+            entry.volume = entry.volume.saturating_add(deployed);
+            entry.rounds += 1;
+            return;
+        }
+
+        let slot = if len < LEADERBOARD_SIZE {
+            self.len += 1;
+            len
+        } else {
+            let Some((lowest, entry)) = self
+                .entries
+                .iter()
+                .enumerate()
+                .min_by_key(|(_, e)| e.settled_pnl)
+            else {
+                return;
+            };
+            if pnl <= entry.settled_pnl {
+                return;
+            }
+            lowest
+        };
+        self.entries[slot] = LeaderboardEntry {
+            authority: *authority,
+            settled_pnl: pnl,
+            volume: deployed,
+            rounds: 1,
+        };
+    }
+}
+
+/// Create the global Leaderboard PDA, paid by `payer`
+pub fn init_leaderboard(payer: &AccountInfo, account: &AccountInfo) -> ProgramResult {
+    let (expected, bump) = find_program_address(&[LEADERBOARD_SEED], &crate::ID);
+    if account.key() != &expected {
+        return Err(MyProgramError::PdaMismatch.into());
+    }
+
+    let bump_seed = [bump];
+    let seeds = [Seed::from(LEADERBOARD_SEED), Seed::from(&bump_seed)];
+    create_pda_account(payer, account, Leaderboard::LEN, &seeds)?;
+
+    let mut data = account.try_borrow_mut_data()?;
+    *unsafe { load_acc_mut_unchecked::<Leaderboard>(&mut data)? } = Leaderboard {
+        discriminator: AccountDiscriminator::Leaderboard as u8,
+        bump,
+        version: Leaderboard::VERSION,
+        ..Leaderboard::zeroed()
+    };
+    Ok(())
+}
+
+/// Add a settled round of `authority` to the Leaderboard PDA
+pub fn record_leaderboard(
+    account: &AccountInfo,
+    authority: &Pubkey,
+    pnl: i64,
+    deployed: u64,
+) -> ProgramResult {
+    let (expected, _) = find_program_address(&[LEADERBOARD_SEED], &crate::ID);
+    if account.key() != &expected {
+        return Err(MyProgramError::PdaMismatch.into());
+    }
+    if !account.is_owned_by(&crate::ID) {
+        return Err(MyProgramError::InvalidOwner.into());
+    }
+
+    let mut data = account.try_borrow_mut_data()?;
+    unsafe { load_acc_mut::<Leaderboard>(&mut data)? }.record(authority, pnl, deployed);
+    Ok(())
+}
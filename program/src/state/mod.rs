@@ -1,5 +1,33 @@
 pub mod utils;
+pub mod bankroll;
+pub mod deploy_nonce;
+pub mod global_config;
+pub mod leaderboard;
+pub mod ore_account;
+pub mod ore_automation;
+pub mod ore_board;
+pub mod ore_miner;
 pub mod ore_round;
+pub mod plan;
+pub mod pyth_price;
+pub mod round_exposure;
+pub mod round_record;
+pub mod schedule;
+pub mod user_stats;
 
 pub use utils::*;
+pub use bankroll::*;
+pub use deploy_nonce::*;
+pub use global_config::*;
+pub use leaderboard::*;
+pub use ore_account::*;
+pub use ore_automation::*;
+pub use ore_board::*;
+pub use ore_miner::*;
 pub use ore_round::*;
+pub use plan::*;
+pub use pyth_price::*;
+pub use round_exposure::*;
+pub use round_record::*;
+pub use schedule::*;
+pub use user_stats::*;
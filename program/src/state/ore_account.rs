@@ -0,0 +1,175 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    pubkey::{find_program_address, Pubkey},
+    ProgramResult,
+};
+use pinocchio_log::log;
+use pinocchio_pubkey::pubkey;
+
+use crate::error::MyProgramError;
+
+/// ORE v3 program
+pub const ORE_PROGRAM_ID: Pubkey = pubkey!("oreV3EG1i9BEgiAJ8b177Z2S2rMarzak4NMv1kULvWv");
+
+/// Programs the deploy CPIs may target, besides the config's override
+pub const ORE_PROGRAM_IDS: &[Pubkey] = &[ORE_PROGRAM_ID];
+
+/// Seed of ORE's board account, `["board"]`; deploys take the board as
+/// passed and check the round against it
+pub const BOARD_SEED: &[u8] = b"board";
+
+/// Seed prefix of ORE automation accounts, `["automation", authority]`
+pub const AUTOMATION_SEED: &[u8] = b"automation";
+
+/// Seed prefix of ORE miner accounts, `["miner", authority]`; a miner spans
+/// rounds, so neither the board nor the round is part of its address
+pub const MINER_SEED: &[u8] = b"miner";
+
+/// Seed prefix of ORE round accounts, `["round", round_id]` with the id as
+/// little-endian bytes
+pub const ROUND_SEED: &[u8] = b"round";
+
+/// Entropy program ORE draws each round's randomness from
+pub const ENTROPY_PROGRAM_ID: Pubkey = pubkey!("3jSkUuYBoJzQPMEzTvkDFXCZUBksPamrVhrnHR9igu2X");
+
+/// Seed prefix of entropy var accounts, `["var", authority, id]`
+pub const ENTROPY_VAR_SEED: &[u8] = b"var";
+
+/// ORE's entropy var id under the board's authority
+pub const ORE_ENTROPY_VAR_ID: u64 = 0;
+
+/// SPL Token program, which holds claimed ORE
+pub const TOKEN_PROGRAM_ID: Pubkey = pubkey!("TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA");
+
+/// ORE account types, stored in the first byte of ORE's 8-byte account
+/// discriminator
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum OreAccount {
+    Automation = 100,
+    Miner = 103,
+    Board = 105,
+    Round = 109,
+}
+
+impl OreAccount {
+    /// The 8 leading bytes of an account of this type
+    pub fn discriminator(self) -> [u8; 8] {
+        [self as u8, 0, 0, 0, 0, 0, 0, 0]
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            OreAccount::Automation => "automation",
+            OreAccount::Miner => "miner",
+            OreAccount::Board => "board",
+            OreAccount::Round => "round",
+        }
+    }
+
+    /// Seed prefix of the account's PDA under its authority, for the types
+    /// ORE derives per authority
+    fn seed(self) -> Option<&'static [u8]> {
+        match self {
+            OreAccount::Automation => Some(AUTOMATION_SEED),
+            OreAccount::Miner => Some(MINER_SEED),
+            _ => None,
+        }
+    }
+
+    fn error(self) -> MyProgramError {
+        match self {
+            OreAccount::Automation => MyProgramError::InvalidAutomationAccount,
+            OreAccount::Miner => MyProgramError::InvalidMinerAccount,
+            OreAccount::Board => MyProgramError::InvalidBoardAccount,
+            OreAccount::Round => MyProgramError::InvalidRoundAccount,
+        }
+    }
+
+    /// ORE creates miner and automation accounts on first use, so before
+    /// then they are empty system accounts
+    fn created_lazily(self) -> bool {
+        matches!(self, OreAccount::Automation | OreAccount::Miner)
+    }
+}
+
+/// Check the entropy accounts ORE's deploy reads: the deployment's entropy
+/// program `expected` (`ENTROPY_PROGRAM_ID` for ORE v3) and the board's
+/// entropy var, owned by it
+pub fn check_entropy_accounts(
+    entropy_program: &AccountInfo,
+    entropy_var: &AccountInfo,
+    board: &AccountInfo,
+    expected: &Pubkey,
+) -> ProgramResult {
+    if entropy_program.key() != expected {
+        log!("Error: entropy_program is not the entropy program");
+        return Err(MyProgramError::InvalidEntropyProgram.into());
+    }
+
+    let (var, _) = find_program_address(
+        &[
+            ENTROPY_VAR_SEED,
+            board.key(),
+            &ORE_ENTROPY_VAR_ID.to_le_bytes(),
+        ],
+        expected,
+    );
+    if entropy_var.key() != &var || !entropy_var.is_owned_by(expected) {
+        log!("Error: entropy_var is not the board's entropy var");
+        return Err(MyProgramError::InvalidEntropyVar.into());
+    }
+
+    Ok(())
+}
+
+/// Check that `account` is `authority`'s ORE account of type `kind`, derived
+/// under `ore_program`
+pub fn check_ore_pda(
+    account: &AccountInfo,
+    authority: &AccountInfo,
+    ore_program: &Pubkey,
+    kind: OreAccount,
+) -> ProgramResult {
+    let Some(seed) = kind.seed() else {
+        return Ok(());
+    };
+    let (expected, _) = find_program_address(&[seed, authority.key()], ore_program);
+    if account.key() != &expected {
+        log!("Error: {} account is not the authority's PDA", kind.name());
+        return Err(kind.error().into());
+    }
+
+    Ok(())
+}
+
+/// Check that `account` is an ORE account of type `kind` owned by
+/// `ore_program`, before anything decodes it
+pub fn check_ore_account(
+    account: &AccountInfo,
+    ore_program: &Pubkey,
+    kind: OreAccount,
+) -> ProgramResult {
+    if kind.created_lazily()
+        && account.data_len() == 0
+        && account.is_owned_by(&pinocchio_system::ID)
+    {
+        return Ok(());
+    }
+
+    if !account.is_owned_by(ore_program) {
+        log!(
+            "Error: {} account is not owned by the ORE program",
+            kind.name()
+        );
+        return Err(kind.error().into());
+    }
+
+    let data = account.try_borrow_data()?;
+    if data.get(..8) != Some(&kind.discriminator()[..]) {
+        log!("Error: {} account has the wrong discriminator", kind.name());
+        return Err(kind.error().into());
+    }
+
+    Ok(())
+}
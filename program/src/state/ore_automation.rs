@@ -0,0 +1,44 @@
+use bytemuck::{Pod, Zeroable};
+use pinocchio::program_error::ProgramError;
+
+use crate::state::DataLen;
+
+#[repr(C)]
+#[derive(Pod, Zeroable, Clone, Copy, Debug, PartialEq)]
+pub struct OreAutomation {
+    pub _disc: [u8; 8],
+
+    /// The amount of SOL to deploy on each square per round.
+    pub amount: u64,
+
+    /// The authority of this automation account.
+    pub authority: [u8; 32],
+
+    /// The amount of SOL left to fund deployments.
+    pub balance: u64,
+
+    /// The executor allowed to deploy on the authority's behalf.
+    pub executor: [u8; 32],
+
+    /// The fee paid to the executor per deployment.
+    pub fee: u64,
+
+    /// The strategy the executor deploys with.
+    pub strategy: u64,
+
+    /// The squares the strategy deploys on.
+    pub mask: u64,
+}
+
+impl DataLen for OreAutomation {
+    const LEN: usize = core::mem::size_of::<OreAutomation>();
+}
+
+/// Read automation data from account
+#[inline(always)]
+pub fn read_ore_automation_data(account_data: &[u8]) -> Result<&OreAutomation, ProgramError> {
+    if account_data.len() < OreAutomation::LEN {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    Ok(bytemuck::from_bytes(&account_data[..OreAutomation::LEN]))
+}
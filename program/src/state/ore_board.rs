@@ -0,0 +1,52 @@
+use bytemuck::{Pod, Zeroable};
+use pinocchio::{
+    program_error::ProgramError,
+    pubkey::{find_program_address, Pubkey},
+};
+
+use crate::state::{DataLen, ROUND_SEED};
+
+#[repr(C)]
+#[derive(Pod, Zeroable, Clone, Copy, Debug, PartialEq)]
+pub struct OreBoard {
+    pub _disc: [u8; 8],
+
+    /// The current round number.
+    pub round_id: u64,
+
+    /// The slot at which the current round starts mining.
+    pub start_slot: u64,
+
+    /// The slot at which the current round ends mining.
+    pub end_slot: u64,
+}
+
+impl OreBoard {
+    /// Address of the current round's account under `ore_program`
+    pub fn round_address(&self, ore_program: &Pubkey) -> Pubkey {
+        find_program_address(&[ROUND_SEED, &self.round_id.to_le_bytes()], ore_program).0
+    }
+
+    /// Whether deploys at `slot` still land in the current round
+    pub fn is_open(&self, slot: u64) -> bool {
+        slot < self.end_slot
+    }
+
+    /// Slots left before the current round's end slot, 0 once it is reached
+    pub fn slots_remaining(&self, slot: u64) -> u64 {
+        self.end_slot.saturating_sub(slot)
+    }
+}
+
+impl DataLen for OreBoard {
+    const LEN: usize = core::mem::size_of::<OreBoard>();
+}
+
+/// Read board data from account
+#[inline(always)]
+pub fn read_ore_board_data(account_data: &[u8]) -> Result<&OreBoard, ProgramError> {
+    if account_data.len() < OreBoard::LEN {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    Ok(bytemuck::from_bytes(&account_data[..OreBoard::LEN]))
+}
@@ -0,0 +1,87 @@
+use bytemuck::{Pod, Zeroable};
+use pinocchio::program_error::ProgramError;
+
+use crate::state::{DataLen, BOARD_SQUARES};
+
+#[repr(C)]
+#[derive(Pod, Zeroable, Clone, Copy, Debug, PartialEq)]
+pub struct OreMiner {
+    pub _disc: [u8; 8],
+
+    /// The authority of this miner account.
+    pub authority: [u8; 32],
+
+    /// The miner's SOL deployed in each square for the current round.
+    pub deployed: [u64; BOARD_SQUARES],
+
+    /// The cumulative amount of SOL deployed in each square prior to this miner's move.
+    pub cumulative: [u64; BOARD_SQUARES],
+
+    /// SOL reserved to pay the checkpoint fee.
+    pub checkpoint_fee: u64,
+
+    /// The last round that this miner checkpointed.
+    pub checkpoint_id: u64,
+
+    /// The last time this miner claimed ORE rewards.
+    pub last_claim_ore_at: i64,
+
+    /// The last time this miner claimed SOL rewards.
+    pub last_claim_sol_at: i64,
+
+    /// The rewards factor last time rewards were updated on this miner account.
+    pub rewards_factor: [u8; 16],
+
+    /// The amount of SOL this miner can claim.
+    pub rewards_sol: u64,
+
+    /// The amount of ORE this miner can claim.
+    pub rewards_ore: u64,
+
+    /// The amount of ORE this miner has earned from claim fees.
+    pub refined_ore: u64,
+
+    /// The ID of the round this miner last played in.
+    pub round_id: u64,
+
+    /// The total amount of SOL this miner has mined across all rounds.
+    pub lifetime_rewards_sol: u64,
+
+    /// The total amount of ORE this miner has mined across all rounds.
+    pub lifetime_rewards_ore: u64,
+}
+
+impl OreMiner {
+    /// The miner's per-square deployments in `round_id`, zero when its
+    /// `deployed` still belongs to an earlier round
+    pub fn deployed_in(&self, round_id: u64) -> [u64; BOARD_SQUARES] {
+        if self.round_id == round_id {
+            self.deployed
+        } else {
+            [0; BOARD_SQUARES]
+        }
+    }
+
+    /// Total SOL the miner has deployed in `round_id`
+    pub fn total_deployed_in(&self, round_id: u64) -> u64 {
+        self.deployed_in(round_id).iter().sum()
+    }
+
+    /// SOL and ORE rewards the miner can claim, as `(sol, ore)`
+    pub fn unclaimed(&self) -> (u64, u64) {
+        (self.rewards_sol, self.rewards_ore)
+    }
+}
+
+impl DataLen for OreMiner {
+    const LEN: usize = core::mem::size_of::<OreMiner>();
+}
+
+/// Read miner data from account
+#[inline(always)]
+pub fn read_ore_miner_data(account_data: &[u8]) -> Result<&OreMiner, ProgramError> {
+    if account_data.len() < OreMiner::LEN {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    Ok(bytemuck::from_bytes(&account_data[..OreMiner::LEN]))
+}
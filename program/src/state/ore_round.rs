@@ -1,49 +1,8 @@
-use bytemuck::{Pod, Zeroable};
 use pinocchio::program_error::ProgramError;
 
 use crate::state::DataLen;
 
-#[repr(C)]
-#[derive(Pod, Zeroable, Clone, Copy, Debug, PartialEq)]
-pub struct OreRound {
-    pub _disc: [u8; 8],
-
-    /// The round number.
-    pub id: u64,
-
-    /// The amount of SOL deployed in each square.
-    pub deployed: [u64; 25],
-
-    /// The hash of the end slot, provided by solana, used for random number generation.
-    pub slot_hash: [u8; 32],
-
-    /// The count of miners on each square.
-    pub count: [u64; 25],
-
-    /// The slot at which claims for this round account end.
-    pub expires_at: u64,
-
-    /// The amount of ORE in the motherlode.
-    pub motherlode: u64,
-
-    /// The account to which rent should be returned when this account is closed.
-    pub rent_payer: [u8; 32],
-
-    /// The top miner of the round.
-    pub top_miner: [u8; 32],
-
-    /// The amount of ORE to distribute to the top miner.
-    pub top_miner_reward: u64,
-
-    /// The total amount of SOL deployed in the round.
-    pub total_deployed: u64,
-
-    /// The total amount of SOL put in the ORE vault.
-    pub total_vaulted: u64,
-
-    /// The total amount of SOL won by miners for the round.
-    pub total_winnings: u64,
-}
+pub use ore_ev_math::{OreRound, BOARD_SQUARES};
 
 impl DataLen for OreRound {
     const LEN: usize = core::mem::size_of::<OreRound>();
@@ -0,0 +1,169 @@
+use bytemuck::{Pod, Zeroable};
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::Seed,
+    program_error::ProgramError,
+    pubkey::{find_program_address, Pubkey},
+    ProgramResult,
+};
+
+use crate::{
+    allocator::{Allocation, MAX_PLAN_BLOCKS},
+    error::MyProgramError,
+    state::{
+        create_pda_account, load_acc, load_acc_mut, load_acc_mut_unchecked, AccountDiscriminator,
+        DataLen, Initialized, Versioned,
+    },
+};
+
+pub const PLAN_SEED: &[u8] = b"plan";
+
+/// A sized deployment plan for one ORE round, stored by OreDeploy with
+/// `FLAG_STORE_PLAN`: the blocks deployed (square, lamports and modeled EV
+/// each) and the window the round accepts deploys in
+#[repr(C)]
+#[derive(Pod, Zeroable, Clone, Copy, Debug, PartialEq)]
+pub struct Plan {
+    pub discriminator: u8,
+
+    /// PDA bump for `[PLAN_SEED, authority]`
+    pub bump: u8,
+
+    /// Layout version (`Versioned::VERSION`)
+    pub version: u8,
+
+    /// Number of blocks in use
+    pub count: u8,
+
+    /// Padding (4 bytes)
+    pub _padding: [u8; 4],
+
+    /// ORE miner authority the plan deploys for
+    pub authority: Pubkey,
+
+    /// ORE round the plan was sized against
+    pub round_id: u64,
+
+    /// Slot the plan was sized at
+    pub created_slot: u64,
+
+    /// First slot at which the plan may no longer be executed
+    pub expires_slot: u64,
+
+    /// OreDeploy `FLAG_*` bits the plan was sized with
+    pub flags: u32,
+
+    /// Square index per block (first `count` entries)
+    pub indices: [u8; MAX_PLAN_BLOCKS],
+
+    /// Padding (3 bytes)
+    pub _padding2: [u8; 3],
+
+    /// Lamports to deploy per block (first `count` entries)
+    pub amounts: [u64; MAX_PLAN_BLOCKS],
+
+    /// Modeled EV in lamports per block (first `count` entries)
+    pub evs: [i64; MAX_PLAN_BLOCKS],
+}
+
+impl DataLen for Plan {
+    const LEN: usize = core::mem::size_of::<Plan>();
+}
+
+impl Initialized for Plan {
+    fn is_initialized(&self) -> bool {
+        self.discriminator == AccountDiscriminator::Plan as u8
+    }
+}
+
+impl Versioned for Plan {
+    const VERSION: u8 = 1;
+
+    fn version_mut(&mut self) -> &mut u8 {
+        &mut self.version
+    }
+}
+
+impl Plan {
+    /// Plan for `authority` holding the blocks of `allocation`, sized
+    /// against `round_id` at `created_slot` and valid until `expires_slot`
+    pub fn new(
+        authority: &Pubkey,
+        round_id: u64,
+        created_slot: u64,
+        expires_slot: u64,
+        flags: u32,
+        allocation: &Allocation,
+    ) -> Self {
+        Plan {
+            discriminator: AccountDiscriminator::Plan as u8,
+            version: Plan::VERSION,
+            count: allocation.count,
+            authority: *authority,
+            round_id,
+            created_slot,
+            expires_slot,
+            flags,
+            indices: allocation.indices,
+            amounts: allocation.amounts,
+            evs: allocation.evs,
+            ..Plan::zeroed()
+        }
+    }
+
+    /// Lamports the plan deploys in total
+    pub fn total_amount(&self) -> u64 {
+        self.amounts[..self.count as usize].iter().sum()
+    }
+
+    /// Whether the plan can still be executed at `slot`
+    pub fn is_live(&self, slot: u64) -> bool {
+        slot < self.expires_slot
+    }
+}
+
+/// Write `plan` to the Plan PDA of its authority, creating it on first use
+/// (paid by `payer`); the stored bump is kept
+pub fn write_plan(payer: &AccountInfo, account: &AccountInfo, plan: &Plan) -> ProgramResult {
+    let (expected, bump) = find_program_address(&[PLAN_SEED, &plan.authority], &crate::ID);
+    if account.key() != &expected {
+        return Err(MyProgramError::PdaMismatch.into());
+    }
+
+    if account.data_len() == 0 {
+        let bump_seed = [bump];
+        let seeds = [
+            Seed::from(PLAN_SEED),
+            Seed::from(&plan.authority),
+            Seed::from(&bump_seed),
+        ];
+        create_pda_account(payer, account, Plan::LEN, &seeds)?;
+        let mut data = account.try_borrow_mut_data()?;
+        *unsafe { load_acc_mut_unchecked::<Plan>(&mut data)? } = Plan { bump, ..*plan };
+        return Ok(());
+    }
+
+    if !account.is_owned_by(&crate::ID) {
+        return Err(MyProgramError::InvalidOwner.into());
+    }
+    let mut data = account.try_borrow_mut_data()?;
+    *unsafe { load_acc_mut::<Plan>(&mut data)? } = Plan { bump, ..*plan };
+    Ok(())
+}
+
+/// Read the Plan PDA of `authority`
+pub fn read_plan(authority: &Pubkey, account: &AccountInfo) -> Result<Plan, ProgramError> {
+    let (expected, _) = find_program_address(&[PLAN_SEED, authority], &crate::ID);
+    if account.key() != &expected {
+        return Err(MyProgramError::PdaMismatch.into());
+    }
+    if !account.is_owned_by(&crate::ID) {
+        return Err(MyProgramError::InvalidOwner.into());
+    }
+    let data = account.try_borrow_data()?;
+    let plan = *unsafe { load_acc::<Plan>(&data)? };
+    if plan.count as usize > MAX_PLAN_BLOCKS {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    Ok(plan)
+}
@@ -0,0 +1,106 @@
+use pinocchio::{program_error::ProgramError, pubkey::Pubkey};
+use pinocchio_pubkey::pubkey;
+
+use crate::error::MyProgramError;
+
+/// Pyth Solana receiver program, owner of `PriceUpdateV2` accounts.
+pub const PYTH_RECEIVER_PROGRAM_ID: Pubkey = pubkey!("rec5EKMGg6MxZYaMdyBfgwp4d5rB9T1VQH5pJv5LtFJ");
+
+/// Pyth SOL/USD price feed id.
+pub const SOL_USD_FEED_ID: [u8; 32] = [
+    0xef, 0x0d, 0x8b, 0x6f, 0xda, 0x2c, 0xeb, 0xa4, 0x1d, 0xa1, 0x5d, 0x40, 0x95, 0xd1, 0xda, 0x39,
+    0x2a, 0x0d, 0x2f, 0x8e, 0xd0, 0xc6, 0xc7, 0xbc, 0x0f, 0x4c, 0xfa, 0xc8, 0xc2, 0x80, 0xb5, 0x6d,
+];
+
+/// Maximum age of an accepted price update, in seconds.
+pub const MAX_PRICE_AGE_SECS: i64 = 60;
+
+/// `VerificationLevel::Full` borsh tag. Partial updates are rejected.
+const VERIFICATION_LEVEL_FULL: u8 = 1;
+
+// PriceUpdateV2 layout (with `VerificationLevel::Full`):
+// disc [8] | write_authority [32] | verification_level [1] | feed_id [32] |
+// price i64 | conf u64 | exponent i32 | publish_time i64 | ...
+const VERIFICATION_LEVEL_OFFSET: usize = 40;
+const FEED_ID_OFFSET: usize = 41;
+const PRICE_OFFSET: usize = 73;
+const CONF_OFFSET: usize = 81;
+const EXPONENT_OFFSET: usize = 89;
+const PUBLISH_TIME_OFFSET: usize = 93;
+const MIN_LEN: usize = 101;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PythPrice {
+    /// Price mantissa (price = `price` × 10^`exponent`).
+    pub price: i64,
+
+    /// Confidence interval, same scale as `price`.
+    pub conf: u64,
+
+    /// Decimal exponent applied to `price` and `conf`.
+    pub exponent: i32,
+
+    /// Unix timestamp at which the price was published.
+    pub publish_time: i64,
+}
+
+impl PythPrice {
+    /// Convert a USD-cent amount into lamports at this SOL/USD price.
+    ///
+    /// lamports = cents × 10^7 / (price × 10^exponent)
+    pub fn usd_cents_to_lamports(&self, cents: u64) -> Result<u64, ProgramError> {
+        if self.price <= 0 {
+            return Err(MyProgramError::InvalidOracle.into());
+        }
+
+        let shift = 7i32 - self.exponent;
+        let (num, den) = if shift >= 0 {
+            let scale = 10u128
+                .checked_pow(shift as u32)
+                .ok_or(ProgramError::ArithmeticOverflow)?;
+            (
+                (cents as u128)
+                    .checked_mul(scale)
+                    .ok_or(ProgramError::ArithmeticOverflow)?,
+                self.price as u128,
+            )
+        } else {
+            let scale = 10u128
+                .checked_pow(shift.unsigned_abs())
+                .ok_or(ProgramError::ArithmeticOverflow)?;
+            (
+                cents as u128,
+                (self.price as u128)
+                    .checked_mul(scale)
+                    .ok_or(ProgramError::ArithmeticOverflow)?,
+            )
+        };
+
+        u64::try_from(num / den).map_err(|_| ProgramError::ArithmeticOverflow)
+    }
+}
+
+/// Read a fully verified SOL/USD price from a Pyth `PriceUpdateV2` account
+#[inline(always)]
+pub fn read_pyth_price(account_data: &[u8]) -> Result<PythPrice, ProgramError> {
+    if account_data.len() < MIN_LEN
+        || account_data[VERIFICATION_LEVEL_OFFSET] != VERIFICATION_LEVEL_FULL
+        || account_data[FEED_ID_OFFSET..FEED_ID_OFFSET + 32] != SOL_USD_FEED_ID
+    {
+        return Err(MyProgramError::InvalidOracle.into());
+    }
+
+    Ok(PythPrice {
+        price: i64::from_le_bytes(read_array(account_data, PRICE_OFFSET)),
+        conf: u64::from_le_bytes(read_array(account_data, CONF_OFFSET)),
+        exponent: i32::from_le_bytes(read_array(account_data, EXPONENT_OFFSET)),
+        publish_time: i64::from_le_bytes(read_array(account_data, PUBLISH_TIME_OFFSET)),
+    })
+}
+
+#[inline(always)]
+fn read_array<const N: usize>(data: &[u8], offset: usize) -> [u8; N] {
+    let mut out = [0u8; N];
+    out.copy_from_slice(&data[offset..offset + N]);
+    out
+}
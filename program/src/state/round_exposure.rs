@@ -0,0 +1,116 @@
+use bytemuck::{Pod, Zeroable};
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::Seed,
+    program_error::ProgramError,
+    pubkey::{find_program_address, Pubkey},
+    ProgramResult,
+};
+
+use crate::{
+    error::MyProgramError,
+    state::{
+        create_pda_account, load_acc, load_acc_mut, load_acc_mut_unchecked, AccountDiscriminator,
+        DataLen, Initialized, Versioned,
+    },
+};
+
+pub const ROUND_EXPOSURE_SEED: &[u8] = b"exposure";
+
+/// Lamports deployed by one authority in one ORE round, across all OreDeploy calls
+#[repr(C)]
+#[derive(Pod, Zeroable, Clone, Copy, Debug, PartialEq)]
+pub struct RoundExposure {
+    pub discriminator: u8,
+
+    /// PDA bump for `[ROUND_EXPOSURE_SEED, authority, round_id]`
+    pub bump: u8,
+
+    /// Layout version (`Versioned::VERSION`); 0 for accounts created before versioning
+    pub version: u8,
+
+    /// Padding (5 bytes)
+    pub _padding: [u8; 5],
+
+    /// ORE miner authority the exposure belongs to
+    pub authority: Pubkey,
+
+    /// ORE round id
+    pub round_id: u64,
+
+    /// Total lamports deployed so far this round
+    pub total_deployed: u64,
+}
+
+impl DataLen for RoundExposure {
+    const LEN: usize = core::mem::size_of::<RoundExposure>();
+}
+
+impl Initialized for RoundExposure {
+    fn is_initialized(&self) -> bool {
+        self.discriminator == AccountDiscriminator::RoundExposure as u8
+    }
+}
+
+impl Versioned for RoundExposure {
+    const VERSION: u8 = 1;
+
+    fn version_mut(&mut self) -> &mut u8 {
+        &mut self.version
+    }
+}
+
+/// Load the RoundExposure PDA for (authority, round_id), creating it on first use
+pub fn load_or_init_round_exposure(
+    payer: &AccountInfo,
+    authority: &AccountInfo,
+    account: &AccountInfo,
+    round_id: u64,
+) -> Result<RoundExposure, ProgramError> {
+    let round_id_bytes = round_id.to_le_bytes();
+    let (expected, bump) = find_program_address(
+        &[ROUND_EXPOSURE_SEED, authority.key(), &round_id_bytes],
+        &crate::ID,
+    );
+    if account.key() != &expected {
+        return Err(MyProgramError::PdaMismatch.into());
+    }
+
+    if account.data_len() == 0 {
+        let bump_seed = [bump];
+        let seeds = [
+            Seed::from(ROUND_EXPOSURE_SEED),
+            Seed::from(authority.key()),
+            Seed::from(&round_id_bytes),
+            Seed::from(&bump_seed),
+        ];
+        create_pda_account(payer, account, RoundExposure::LEN, &seeds)?;
+
+        let exposure = RoundExposure {
+            discriminator: AccountDiscriminator::RoundExposure as u8,
+            bump,
+            version: RoundExposure::VERSION,
+            _padding: [0; 5],
+            authority: *authority.key(),
+            round_id,
+            total_deployed: 0,
+        };
+        let mut data = account.try_borrow_mut_data()?;
+        *unsafe { load_acc_mut_unchecked::<RoundExposure>(&mut data)? } = exposure;
+        return Ok(exposure);
+    }
+
+    if !account.is_owned_by(&crate::ID) {
+        return Err(MyProgramError::InvalidOwner.into());
+    }
+    let data = account.try_borrow_data()?;
+    Ok(*unsafe { load_acc::<RoundExposure>(&data)? })
+}
+
+/// Add `amount` lamports to a RoundExposure account
+pub fn add_round_exposure(account: &AccountInfo, amount: u64) -> ProgramResult {
+    let mut data = account.try_borrow_mut_data()?;
+    let exposure = unsafe { load_acc_mut::<RoundExposure>(&mut data)? };
+    exposure.total_deployed = exposure.total_deployed.saturating_add(amount);
+    Ok(())
+}
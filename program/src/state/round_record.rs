@@ -0,0 +1,210 @@
+use bytemuck::{Pod, Zeroable};
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::Seed,
+    program_error::ProgramError,
+    pubkey::{find_program_address, Pubkey},
+    ProgramResult,
+};
+
+use crate::{
+    allocator::{isqrt_u128, MAX_PLAN_BLOCKS},
+    error::MyProgramError,
+    state::{
+        create_pda_account, load_acc_mut, load_acc_mut_unchecked, AccountDiscriminator, DataLen,
+        Initialized, OreRound, Versioned, BOARD_SQUARES,
+    },
+};
+
+pub const ROUND_RECORD_SEED: &[u8] = b"record";
+
+/// One authority's plan for one ORE round, merged across its OreDeploy
+/// calls, and the round's realized result once it settles
+#[repr(C)]
+#[derive(Pod, Zeroable, Clone, Copy, Debug, PartialEq)]
+pub struct RoundRecord {
+    pub discriminator: u8,
+
+    /// PDA bump for `[ROUND_RECORD_SEED, authority, round_id]`
+    pub bump: u8,
+
+    /// Non-zero once the realized result has been filled in
+    pub settled: u8,
+
+    /// Number of OreDeploy calls merged into the plan
+    pub deploys: u8,
+
+    /// Non-zero once a deploy opted the round into the Leaderboard
+    pub leaderboard: u8,
+
+    /// Layout version (`Versioned::VERSION`); 0 for accounts created before versioning
+    pub version: u8,
+
+    /// Padding (2 bytes)
+    pub _padding: [u8; 2],
+
+    /// ORE miner authority the record belongs to
+    pub authority: Pubkey,
+
+    /// ORE round id
+    pub round_id: u64,
+
+    /// Total lamports deployed in the round
+    pub total_deployed: u64,
+
+    /// Modeled EV of the plans in lamports
+    pub modeled_ev: i64,
+
+    /// Modeled standard deviation of the plans' PnL in lamports
+    pub modeled_std_dev: u64,
+
+    /// SOL won from the round, once settled
+    pub won: u64,
+
+    /// Lamports deployed per square
+    pub amounts: [u64; BOARD_SQUARES],
+
+    /// Modeled EV in lamports per square
+    pub evs: [i64; BOARD_SQUARES],
+}
+
+impl DataLen for RoundRecord {
+    const LEN: usize = core::mem::size_of::<RoundRecord>();
+}
+
+impl Initialized for RoundRecord {
+    fn is_initialized(&self) -> bool {
+        self.discriminator == AccountDiscriminator::RoundRecord as u8
+    }
+}
+
+impl Versioned for RoundRecord {
+    const VERSION: u8 = 1;
+
+    fn version_mut(&mut self) -> &mut u8 {
+        &mut self.version
+    }
+}
+
+impl RoundRecord {
+    /// Realized PnL in lamports: SOL won minus SOL deployed
+    pub fn realized_pnl(&self) -> i64 {
+        let pnl = self.won as i128 - self.total_deployed as i128;
+        pnl.clamp(i64::MIN as i128, i64::MAX as i128) as i64
+    }
+}
+
+/// Check the RoundRecord PDA for (authority, round_id), creating it on first
+/// use, and opt the round into the Leaderboard if `leaderboard` is set
+pub fn load_or_init_round_record(
+    payer: &AccountInfo,
+    authority: &AccountInfo,
+    account: &AccountInfo,
+    round_id: u64,
+    leaderboard: bool,
+) -> ProgramResult {
+    let round_id_bytes = round_id.to_le_bytes();
+    let (expected, bump) = find_program_address(
+        &[ROUND_RECORD_SEED, authority.key(), &round_id_bytes],
+        &crate::ID,
+    );
+    if account.key() != &expected {
+        return Err(MyProgramError::PdaMismatch.into());
+    }
+
+    if account.data_len() == 0 {
+        let bump_seed = [bump];
+        let seeds = [
+            Seed::from(ROUND_RECORD_SEED),
+            Seed::from(authority.key()),
+            Seed::from(&round_id_bytes),
+            Seed::from(&bump_seed),
+        ];
+        create_pda_account(payer, account, RoundRecord::LEN, &seeds)?;
+
+        let record = RoundRecord {
+            discriminator: AccountDiscriminator::RoundRecord as u8,
+            bump,
+            version: RoundRecord::VERSION,
+            leaderboard: leaderboard as u8,
+            authority: *authority.key(),
+            round_id,
+            ..RoundRecord::zeroed()
+        };
+        let mut data = account.try_borrow_mut_data()?;
+        *unsafe { load_acc_mut_unchecked::<RoundRecord>(&mut data)? } = record;
+        return Ok(());
+    }
+
+    if !account.is_owned_by(&crate::ID) {
+        return Err(MyProgramError::InvalidOwner.into());
+    }
+    let mut data = account.try_borrow_mut_data()?;
+    let record = unsafe { load_acc_mut::<RoundRecord>(&mut data)? };
+    if leaderboard {
+        record.leaderboard = 1;
+    }
+    Ok(())
+}
+
+/// Fill in the realized result of the authority's RoundRecord for a drawn
+/// `round`: the SOL the winning square pays out on our stake, or every
+/// lamport back when ORE reset the round without a winner
+pub fn settle_round_record(
+    authority: &AccountInfo,
+    account: &AccountInfo,
+    round: &OreRound,
+) -> Result<RoundRecord, ProgramError> {
+    let (expected, _) = find_program_address(
+        &[ROUND_RECORD_SEED, authority.key(), &round.id.to_le_bytes()],
+        &crate::ID,
+    );
+    if account.key() != &expected {
+        return Err(MyProgramError::PdaMismatch.into());
+    }
+    if !account.is_owned_by(&crate::ID) {
+        return Err(MyProgramError::InvalidOwner.into());
+    }
+
+    let mut data = account.try_borrow_mut_data()?;
+    let record = unsafe { load_acc_mut::<RoundRecord>(&mut data)? };
+    if record.settled != 0 {
+        return Err(MyProgramError::RoundAlreadySettled.into());
+    }
+    record.won = match round.winning_square() {
+        Some(square) => round.sol_payout(square, record.amounts[square]),
+        None => record.total_deployed,
+    };
+    record.settled = 1;
+    Ok(*record)
+}
+
+/// Merge an executed plan (the first `num_blocks` of `amounts`, `evs` and
+/// square `indices`, with its portfolio EV and standard deviation) into a
+/// RoundRecord
+pub fn record_round_plan(
+    account: &AccountInfo,
+    amounts: &[u64; MAX_PLAN_BLOCKS],
+    evs: &[i64; MAX_PLAN_BLOCKS],
+    indices: &[u8; MAX_PLAN_BLOCKS],
+    num_blocks: usize,
+    portfolio_ev: i64,
+    portfolio_std_dev: u64,
+) -> ProgramResult {
+    let mut data = account.try_borrow_mut_data()?;
+    let record = unsafe { load_acc_mut::<RoundRecord>(&mut data)? };
+    let blocks = amounts.iter().zip(evs).zip(indices).take(num_blocks);
+    for ((&amount, &ev), &square) in blocks {
+        let square = square as usize;
+        record.amounts[square] = record.amounts[square].saturating_add(amount);
+        record.evs[square] = record.evs[square].saturating_add(ev);
+        record.total_deployed = record.total_deployed.saturating_add(amount);
+    }
+    record.modeled_ev = record.modeled_ev.saturating_add(portfolio_ev);
+    // Standard deviations of separate plans combine in quadrature
+    let variance = record.modeled_std_dev as u128 * record.modeled_std_dev as u128
+        + portfolio_std_dev as u128 * portfolio_std_dev as u128;
+    record.modeled_std_dev = isqrt_u128(variance) as u64;
+    record.deploys = record.deploys.saturating_add(1);
+    Ok(())
+}
@@ -0,0 +1,120 @@
+use bytemuck::{Pod, Zeroable};
+use pinocchio::{
+    account_info::AccountInfo,
+    program_error::ProgramError,
+    pubkey::{create_program_address, Pubkey},
+    ProgramResult,
+};
+
+use crate::{
+    error::MyProgramError,
+    instruction::OreDeployIxData,
+    state::{
+        load_acc, load_acc_mut, realloc_account, AccountDiscriminator, DataLen, Initialized,
+        Versioned,
+    },
+};
+
+pub const SCHEDULE_SEED: &[u8] = b"schedule";
+
+/// Seed of the system-owned vault PDA (`[SCHEDULE_VAULT_SEED, schedule]`)
+/// that holds a schedule's funds and deploys as the ORE miner authority
+pub const SCHEDULE_VAULT_SEED: &[u8] = b"vault";
+
+/// `last_round_id` of a schedule that hasn't executed yet
+pub const NO_ROUND: u64 = u64::MAX;
+
+/// Dollar-cost-averaged deployment: a fixed budget per round for a number of
+/// rounds, executed with a stored OreDeploy strategy
+#[repr(C)]
+#[derive(Pod, Zeroable, Clone, Copy, Debug)]
+pub struct Schedule {
+    pub discriminator: u8,
+
+    /// PDA bump for `[SCHEDULE_SEED, authority]`
+    pub bump: u8,
+
+    /// PDA bump for `[SCHEDULE_VAULT_SEED, schedule]`
+    pub vault_bump: u8,
+
+    /// Layout version (`Versioned::VERSION`); 0 for accounts created before versioning
+    pub version: u8,
+
+    /// Padding (4 bytes)
+    pub _padding: [u8; 4],
+
+    /// Wallet that funded the schedule and receives the vault on close
+    pub authority: Pubkey,
+
+    /// Lamports deployed per executed round (at most)
+    pub budget_per_round: u64,
+
+    /// ORE round id of the last execution (`NO_ROUND` before the first)
+    pub last_round_id: u64,
+
+    /// Executions left
+    pub rounds_remaining: u32,
+
+    /// Padding (4 bytes)
+    pub _padding2: [u8; 4],
+
+    /// OreDeploy parameters for each execution; `total_amount` is replaced by
+    /// `budget_per_round`
+    pub strategy: OreDeployIxData,
+}
+
+impl DataLen for Schedule {
+    const LEN: usize = core::mem::size_of::<Schedule>();
+}
+
+/// Length of version 0 accounts, whose strategy predates OreDeploy's
+/// `max_total_deployed` and `expected_nonce`
+pub const SCHEDULE_V0_LEN: usize = core::mem::offset_of!(Schedule, strategy)
+    + core::mem::offset_of!(OreDeployIxData, max_total_deployed);
+
+impl Initialized for Schedule {
+    fn is_initialized(&self) -> bool {
+        self.discriminator == AccountDiscriminator::Schedule as u8
+    }
+}
+
+impl Versioned for Schedule {
+    const VERSION: u8 = 1;
+
+    fn version_mut(&mut self) -> &mut u8 {
+        &mut self.version
+    }
+}
+
+impl Schedule {
+    /// Address of the schedule's vault
+    pub fn vault_address(&self, schedule: &Pubkey) -> Result<Pubkey, ProgramError> {
+        create_program_address(
+            &[SCHEDULE_VAULT_SEED, schedule, &[self.vault_bump]],
+            &crate::ID,
+        )
+        .map_err(|_| MyProgramError::PdaMismatch.into())
+    }
+}
+
+/// Grow a version 0 Schedule account to the current layout (the appended
+/// strategy fields start at zero: no pool bound, nonce unchecked), with
+/// `payer` funding the extra rent
+pub fn upgrade_schedule(payer: &AccountInfo, account: &AccountInfo) -> ProgramResult {
+    if account.data_len() != SCHEDULE_V0_LEN {
+        return Ok(());
+    }
+    realloc_account(payer, account, Schedule::LEN)?;
+    let mut data = account.try_borrow_mut_data()?;
+    unsafe { load_acc_mut::<Schedule>(&mut data)? }.version = Schedule::VERSION;
+    Ok(())
+}
+
+/// Load a Schedule account owned by this program
+pub fn read_schedule(account: &AccountInfo) -> Result<Schedule, ProgramError> {
+    if !account.is_owned_by(&crate::ID) {
+        return Err(MyProgramError::InvalidOwner.into());
+    }
+    let data = account.try_borrow_data()?;
+    Ok(*unsafe { load_acc::<Schedule>(&data)? })
+}
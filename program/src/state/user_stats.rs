@@ -0,0 +1,350 @@
+use bytemuck::{Pod, Zeroable};
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::Seed,
+    program_error::ProgramError,
+    pubkey::{find_program_address, Pubkey},
+    ProgramResult,
+};
+
+use crate::{
+    allocator::isqrt_u128,
+    error::MyProgramError,
+    state::{
+        create_pda_account, load_acc, load_acc_mut, load_acc_mut_unchecked, realloc_account,
+        AccountDiscriminator, DataLen, Initialized, Versioned,
+    },
+};
+
+pub const USER_STATS_SEED: &[u8] = b"stats";
+
+/// Rounds of modeled-vs-realized history kept per authority
+pub const STATS_WINDOW: usize = 16;
+
+/// Modeled and realized outcome of one round's deployments
+#[repr(C)]
+#[derive(Pod, Zeroable, Clone, Copy, Debug, PartialEq)]
+pub struct RoundResult {
+    /// ORE round id
+    pub round_id: u64,
+
+    /// Lamports deployed in the round
+    pub deployed: u64,
+
+    /// SOL won from the round (credited once the miner checkpoints it)
+    pub won: u64,
+
+    /// Modeled plan EV in lamports
+    pub modeled_ev: i64,
+
+    /// Modeled standard deviation of the plan's PnL in lamports
+    pub std_dev: u64,
+}
+
+/// Cumulative results of one authority's deployments through this program
+#[repr(C)]
+#[derive(Pod, Zeroable, Clone, Copy, Debug, PartialEq)]
+pub struct UserStats {
+    pub discriminator: u8,
+
+    /// PDA bump for `[USER_STATS_SEED, authority]`
+    pub bump: u8,
+
+    /// Slot in `history` of the most recent round
+    pub history_head: u8,
+
+    /// Number of `history` slots in use
+    pub history_len: u8,
+
+    /// Layout version (`Versioned::VERSION`); 0 for accounts created before versioning
+    pub version: u8,
+
+    /// Padding (3 bytes)
+    pub _padding: [u8; 3],
+
+    /// ORE miner authority the stats belong to
+    pub authority: Pubkey,
+
+    /// Total lamports deployed through OreDeploy
+    pub total_deployed: u64,
+
+    /// SOL won by the miner since the stats account was created
+    pub total_won: u64,
+
+    /// Miner `lifetime_rewards_sol` when `total_won` was last synced
+    pub rewards_checkpoint: u64,
+
+    /// Highest PnL reached, in lamports
+    pub peak_pnl: i64,
+
+    /// Last round the miner had checkpointed when `total_won` was synced;
+    /// rounds up to it are settled
+    pub settled_round_id: u64,
+
+    /// Ring buffer of the last `STATS_WINDOW` rounds deployed into
+    pub history: [RoundResult; STATS_WINDOW],
+
+    /// Rounds deployed into
+    pub rounds_participated: u64,
+
+    /// Rounds deployed into whose winnings have been settled
+    pub rounds_settled: u64,
+
+    /// Settled rounds that won more SOL than was deployed into them
+    pub rounds_won: u64,
+
+    /// Settled round with the highest PnL, and that PnL in lamports
+    pub best_round_id: u64,
+    pub best_round_pnl: i64,
+
+    /// Settled round with the lowest PnL, and that PnL in lamports
+    pub worst_round_id: u64,
+    pub worst_round_pnl: i64,
+}
+
+impl DataLen for UserStats {
+    const LEN: usize = core::mem::size_of::<UserStats>();
+}
+
+/// Length of version 0 accounts, created before the round counters were
+/// appended
+pub const USER_STATS_V0_LEN: usize = core::mem::offset_of!(UserStats, rounds_participated);
+
+impl Initialized for UserStats {
+    fn is_initialized(&self) -> bool {
+        self.discriminator == AccountDiscriminator::UserStats as u8
+    }
+}
+
+impl Versioned for UserStats {
+    const VERSION: u8 = 1;
+
+    fn version_mut(&mut self) -> &mut u8 {
+        &mut self.version
+    }
+}
+
+impl UserStats {
+    /// Realized PnL in lamports: SOL won minus SOL deployed
+    pub fn pnl(&self) -> i64 {
+        let pnl = self.total_won as i128 - self.total_deployed as i128;
+        pnl.clamp(i64::MIN as i128, i64::MAX as i128) as i64
+    }
+
+    /// Credit SOL the miner has won since the last sync, attributed to the
+    /// latest round the miner has checkpointed, and fold rounds settled since
+    /// into the round counters; returns the credited round's updated result
+    /// when anything was credited to it
+    pub fn sync_winnings(
+        &mut self,
+        lifetime_rewards_sol: u64,
+        checkpoint_id: u64,
+    ) -> Option<RoundResult> {
+        let won = lifetime_rewards_sol.saturating_sub(self.rewards_checkpoint);
+        self.total_won = self.total_won.saturating_add(won);
+        self.rewards_checkpoint = lifetime_rewards_sol;
+        self.peak_pnl = self.peak_pnl.max(self.pnl());
+
+        let credited = if won == 0 {
+            None
+        } else {
+            self.rounds_mut()
+                .find(|r| r.round_id <= checkpoint_id)
+                .map(|round| {
+                    round.won = round.won.saturating_add(won);
+                    *round
+                })
+        };
+        self.settle_rounds(self.settled_round_id, checkpoint_id);
+        self.settled_round_id = checkpoint_id;
+        credited
+    }
+
+    /// Count the history rounds in `(from, to]` as settled, tracking wins and
+    /// the best and worst round
+    fn settle_rounds(&mut self, from: u64, to: u64) {
+        let history = self.history;
+        for round in history.iter().take(self.history_len as usize) {
+            if round.round_id <= from || round.round_id > to {
+                continue;
+            }
+            let pnl = (round.won as i128 - round.deployed as i128)
+                .clamp(i64::MIN as i128, i64::MAX as i128) as i64;
+            if round.won > round.deployed {
+                self.rounds_won += 1;
+            }
+            if self.rounds_settled == 0 || pnl > self.best_round_pnl {
+                self.best_round_id = round.round_id;
+                self.best_round_pnl = pnl;
+            }
+            if self.rounds_settled == 0 || pnl < self.worst_round_pnl {
+                self.worst_round_id = round.round_id;
+                self.worst_round_pnl = pnl;
+            }
+            self.rounds_settled += 1;
+        }
+    }
+
+    /// Add a deployment to the round's history, starting a new slot for a new round
+    pub fn record_round(&mut self, round_id: u64, deployed: u64, modeled_ev: i64, std_dev: u64) {
+        let head = self.history_head as usize;
+        if self.history_len > 0 && self.history[head].round_id == round_id {
+            let round = &mut self.history[head];
+            round.deployed = round.deployed.saturating_add(deployed);
+            round.modeled_ev = round.modeled_ev.saturating_add(modeled_ev);
+            // Standard deviations of separate plans combine in quadrature
+            let variance =
+                round.std_dev as u128 * round.std_dev as u128 + std_dev as u128 * std_dev as u128;
+            round.std_dev = isqrt_u128(variance) as u64;
+            return;
+        }
+
+        let head = if self.history_len == 0 {
+            0
+        } else {
+            (head + 1) % STATS_WINDOW
+        };
+        self.history[head] = RoundResult {
+            round_id,
+            deployed,
+            won: 0,
+            modeled_ev,
+            std_dev,
+        };
+        self.history_head = head as u8;
+        self.history_len = (self.history_len + 1).min(STATS_WINDOW as u8);
+        self.rounds_participated += 1;
+    }
+
+    /// Rounds in the history whose winnings have been credited, newest first
+    pub fn settled_rounds(&self) -> impl Iterator<Item = &RoundResult> {
+        let settled = self.settled_round_id;
+        self.rounds().filter(move |r| r.round_id <= settled)
+    }
+
+    fn rounds(&self) -> impl Iterator<Item = &RoundResult> {
+        let head = self.history_head as usize;
+        (0..self.history_len as usize)
+            .map(move |i| &self.history[(head + STATS_WINDOW - i) % STATS_WINDOW])
+    }
+
+    fn rounds_mut(&mut self) -> impl Iterator<Item = &mut RoundResult> {
+        let (head, len) = (self.history_head as usize, self.history_len as usize);
+        let (newer, older) = self.history.split_at_mut(head + 1);
+        newer
+            .iter_mut()
+            .rev()
+            .chain(older.iter_mut().rev())
+            .take(len)
+    }
+}
+
+/// Load the UserStats PDA for `authority`, creating it on first use with
+/// winnings counted from the miner's current `lifetime_rewards_sol`
+pub fn load_or_init_user_stats(
+    payer: &AccountInfo,
+    authority: &AccountInfo,
+    account: &AccountInfo,
+    lifetime_rewards_sol: u64,
+) -> Result<UserStats, ProgramError> {
+    let (expected, bump) = find_program_address(&[USER_STATS_SEED, authority.key()], &crate::ID);
+    if account.key() != &expected {
+        return Err(MyProgramError::PdaMismatch.into());
+    }
+
+    if account.data_len() == 0 {
+        let bump_seed = [bump];
+        let seeds = [
+            Seed::from(USER_STATS_SEED),
+            Seed::from(authority.key()),
+            Seed::from(&bump_seed),
+        ];
+        create_pda_account(payer, account, UserStats::LEN, &seeds)?;
+
+        let stats = UserStats {
+            discriminator: AccountDiscriminator::UserStats as u8,
+            bump,
+            history_head: 0,
+            history_len: 0,
+            version: UserStats::VERSION,
+            _padding: [0; 3],
+            authority: *authority.key(),
+            total_deployed: 0,
+            total_won: 0,
+            rewards_checkpoint: lifetime_rewards_sol,
+            peak_pnl: 0,
+            settled_round_id: 0,
+            history: [RoundResult::zeroed(); STATS_WINDOW],
+            rounds_participated: 0,
+            rounds_settled: 0,
+            rounds_won: 0,
+            best_round_id: 0,
+            best_round_pnl: 0,
+            worst_round_id: 0,
+            worst_round_pnl: 0,
+        };
+        let mut data = account.try_borrow_mut_data()?;
+        *unsafe { load_acc_mut_unchecked::<UserStats>(&mut data)? } = stats;
+        return Ok(stats);
+    }
+
+    if !account.is_owned_by(&crate::ID) {
+        return Err(MyProgramError::InvalidOwner.into());
+    }
+    upgrade_user_stats(payer, account)?;
+    let data = account.try_borrow_data()?;
+    Ok(*unsafe { load_acc::<UserStats>(&data)? })
+}
+
+/// Load the UserStats PDA for `authority`; it must already exist
+pub fn load_user_stats(
+    authority: &AccountInfo,
+    account: &AccountInfo,
+) -> Result<UserStats, ProgramError> {
+    let (expected, _) = find_program_address(&[USER_STATS_SEED, authority.key()], &crate::ID);
+    if account.key() != &expected {
+        return Err(MyProgramError::PdaMismatch.into());
+    }
+    if !account.is_owned_by(&crate::ID) {
+        return Err(MyProgramError::InvalidOwner.into());
+    }
+    let data = account.try_borrow_data()?;
+    Ok(*unsafe { load_acc::<UserStats>(&data)? })
+}
+
+/// Grow a version 0 UserStats account to the current layout (the appended
+/// round counters start at zero), with `payer` funding the extra rent
+pub fn upgrade_user_stats(payer: &AccountInfo, account: &AccountInfo) -> ProgramResult {
+    if account.data_len() != USER_STATS_V0_LEN {
+        return Ok(());
+    }
+    realloc_account(payer, account, UserStats::LEN)?;
+    let mut data = account.try_borrow_mut_data()?;
+    unsafe { load_acc_mut::<UserStats>(&mut data)? }.version = UserStats::VERSION;
+    Ok(())
+}
+
+/// Write back synced stats
+pub fn store_user_stats(account: &AccountInfo, stats: &UserStats) -> ProgramResult {
+    let mut data = account.try_borrow_mut_data()?;
+    *unsafe { load_acc_mut::<UserStats>(&mut data)? } = *stats;
+    Ok(())
+}
+
+/// Write back synced stats with this round's deployment (lamports, modeled
+/// EV and standard deviation) added
+pub fn record_user_stats(
+    account: &AccountInfo,
+    stats: &UserStats,
+    round_id: u64,
+    deployed: u64,
+    modeled_ev: i64,
+    std_dev: u64,
+) -> ProgramResult {
+    let mut data = account.try_borrow_mut_data()?;
+    let stored = unsafe { load_acc_mut::<UserStats>(&mut data)? };
+    *stored = *stats;
+    stored.total_deployed = stored.total_deployed.saturating_add(deployed);
+    stored.record_round(round_id, deployed, modeled_ev, std_dev);
+    Ok(())
+}
@@ -1,7 +1,50 @@
-use pinocchio::{account_info::AccountInfo, program_error::ProgramError};
+use bytemuck::Pod;
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::{Seed, Signer},
+    program_error::ProgramError,
+    sysvars::{rent::Rent, Sysvar},
+    ProgramResult,
+};
+use pinocchio_log::log;
+use pinocchio_system::instructions::{Allocate, Assign, CreateAccount, Transfer};
 
 use crate::error::MyProgramError;
 
+/// First byte of every account owned by this program
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum AccountDiscriminator {
+    GlobalConfig = 1,
+    RoundExposure = 2,
+    UserStats = 3,
+    Bankroll = 4,
+    Schedule = 5,
+    DeployNonce = 6,
+    RoundRecord = 7,
+    Leaderboard = 8,
+    Plan = 9,
+}
+
+impl TryFrom<u8> for AccountDiscriminator {
+    type Error = ProgramError;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            1 => Ok(AccountDiscriminator::GlobalConfig),
+            2 => Ok(AccountDiscriminator::RoundExposure),
+            3 => Ok(AccountDiscriminator::UserStats),
+            4 => Ok(AccountDiscriminator::Bankroll),
+            5 => Ok(AccountDiscriminator::Schedule),
+            6 => Ok(AccountDiscriminator::DeployNonce),
+            7 => Ok(AccountDiscriminator::RoundRecord),
+            8 => Ok(AccountDiscriminator::Leaderboard),
+            9 => Ok(AccountDiscriminator::Plan),
+            _ => Err(ProgramError::InvalidAccountData),
+        }
+    }
+}
+
 pub trait DataLen {
     const LEN: usize;
 }
@@ -10,6 +53,15 @@ pub trait Initialized {
     fn is_initialized(&self) -> bool;
 }
 
+/// Layout version of a program account, stamped at creation and upgraded by
+/// MigrateAccount
+pub trait Versioned {
+    /// Version accounts are created at
+    const VERSION: u8;
+
+    fn version_mut(&mut self) -> &mut u8;
+}
+
 #[inline(always)]
 pub unsafe fn load_acc<T: DataLen + Initialized>(bytes: &[u8]) -> Result<&T, ProgramError> {
     load_acc_unchecked::<T>(bytes).and_then(|acc| {
@@ -50,12 +102,19 @@ pub unsafe fn load_acc_mut_unchecked<T: DataLen>(bytes: &mut [u8]) -> Result<&mu
     Ok(&mut *(bytes.as_mut_ptr() as *mut T))
 }
 
+/// Parse instruction data of exactly `T::LEN` bytes
+///
+/// Instruction data carries no alignment guarantee, so a buffer that isn't
+/// aligned for `T` is copied out instead of cast in place.
 #[inline(always)]
-pub unsafe fn load_ix_data<T: DataLen>(bytes: &[u8]) -> Result<&T, ProgramError> {
+pub fn parse_ix_data<T: DataLen + Pod>(bytes: &[u8]) -> Result<T, ProgramError> {
     if bytes.len() != T::LEN {
-        return Err(MyProgramError::InvalidInstructionData.into());
+        return Err(MyProgramError::InvalidIxDataLength.into());
     }
-    Ok(&*(bytes.as_ptr() as *const T))
+    Ok(match bytemuck::try_from_bytes::<T>(bytes) {
+        Ok(data) => *data,
+        Err(_) => bytemuck::pod_read_unaligned(bytes),
+    })
 }
 
 pub unsafe fn to_bytes<T: DataLen>(data: &T) -> &[u8] {
@@ -93,3 +152,84 @@ pub unsafe fn try_from_account_info_mut<T: DataLen>(
 
     Ok(&mut *(bytes.as_mut_ptr() as *mut T))
 }
+
+/// Resize a program-owned account to `len` bytes, zeroing any new space, with
+/// `payer` funding the rise in its rent-exempt minimum
+pub fn realloc_account(payer: &AccountInfo, account: &AccountInfo, len: usize) -> ProgramResult {
+    let shortfall = Rent::get()?
+        .minimum_balance(len)
+        .saturating_sub(account.lamports());
+    if shortfall > 0 {
+        Transfer {
+            from: payer,
+            to: account,
+            lamports: shortfall,
+        }
+        .invoke()?;
+    }
+    account.resize(len)
+}
+
+/// Create a rent-exempt, program-owned PDA of `space` bytes funded by `payer`
+///
+/// Anyone can send lamports to a PDA's address before it exists, which
+/// CreateAccount refuses; such an account is topped up to rent exemption,
+/// then allocated and assigned under the PDA's signature instead.
+pub fn create_pda_account(
+    payer: &AccountInfo,
+    account: &AccountInfo,
+    space: usize,
+    seeds: &[Seed],
+) -> ProgramResult {
+    let lamports = Rent::get()?.minimum_balance(space);
+    let signers = [Signer::from(seeds)];
+    let created = if account.lamports() == 0 {
+        CreateAccount {
+            from: payer,
+            to: account,
+            lamports,
+            space: space as u64,
+            owner: &crate::ID,
+        }
+        .invoke_signed(&signers)
+    } else {
+        adopt_prefunded_account(payer, account, lamports, space, &signers)
+    };
+    created.map_err(|e| {
+        log!(
+            "Error: creating a {} byte PDA failed: error {}",
+            space,
+            u64::from(e)
+        );
+        MyProgramError::AccountCreationFailed.into()
+    })
+}
+
+/// CreateAccount's effect on an address already holding lamports
+fn adopt_prefunded_account(
+    payer: &AccountInfo,
+    account: &AccountInfo,
+    lamports: u64,
+    space: usize,
+    signers: &[Signer],
+) -> ProgramResult {
+    let shortfall = lamports.saturating_sub(account.lamports());
+    if shortfall > 0 {
+        Transfer {
+            from: payer,
+            to: account,
+            lamports: shortfall,
+        }
+        .invoke()?;
+    }
+    Allocate {
+        account,
+        space: space as u64,
+    }
+    .invoke_signed(signers)?;
+    Assign {
+        account,
+        owner: &crate::ID,
+    }
+    .invoke_signed(signers)
+}
@@ -0,0 +1,74 @@
+//! Games the deploy engine can place deployments on. The EV/Kelly sizing is
+//! game-agnostic; a target supplies the accounts, the state decoding into the
+//! allocator's round view, and the CPI that places each deployment.
+
+pub mod ore;
+
+pub use ore::*;
+
+use pinocchio::{
+    account_info::AccountInfo, instruction::Signer, program_error::ProgramError, ProgramResult,
+};
+
+use crate::state::{GlobalConfig, OreRound, BOARD_SQUARES};
+
+/// The authority's standing in the target game
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct MinerPosition {
+    /// Round the per-square deployments belong to
+    pub round_id: u64,
+
+    /// Lamports deployed per square in `round_id`
+    pub deployed: [u64; BOARD_SQUARES],
+
+    /// SOL won across all rounds, the source settled winnings are synced from
+    pub lifetime_rewards_sol: u64,
+
+    /// Last round the game settled for the authority
+    pub checkpoint_id: u64,
+}
+
+impl MinerPosition {
+    /// Per-square deployments in `round_id`, zero when the position is from
+    /// an earlier round
+    pub fn deployed_in(&self, round_id: u64) -> [u64; BOARD_SQUARES] {
+        if self.round_id == round_id {
+            self.deployed
+        } else {
+            [0; BOARD_SQUARES]
+        }
+    }
+}
+
+/// A mining-style game whose rounds split a pot among the stakes on one
+/// winning square
+pub trait DeployTarget<'a>: Sized {
+    /// Split the target's accounts off the front of the deploy accounts,
+    /// returning the rest
+    fn from_accounts(
+        accounts: &'a [AccountInfo],
+    ) -> Result<(Self, &'a [AccountInfo]), ProgramError>;
+
+    /// Pays for and signs each deployment
+    fn signer(&self) -> &'a AccountInfo;
+
+    /// Owns the deployments; the program's per-authority accounts are keyed by it
+    fn authority(&self) -> &'a AccountInfo;
+
+    /// Check the accounts belong to a deployment of the game the config
+    /// permits, before anything decodes them
+    fn validate(&self, config: &GlobalConfig) -> ProgramResult;
+
+    /// The round being deployed into, checked to still accept deployments at
+    /// `slot`
+    fn read_open_round(&self, slot: u64) -> Result<OreRound, ProgramError>;
+
+    /// First slot at which the round no longer accepts deployments
+    fn round_end_slot(&self) -> Result<u64, ProgramError>;
+
+    /// The authority's position, `None` before its first deployment
+    fn read_position(&self) -> Result<Option<MinerPosition>, ProgramError>;
+
+    /// Deploy `amount` lamports on each square in `squares` (bit i = square i)
+    fn deploy(&self, amount: u64, squares: u32, signers: &[Signer]) -> ProgramResult;
+}
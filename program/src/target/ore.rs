@@ -0,0 +1,292 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    cpi::slice_invoke_signed,
+    instruction::{AccountMeta, Instruction, Signer},
+    program_error::ProgramError,
+    ProgramResult,
+};
+use pinocchio_log::log;
+
+use crate::{
+    error::MyProgramError,
+    state::{
+        check_entropy_accounts, check_ore_account, check_ore_pda, read_ore_board_data,
+        read_ore_miner_data, read_ore_round_data, GlobalConfig, OreAccount, OreMiner, OreRound,
+    },
+    target::{DeployTarget, MinerPosition},
+};
+
+/// ORE's own deploy instruction, the first byte of each deploy CPI
+pub const ORE_DEPLOY_CPI_DISCRIMINATOR: u8 = 6;
+
+/// ORE's ClaimSOL instruction, paying a miner's `rewards_sol` to its authority
+pub const ORE_CLAIM_SOL_CPI_DISCRIMINATOR: u8 = 3;
+
+/// ORE's ClaimORE instruction, paying a miner's `rewards_ore` to its
+/// authority's ORE token account
+pub const ORE_CLAIM_ORE_CPI_DISCRIMINATOR: u8 = 4;
+
+impl From<&OreMiner> for MinerPosition {
+    fn from(miner: &OreMiner) -> Self {
+        MinerPosition {
+            round_id: miner.round_id,
+            deployed: miner.deployed,
+            lifetime_rewards_sol: miner.lifetime_rewards_sol,
+            checkpoint_id: miner.checkpoint_id,
+        }
+    }
+}
+
+/// ORE v3, or a deployment of it the config permits
+pub struct OreTarget<'a> {
+    pub ore_program: &'a AccountInfo,
+    pub signer: &'a AccountInfo,
+    pub authority: &'a AccountInfo,
+    pub automation: &'a AccountInfo,
+    pub board: &'a AccountInfo,
+    pub miner: &'a AccountInfo,
+    pub round: &'a AccountInfo,
+    pub system_program: &'a AccountInfo,
+    pub entropy_var: &'a AccountInfo,
+    pub entropy_program: &'a AccountInfo,
+}
+
+impl<'a> DeployTarget<'a> for OreTarget<'a> {
+    fn from_accounts(
+        accounts: &'a [AccountInfo],
+    ) -> Result<(Self, &'a [AccountInfo]), ProgramError> {
+        let [ore_program, signer, authority, automation, board, miner, round, system_program, entropy_var, entropy_program, rest @ ..] =
+            accounts
+        else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+        let target = OreTarget {
+            ore_program,
+            signer,
+            authority,
+            automation,
+            board,
+            miner,
+            round,
+            system_program,
+            entropy_var,
+            entropy_program,
+        };
+        Ok((target, rest))
+    }
+
+    fn signer(&self) -> &'a AccountInfo {
+        self.signer
+    }
+
+    fn authority(&self) -> &'a AccountInfo {
+        self.authority
+    }
+
+    fn validate(&self, config: &GlobalConfig) -> ProgramResult {
+        let ore_program = self.ore_program.key();
+
+        // Every deploy CPI targets this program, so it must be a known ORE
+        if !config.is_allowed_ore_program(ore_program) {
+            log!("Error: ore_program is not an allowed ORE program");
+            return Err(MyProgramError::InvalidOreProgram.into());
+        }
+
+        // The round is checked as it is read
+        check_ore_account(self.board, ore_program, OreAccount::Board)?;
+        check_ore_account(self.miner, ore_program, OreAccount::Miner)?;
+        check_ore_account(self.automation, ore_program, OreAccount::Automation)?;
+
+        // A keeper could otherwise deploy through another authority's automation,
+        // and a wrong miner would only fail deep inside ORE's deploy
+        check_ore_pda(
+            self.automation,
+            self.authority,
+            ore_program,
+            OreAccount::Automation,
+        )?;
+        check_ore_pda(self.miner, self.authority, ore_program, OreAccount::Miner)?;
+
+        // Every deployment is checked against an entropy program, the override
+        // included, so a substitute can't slip through unchecked
+        let Some(expected) = config.entropy_program_for(ore_program) else {
+            return Err(MyProgramError::InvalidOreProgram.into());
+        };
+        check_entropy_accounts(
+            self.entropy_program,
+            self.entropy_var,
+            self.board,
+            &expected,
+        )
+    }
+
+    fn read_open_round(&self, slot: u64) -> Result<OreRound, ProgramError> {
+        let round_data = read_round_data(self.ore_program, self.round)?;
+
+        // Settled rounds stay around for claims; deploying into one is wasted
+        let board_data = {
+            let data = self.board.try_borrow_data()?;
+            *read_ore_board_data(&data)?
+        };
+        if round_data.id != board_data.round_id {
+            log!(
+                "Error: round {} is not the board's current round {}",
+                round_data.id,
+                board_data.round_id
+            );
+            return Err(MyProgramError::StaleRound.into());
+        }
+
+        // Once the end slot's hash is drawn, or the end slot reached, the round
+        // can no longer be won
+        if round_data.slot_hash != [0; 32] {
+            log!("Error: round {} has already ended", round_data.id);
+            return Err(MyProgramError::RoundAlreadyEnded.into());
+        }
+        if !board_data.is_open(slot) {
+            log!(
+                "Error: slot {} is at or past the round's end slot {}",
+                slot,
+                board_data.end_slot
+            );
+            return Err(MyProgramError::RoundExpiring.into());
+        }
+
+        Ok(round_data)
+    }
+
+    fn round_end_slot(&self) -> Result<u64, ProgramError> {
+        let data = self.board.try_borrow_data()?;
+        Ok(read_ore_board_data(&data)?.end_slot)
+    }
+
+    fn read_position(&self) -> Result<Option<MinerPosition>, ProgramError> {
+        // A miner that hasn't been created yet has no data
+        if self.miner.data_len() == 0 {
+            return Ok(None);
+        }
+        let data = self.miner.try_borrow_data()?;
+        Ok(Some(read_ore_miner_data(&data)?.into()))
+    }
+
+    fn deploy(&self, amount: u64, squares: u32, signers: &[Signer]) -> ProgramResult {
+        let mut instruction_data = [0u8; 13];
+        instruction_data[0..1].copy_from_slice(&ORE_DEPLOY_CPI_DISCRIMINATOR.to_le_bytes());
+        instruction_data[1..9].copy_from_slice(&amount.to_le_bytes());
+        instruction_data[9..13].copy_from_slice(&squares.to_le_bytes());
+
+        let account_metas: [AccountMeta; 9] = [
+            AccountMeta::writable_signer(self.signer.key()),
+            AccountMeta::writable_signer(self.authority.key()),
+            AccountMeta::writable(self.automation.key()),
+            AccountMeta::writable(self.board.key()),
+            AccountMeta::writable(self.miner.key()),
+            AccountMeta::writable(self.round.key()),
+            AccountMeta::readonly(self.system_program.key()),
+            AccountMeta::writable(self.entropy_var.key()),
+            AccountMeta::readonly(self.entropy_program.key()),
+        ];
+
+        let instruction = Instruction {
+            program_id: self.ore_program.key(),
+            accounts: &account_metas,
+            data: &instruction_data,
+        };
+
+        let account_refs: [&AccountInfo; 9] = [
+            self.signer,
+            self.authority,
+            self.automation,
+            self.board,
+            self.miner,
+            self.round,
+            self.system_program,
+            self.entropy_var,
+            self.entropy_program,
+        ];
+
+        slice_invoke_signed(&instruction, &account_refs, signers)
+    }
+}
+
+/// The accounts ORE's ClaimORE reads besides the miner and its authority
+pub struct OreClaimAccounts<'a> {
+    pub mint: &'a AccountInfo,
+    /// The authority's ORE token account, created by ORE if missing
+    pub recipient: &'a AccountInfo,
+    pub treasury: &'a AccountInfo,
+    pub treasury_tokens: &'a AccountInfo,
+    pub system_program: &'a AccountInfo,
+    pub token_program: &'a AccountInfo,
+    pub associated_token_program: &'a AccountInfo,
+}
+
+/// Claim the SOL `miner` has been credited to its `authority`
+pub(crate) fn claim_sol(
+    ore_program: &AccountInfo,
+    authority: &AccountInfo,
+    miner: &AccountInfo,
+    system_program: &AccountInfo,
+    signers: &[Signer],
+) -> ProgramResult {
+    let account_metas = [
+        AccountMeta::writable_signer(authority.key()),
+        AccountMeta::writable(miner.key()),
+        AccountMeta::readonly(system_program.key()),
+    ];
+    let instruction = Instruction {
+        program_id: ore_program.key(),
+        accounts: &account_metas,
+        data: &[ORE_CLAIM_SOL_CPI_DISCRIMINATOR],
+    };
+    slice_invoke_signed(&instruction, &[authority, miner, system_program], signers)
+}
+
+/// Claim the ORE `miner` has been credited to its `authority`'s token account
+pub(crate) fn claim_ore(
+    ore_program: &AccountInfo,
+    authority: &AccountInfo,
+    miner: &AccountInfo,
+    accounts: &OreClaimAccounts,
+    signers: &[Signer],
+) -> ProgramResult {
+    let account_metas = [
+        AccountMeta::writable_signer(authority.key()),
+        AccountMeta::writable(miner.key()),
+        AccountMeta::writable(accounts.mint.key()),
+        AccountMeta::writable(accounts.recipient.key()),
+        AccountMeta::writable(accounts.treasury.key()),
+        AccountMeta::writable(accounts.treasury_tokens.key()),
+        AccountMeta::readonly(accounts.system_program.key()),
+        AccountMeta::readonly(accounts.token_program.key()),
+        AccountMeta::readonly(accounts.associated_token_program.key()),
+    ];
+    let instruction = Instruction {
+        program_id: ore_program.key(),
+        accounts: &account_metas,
+        data: &[ORE_CLAIM_ORE_CPI_DISCRIMINATOR],
+    };
+    let account_refs = [
+        authority,
+        miner,
+        accounts.mint,
+        accounts.recipient,
+        accounts.treasury,
+        accounts.treasury_tokens,
+        accounts.system_program,
+        accounts.token_program,
+        accounts.associated_token_program,
+    ];
+    slice_invoke_signed(&instruction, &account_refs, signers)
+}
+
+/// Decode the ORE round account after checking it is one
+pub(crate) fn read_round_data(
+    ore_program: &AccountInfo,
+    round: &AccountInfo,
+) -> Result<OreRound, ProgramError> {
+    check_ore_account(round, ore_program.key(), OreAccount::Round)?;
+    let data = round.try_borrow_data()?;
+    let decoded_round = read_ore_round_data(&data)?;
+    Ok(*decoded_round)
+}
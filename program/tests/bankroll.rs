@@ -0,0 +1,116 @@
+//! SetBankroll under Mollusk: the winnings checkpoint is read from the
+//! authority's own ORE miner, and from no other account.
+
+mod common;
+
+use common::{account_with, Fixture, ORE_PROGRAM_ID, PROGRAM_ID};
+use mollusk_svm::{program, result::Check};
+use solana_pinocchio_starter::{
+    error::MyProgramError,
+    instruction::{discriminator::SET_BANKROLL, SetBankrollIxData},
+    state::{Bankroll, OreAccount, OreMiner, BANKROLL_SEED, MINER_SEED},
+};
+use solana_sdk::{
+    account::Account,
+    instruction::{AccountMeta, Instruction},
+    program_error::ProgramError,
+    pubkey::Pubkey,
+};
+
+const CAPITAL: u64 = 10_000_000_000;
+
+fn set_bankroll_ix(fixture: &Fixture, miner: Pubkey) -> (Instruction, Vec<(Pubkey, Account)>) {
+    let bankroll =
+        Pubkey::find_program_address(&[BANKROLL_SEED, fixture.signer.as_ref()], &PROGRAM_ID).0;
+    let mut data = vec![SET_BANKROLL];
+    data.extend_from_slice(bytemuck::bytes_of(&SetBankrollIxData { capital: CAPITAL }));
+    let ix = Instruction {
+        program_id: PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new(fixture.signer, true),
+            AccountMeta::new_readonly(ORE_PROGRAM_ID, false),
+            AccountMeta::new_readonly(fixture.config, false),
+            AccountMeta::new_readonly(miner, false),
+            AccountMeta::new(bankroll, false),
+            AccountMeta::new_readonly(program::keyed_account_for_system_program().0, false),
+        ],
+        data,
+    };
+
+    let mut accounts = fixture.accounts();
+    accounts.push((bankroll, Account::default()));
+    (ix, accounts)
+}
+
+/// An ORE miner with `lifetime_rewards_sol` won, owned by `owner`
+fn miner_account(authority: &Pubkey, lifetime_rewards_sol: u64, owner: &Pubkey) -> Account {
+    let mut miner: OreMiner = bytemuck::Zeroable::zeroed();
+    miner._disc = OreAccount::Miner.discriminator();
+    miner.authority = authority.to_bytes();
+    miner.lifetime_rewards_sol = lifetime_rewards_sol;
+    account_with(bytemuck::bytes_of(&miner), owner)
+}
+
+#[test]
+fn checkpoints_the_authoritys_miner() {
+    let fixture = Fixture::new();
+    let (ix, mut accounts) = set_bankroll_ix(&fixture, fixture.miner);
+    let miner = accounts
+        .iter_mut()
+        .find(|(key, _)| *key == fixture.miner)
+        .unwrap();
+    miner.1 = miner_account(&fixture.signer, 3_000_000_000, &ORE_PROGRAM_ID);
+
+    let result =
+        fixture
+            .mollusk
+            .process_and_validate_instruction(&ix, &accounts, &[Check::success()]);
+    let bankroll = result.get_account(&ix.accounts[4].pubkey).unwrap();
+    let bankroll: Bankroll = bytemuck::pod_read_unaligned(&bankroll.data);
+    assert_eq!(
+        (bankroll.capital, bankroll.rewards_checkpoint),
+        (CAPITAL, 3_000_000_000)
+    );
+}
+
+#[test]
+fn rejects_a_miner_ore_does_not_own() {
+    let fixture = Fixture::new();
+    let (ix, mut accounts) = set_bankroll_ix(&fixture, fixture.miner);
+    let miner = accounts
+        .iter_mut()
+        .find(|(key, _)| *key == fixture.miner)
+        .unwrap();
+    miner.1 = miner_account(&fixture.signer, u64::MAX, &Pubkey::new_unique());
+
+    fixture.mollusk.process_and_validate_instruction(
+        &ix,
+        &accounts,
+        &[Check::err(ProgramError::Custom(
+            MyProgramError::InvalidMinerAccount as u32,
+        ))],
+    );
+}
+
+#[test]
+fn rejects_another_authoritys_miner() {
+    let fixture = Fixture::new();
+    let other = Pubkey::find_program_address(
+        &[MINER_SEED, Pubkey::new_unique().as_ref()],
+        &ORE_PROGRAM_ID,
+    )
+    .0;
+    let (ix, mut accounts) = set_bankroll_ix(&fixture, other);
+    accounts.push((
+        other,
+        miner_account(&fixture.signer, u64::MAX, &ORE_PROGRAM_ID),
+    ));
+
+    fixture.mollusk.process_and_validate_instruction(
+        &ix,
+        &accounts,
+        &[Check::err(ProgramError::Custom(
+            MyProgramError::InvalidMinerAccount as u32,
+        ))],
+    );
+}
@@ -0,0 +1,235 @@
+//! Shared Mollusk fixture: the program plus the mock ORE program, a funded
+//! signer, the GlobalConfig PDA and a synthetic round.
+//!
+//! Both programs must be built first (`cargo build-sbf` in `program/` and
+//! `mock-ore/`), with `SBF_OUT_DIR` pointing at the `target/deploy` they
+//! land in.
+
+#![allow(dead_code)]
+
+use mollusk_svm::{program, Mollusk};
+use solana_pinocchio_starter::{
+    instruction::{OreDeployIxData, OreDeployResult},
+    state::{
+        AccountDiscriminator, ConfigParams, GlobalConfig, OreAccount, OreBoard, OreRound,
+        Versioned, AUTOMATION_SEED, ENTROPY_VAR_SEED, GLOBAL_CONFIG_SEED, MINER_SEED,
+        ORE_ENTROPY_VAR_ID,
+    },
+};
+use solana_sdk::{
+    account::Account,
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+};
+
+pub const PROGRAM_ID: Pubkey = Pubkey::new_from_array(solana_pinocchio_starter::ID);
+
+/// The mock ORE program is loaded at the real ORE address, which the deploy
+/// allowlist accepts
+pub const ORE_PROGRAM_ID: Pubkey =
+    Pubkey::new_from_array(solana_pinocchio_starter::state::ORE_PROGRAM_ID);
+
+pub const ENTROPY_PROGRAM_ID: Pubkey =
+    Pubkey::new_from_array(solana_pinocchio_starter::state::ENTROPY_PROGRAM_ID);
+
+pub use solana_pinocchio_starter::instruction::discriminator::ORE_DEPLOY;
+
+pub const SIGNER_LAMPORTS: u64 = 1_000_000_000_000;
+
+/// Lamports on each thin square of the synthetic round
+pub const THIN_SQUARE: u64 = 20_000_000;
+
+/// Squares funded at `THIN_SQUARE`; the rest hold a deep pool
+pub const THIN_SQUARES: [usize; 12] = [0, 2, 4, 6, 8, 10, 12, 14, 16, 18, 20, 22];
+
+pub struct Fixture {
+    pub mollusk: Mollusk,
+    pub signer: Pubkey,
+    pub round: Pubkey,
+    pub config: Pubkey,
+    pub automation: Pubkey,
+    pub board: Pubkey,
+    pub miner: Pubkey,
+    pub entropy_var: Pubkey,
+    pub entropy_program: Pubkey,
+}
+
+impl Fixture {
+    pub fn new() -> Self {
+        let mut mollusk = Mollusk::new(&PROGRAM_ID, "solana_pinocchio_starter");
+        mollusk.add_program(
+            &ORE_PROGRAM_ID,
+            "mock_ore",
+            &program::loader_keys::LOADER_V3,
+        );
+        let signer = Pubkey::new_unique();
+        let board = Pubkey::new_unique();
+        Fixture {
+            mollusk,
+            signer,
+            round: Pubkey::new_unique(),
+            config: Pubkey::find_program_address(&[GLOBAL_CONFIG_SEED], &PROGRAM_ID).0,
+            automation: Pubkey::find_program_address(
+                &[AUTOMATION_SEED, signer.as_ref()],
+                &ORE_PROGRAM_ID,
+            )
+            .0,
+            board,
+            miner: Pubkey::find_program_address(&[MINER_SEED, signer.as_ref()], &ORE_PROGRAM_ID).0,
+            entropy_var: entropy_var_address(&board),
+            entropy_program: ENTROPY_PROGRAM_ID,
+        }
+    }
+
+    /// OreDeploy over the fixture accounts, sized for `num_blocks` blocks
+    pub fn deploy_ix(&self, total_amount: u64, num_blocks: u8) -> Instruction {
+        let ix_data = OreDeployIxData {
+            total_amount,
+            ore_price_lamports: 1_000_000_000,
+            min_ev_threshold: -10_000,
+            num_blocks,
+            max_cpis: num_blocks,
+            ..bytemuck::Zeroable::zeroed()
+        };
+        let mut data = vec![ORE_DEPLOY];
+        data.extend_from_slice(bytemuck::bytes_of(&ix_data));
+
+        Instruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new_readonly(ORE_PROGRAM_ID, false),
+                AccountMeta::new(self.signer, true),
+                AccountMeta::new(self.signer, true),
+                AccountMeta::new(self.automation, false),
+                AccountMeta::new(self.board, false),
+                AccountMeta::new(self.miner, false),
+                AccountMeta::new(self.round, false),
+                AccountMeta::new_readonly(program::keyed_account_for_system_program().0, false),
+                AccountMeta::new(self.entropy_var, false),
+                AccountMeta::new_readonly(self.entropy_program, false),
+                AccountMeta::new_readonly(self.config, false),
+            ],
+            data,
+        }
+    }
+
+    /// Accounts for `deploy_ix`, once each (the signer is also the authority)
+    pub fn accounts(&self) -> Vec<(Pubkey, Account)> {
+        let empty = Account::new(0, 0, &solana_sdk::system_program::ID);
+        vec![
+            (
+                ORE_PROGRAM_ID,
+                program::create_program_account_loader_v3(&ORE_PROGRAM_ID),
+            ),
+            (
+                self.signer,
+                Account::new(SIGNER_LAMPORTS, 0, &solana_sdk::system_program::ID),
+            ),
+            (self.automation, empty.clone()),
+            (self.board, self.board_account()),
+            (self.miner, empty.clone()),
+            (self.round, self.round_account()),
+            program::keyed_account_for_system_program(),
+            (
+                self.entropy_var,
+                account_with(&[0; 64], &ENTROPY_PROGRAM_ID),
+            ),
+            (self.entropy_program, empty),
+            (self.config, self.config_account()),
+        ]
+    }
+
+    fn round_account(&self) -> Account {
+        let mut round: OreRound = bytemuck::Zeroable::zeroed();
+        round._disc = OreAccount::Round.discriminator();
+        round.id = 1;
+        round.deployed = [2_000_000_000; 25];
+        round.count = [50; 25];
+        for square in THIN_SQUARES {
+            round.deployed[square] = THIN_SQUARE;
+            round.count[square] = 3;
+        }
+        round.total_deployed = round.deployed.iter().sum();
+        account_with(bytemuck::bytes_of(&round), &ORE_PROGRAM_ID)
+    }
+
+    /// Board whose current round is the fixture's round
+    fn board_account(&self) -> Account {
+        let board = OreBoard {
+            _disc: OreAccount::Board.discriminator(),
+            round_id: 1,
+            start_slot: 0,
+            end_slot: u64::MAX,
+        };
+        account_with(bytemuck::bytes_of(&board), &ORE_PROGRAM_ID)
+    }
+
+    fn config_account(&self) -> Account {
+        let (_, bump) = Pubkey::find_program_address(&[GLOBAL_CONFIG_SEED], &PROGRAM_ID);
+        let config = GlobalConfig {
+            discriminator: AccountDiscriminator::GlobalConfig as u8,
+            bump,
+            version: GlobalConfig::VERSION,
+            _padding: [0; 5],
+            admin: self.signer.to_bytes(),
+            params: default_params(),
+            ore_program_override: [0; 32],
+            payout_multipliers_bps: [0; 25],
+            _padding2: [0; 6],
+            ore_environments: Default::default(),
+        };
+        account_with(bytemuck::bytes_of(&config), &PROGRAM_ID)
+    }
+}
+
+/// ORE's fees with the fee-free Kelly constant for 25 squares
+pub fn default_params() -> ConfigParams {
+    ConfigParams {
+        protocol_fee_bps: 1000,
+        refining_fee_bps: 1000,
+        admin_fee_bps: 101,
+        wrapper_fee_bps: 0,
+        kelly_c_scaled: 24_000_000_000,
+        min_ore_price_lamports: 1,
+        max_ore_price_lamports: u64::MAX,
+        max_round_deploy_lamports: 0,
+        min_total_deployed: 0,
+        min_deploy_per_block: 10_000,
+        motherlode_odds: 625,
+        motherlode_split: 0,
+        _padding2: [0; 3],
+        drawdown_trigger_bps: 0,
+        drawdown_scale_bps: 10_000,
+        max_threshold_adjust_bps: 0,
+        _padding3: [0; 2],
+    }
+}
+
+/// The entropy var ORE draws `board`'s randomness from
+pub fn entropy_var_address(board: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[
+            ENTROPY_VAR_SEED,
+            board.as_ref(),
+            &ORE_ENTROPY_VAR_ID.to_le_bytes(),
+        ],
+        &ENTROPY_PROGRAM_ID,
+    )
+    .0
+}
+
+/// Rent-exempt account holding `data`, owned by `owner`
+pub fn account_with(data: &[u8], owner: &Pubkey) -> Account {
+    Account {
+        lamports: 1_000_000_000,
+        data: data.to_vec(),
+        owner: *owner,
+        executable: false,
+        rent_epoch: 0,
+    }
+}
+
+/// Decode the plan returned by OreDeploy
+pub fn read_result(return_data: &[u8]) -> OreDeployResult {
+    bytemuck::pod_read_unaligned(return_data)
+}
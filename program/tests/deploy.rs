@@ -0,0 +1,487 @@
+//! OreDeploy end to end under Mollusk: sizing, the deploy CPIs into the mock
+//! ORE program, and compute-unit ceilings per plan size.
+
+mod common;
+
+use common::{
+    account_with, read_result, Fixture, ORE_PROGRAM_ID, PROGRAM_ID, SIGNER_LAMPORTS, THIN_SQUARE,
+    THIN_SQUARES,
+};
+use mollusk_svm::result::Check;
+use solana_pinocchio_starter::{
+    error::MyProgramError,
+    instruction::{
+        BlockQuote, OreDeployIxData, OreDeployIxDataV3, DEPLOY_CPI_COMPUTE_UNITS,
+        FLAG_DROP_SLIPPED_BLOCKS, FLAG_NONCE, FLAG_ROUND_RECORD, FLAG_STORE_PLAN, FLAG_TRACK_STATS,
+        PLAN_COMPUTE_UNITS, POST_DEPLOY_COMPUTE_UNITS,
+    },
+    state::{
+        DataLen, DeployNonce, OreAccount, OreBoard, OreMiner, OreRound, Plan, RoundRecord,
+        UserStats, DEPLOY_NONCE_SEED, PLAN_SEED, ROUND_RECORD_SEED, USER_STATS_SEED,
+    },
+};
+use solana_sdk::{
+    account::Account,
+    instruction::{AccountMeta, Instruction},
+    program_error::ProgramError,
+    pubkey::Pubkey,
+};
+
+const BUDGET: u64 = 1_000_000_000;
+
+#[test]
+fn deploys_the_plan_through_ore() {
+    let fixture = Fixture::new();
+    let ix = fixture.deploy_ix(BUDGET, 5);
+    let accounts = fixture.accounts();
+
+    let result =
+        fixture
+            .mollusk
+            .process_and_validate_instruction(&ix, &accounts, &[Check::success()]);
+    let plan = read_result(&result.return_data);
+    let count = plan.num_blocks as usize;
+    assert!(count > 0 && count <= 5, "{count} blocks");
+    assert!(plan.total_deployed <= BUDGET);
+    assert_eq!(
+        plan.amounts[..count].iter().sum::<u64>(),
+        plan.total_deployed
+    );
+
+    // Only the thin squares are worth funding
+    for &index in &plan.indices[..count] {
+        assert!(THIN_SQUARES.contains(&(index as usize)), "square {index}");
+    }
+
+    // The mock ORE program saw every deploy
+    let round = result.get_account(&fixture.round).unwrap();
+    let round: OreRound = bytemuck::pod_read_unaligned(&round.data);
+    for (index, amount) in plan.indices[..count].iter().zip(&plan.amounts[..count]) {
+        assert_eq!(round.deployed[*index as usize], THIN_SQUARE + amount);
+    }
+    let signer = result.get_account(&fixture.signer).unwrap();
+    assert_eq!(signer.lamports, SIGNER_LAMPORTS - plan.total_deployed);
+}
+
+#[test]
+fn compute_units_stay_within_budget_per_plan_size() {
+    let fixture = Fixture::new();
+    let accounts = fixture.accounts();
+
+    for num_blocks in [1u8, 2, 4, 8] {
+        let ix = fixture.deploy_ix(BUDGET, num_blocks);
+        let result =
+            fixture
+                .mollusk
+                .process_and_validate_instruction(&ix, &accounts, &[Check::success()]);
+        let plan = read_result(&result.return_data);
+        assert_eq!(plan.num_blocks, num_blocks, "plan size");
+
+        // The program reserves these per CPI when truncating plans, so
+        // exceeding them means a plan the truncation would let through can
+        // run out of compute
+        let ceiling = PLAN_COMPUTE_UNITS
+            + DEPLOY_CPI_COMPUTE_UNITS * num_blocks as u64
+            + POST_DEPLOY_COMPUTE_UNITS;
+        assert!(
+            result.compute_units_consumed <= ceiling,
+            "{num_blocks} blocks used {} CUs, ceiling {ceiling}",
+            result.compute_units_consumed
+        );
+    }
+}
+
+#[test]
+fn rejects_a_config_at_the_wrong_address() {
+    // A well-formed GlobalConfig, but not at the config PDA
+    let mut fixture = Fixture::new();
+    fixture.config = Pubkey::new_unique();
+    let ix = fixture.deploy_ix(BUDGET, 5);
+    let accounts = fixture.accounts();
+
+    let result = fixture.mollusk.process_instruction(&ix, &accounts);
+    assert!(result.program_result.is_err());
+}
+
+#[test]
+fn rejects_a_round_not_owned_by_ore() {
+    let fixture = Fixture::new();
+    let ix = fixture.deploy_ix(BUDGET, 5);
+    let mut accounts = fixture.accounts();
+    let (_, round) = accounts
+        .iter_mut()
+        .find(|(key, _)| *key == fixture.round)
+        .unwrap();
+    round.owner = Pubkey::new_unique();
+
+    let result = fixture.mollusk.process_instruction(&ix, &accounts);
+    assert!(result.program_result.is_err());
+}
+
+#[test]
+fn rejects_an_unknown_ore_program() {
+    let fixture = Fixture::new();
+    let mut ix = fixture.deploy_ix(BUDGET, 5);
+    let mut accounts = fixture.accounts();
+    let impostor = Pubkey::new_unique();
+    ix.accounts[0].pubkey = impostor;
+    accounts[0].0 = impostor;
+
+    let result = fixture.mollusk.process_instruction(&ix, &accounts);
+    assert!(result.program_result.is_err());
+}
+
+#[test]
+fn rejects_an_entropy_var_of_another_board() {
+    let fixture = Fixture::new();
+    let mut ix = fixture.deploy_ix(BUDGET, 5);
+    let mut accounts = fixture.accounts();
+    let other = common::entropy_var_address(&Pubkey::new_unique());
+    ix.accounts[8].pubkey = other;
+    accounts[7].0 = other;
+
+    let result = fixture.mollusk.process_instruction(&ix, &accounts);
+    assert!(result.program_result.is_err());
+}
+
+#[test]
+fn rejects_another_authoritys_automation() {
+    let fixture = Fixture::new();
+    let mut ix = fixture.deploy_ix(BUDGET, 5);
+    let mut accounts = fixture.accounts();
+    let other = Pubkey::new_unique();
+    ix.accounts[3].pubkey = other;
+    accounts[2].0 = other;
+
+    let result = fixture.mollusk.process_instruction(&ix, &accounts);
+    assert!(result.program_result.is_err());
+}
+
+#[test]
+fn rejects_another_authoritys_miner() {
+    let fixture = Fixture::new();
+    let mut ix = fixture.deploy_ix(BUDGET, 5);
+    let mut accounts = fixture.accounts();
+    let other = Pubkey::new_unique();
+    ix.accounts[5].pubkey = other;
+    accounts[4].0 = other;
+
+    let result = fixture.mollusk.process_instruction(&ix, &accounts);
+    assert!(result.program_result.is_err());
+}
+
+#[test]
+fn rejects_a_round_the_board_has_moved_past() {
+    let fixture = Fixture::new();
+    let ix = fixture.deploy_ix(BUDGET, 5);
+    let mut accounts = fixture.accounts();
+    let round: &mut OreRound = bytemuck::from_bytes_mut(&mut accounts[5].1.data);
+    round.id = 0;
+
+    let result = fixture.mollusk.process_instruction(&ix, &accounts);
+    assert!(result.program_result.is_err());
+}
+
+#[test]
+fn rejects_an_unsigned_signer_and_authority() {
+    let fixture = Fixture::new();
+    let mut ix = fixture.deploy_ix(BUDGET, 5);
+    ix.accounts[2].is_signer = false;
+    ix.accounts[1].is_signer = false;
+
+    let result = fixture
+        .mollusk
+        .process_instruction(&ix, &fixture.accounts());
+    assert!(result.program_result.is_err());
+}
+
+#[test]
+fn rejects_a_round_past_its_end_slot() {
+    let mut fixture = Fixture::new();
+    let ix = fixture.deploy_ix(BUDGET, 5);
+    let mut accounts = fixture.accounts();
+    let board: &mut OreBoard = bytemuck::from_bytes_mut(&mut accounts[3].1.data);
+    board.end_slot = 100;
+    fixture.mollusk.warp_to_slot(100);
+
+    let result = fixture.mollusk.process_instruction(&ix, &accounts);
+    assert!(result.program_result.is_err());
+}
+
+#[test]
+fn rejects_a_round_with_its_slot_hash_drawn() {
+    let fixture = Fixture::new();
+    let ix = fixture.deploy_ix(BUDGET, 5);
+    let mut accounts = fixture.accounts();
+    let round: &mut OreRound = bytemuck::from_bytes_mut(&mut accounts[5].1.data);
+    round.slot_hash = [7; 32];
+
+    let result = fixture.mollusk.process_instruction(&ix, &accounts);
+    assert!(result.program_result.is_err());
+}
+
+#[test]
+fn rejects_a_pool_grown_past_max_total_deployed() {
+    let fixture = Fixture::new();
+    let accounts = fixture.accounts();
+    let round: OreRound = bytemuck::pod_read_unaligned(&accounts[5].1.data);
+    let mut ix = fixture.deploy_ix(BUDGET, 5);
+    let mut ix_data: OreDeployIxData = bytemuck::pod_read_unaligned(&ix.data[1..]);
+    ix_data.max_total_deployed = round.total_deployed - 1;
+    ix.data[1..].copy_from_slice(bytemuck::bytes_of(&ix_data));
+
+    let result = fixture.mollusk.process_instruction(&ix, &accounts);
+    assert!(result.program_result.is_err());
+}
+
+/// Re-encode a fixture deploy as V3 data quoted against `quote`
+fn with_quote(ix: &mut Instruction, quote: BlockQuote, flags: u32) {
+    let mut ix_data: OreDeployIxDataV3 = bytemuck::Zeroable::zeroed();
+    ix_data.v2.base = bytemuck::pod_read_unaligned(&ix.data[1..]);
+    ix_data.v2.base.flags |= flags;
+    ix_data.quote = quote;
+    ix.data.truncate(1);
+    ix.data.extend_from_slice(bytemuck::bytes_of(&ix_data));
+}
+
+/// `squares` (thin) quoted at half their size, with 1% slippage allowed
+fn thin_squares_quoted_at_half(squares: &[usize]) -> BlockQuote {
+    let mut quote: BlockQuote = bytemuck::Zeroable::zeroed();
+    quote.block_slippage_bps = 100;
+    for &square in squares {
+        quote.quoted_squares |= 1 << square;
+        quote.quoted_deployed[square] = THIN_SQUARE / 2;
+    }
+    quote
+}
+
+#[test]
+fn rejects_quoted_squares_grown_past_their_slippage_bound() {
+    let fixture = Fixture::new();
+    let mut ix = fixture.deploy_ix(BUDGET, 5);
+    with_quote(&mut ix, thin_squares_quoted_at_half(&THIN_SQUARES), 0);
+
+    let result = fixture
+        .mollusk
+        .process_instruction(&ix, &fixture.accounts());
+    assert!(result.program_result.is_err());
+}
+
+#[test]
+fn drops_slipped_squares_when_asked() {
+    let fixture = Fixture::new();
+    let mut ix = fixture.deploy_ix(BUDGET, 5);
+    let slipped = &THIN_SQUARES[..6];
+    with_quote(
+        &mut ix,
+        thin_squares_quoted_at_half(slipped),
+        FLAG_DROP_SLIPPED_BLOCKS,
+    );
+
+    let result = fixture.mollusk.process_and_validate_instruction(
+        &ix,
+        &fixture.accounts(),
+        &[Check::success()],
+    );
+    let plan = read_result(&result.return_data);
+    assert!(plan.num_blocks > 0);
+    for &index in &plan.indices[..plan.num_blocks as usize] {
+        assert!(!slipped.contains(&(index as usize)), "square {index}");
+    }
+}
+
+#[test]
+fn a_nonce_checked_deploy_cannot_be_replayed() {
+    let fixture = Fixture::new();
+    let mut ix = fixture.deploy_ix(BUDGET, 5);
+    let mut ix_data: OreDeployIxData = bytemuck::pod_read_unaligned(&ix.data[1..]);
+    ix_data.flags |= FLAG_NONCE;
+    ix_data.expected_nonce = 0;
+    ix.data[1..].copy_from_slice(bytemuck::bytes_of(&ix_data));
+
+    let (nonce, _) =
+        Pubkey::find_program_address(&[DEPLOY_NONCE_SEED, fixture.signer.as_ref()], &PROGRAM_ID);
+    ix.accounts.push(AccountMeta::new(nonce, false));
+    let mut accounts = fixture.accounts();
+    accounts.push((nonce, Account::default()));
+
+    let result =
+        fixture
+            .mollusk
+            .process_and_validate_instruction(&ix, &accounts, &[Check::success()]);
+    let stored: DeployNonce =
+        bytemuck::pod_read_unaligned(&result.get_account(&nonce).unwrap().data);
+    assert_eq!(stored.nonce, 1);
+
+    // The same transaction replayed against the advanced nonce
+    let replayed = fixture
+        .mollusk
+        .process_instruction(&ix, &result.resulting_accounts);
+    assert!(replayed.program_result.is_err());
+}
+
+#[test]
+fn a_pda_funded_before_creation_is_still_created() {
+    let fixture = Fixture::new();
+    let mut ix = fixture.deploy_ix(BUDGET, 5);
+    let mut ix_data: OreDeployIxData = bytemuck::pod_read_unaligned(&ix.data[1..]);
+    ix_data.flags |= FLAG_NONCE;
+    ix.data[1..].copy_from_slice(bytemuck::bytes_of(&ix_data));
+
+    // A lamport sent to the address ahead of the first deploy
+    let (nonce, _) =
+        Pubkey::find_program_address(&[DEPLOY_NONCE_SEED, fixture.signer.as_ref()], &PROGRAM_ID);
+    ix.accounts.push(AccountMeta::new(nonce, false));
+    let mut accounts = fixture.accounts();
+    accounts.push((nonce, Account::new(1, 0, &solana_sdk::system_program::ID)));
+
+    let result =
+        fixture
+            .mollusk
+            .process_and_validate_instruction(&ix, &accounts, &[Check::success()]);
+    let account = result.get_account(&nonce).unwrap();
+    assert_eq!(account.owner, PROGRAM_ID);
+    assert_eq!(account.data.len(), DeployNonce::LEN);
+    let stored: DeployNonce = bytemuck::pod_read_unaligned(&account.data);
+    assert_eq!(stored.nonce, 1);
+}
+
+#[test]
+fn records_the_plan_in_the_round_record() {
+    let fixture = Fixture::new();
+    let mut ix = fixture.deploy_ix(BUDGET, 5);
+    let mut ix_data: OreDeployIxData = bytemuck::pod_read_unaligned(&ix.data[1..]);
+    ix_data.flags |= FLAG_ROUND_RECORD;
+    ix.data[1..].copy_from_slice(bytemuck::bytes_of(&ix_data));
+
+    let (record, _) = Pubkey::find_program_address(
+        &[
+            ROUND_RECORD_SEED,
+            fixture.signer.as_ref(),
+            &1u64.to_le_bytes(),
+        ],
+        &PROGRAM_ID,
+    );
+    ix.accounts.push(AccountMeta::new(record, false));
+    let mut accounts = fixture.accounts();
+    accounts.push((record, Account::default()));
+
+    let result =
+        fixture
+            .mollusk
+            .process_and_validate_instruction(&ix, &accounts, &[Check::success()]);
+    let plan = read_result(&result.return_data);
+    let stored: RoundRecord =
+        bytemuck::pod_read_unaligned(&result.get_account(&record).unwrap().data);
+    assert_eq!((stored.round_id, stored.deploys, stored.settled), (1, 1, 0));
+    assert_eq!(stored.total_deployed, plan.total_deployed);
+    assert_eq!(stored.modeled_ev, plan.portfolio_ev);
+    let count = plan.num_blocks as usize;
+    for (index, amount) in plan.indices[..count].iter().zip(&plan.amounts[..count]) {
+        assert_eq!(stored.amounts[*index as usize], *amount);
+    }
+}
+
+#[test]
+fn stores_the_executed_plan() {
+    let fixture = Fixture::new();
+    let mut ix = fixture.deploy_ix(BUDGET, 5);
+    let mut ix_data: OreDeployIxData = bytemuck::pod_read_unaligned(&ix.data[1..]);
+    ix_data.flags |= FLAG_STORE_PLAN;
+    ix.data[1..].copy_from_slice(bytemuck::bytes_of(&ix_data));
+
+    let (plan_address, _) =
+        Pubkey::find_program_address(&[PLAN_SEED, fixture.signer.as_ref()], &PROGRAM_ID);
+    ix.accounts.push(AccountMeta::new(plan_address, false));
+    let mut accounts = fixture.accounts();
+    accounts.push((plan_address, Account::default()));
+
+    let result =
+        fixture
+            .mollusk
+            .process_and_validate_instruction(&ix, &accounts, &[Check::success()]);
+    let plan = read_result(&result.return_data);
+    let stored: Plan =
+        bytemuck::pod_read_unaligned(&result.get_account(&plan_address).unwrap().data);
+    assert_eq!(stored.authority, fixture.signer.to_bytes());
+    assert_eq!((stored.round_id, stored.count), (1, plan.num_blocks));
+    assert_eq!(stored.flags, ix_data.flags);
+    assert_eq!(stored.expires_slot, u64::MAX);
+    assert_eq!(stored.total_amount(), plan.total_deployed);
+    assert_eq!(stored.indices, plan.indices);
+    assert_eq!(stored.evs, plan.evs);
+}
+
+/// The fixture's miner, checkpointed through `checkpoint_id` with
+/// `lifetime_rewards_sol` won
+fn miner_account(fixture: &Fixture, lifetime_rewards_sol: u64, checkpoint_id: u64) -> Account {
+    let mut miner: OreMiner = bytemuck::Zeroable::zeroed();
+    miner._disc = OreAccount::Miner.discriminator();
+    miner.authority = fixture.signer.to_bytes();
+    miner.lifetime_rewards_sol = lifetime_rewards_sol;
+    miner.checkpoint_id = checkpoint_id;
+    account_with(bytemuck::bytes_of(&miner), &ORE_PROGRAM_ID)
+}
+
+#[test]
+fn tracked_stats_sync_against_the_miners_winnings() {
+    let fixture = Fixture::new();
+    let mut ix = fixture.deploy_ix(BUDGET, 5);
+    let mut ix_data: OreDeployIxData = bytemuck::pod_read_unaligned(&ix.data[1..]);
+    ix_data.flags |= FLAG_TRACK_STATS;
+    ix.data[1..].copy_from_slice(bytemuck::bytes_of(&ix_data));
+
+    let (stats, _) =
+        Pubkey::find_program_address(&[USER_STATS_SEED, fixture.signer.as_ref()], &PROGRAM_ID);
+    ix.accounts.push(AccountMeta::new(stats, false));
+    let mut accounts = fixture.accounts();
+    accounts.push((stats, Account::default()));
+    let (_, miner) = accounts
+        .iter_mut()
+        .find(|(key, _)| *key == fixture.miner)
+        .unwrap();
+    *miner = miner_account(&fixture, 3_000_000_000, 0);
+
+    // Winnings from before the stats existed are checkpointed, not won
+    let first =
+        fixture
+            .mollusk
+            .process_and_validate_instruction(&ix, &accounts, &[Check::success()]);
+    let stored: UserStats = bytemuck::pod_read_unaligned(&first.get_account(&stats).unwrap().data);
+    assert_eq!(
+        (stored.rewards_checkpoint, stored.total_won),
+        (3_000_000_000, 0)
+    );
+
+    // Round 1 settles with 1 SOL won, which the next deploy credits once
+    let mut accounts = first.resulting_accounts;
+    let (_, miner) = accounts
+        .iter_mut()
+        .find(|(key, _)| *key == fixture.miner)
+        .unwrap();
+    *miner = miner_account(&fixture, 4_000_000_000, 1);
+    let second =
+        fixture
+            .mollusk
+            .process_and_validate_instruction(&ix, &accounts, &[Check::success()]);
+    let stored: UserStats = bytemuck::pod_read_unaligned(&second.get_account(&stats).unwrap().data);
+    assert_eq!(
+        (stored.rewards_checkpoint, stored.total_won),
+        (4_000_000_000, 1_000_000_000)
+    );
+    assert_eq!((stored.settled_round_id, stored.rounds_settled), (1, 1));
+}
+
+#[test]
+fn invalid_parameters_fail_with_their_own_error_code() {
+    let fixture = Fixture::new();
+    let ix = fixture.deploy_ix(BUDGET, 0);
+
+    fixture.mollusk.process_and_validate_instruction(
+        &ix,
+        &fixture.accounts(),
+        &[Check::err(ProgramError::Custom(
+            MyProgramError::InvalidNumBlocks as u32,
+        ))],
+    );
+}
@@ -0,0 +1,64 @@
+//! GlobalConfig ORE environments: configured deployments are permitted and
+//! checked against their own entropy program, and every other permitted
+//! deployment against ORE v3's.
+
+use bytemuck::Zeroable;
+use solana_pinocchio_starter::state::{
+    GlobalConfig, OreEnvironment, ENTROPY_PROGRAM_ID, ORE_PROGRAM_ID,
+};
+
+const DEVNET_ORE: [u8; 32] = [7; 32];
+const DEVNET_ENTROPY: [u8; 32] = [8; 32];
+const OVERRIDE: [u8; 32] = [9; 32];
+
+fn config() -> GlobalConfig {
+    let mut config = GlobalConfig::zeroed();
+    config.ore_program_override = OVERRIDE;
+    config.ore_environments[1] = OreEnvironment {
+        ore_program: DEVNET_ORE,
+        entropy_program: DEVNET_ENTROPY,
+    };
+    config
+}
+
+#[test]
+fn a_configured_environment_is_checked_against_its_entropy_program() {
+    let config = config();
+    assert!(config.is_allowed_ore_program(&DEVNET_ORE));
+    assert_eq!(
+        config.entropy_program_for(&DEVNET_ORE),
+        Some(DEVNET_ENTROPY)
+    );
+
+    assert!(config.is_allowed_ore_program(&ORE_PROGRAM_ID));
+    assert_eq!(
+        config.entropy_program_for(&ORE_PROGRAM_ID),
+        Some(ENTROPY_PROGRAM_ID)
+    );
+}
+
+#[test]
+fn unused_entries_permit_nothing() {
+    let config = config();
+    assert!(!config.is_allowed_ore_program(&[0; 32]));
+    assert!(!config.is_allowed_ore_program(&[6; 32]));
+
+    assert_eq!(config.entropy_program_for(&[6; 32]), None);
+}
+
+#[test]
+fn the_override_is_checked_against_an_entropy_program_too() {
+    let mut config = config();
+    assert!(config.is_allowed_ore_program(&OVERRIDE));
+    assert_eq!(
+        config.entropy_program_for(&OVERRIDE),
+        Some(ENTROPY_PROGRAM_ID)
+    );
+
+    // Its own entropy program comes from an environment entry
+    config.ore_environments[0] = OreEnvironment {
+        ore_program: OVERRIDE,
+        entropy_program: DEVNET_ENTROPY,
+    };
+    assert_eq!(config.entropy_program_for(&OVERRIDE), Some(DEVNET_ENTROPY));
+}
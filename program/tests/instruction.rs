@@ -0,0 +1,78 @@
+//! Instruction discriminators: every instruction round-trips through its
+//! byte, and the bytes match the constants clients build instructions with.
+
+use solana_pinocchio_starter::instruction::{discriminator, MyProgramInstruction};
+
+#[test]
+fn every_instruction_round_trips_through_its_discriminator() {
+    for ix in MyProgramInstruction::ALL {
+        assert_eq!(MyProgramInstruction::try_from(&(ix as u8)), Ok(ix));
+    }
+}
+
+#[test]
+fn discriminators_match_the_shared_constants() {
+    let expected = [
+        (MyProgramInstruction::OreDeploy, discriminator::ORE_DEPLOY),
+        (
+            MyProgramInstruction::InitializeConfig,
+            discriminator::INITIALIZE_CONFIG,
+        ),
+        (
+            MyProgramInstruction::UpdateConfig,
+            discriminator::UPDATE_CONFIG,
+        ),
+        (
+            MyProgramInstruction::SetBankroll,
+            discriminator::SET_BANKROLL,
+        ),
+        (
+            MyProgramInstruction::CreateSchedule,
+            discriminator::CREATE_SCHEDULE,
+        ),
+        (
+            MyProgramInstruction::ExecuteSchedule,
+            discriminator::EXECUTE_SCHEDULE,
+        ),
+        (
+            MyProgramInstruction::CloseSchedule,
+            discriminator::CLOSE_SCHEDULE,
+        ),
+        (
+            MyProgramInstruction::SettleRound,
+            discriminator::SETTLE_ROUND,
+        ),
+        (
+            MyProgramInstruction::InitializeLeaderboard,
+            discriminator::INITIALIZE_LEADERBOARD,
+        ),
+        (
+            MyProgramInstruction::MigrateAccount,
+            discriminator::MIGRATE_ACCOUNT,
+        ),
+        (
+            MyProgramInstruction::ClaimSchedule,
+            discriminator::CLAIM_SCHEDULE,
+        ),
+        (
+            MyProgramInstruction::DepositSchedule,
+            discriminator::DEPOSIT_SCHEDULE,
+        ),
+    ];
+    assert_eq!(expected.len(), MyProgramInstruction::ALL.len());
+    for (ix, byte) in expected {
+        assert_eq!(ix as u8, byte);
+    }
+}
+
+#[test]
+fn unknown_discriminators_are_rejected() {
+    for byte in 0..=u8::MAX {
+        let known = MyProgramInstruction::ALL.iter().any(|ix| *ix as u8 == byte);
+        assert_eq!(
+            MyProgramInstruction::try_from(&byte).is_ok(),
+            known,
+            "{byte}"
+        );
+    }
+}
@@ -0,0 +1,46 @@
+//! Leaderboard aggregation: settled rounds add to an authority's entry, and
+//! a full board keeps its best performers.
+
+use bytemuck::Zeroable;
+use solana_pinocchio_starter::state::{Leaderboard, LEADERBOARD_SIZE};
+
+fn authority(n: usize) -> [u8; 32] {
+    let mut key = [0; 32];
+    key[..8].copy_from_slice(&(n as u64).to_le_bytes());
+    key
+}
+
+#[test]
+fn settled_rounds_aggregate_per_authority() {
+    let mut board = Leaderboard::zeroed();
+    board.record(&authority(1), 500, 1_000);
+    board.record(&authority(2), -200, 400);
+    board.record(&authority(1), -100, 1_000);
+
+    assert_eq!(board.len, 2);
+    let entry = board.entries[0];
+    assert_eq!(entry.authority, authority(1));
+    assert_eq!(
+        (entry.settled_pnl, entry.volume, entry.rounds),
+        (400, 2_000, 2)
+    );
+}
+
+#[test]
+fn a_full_board_replaces_its_lowest_pnl_only_when_beaten() {
+    let mut board = Leaderboard::zeroed();
+    for n in 0..LEADERBOARD_SIZE {
+        board.record(&authority(n), n as i64, 1);
+    }
+
+    // Authority 0 holds the lowest PnL
+    board.record(&authority(1_000), 0, 1);
+    assert!(board
+        .entries
+        .iter()
+        .all(|e| e.authority != authority(1_000)));
+
+    board.record(&authority(1_000), 10, 1);
+    assert_eq!(board.len as usize, LEADERBOARD_SIZE);
+    assert_eq!(board.entries[0].authority, authority(1_000));
+}
@@ -0,0 +1,101 @@
+//! MigrateAccount under Mollusk: version 0 accounts are upgraded in place,
+//! growing them when the current layout is longer.
+
+mod common;
+
+use common::{account_with, Fixture, PROGRAM_ID};
+use mollusk_svm::{program, result::Check};
+use solana_pinocchio_starter::{
+    instruction::discriminator::MIGRATE_ACCOUNT,
+    state::{
+        AccountDiscriminator, DataLen, GlobalConfig, Schedule, UserStats, Versioned,
+        GLOBAL_CONFIG_V1_LEN, GLOBAL_CONFIG_V2_LEN, SCHEDULE_V0_LEN, USER_STATS_V0_LEN,
+    },
+};
+use solana_sdk::{
+    account::Account,
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+};
+
+/// Run MigrateAccount on a zeroed version 0 account of `kind` and `len`
+/// bytes, returning its data afterwards
+fn migrate(fixture: &Fixture, kind: AccountDiscriminator, len: usize) -> Vec<u8> {
+    let address = Pubkey::new_unique();
+    let mut data = vec![0; len];
+    data[0] = kind as u8;
+    let mut account = account_with(&data, &PROGRAM_ID);
+    account.lamports = fixture.mollusk.sysvars.rent.minimum_balance(len);
+
+    let ix = Instruction {
+        program_id: PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new(fixture.signer, true),
+            AccountMeta::new(address, false),
+            AccountMeta::new_readonly(program::keyed_account_for_system_program().0, false),
+        ],
+        data: vec![MIGRATE_ACCOUNT],
+    };
+    let accounts = vec![
+        (
+            fixture.signer,
+            Account::new(1_000_000_000, 0, &solana_sdk::system_program::ID),
+        ),
+        (address, account),
+        program::keyed_account_for_system_program(),
+    ];
+
+    let result =
+        fixture
+            .mollusk
+            .process_and_validate_instruction(&ix, &accounts, &[Check::success()]);
+    result.get_account(&address).unwrap().data.clone()
+}
+
+#[test]
+fn grows_a_version_0_user_stats_account() {
+    let fixture = Fixture::new();
+    let data = migrate(&fixture, AccountDiscriminator::UserStats, USER_STATS_V0_LEN);
+    assert_eq!(data.len(), UserStats::LEN);
+    let migrated: UserStats = bytemuck::pod_read_unaligned(&data);
+    assert_eq!(migrated.version, UserStats::VERSION);
+    assert_eq!(migrated.rounds_participated, 0);
+}
+
+#[test]
+fn grows_a_config_created_before_payout_multipliers() {
+    let fixture = Fixture::new();
+    let data = migrate(
+        &fixture,
+        AccountDiscriminator::GlobalConfig,
+        GLOBAL_CONFIG_V1_LEN,
+    );
+    assert_eq!(data.len(), GlobalConfig::LEN);
+    let migrated: GlobalConfig = bytemuck::pod_read_unaligned(&data);
+    assert_eq!(migrated.version, GlobalConfig::VERSION);
+    assert_eq!(migrated.payout_multipliers(), None);
+}
+
+#[test]
+fn grows_a_config_created_before_ore_environments() {
+    let fixture = Fixture::new();
+    let data = migrate(
+        &fixture,
+        AccountDiscriminator::GlobalConfig,
+        GLOBAL_CONFIG_V2_LEN,
+    );
+    assert_eq!(data.len(), GlobalConfig::LEN);
+    let migrated: GlobalConfig = bytemuck::pod_read_unaligned(&data);
+    assert_eq!(migrated.version, GlobalConfig::VERSION);
+    assert_eq!(migrated.ore_environments, Default::default());
+}
+
+#[test]
+fn grows_a_version_0_schedule() {
+    let fixture = Fixture::new();
+    let data = migrate(&fixture, AccountDiscriminator::Schedule, SCHEDULE_V0_LEN);
+    assert_eq!(data.len(), Schedule::LEN);
+    let migrated: Schedule = bytemuck::pod_read_unaligned(&data);
+    assert_eq!(migrated.version, Schedule::VERSION);
+    assert_eq!(migrated.strategy.max_total_deployed, 0);
+}
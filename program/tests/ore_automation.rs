@@ -0,0 +1,35 @@
+//! OreAutomation decoding: fields sit at ORE's offsets after the 8-byte
+//! discriminator.
+
+use solana_pinocchio_starter::state::{read_ore_automation_data, DataLen, OreAutomation};
+
+#[test]
+fn decodes_an_automation_at_ores_offsets() {
+    // Account data is 8-byte aligned, so build it over u64 words
+    let mut words = vec![0u64; OreAutomation::LEN / 8 + 1];
+    let data: &mut [u8] = bytemuck::cast_slice_mut(&mut words);
+    data[8..16].copy_from_slice(&1_000u64.to_le_bytes());
+    data[16..48].copy_from_slice(&[7; 32]);
+    data[48..56].copy_from_slice(&50_000u64.to_le_bytes());
+    data[56..88].copy_from_slice(&[9; 32]);
+    data[88..96].copy_from_slice(&5u64.to_le_bytes());
+    data[96..104].copy_from_slice(&1u64.to_le_bytes());
+    data[104..112].copy_from_slice(&0b1011u64.to_le_bytes());
+
+    let automation = read_ore_automation_data(data).unwrap();
+    assert_eq!(automation.amount, 1_000);
+    assert_eq!(automation.authority, [7; 32]);
+    assert_eq!(automation.balance, 50_000);
+    assert_eq!(automation.executor, [9; 32]);
+    assert_eq!(
+        (automation.fee, automation.strategy, automation.mask),
+        (5, 1, 0b1011)
+    );
+}
+
+#[test]
+fn rejects_a_short_account() {
+    let words = vec![0u64; OreAutomation::LEN / 8];
+    let data: &[u8] = bytemuck::cast_slice(&words);
+    assert!(read_ore_automation_data(&data[..OreAutomation::LEN - 1]).is_err());
+}
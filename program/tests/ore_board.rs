@@ -0,0 +1,31 @@
+//! OreBoard helpers: the current round's PDA and the board's deploy window.
+
+use solana_pinocchio_starter::state::{OreAccount, OreBoard, ORE_PROGRAM_ID, ROUND_SEED};
+use solana_sdk::pubkey::Pubkey;
+
+fn board() -> OreBoard {
+    OreBoard {
+        _disc: OreAccount::Board.discriminator(),
+        round_id: 42,
+        start_slot: 1_000,
+        end_slot: 1_150,
+    }
+}
+
+#[test]
+fn the_round_address_is_the_current_rounds_pda() {
+    let ore_program = Pubkey::new_from_array(ORE_PROGRAM_ID);
+    let (expected, _) =
+        Pubkey::find_program_address(&[ROUND_SEED, &42u64.to_le_bytes()], &ore_program);
+    assert_eq!(board().round_address(&ORE_PROGRAM_ID), expected.to_bytes());
+}
+
+#[test]
+fn the_board_closes_at_its_end_slot() {
+    let board = board();
+    assert!(board.is_open(1_149));
+    assert_eq!(board.slots_remaining(1_149), 1);
+    assert!(!board.is_open(1_150));
+    assert_eq!(board.slots_remaining(1_150), 0);
+    assert_eq!(board.slots_remaining(2_000), 0);
+}
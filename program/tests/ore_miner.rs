@@ -0,0 +1,31 @@
+//! OreMiner helpers: a miner's positions only count in the round it last
+//! played.
+
+use bytemuck::Zeroable;
+use solana_pinocchio_starter::state::{OreAccount, OreMiner};
+
+fn miner() -> OreMiner {
+    let mut miner = OreMiner::zeroed();
+    miner._disc = OreAccount::Miner.discriminator();
+    miner.round_id = 42;
+    miner.deployed[3] = 500;
+    miner.deployed[7] = 250;
+    miner.rewards_sol = 1_200;
+    miner.rewards_ore = 9;
+    miner
+}
+
+#[test]
+fn positions_count_only_in_the_miners_round() {
+    let miner = miner();
+    assert_eq!(miner.deployed_in(42), miner.deployed);
+    assert_eq!(miner.total_deployed_in(42), 750);
+
+    assert_eq!(miner.deployed_in(43), [0; 25]);
+    assert_eq!(miner.total_deployed_in(41), 0);
+}
+
+#[test]
+fn unclaimed_reports_sol_and_ore_rewards() {
+    assert_eq!(miner().unclaimed(), (1_200, 9));
+}
@@ -0,0 +1,39 @@
+//! Plan layout: a stored plan carries an allocation's blocks verbatim and
+//! stays executable until its expiry slot.
+
+use solana_pinocchio_starter::{
+    allocator::Allocation,
+    state::{AccountDiscriminator, DataLen, Plan, Versioned},
+};
+
+fn allocation() -> Allocation {
+    let mut allocation = Allocation {
+        count: 2,
+        ..Default::default()
+    };
+    allocation.indices[..2].copy_from_slice(&[4, 9]);
+    allocation.amounts[..2].copy_from_slice(&[300, 200]);
+    allocation.evs[..2].copy_from_slice(&[12, -3]);
+    allocation
+}
+
+#[test]
+fn a_plan_keeps_the_allocations_blocks() {
+    let plan = Plan::new(&[7; 32], 42, 1_000, 1_150, 0, &allocation());
+    let decoded: Plan = bytemuck::pod_read_unaligned(bytemuck::bytes_of(&plan));
+    assert_eq!(decoded, plan);
+    assert_eq!(Plan::LEN, core::mem::size_of::<Plan>());
+
+    assert_eq!(plan.discriminator, AccountDiscriminator::Plan as u8);
+    assert_eq!(plan.version, Plan::VERSION);
+    assert_eq!((plan.round_id, plan.count), (42, 2));
+    assert_eq!(&plan.indices[..2], &[4, 9]);
+    assert_eq!(plan.total_amount(), 500);
+}
+
+#[test]
+fn a_plan_expires_at_its_expiry_slot() {
+    let plan = Plan::new(&[7; 32], 42, 1_000, 1_150, 0, &allocation());
+    assert!(plan.is_live(1_149));
+    assert!(!plan.is_live(1_150));
+}
@@ -0,0 +1,309 @@
+//! Schedules under Mollusk: deposits fund more rounds, the vault's ORE
+//! winnings are claimed back to the authority before the schedule can
+//! close, and a crank that deploys nothing doesn't use up a round.
+
+mod common;
+
+use common::{account_with, Fixture, ORE_PROGRAM_ID, PROGRAM_ID};
+use mollusk_svm::{program, result::Check};
+use solana_pinocchio_starter::{
+    error::MyProgramError,
+    instruction::{
+        discriminator::{CLAIM_SCHEDULE, CLOSE_SCHEDULE, DEPOSIT_SCHEDULE, EXECUTE_SCHEDULE},
+        ClaimScheduleIxData, DepositScheduleIxData, OreDeployIxData, FLAG_NOOP_ON_SMALL_POOL,
+    },
+    state::{
+        AccountDiscriminator, GlobalConfig, OreAccount, OreMiner, Schedule, Versioned,
+        AUTOMATION_SEED, MINER_SEED, NO_ROUND, SCHEDULE_SEED, SCHEDULE_VAULT_SEED,
+    },
+};
+use solana_sdk::{
+    account::Account,
+    instruction::{AccountMeta, Instruction},
+    program_error::ProgramError,
+    pubkey::Pubkey,
+};
+
+const VAULT_LAMPORTS: u64 = 5_000_000_000;
+const WINNINGS: u64 = 300_000_000;
+const BUDGET_PER_ROUND: u64 = 100_000_000;
+
+struct ScheduleFixture {
+    fixture: Fixture,
+    schedule: Pubkey,
+    vault: Pubkey,
+    vault_miner: Pubkey,
+    accounts: Vec<(Pubkey, Account)>,
+}
+
+impl ScheduleFixture {
+    /// The fixture signer's schedule with `rounds` left, and its vault's ORE
+    /// miner credited `rewards_sol` with rounds up to `checkpoint_id` checkpointed
+    fn new(rounds: u32, rewards_sol: u64, checkpoint_id: u64) -> Self {
+        let fixture = Fixture::new();
+        let (schedule, bump) =
+            Pubkey::find_program_address(&[SCHEDULE_SEED, fixture.signer.as_ref()], &PROGRAM_ID);
+        let (vault, vault_bump) =
+            Pubkey::find_program_address(&[SCHEDULE_VAULT_SEED, schedule.as_ref()], &PROGRAM_ID);
+        let vault_miner =
+            Pubkey::find_program_address(&[MINER_SEED, vault.as_ref()], &ORE_PROGRAM_ID).0;
+
+        let state = Schedule {
+            discriminator: AccountDiscriminator::Schedule as u8,
+            bump,
+            vault_bump,
+            version: Schedule::VERSION,
+            _padding: [0; 4],
+            authority: fixture.signer.to_bytes(),
+            budget_per_round: BUDGET_PER_ROUND,
+            last_round_id: NO_ROUND,
+            rounds_remaining: rounds,
+            _padding2: [0; 4],
+            strategy: OreDeployIxData {
+                ore_price_lamports: 1_000_000_000,
+                min_ev_threshold: -10_000,
+                num_blocks: 1,
+                max_cpis: 1,
+                flags: FLAG_NOOP_ON_SMALL_POOL,
+                ..bytemuck::Zeroable::zeroed()
+            },
+        };
+
+        let mut miner: OreMiner = bytemuck::Zeroable::zeroed();
+        miner._disc = OreAccount::Miner.discriminator();
+        miner.authority = vault.to_bytes();
+        miner.round_id = 1;
+        miner.checkpoint_id = checkpoint_id;
+        miner.rewards_sol = rewards_sol;
+
+        let mut accounts = fixture.accounts();
+        accounts.extend([
+            (
+                schedule,
+                account_with(bytemuck::bytes_of(&state), &PROGRAM_ID),
+            ),
+            (
+                vault,
+                Account::new(VAULT_LAMPORTS, 0, &solana_sdk::system_program::ID),
+            ),
+            (
+                vault_miner,
+                account_with(bytemuck::bytes_of(&miner), &ORE_PROGRAM_ID),
+            ),
+        ]);
+        ScheduleFixture {
+            fixture,
+            schedule,
+            vault,
+            vault_miner,
+            accounts,
+        }
+    }
+
+    /// ClaimSchedule (SOL only) or CloseSchedule by the schedule's authority
+    fn authority_ix(&self, discriminator: u8) -> Instruction {
+        let mut data = vec![discriminator];
+        if discriminator == CLAIM_SCHEDULE {
+            data.extend_from_slice(bytemuck::bytes_of(&ClaimScheduleIxData { flags: 0 }));
+        }
+        Instruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(self.fixture.signer, true),
+                AccountMeta::new(self.schedule, false),
+                AccountMeta::new(self.vault, false),
+                AccountMeta::new_readonly(program::keyed_account_for_system_program().0, false),
+                AccountMeta::new_readonly(ORE_PROGRAM_ID, false),
+                AccountMeta::new_readonly(self.fixture.config, false),
+                AccountMeta::new(self.vault_miner, false),
+            ],
+            data,
+        }
+    }
+
+    /// DepositSchedule of `rounds` by the schedule's authority
+    fn deposit_ix(&self, rounds: u32) -> Instruction {
+        let mut data = vec![DEPOSIT_SCHEDULE];
+        data.extend_from_slice(bytemuck::bytes_of(&DepositScheduleIxData { rounds }));
+        Instruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(self.fixture.signer, true),
+                AccountMeta::new(self.schedule, false),
+                AccountMeta::new(self.vault, false),
+                AccountMeta::new_readonly(program::keyed_account_for_system_program().0, false),
+            ],
+            data,
+        }
+    }
+
+    /// ExecuteSchedule cranked by a fresh keeper, with the vault's
+    /// automation added to the accounts
+    fn execute_ix(&mut self) -> Instruction {
+        let keeper = Pubkey::new_unique();
+        let automation =
+            Pubkey::find_program_address(&[AUTOMATION_SEED, self.vault.as_ref()], &ORE_PROGRAM_ID)
+                .0;
+        self.accounts.extend([
+            (
+                keeper,
+                Account::new(1_000_000_000, 0, &solana_sdk::system_program::ID),
+            ),
+            (
+                automation,
+                Account::new(0, 0, &solana_sdk::system_program::ID),
+            ),
+        ]);
+        let fixture = &self.fixture;
+        Instruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(keeper, true),
+                AccountMeta::new(self.schedule, false),
+                AccountMeta::new_readonly(ORE_PROGRAM_ID, false),
+                AccountMeta::new(self.vault, false),
+                AccountMeta::new(self.vault, false),
+                AccountMeta::new(automation, false),
+                AccountMeta::new(fixture.board, false),
+                AccountMeta::new(self.vault_miner, false),
+                AccountMeta::new(fixture.round, false),
+                AccountMeta::new_readonly(program::keyed_account_for_system_program().0, false),
+                AccountMeta::new(fixture.entropy_var, false),
+                AccountMeta::new_readonly(fixture.entropy_program, false),
+                AccountMeta::new_readonly(fixture.config, false),
+            ],
+            data: vec![EXECUTE_SCHEDULE],
+        }
+    }
+}
+
+fn lamports(accounts: &[(Pubkey, Account)], key: &Pubkey) -> u64 {
+    accounts.iter().find(|(k, _)| k == key).unwrap().1.lamports
+}
+
+#[test]
+fn a_deposit_funds_more_rounds() {
+    let schedule = ScheduleFixture::new(3, 0, 1);
+    let authority = schedule.fixture.signer;
+
+    let result = schedule.fixture.mollusk.process_and_validate_instruction(
+        &schedule.deposit_ix(2),
+        &schedule.accounts,
+        &[Check::success()],
+    );
+    let state = result.get_account(&schedule.schedule).unwrap();
+    let state: Schedule = bytemuck::pod_read_unaligned(&state.data);
+    assert_eq!(state.rounds_remaining, 5);
+    assert_eq!(
+        lamports(&result.resulting_accounts, &schedule.vault),
+        VAULT_LAMPORTS + 2 * BUDGET_PER_ROUND
+    );
+    assert_eq!(
+        lamports(&result.resulting_accounts, &authority),
+        lamports(&schedule.accounts, &authority) - 2 * BUDGET_PER_ROUND
+    );
+
+    schedule.fixture.mollusk.process_and_validate_instruction(
+        &schedule.deposit_ix(0),
+        &schedule.accounts,
+        &[Check::err(ProgramError::Custom(
+            MyProgramError::InvalidScheduleParams as u32,
+        ))],
+    );
+}
+
+#[test]
+fn claim_pays_the_vaults_winnings_to_the_authority() {
+    let schedule = ScheduleFixture::new(3, WINNINGS, 1);
+    let authority = schedule.fixture.signer;
+
+    let result = schedule.fixture.mollusk.process_and_validate_instruction(
+        &schedule.authority_ix(CLAIM_SCHEDULE),
+        &schedule.accounts,
+        &[Check::success()],
+    );
+    assert_eq!(
+        lamports(&result.resulting_accounts, &authority),
+        lamports(&schedule.accounts, &authority) + WINNINGS
+    );
+    assert_eq!(
+        lamports(&result.resulting_accounts, &schedule.vault),
+        VAULT_LAMPORTS
+    );
+    let miner = result.get_account(&schedule.vault_miner).unwrap();
+    let miner: OreMiner = bytemuck::pod_read_unaligned(&miner.data);
+    assert_eq!(miner.rewards_sol, 0);
+}
+
+#[test]
+fn close_waits_for_the_winnings_to_be_claimed() {
+    let schedule = ScheduleFixture::new(3, WINNINGS, 1);
+    let authority = schedule.fixture.signer;
+    let mollusk = &schedule.fixture.mollusk;
+    let unclaimed = Check::err(ProgramError::Custom(
+        MyProgramError::ScheduleRewardsUnclaimed as u32,
+    ));
+
+    mollusk.process_and_validate_instruction(
+        &schedule.authority_ix(CLOSE_SCHEDULE),
+        &schedule.accounts,
+        &[unclaimed],
+    );
+
+    let claimed = mollusk.process_and_validate_instruction(
+        &schedule.authority_ix(CLAIM_SCHEDULE),
+        &schedule.accounts,
+        &[Check::success()],
+    );
+    let closed = mollusk.process_and_validate_instruction(
+        &schedule.authority_ix(CLOSE_SCHEDULE),
+        &claimed.resulting_accounts,
+        &[Check::success()],
+    );
+    let rent = lamports(&schedule.accounts, &schedule.schedule);
+    assert_eq!(
+        lamports(&closed.resulting_accounts, &authority),
+        lamports(&schedule.accounts, &authority) + WINNINGS + VAULT_LAMPORTS + rent
+    );
+}
+
+#[test]
+fn close_waits_for_the_last_round_to_be_checkpointed() {
+    let schedule = ScheduleFixture::new(3, 0, 0);
+
+    schedule.fixture.mollusk.process_and_validate_instruction(
+        &schedule.authority_ix(CLOSE_SCHEDULE),
+        &schedule.accounts,
+        &[Check::err(ProgramError::Custom(
+            MyProgramError::ScheduleRewardsUnclaimed as u32,
+        ))],
+    );
+}
+
+#[test]
+fn a_crank_that_deploys_nothing_keeps_its_round() {
+    let mut schedule = ScheduleFixture::new(3, 0, 1);
+    let config = schedule.fixture.config;
+    let (_, config_account) = schedule
+        .accounts
+        .iter_mut()
+        .find(|(key, _)| *key == config)
+        .unwrap();
+    let mut state: GlobalConfig = bytemuck::pod_read_unaligned(&config_account.data);
+    state.params.min_total_deployed = u64::MAX;
+    config_account.data = bytemuck::bytes_of(&state).to_vec();
+
+    let ix = schedule.execute_ix();
+    let result = schedule.fixture.mollusk.process_and_validate_instruction(
+        &ix,
+        &schedule.accounts,
+        &[Check::success()],
+    );
+    let state = result.get_account(&schedule.schedule).unwrap();
+    let state: Schedule = bytemuck::pod_read_unaligned(&state.data);
+    assert_eq!((state.rounds_remaining, state.last_round_id), (3, NO_ROUND));
+    assert_eq!(
+        lamports(&result.resulting_accounts, &schedule.vault),
+        VAULT_LAMPORTS
+    );
+}
@@ -0,0 +1,89 @@
+//! SettleRound under Mollusk: a deploy recorded in its RoundRecord is
+//! settled against the drawn round, once.
+
+mod common;
+
+use common::{read_result, Fixture, ORE_PROGRAM_ID, PROGRAM_ID};
+use mollusk_svm::result::Check;
+use solana_pinocchio_starter::{
+    error::MyProgramError,
+    instruction::{discriminator::SETTLE_ROUND, OreDeployIxData, FLAG_ROUND_RECORD},
+    state::{OreRound, RoundRecord, ROUND_RECORD_SEED},
+};
+use solana_sdk::{
+    account::Account,
+    instruction::{AccountMeta, Instruction},
+    program_error::ProgramError,
+    pubkey::Pubkey,
+};
+
+#[test]
+fn settles_a_recorded_round_once() {
+    let fixture = Fixture::new();
+    let mut ix = fixture.deploy_ix(1_000_000_000, 5);
+    let mut ix_data: OreDeployIxData = bytemuck::pod_read_unaligned(&ix.data[1..]);
+    ix_data.flags |= FLAG_ROUND_RECORD;
+    ix.data[1..].copy_from_slice(bytemuck::bytes_of(&ix_data));
+
+    let (record, _) = Pubkey::find_program_address(
+        &[
+            ROUND_RECORD_SEED,
+            fixture.signer.as_ref(),
+            &1u64.to_le_bytes(),
+        ],
+        &PROGRAM_ID,
+    );
+    ix.accounts.push(AccountMeta::new(record, false));
+    let mut accounts = fixture.accounts();
+    accounts.push((record, Account::default()));
+
+    let deployed =
+        fixture
+            .mollusk
+            .process_and_validate_instruction(&ix, &accounts, &[Check::success()]);
+    let plan = read_result(&deployed.return_data);
+    let winner = plan.indices[0];
+
+    // Draw our first block's square, with no winnings beyond the stake
+    let mut accounts = deployed.resulting_accounts;
+    let round = accounts
+        .iter_mut()
+        .find(|(key, _)| *key == fixture.round)
+        .unwrap();
+    let mut round_data: OreRound = bytemuck::pod_read_unaligned(&round.1.data);
+    round_data.slot_hash = [0; 32];
+    round_data.slot_hash[..8].copy_from_slice(&(winner as u64).to_le_bytes());
+    round.1.data = bytemuck::bytes_of(&round_data).to_vec();
+
+    let settle = Instruction {
+        program_id: PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new_readonly(ORE_PROGRAM_ID, false),
+            AccountMeta::new_readonly(fixture.config, false),
+            AccountMeta::new_readonly(fixture.round, false),
+            AccountMeta::new_readonly(fixture.signer, false),
+            AccountMeta::new(record, false),
+        ],
+        data: vec![SETTLE_ROUND, 0],
+    };
+    let settled =
+        fixture
+            .mollusk
+            .process_and_validate_instruction(&settle, &accounts, &[Check::success()]);
+    let stored: RoundRecord =
+        bytemuck::pod_read_unaligned(&settled.get_account(&record).unwrap().data);
+    assert_eq!(stored.settled, 1);
+    assert_eq!(stored.won, plan.amounts[0]);
+    assert_eq!(
+        stored.realized_pnl(),
+        plan.amounts[0] as i64 - plan.total_deployed as i64
+    );
+
+    fixture.mollusk.process_and_validate_instruction(
+        &settle,
+        &settled.resulting_accounts,
+        &[Check::err(ProgramError::Custom(
+            MyProgramError::RoundAlreadySettled as u32,
+        ))],
+    );
+}
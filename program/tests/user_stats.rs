@@ -0,0 +1,45 @@
+//! UserStats bookkeeping: round counters and the best and worst round are
+//! updated as the miner's checkpoints settle deployed rounds.
+
+use bytemuck::Zeroable;
+use solana_pinocchio_starter::state::UserStats;
+
+const SOL: u64 = 1_000_000_000;
+
+#[test]
+fn settled_rounds_update_wins_and_extremes() {
+    let mut stats = UserStats::zeroed();
+    stats.record_round(10, SOL, 0, 0);
+    stats.record_round(11, SOL, 0, 0);
+    stats.record_round(11, SOL, 0, 0);
+    assert_eq!(stats.rounds_participated, 2);
+
+    // Round 10 checkpointed with 3 SOL won; round 11 is still open
+    stats.sync_winnings(3 * SOL, 10);
+    assert_eq!(stats.rounds_settled, 1);
+    assert_eq!(stats.rounds_won, 1);
+    assert_eq!(
+        (stats.best_round_id, stats.best_round_pnl),
+        (10, 2 * SOL as i64)
+    );
+
+    // Round 11 settles with nothing won
+    stats.sync_winnings(3 * SOL, 11);
+    assert_eq!(stats.rounds_settled, 2);
+    assert_eq!(stats.rounds_won, 1);
+    assert_eq!(
+        (stats.worst_round_id, stats.worst_round_pnl),
+        (11, -2 * SOL as i64)
+    );
+    assert_eq!(stats.best_round_id, 10);
+}
+
+#[test]
+fn a_repeated_checkpoint_settles_nothing_twice() {
+    let mut stats = UserStats::zeroed();
+    stats.record_round(10, SOL, 0, 0);
+    stats.sync_winnings(0, 10);
+    stats.sync_winnings(0, 10);
+    assert_eq!(stats.rounds_settled, 1);
+    assert_eq!(stats.pnl(), -(SOL as i64));
+}
@@ -0,0 +1,190 @@
+//! WebAssembly bindings for `ore-ev-math`, so web front-ends can show the
+//! EV of every square and the plan a deploy would make for the current
+//! round without a simulateTransaction round trip.
+//!
+//! Rounds are passed as ORE round account data and config parameters as
+//! the `ConfigParams` bytes of the program's config account; amounts are
+//! lamports (`bigint` in JavaScript).
+
+use ore_ev_math::{
+    calculate_ev_scaled, calculate_optimal_deployments, motherlode_value, ore_value, Allocation,
+    AllocationParams, ConfigParams, Objective, OreRound, Selection, BOARD_SQUARES, FLAT_PAYOUT_BPS,
+    MAX_PLAN_BLOCKS,
+};
+use wasm_bindgen::prelude::*;
+
+/// Every square eligible
+const ALL_SQUARES: u32 = (1 << BOARD_SQUARES) - 1;
+
+/// Length of the `ConfigParams` bytes the functions take
+#[wasm_bindgen(js_name = configParamsLen)]
+pub fn config_params_len() -> usize {
+    size_of::<ConfigParams>()
+}
+
+/// Modeled EV in lamports of deploying `amount` on each square, in square
+/// order, `i64::MIN` on squares nobody has deployed on (which the allocator
+/// never funds); `payout_multipliers_bps` is the config's per-square
+/// multipliers, or empty for none
+#[wasm_bindgen(js_name = evSurface)]
+pub fn ev_surface(
+    round_data: &[u8],
+    params_data: &[u8],
+    amount: u64,
+    ore_price_lamports: u64,
+    payout_multipliers_bps: &[u16],
+) -> Result<Vec<i64>, JsValue> {
+    let round = decode::<OreRound>(round_data, "round")?;
+    let params = decode::<ConfigParams>(params_data, "config params")?;
+    let multipliers = multipliers(payout_multipliers_bps)?;
+    let motherlode = motherlode_value(ore_price_lamports, round.motherlode, &params);
+    let ore_value = ore_value(ore_price_lamports, motherlode, &params);
+    Ok((0..BOARD_SQUARES)
+        .map(|square| {
+            calculate_ev_scaled(
+                round.deployed[square],
+                amount,
+                round.total_deployed,
+                ore_value,
+                &params,
+                multipliers.map_or(FLAT_PAYOUT_BPS, |m| m[square] as u64),
+            )
+        })
+        .collect())
+}
+
+/// Modeled EV in lamports of deploying `amount` on `square`
+#[wasm_bindgen(js_name = blockEv)]
+pub fn block_ev(
+    round_data: &[u8],
+    params_data: &[u8],
+    square: usize,
+    amount: u64,
+    ore_price_lamports: u64,
+    payout_multipliers_bps: &[u16],
+) -> Result<i64, JsValue> {
+    if square >= BOARD_SQUARES {
+        return Err(JsValue::from_str("square out of range"));
+    }
+    let surface = ev_surface(
+        round_data,
+        params_data,
+        amount,
+        ore_price_lamports,
+        payout_multipliers_bps,
+    )?;
+    Ok(surface[square])
+}
+
+/// The allocator's plan, in selection order
+#[wasm_bindgen]
+pub struct Quote {
+    plan: Allocation,
+}
+
+#[wasm_bindgen]
+impl Quote {
+    /// Blocks planned
+    #[wasm_bindgen(getter)]
+    pub fn count(&self) -> u8 {
+        self.plan.count
+    }
+
+    /// Square of each planned block
+    #[wasm_bindgen(getter)]
+    pub fn squares(&self) -> Vec<u8> {
+        self.plan.indices[..self.plan.count as usize].to_vec()
+    }
+
+    /// Lamports planned per block
+    #[wasm_bindgen(getter)]
+    pub fn amounts(&self) -> Vec<u64> {
+        self.plan.amounts[..self.plan.count as usize].to_vec()
+    }
+
+    /// Modeled EV in lamports per block
+    #[wasm_bindgen(getter)]
+    pub fn evs(&self) -> Vec<i64> {
+        self.plan.evs[..self.plan.count as usize].to_vec()
+    }
+
+    /// Modeled EV of the whole plan
+    #[wasm_bindgen(getter, js_name = portfolioEv)]
+    pub fn portfolio_ev(&self) -> i64 {
+        self.plan.portfolio_ev
+    }
+
+    /// Standard deviation of the whole plan's PnL
+    #[wasm_bindgen(getter, js_name = portfolioStdDev)]
+    pub fn portfolio_std_dev(&self) -> u64 {
+        self.plan.portfolio_std_dev
+    }
+}
+
+/// The plan a deploy of `total_budget` lamports makes with these
+/// parameters and no mode flags, as the program plans it
+#[allow(clippy::too_many_arguments)]
+#[wasm_bindgen]
+pub fn quote(
+    round_data: &[u8],
+    params_data: &[u8],
+    total_budget: u64,
+    max_blocks: u8,
+    ore_price_lamports: u64,
+    min_ev_threshold: i32,
+    max_block_share_bps: u16,
+    objective: u8,
+    payout_multipliers_bps: &[u16],
+) -> Result<Quote, JsValue> {
+    let round = decode::<OreRound>(round_data, "round")?;
+    let params = decode::<ConfigParams>(params_data, "config params")?;
+    if max_blocks == 0 || max_blocks as usize > MAX_PLAN_BLOCKS {
+        return Err(JsValue::from_str("max_blocks out of range"));
+    }
+    let objective = Objective::try_from(objective).map_err(error)?;
+    let plan = calculate_optimal_deployments(&AllocationParams {
+        round: &round,
+        config: &params,
+        total_budget,
+        max_blocks,
+        ore_price_lamports,
+        min_ev_threshold,
+        rank_thresholds: &[],
+        absolute_threshold: false,
+        max_block_share_bps,
+        objective,
+        selection: Selection::EvPerLamport,
+        max_std_dev_bps: 0,
+        portfolio: false,
+        eligible_squares: ALL_SQUARES,
+        crowd_penalty_bps: 0,
+        held: [0; BOARD_SQUARES],
+        top_miner: false,
+        motherlode_weight_bps: 10_000,
+        kelly_fraction_bps: 10_000,
+        payout_multipliers_bps: multipliers(payout_multipliers_bps)?,
+    })
+    .map_err(error)?;
+    Ok(Quote { plan })
+}
+
+/// `T` from the leading bytes of `data`; account layouts may grow
+fn decode<T: bytemuck::Pod>(data: &[u8], name: &str) -> Result<T, JsValue> {
+    data.get(..size_of::<T>())
+        .map(bytemuck::pod_read_unaligned)
+        .ok_or_else(|| JsValue::from_str(&format!("{name} data too short")))
+}
+
+/// Per-square multipliers, `None` when empty
+fn multipliers(bps: &[u16]) -> Result<Option<&[u16; BOARD_SQUARES]>, JsValue> {
+    if bps.is_empty() {
+        return Ok(None);
+    }
+    bps.try_into()
+        .map(Some)
+        .map_err(|_| JsValue::from_str("payout multipliers need one entry per square"))
+}
+
+fn error(e: ore_ev_math::Error) -> JsValue {
+    JsValue::from_str(&format!("{e:?}"))
+}
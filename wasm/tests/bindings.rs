@@ -0,0 +1,83 @@
+use ore_ev_math::{
+    calculate_ev, calculate_optimal_deployments, motherlode_value, ore_value,
+    test_utils::{config_params as params, round},
+    AllocationParams, ConfigParams, Objective, Selection,
+};
+use ore_ev_wasm::{config_params_len, ev_surface, quote};
+
+const ORE_PRICE: u64 = 1_000_000_000;
+
+/// Account data, with trailing bytes as a grown layout would have
+fn bytes<T: bytemuck::Pod>(value: &T) -> Vec<u8> {
+    let mut data = bytemuck::bytes_of(value).to_vec();
+    data.extend([0; 8]);
+    data
+}
+
+#[test]
+fn ev_surface_is_the_math_crates_ev_per_square() {
+    let (params, round) = (params(), round());
+    let surface = ev_surface(&bytes(&round), &bytes(&params), 1_000_000, ORE_PRICE, &[]).unwrap();
+
+    let motherlode = motherlode_value(ORE_PRICE, round.motherlode, &params);
+    let ore_value = ore_value(ORE_PRICE, motherlode, &params);
+    for (square, ev) in surface.iter().enumerate() {
+        let expected = calculate_ev(
+            round.deployed[square],
+            1_000_000,
+            round.total_deployed,
+            ore_value,
+            &params,
+        );
+        assert_eq!(*ev, expected);
+    }
+    assert_eq!(config_params_len(), size_of::<ConfigParams>());
+}
+
+#[test]
+fn quote_matches_the_allocator() {
+    let (params, round) = (params(), round());
+    let plan = quote(
+        &bytes(&round),
+        &bytes(&params),
+        500_000_000,
+        5,
+        ORE_PRICE,
+        -10_000,
+        0,
+        0,
+        &[],
+    )
+    .unwrap();
+
+    let direct = calculate_optimal_deployments(&AllocationParams {
+        round: &round,
+        config: &params,
+        total_budget: 500_000_000,
+        max_blocks: 5,
+        ore_price_lamports: ORE_PRICE,
+        min_ev_threshold: -10_000,
+        rank_thresholds: &[],
+        absolute_threshold: false,
+        max_block_share_bps: 0,
+        objective: Objective::Kelly,
+        selection: Selection::EvPerLamport,
+        max_std_dev_bps: 0,
+        portfolio: false,
+        eligible_squares: 0x1ff_ffff,
+        crowd_penalty_bps: 0,
+        held: [0; 25],
+        top_miner: false,
+        motherlode_weight_bps: 10_000,
+        kelly_fraction_bps: 10_000,
+        payout_multipliers_bps: None,
+    })
+    .unwrap();
+    let count = direct.count as usize;
+    assert!(count > 0);
+    assert_eq!(plan.count(), direct.count);
+    assert_eq!(plan.squares(), direct.indices[..count]);
+    assert_eq!(plan.amounts(), direct.amounts[..count]);
+    assert_eq!(plan.evs(), direct.evs[..count]);
+    assert_eq!(plan.portfolio_ev(), direct.portfolio_ev);
+}